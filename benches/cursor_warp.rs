@@ -0,0 +1,59 @@
+//! Measures warping the cursor many times per event loop iteration, like a first-person camera
+//! re-centering the cursor on every mouse event would.
+//!
+//! Run it with `cargo bench --bench cursor_warp`. On X11 the warps of an iteration are coalesced
+//! into one request that is flushed when the iteration ends, so `set_cursor_position` doesn't
+//! talk to the X server at all and the time per warp should stay far below a microsecond.
+
+#[cfg(x11_platform)]
+fn main() {
+    use std::time::{Duration, Instant};
+
+    use winit::dpi::PhysicalPosition;
+    use winit::event_loop::EventLoopBuilder;
+    use winit::platform::pump_events::EventLoopExtPumpEvents;
+    use winit::platform::x11::EventLoopBuilderExtX11;
+    use winit::window::WindowBuilder;
+
+    const ITERATIONS: u32 = 100;
+    const WARPS_PER_ITERATION: u32 = 1_000;
+
+    let mut event_loop = match EventLoopBuilder::new().with_x11().build() {
+        Ok(event_loop) => event_loop,
+        Err(err) => {
+            println!("skipping, no X server to connect to: {err}");
+            return;
+        }
+    };
+    let window = WindowBuilder::new()
+        .with_title("Cursor warp benchmark")
+        .build(&event_loop)
+        .unwrap();
+
+    let mut warping = Duration::ZERO;
+    let mut iterating = Duration::ZERO;
+    for iteration in 0..ITERATIONS {
+        let start = Instant::now();
+        for warp in 0..WARPS_PER_ITERATION {
+            let position = PhysicalPosition::new(warp % 100, iteration % 100);
+            window.set_cursor_position(position).unwrap();
+        }
+        warping += start.elapsed();
+
+        let start = Instant::now();
+        event_loop.pump_events(Some(Duration::ZERO), |_, _| {});
+        iterating += start.elapsed();
+    }
+
+    let warps = ITERATIONS * WARPS_PER_ITERATION;
+    println!(
+        "{warps} warps in {ITERATIONS} iterations: {:?} per warp, {:?} per iteration",
+        warping / warps,
+        iterating / ITERATIONS,
+    );
+}
+
+#[cfg(not(x11_platform))]
+fn main() {
+    println!("This benchmark only measures X11.");
+}
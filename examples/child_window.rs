@@ -77,7 +77,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 } => {
                     spawn_child_window(&parent_window, elwt, &mut windows);
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     if let Some(window) = windows.get(&window_id) {
                         fill::fill_window(window);
                     }
@@ -38,7 +38,7 @@ fn main() -> Result<(), impl std::error::Error> {
                         cursor_idx = 0;
                     }
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 WindowEvent::CloseRequested => {
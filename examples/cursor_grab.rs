@@ -57,7 +57,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 }
             }
             WindowEvent::ModifiersChanged(new) => modifiers = new.state(),
-            WindowEvent::RedrawRequested => fill::fill_window(&window),
+            WindowEvent::RedrawRequested { .. } => fill::fill_window(&window),
             _ => (),
         },
         Event::DeviceEvent { event, .. } => match event {
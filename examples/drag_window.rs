@@ -79,7 +79,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 }
                 _ => (),
             },
-            WindowEvent::RedrawRequested => {
+            WindowEvent::RedrawRequested { .. } => {
                 if window_id == window_1.id() {
                     fill::fill_window(&window_1);
                 } else if window_id == window_2.id() {
@@ -10,7 +10,7 @@ use web_time as time;
 use winit::{
     event::{Event, StartCause, WindowEvent},
     event_loop::EventLoop,
-    window::WindowBuilder,
+    window::{UserAttentionType, WindowBuilder},
 };
 
 #[path = "util/fill.rs"]
@@ -33,11 +33,14 @@ fn main() -> Result<(), impl std::error::Error> {
                 // Timeout reached; focus the window.
                 println!("Re-focusing the window.");
                 deadline += time::Duration::from_secs(3);
-                window.focus_window();
+                if window.focus_window().is_err() {
+                    // The window can't focus itself; ask the user to do it instead.
+                    window.request_user_attention(Some(UserAttentionType::Informational));
+                }
             }
             Event::WindowEvent { event, window_id } if window_id == window.id() => match event {
                 WindowEvent::CloseRequested => elwt.exit(),
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     // Notify the windowing system that we'll be presenting to the window.
                     window.pre_present_notify();
                     fill::fill_window(&window);
@@ -0,0 +1,99 @@
+#![allow(clippy::single_match)]
+
+//! A first person camera: click in the window to capture the cursor, move the mouse to look
+//! around, and press Escape to release it.
+
+use simple_logger::SimpleLogger;
+use winit::{
+    event::{DeviceEvent, ElementState, Event, KeyEvent, MouseButton, WindowEvent},
+    event_loop::EventLoop,
+    keyboard::{Key, NamedKey},
+    window::{PointerMode, Window, WindowBuilder},
+};
+
+#[path = "util/fill.rs"]
+mod fill;
+
+/// How many degrees the camera turns per unit of mouse motion.
+const SENSITIVITY: f64 = 0.1;
+
+/// Hides and grabs the cursor, locking it in place where possible.
+fn capture(window: &Window) {
+    let result = window
+        .set_pointer_mode(PointerMode::Grabbed {
+            locked: true,
+            hidden: true,
+        })
+        // X11 and Windows can't lock the cursor, but confining it is enough since the camera
+        // only reads the relative motion.
+        .or_else(|_| {
+            window.set_pointer_mode(PointerMode::Grabbed {
+                locked: false,
+                hidden: true,
+            })
+        });
+    if let Err(err) = result {
+        log::error!("failed to capture the cursor: {err}");
+    }
+}
+
+fn release(window: &Window) {
+    if let Err(err) = window.set_pointer_mode(PointerMode::Visible) {
+        log::error!("failed to release the cursor: {err}");
+    }
+}
+
+fn main() -> Result<(), impl std::error::Error> {
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoop::new().unwrap();
+
+    let window = WindowBuilder::new()
+        .with_title("Click to look around, Escape to release the cursor")
+        .build(&event_loop)
+        .unwrap();
+
+    let mut captured = false;
+    let (mut yaw, mut pitch) = (0.0f64, 0.0f64);
+
+    event_loop.run(move |event, elwt| match event {
+        Event::WindowEvent { event, .. } => match event {
+            WindowEvent::CloseRequested => elwt.exit(),
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if !captured => {
+                capture(&window);
+                captured = true;
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        logical_key: Key::Named(NamedKey::Escape),
+                        state: ElementState::Pressed,
+                        ..
+                    },
+                ..
+            } if captured => {
+                release(&window);
+                captured = false;
+            }
+            // Give the cursor back when the user switches to another application.
+            WindowEvent::Focused(false) if captured => {
+                release(&window);
+                captured = false;
+            }
+            WindowEvent::RedrawRequested { .. } => fill::fill_window(&window),
+            _ => (),
+        },
+        Event::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta: (dx, dy) },
+            ..
+        } if captured => {
+            yaw = (yaw + dx * SENSITIVITY).rem_euclid(360.0);
+            pitch = (pitch - dy * SENSITIVITY).clamp(-89.0, 89.0);
+            println!("yaw: {yaw:.1}°, pitch: {pitch:.1}°");
+        }
+        _ => (),
+    })
+}
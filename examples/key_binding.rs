@@ -52,7 +52,7 @@ fn main() -> Result<(), impl std::error::Error> {
                         }
                     }
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
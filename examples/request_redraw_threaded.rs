@@ -42,7 +42,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 ..
             } => elwt.exit(),
             Event::WindowEvent {
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { .. },
                 ..
             } => {
                 println!("\nredrawing!\n");
@@ -44,7 +44,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     println!("Resizable: {resizable}");
                     window.set_resizable(resizable);
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
@@ -0,0 +1,97 @@
+#![allow(clippy::single_match)]
+
+//! Two windows drawing with a single graphics context.
+//!
+//! A graphics API like OpenGL needs a display to create its context, and a valid window handle
+//! to create the surface of each window. This example creates the surfaces only once
+//! `Window::raw_window_handle_ready` succeeds, which is after `Event::Resumed` on every platform,
+//! and drops them on `Event::Suspended`, when the handles may become invalid on Android.
+//!
+//! The windows are filled through the example `fill` module, which keeps one `softbuffer` context
+//! shared by all the surfaces: a GL context shared between windows, for example with `glutin`,
+//! would be created and destroyed at the same points.
+
+use std::collections::{HashMap, HashSet};
+
+use simple_logger::SimpleLogger;
+use winit::{
+    event::{Event, WindowEvent},
+    event_loop::{EventLoop, EventLoopWindowTarget},
+    window::{Window, WindowBuilder},
+};
+
+#[path = "util/fill.rs"]
+mod fill;
+
+fn main() -> Result<(), impl std::error::Error> {
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoop::new().unwrap();
+
+    let mut windows = HashMap::new();
+    // The windows which currently have a surface.
+    let mut surfaces = HashSet::new();
+
+    event_loop.run(move |event, elwt| match event {
+        Event::Resumed => {
+            // Windows should be created after the first `Resumed`, so that the same code
+            // works on platforms which can't create a surface before it.
+            if windows.is_empty() {
+                for title in ["First window", "Second window"] {
+                    let window = create_window(elwt, title);
+                    windows.insert(window.id(), window);
+                }
+            }
+
+            for (&window_id, window) in &windows {
+                match window.raw_window_handle_ready() {
+                    Ok(()) => {
+                        // This is where the shared context, when it doesn't exist yet, and the
+                        // surface of the window would be created.
+                        println!("Creating the surface of {window_id:?}");
+                        surfaces.insert(window_id);
+                        window.request_redraw();
+                    }
+                    Err(err) => println!("The handle of {window_id:?} isn't ready yet: {err}"),
+                }
+            }
+        }
+        Event::Suspended => {
+            // The surfaces must be dropped before returning on Android, while the shared
+            // context can be kept to recreate them on the next `Resumed`.
+            for window_id in surfaces.drain() {
+                println!("Dropping the surface of {window_id:?}");
+                fill::cleanup_window(&windows[&window_id]);
+            }
+        }
+        Event::WindowEvent { event, window_id } => match event {
+            WindowEvent::CloseRequested => {
+                if let Some(window) = windows.remove(&window_id) {
+                    if surfaces.remove(&window_id) {
+                        fill::cleanup_window(&window);
+                    }
+                }
+
+                if windows.is_empty() {
+                    elwt.exit();
+                }
+            }
+            WindowEvent::RedrawRequested { .. } if surfaces.contains(&window_id) => {
+                let window = &windows[&window_id];
+                window.pre_present_notify();
+                fill::fill_window(window);
+            }
+            _ => (),
+        },
+        _ => (),
+    })
+}
+
+fn create_window(elwt: &EventLoopWindowTarget<()>, title: &str) -> Window {
+    let window = WindowBuilder::new()
+        .with_title(title)
+        .with_inner_size(winit::dpi::LogicalSize::new(320.0, 240.0))
+        .build(elwt)
+        .unwrap();
+    println!("Opened a new window: {:?}", window.id());
+    window
+}
@@ -69,7 +69,7 @@ mod example {
                         current_token = Some(token);
                     }
 
-                    WindowEvent::RedrawRequested => {
+                    WindowEvent::RedrawRequested { .. } => {
                         if let Some(window) = windows.get(&window_id) {
                             super::fill::fill_window(window);
                         }
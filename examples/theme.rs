@@ -57,7 +57,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     }
                     _ => (),
                 },
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     println!("\nredrawing!\n");
                     fill::fill_window(&window);
                 }
@@ -17,7 +17,7 @@ fn main() -> Result<(), impl std::error::Error> {
         .build(&event_loop)
         .unwrap();
 
-    println!("Only supported on macOS at the moment.");
+    println!("Only supported on macOS, and on X11 for rotation and panning, at the moment.");
 
     event_loop.run(move |event, elwt| {
         if let Event::WindowEvent { event, .. } = event {
@@ -33,14 +33,17 @@ fn main() -> Result<(), impl std::error::Error> {
                 WindowEvent::SmartMagnify { .. } => {
                     println!("Smart zoom");
                 }
-                WindowEvent::TouchpadRotate { delta, .. } => {
-                    if delta > 0.0 {
-                        println!("Rotated counterclockwise {delta}");
+                WindowEvent::RotationGesture { delta_radians, .. } => {
+                    if delta_radians > 0.0 {
+                        println!("Rotated counterclockwise {delta_radians}");
                     } else {
-                        println!("Rotated clockwise {delta}");
+                        println!("Rotated clockwise {delta_radians}");
                     }
                 }
-                WindowEvent::RedrawRequested => {
+                WindowEvent::PanGesture { delta, phase, .. } => {
+                    println!("Panned {delta:?} ({phase:?})");
+                }
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
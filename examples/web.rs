@@ -48,7 +48,10 @@ pub fn main() -> Result<(), impl std::error::Error> {
                         event:
                             KeyEvent {
                                 logical_key: Key::Character(c),
-                                state: ElementState::Released,
+                                // Browsers only allow entering fullscreen during a user activation,
+                                // which a key press is but a key release is not.
+                                state: ElementState::Pressed,
+                                repeat: false,
                                 ..
                             },
                         ..
@@ -61,21 +64,26 @@ pub fn main() -> Result<(), impl std::error::Error> {
                 }
             }
             Event::WindowEvent {
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::FullscreenFailed,
                 window_id,
             } if window_id == window.id() => {
-                // Panicking in user code, during a redraw requested, while holding a lock, causes a 2nd panic when refreshing the tab.
-                // I can reproduce locking any web_sys object, like the canvas or a `WebGl2RenderingContext`
+                log::warn!("the browser denied the fullscreen request");
+            }
+            Event::WindowEvent {
+                event: WindowEvent::RedrawRequested { .. },
+                window_id,
+            } if window_id == window.id() => {
+                // A panic of the application, here during a redraw while holding a lock, only
+                // shows up once in the JS console, even when the tab is refreshed afterwards.
+                //
+                // To check it, run `cargo run-wasm --example web`, open the JS console and refresh
+                // the tab a couple of times: no `BorrowMutError` of winit follows the panic.
                 let canvas = window.canvas().unwrap();
                 let mutex = std::sync::Mutex::new(canvas);
                 {
                     let mut guard = mutex.lock().unwrap();
                     let _canvas = &mut *guard;
                     panic!("Simulating a panic in user code!");
-                    // expected: the JS console should only see this panic happen, in the form of an exception
-                    // actual: we see this panic and, when refreshing the tab, for barely 1 second, we see another BorrowMutError panic inside of Winit's code
-                    // (it doesn't happen every time, but most of the times)
-                    // repro steps: just run this code with `cargo run-wasm --example web`, check the JS console, and refresh. If it doesn't happen the 1st time, repeat a couple of times.
                 }
             }
             _ => (),
@@ -144,7 +152,7 @@ mod wasm {
         // As a bonus its also kind of handy on desktop.
         let event = match event {
             Event::WindowEvent {
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { .. },
                 ..
             } => None,
             Event::WindowEvent { event, .. } => Some(format!("{event:?}")),
@@ -0,0 +1,96 @@
+#![allow(clippy::single_match)]
+
+//! Cycles through every [`CursorIcon`] once per second, to check how each one is shown.
+
+use std::time::Duration;
+#[cfg(not(wasm_platform))]
+use std::time::Instant;
+#[cfg(wasm_platform)]
+use web_time::Instant;
+
+use winit::{
+    event::{Event, StartCause, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::{CursorIcon, WindowBuilder},
+};
+
+#[path = "util/fill.rs"]
+mod fill;
+
+const SWITCH_INTERVAL: Duration = Duration::from_secs(1);
+
+fn main() -> Result<(), impl std::error::Error> {
+    #[cfg(not(wasm_platform))]
+    simple_logger::SimpleLogger::new().init().unwrap();
+    #[cfg(wasm_platform)]
+    console_log::init_with_level(log::Level::Debug).unwrap();
+
+    let event_loop = EventLoop::new().unwrap();
+
+    let builder = WindowBuilder::new().with_title("Cursors");
+    #[cfg(wasm_platform)]
+    let builder = {
+        use winit::platform::web::WindowBuilderExtWebSys;
+        builder.with_append(true)
+    };
+    let window = builder.build(&event_loop).unwrap();
+
+    let mut cursor_idx = 0;
+
+    event_loop.run(move |event, elwt| match event {
+        Event::NewEvents(StartCause::Init | StartCause::ResumeTimeReached { .. }) => {
+            let cursor = CURSORS[cursor_idx];
+            log::info!("Setting cursor to {cursor:?} (`{}`)", cursor.name());
+            window.set_cursor_icon(cursor);
+            window.set_title(&format!("Cursor: {cursor:?}"));
+            cursor_idx = (cursor_idx + 1) % CURSORS.len();
+
+            elwt.set_control_flow(ControlFlow::WaitUntil(Instant::now() + SWITCH_INTERVAL));
+        }
+        Event::WindowEvent { event, .. } => match event {
+            WindowEvent::RedrawRequested { .. } => fill::fill_window(&window),
+            WindowEvent::CloseRequested => elwt.exit(),
+            _ => (),
+        },
+        _ => (),
+    })
+}
+
+const CURSORS: &[CursorIcon] = &[
+    CursorIcon::Default,
+    CursorIcon::ContextMenu,
+    CursorIcon::Help,
+    CursorIcon::Pointer,
+    CursorIcon::Progress,
+    CursorIcon::Wait,
+    CursorIcon::Cell,
+    CursorIcon::Crosshair,
+    CursorIcon::Text,
+    CursorIcon::VerticalText,
+    CursorIcon::Alias,
+    CursorIcon::Copy,
+    CursorIcon::Move,
+    CursorIcon::NoDrop,
+    CursorIcon::NotAllowed,
+    CursorIcon::Grab,
+    CursorIcon::Grabbing,
+    CursorIcon::EResize,
+    CursorIcon::NResize,
+    CursorIcon::NeResize,
+    CursorIcon::NwResize,
+    CursorIcon::SResize,
+    CursorIcon::SeResize,
+    CursorIcon::SwResize,
+    CursorIcon::WResize,
+    CursorIcon::EwResize,
+    CursorIcon::NsResize,
+    CursorIcon::NeswResize,
+    CursorIcon::NwseResize,
+    CursorIcon::ColResize,
+    CursorIcon::RowResize,
+    CursorIcon::AllScroll,
+    CursorIcon::ZoomIn,
+    CursorIcon::ZoomOut,
+    CursorIcon::DndAsk,
+    CursorIcon::AllResize,
+];
@@ -55,7 +55,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     if minimized {
                         minimized = !minimized;
                         window.set_minimized(minimized);
-                        window.focus_window();
+                        let _ = window.focus_window();
                     }
                 }
                 PhysicalKey::Code(KeyCode::KeyV) => {
@@ -126,7 +126,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     _ => (),
                 },
                 WindowEvent::CloseRequested if window_id == window.id() => elwt.exit(),
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     fill::fill_window(&window);
                 }
                 _ => (),
@@ -0,0 +1,55 @@
+#![allow(clippy::single_match)]
+
+use simple_logger::SimpleLogger;
+use winit::{
+    dpi::{LogicalSize, PhysicalPosition},
+    event::{Event, StartCause, WindowEvent},
+    event_loop::EventLoop,
+    window::WindowBuilder,
+};
+
+#[path = "util/fill.rs"]
+mod fill;
+
+fn main() -> Result<(), impl std::error::Error> {
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoop::new().unwrap();
+
+    // Create the window hidden, so it's never shown with its default geometry.
+    let window = WindowBuilder::new()
+        .with_title("A window shown once it's ready")
+        .with_visible(false)
+        .build(&event_loop)
+        .unwrap();
+
+    // Configure the window while it's still hidden.
+    let _ = window.request_inner_size(LogicalSize::new(400.0, 300.0));
+    if let Some(monitor) = window.current_monitor() {
+        let monitor_size = monitor.size();
+        let window_size = window.outer_size();
+        let position = PhysicalPosition::new(
+            monitor.position().x + (monitor_size.width as i32 - window_size.width as i32) / 2,
+            monitor.position().y + (monitor_size.height as i32 - window_size.height as i32) / 2,
+        );
+        window.set_outer_position(position);
+    }
+
+    event_loop.run(move |event, elwt| {
+        println!("{event:?}");
+
+        match event {
+            // Show the window once the event loop is running, the first frame is drawn in
+            // response to the following `RedrawRequested`.
+            Event::NewEvents(StartCause::Init) => window.set_visible(true),
+            Event::WindowEvent { event, window_id } if window_id == window.id() => match event {
+                WindowEvent::CloseRequested => elwt.exit(),
+                WindowEvent::RedrawRequested { .. } => {
+                    window.pre_present_notify();
+                    fill::fill_window(&window);
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+    })
+}
@@ -0,0 +1,78 @@
+#![allow(clippy::single_match)]
+
+#[cfg(target_os = "macos")]
+use simple_logger::SimpleLogger;
+#[cfg(target_os = "macos")]
+use winit::{
+    event::{ElementState, Event, KeyEvent, WindowEvent},
+    event_loop::EventLoop,
+    keyboard::{Key, ModifiersState},
+    platform::macos::WindowExtMacOS,
+    window::WindowBuilder,
+};
+
+#[cfg(target_os = "macos")]
+#[path = "util/fill.rs"]
+mod fill;
+
+#[cfg(target_os = "macos")]
+fn main() -> Result<(), impl std::error::Error> {
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoop::new().unwrap();
+
+    // A document window: the name of the document as the title, its location as the subtitle,
+    // and the unsaved changes indicator in the close button.
+    let window = WindowBuilder::new()
+        .with_title("Untitled.txt")
+        .build(&event_loop)
+        .unwrap();
+    window.set_subtitle("Documents");
+
+    println!("Type to edit the document, and press Cmd+S to save it.");
+
+    let mut modifiers = ModifiersState::default();
+    let mut saves = 0;
+    event_loop.run(move |event, elwt| {
+        if let Event::WindowEvent { event, .. } = event {
+            match event {
+                WindowEvent::CloseRequested => {
+                    if window.is_document_edited() {
+                        println!("The document has unsaved changes, press Cmd+S to save it.");
+                    } else {
+                        elwt.exit();
+                    }
+                }
+                WindowEvent::ModifiersChanged(new) => modifiers = new.state(),
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            state: ElementState::Pressed,
+                            logical_key,
+                            ..
+                        },
+                    ..
+                } => match logical_key.as_ref() {
+                    Key::Character("s") if modifiers.super_key() => {
+                        saves += 1;
+                        window.set_document_edited(false);
+                        window.set_subtitle(&format!("Documents - saved {saves} times"));
+                    }
+                    Key::Character(_) if !modifiers.super_key() => {
+                        window.set_document_edited(true);
+                        window.set_subtitle("Documents - edited");
+                    }
+                    _ => (),
+                },
+                WindowEvent::RedrawRequested { .. } => {
+                    fill::fill_window(&window);
+                }
+                _ => (),
+            }
+        }
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn main() {
+    println!("This example is only supported on MacOS");
+}
@@ -68,7 +68,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 border = !border;
                 window.set_decorations(border);
             }
-            WindowEvent::RedrawRequested => {
+            WindowEvent::RedrawRequested { .. } => {
                 fill::fill_window(&window);
             }
             _ => (),
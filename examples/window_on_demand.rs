@@ -45,7 +45,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     }
                     Event::AboutToWait => window.request_redraw(),
                     Event::WindowEvent {
-                        event: WindowEvent::RedrawRequested,
+                        event: WindowEvent::RedrawRequested { .. },
                         ..
                     }  => {
                         fill::fill_window(window);
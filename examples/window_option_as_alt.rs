@@ -53,7 +53,7 @@ fn main() -> Result<(), impl std::error::Error> {
                 window.set_option_as_alt(option_as_alt);
             }
             WindowEvent::KeyboardInput { .. } => println!("KeyboardInput: {event:?}"),
-            WindowEvent::RedrawRequested => {
+            WindowEvent::RedrawRequested { .. } => {
                 fill::fill_window(&window);
             }
             _ => (),
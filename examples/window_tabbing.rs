@@ -90,7 +90,7 @@ fn main() -> Result<(), impl std::error::Error> {
                     }
                     _ => (),
                 },
-                WindowEvent::RedrawRequested => {
+                WindowEvent::RedrawRequested { .. } => {
                     if let Some(window) = windows.get(&window_id) {
                         fill::fill_window(window);
                     }
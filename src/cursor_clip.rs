@@ -0,0 +1,136 @@
+//! Releasing of the cursor clip of
+//! [`EventLoopWindowTarget::clip_cursor`](crate::event_loop::EventLoopWindowTarget::clip_cursor)
+//! while the application has no focused window.
+//!
+//! The clip applies to the whole desktop, so it would keep confining the cursor once the user
+//! switched to another application. It's released while no window of the application is
+//! focused, which is the case until the first one gets focused, and applied again once one of
+//! them is.
+
+use std::rc::Rc;
+
+use crate::dpi::PhysicalRect;
+use crate::event::{Event, WindowEvent};
+use crate::event_loop::EventLoopWindowTarget;
+use crate::scheduler::HandlerState;
+use crate::window::WindowId;
+
+/// Wraps the application's event handler to release and re-apply the cursor clip on focus
+/// changes.
+pub(crate) fn wrap<T, F>(
+    state: Rc<HandlerState>,
+    mut event_handler: F,
+) -> impl FnMut(Event<T>, &EventLoopWindowTarget<T>)
+where
+    F: FnMut(Event<T>, &EventLoopWindowTarget<T>),
+{
+    move |event, elwt| {
+        let update = match event {
+            Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Focused(focused),
+            } => state
+                .cursor_clip
+                .borrow_mut()
+                .focus_changed(window_id, focused),
+            Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Destroyed,
+            } => state
+                .cursor_clip
+                .borrow_mut()
+                .focus_changed(window_id, false),
+            _ => None,
+        };
+        if let Some(rect) = update {
+            if let Err(err) = elwt.p.clip_cursor(rect) {
+                log::warn!("Failed to update the cursor clip after a focus change: {err}");
+            }
+        }
+        event_handler(event, elwt)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct CursorClip {
+    /// The rectangle requested by the application.
+    rect: Option<PhysicalRect>,
+    /// The focused windows of the application.
+    focused: Vec<WindowId>,
+}
+
+impl CursorClip {
+    /// Returns whether the clip is released because no window is focused.
+    pub(crate) fn is_released(&self) -> bool {
+        self.focused.is_empty()
+    }
+
+    /// Called when the application clipped the cursor to `rect`.
+    pub(crate) fn set(&mut self, rect: Option<PhysicalRect>) {
+        self.rect = rect;
+    }
+
+    /// Returns the clip to apply after the focus change, if it changes.
+    fn focus_changed(
+        &mut self,
+        window_id: WindowId,
+        focused: bool,
+    ) -> Option<Option<PhysicalRect>> {
+        let was_released = self.is_released();
+        if focused {
+            if !self.focused.contains(&window_id) {
+                self.focused.push(window_id);
+            }
+        } else {
+            self.focused.retain(|&focused| focused != window_id);
+        }
+
+        let rect = self.rect?;
+        match (was_released, self.is_released()) {
+            (true, false) => Some(Some(rect)),
+            (false, true) => Some(None),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_is_released_without_focused_window() {
+        let rect = PhysicalRect::new((10, 10).into(), (100, 100).into());
+        let (first, second) = (WindowId::from(1), WindowId::from(2));
+        let mut clip = CursorClip::default();
+
+        assert_eq!(clip.focus_changed(first, true), None);
+        clip.set(Some(rect));
+        assert_eq!(clip.focus_changed(second, true), None);
+        assert_eq!(clip.focus_changed(first, false), None);
+        assert_eq!(clip.focus_changed(second, false), Some(None));
+
+        // A new rectangle waits for the focus to come back.
+        let rect = PhysicalRect::new((0, 0).into(), (50, 50).into());
+        assert!(clip.is_released());
+        clip.set(Some(rect));
+        assert_eq!(clip.focus_changed(second, true), Some(Some(rect)));
+
+        clip.set(None);
+        assert_eq!(clip.focus_changed(second, false), None);
+        assert_eq!(clip.focus_changed(second, true), None);
+    }
+
+    #[test]
+    fn clip_is_released_until_a_window_is_focused() {
+        let rect = PhysicalRect::new((10, 10).into(), (100, 100).into());
+        let window = WindowId::from(1);
+        let mut clip = CursorClip::default();
+
+        assert!(clip.is_released());
+        clip.set(Some(rect));
+        assert_eq!(clip.focus_changed(window, false), None);
+        assert_eq!(clip.focus_changed(window, true), Some(Some(rect)));
+        assert!(!clip.is_released());
+    }
+}
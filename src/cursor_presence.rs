@@ -0,0 +1,240 @@
+//! Balancing of [`WindowEvent::CursorEntered`] and [`WindowEvent::CursorLeft`].
+//!
+//! The backends report the enter and leave notifications of the system as they come, which
+//! isn't reliable around cursor grabs, fullscreen transitions and focus changes: some systems
+//! repeat them, drop them, or stop reporting a cursor which is still inside when the window loses
+//! focus or gets hidden. The events are filtered here so that they alternate for every window and
+//! device, and a `CursorLeft` is synthesized when the window loses focus, is hidden or is
+//! destroyed while the cursor is inside.
+
+use std::rc::Rc;
+
+use crate::event::{DeviceId, Event, WindowEvent};
+use crate::event_loop::EventLoopWindowTarget;
+use crate::scheduler::HandlerState;
+use crate::window::WindowId;
+
+/// Wraps the application's event handler to balance the enter and leave events it receives.
+pub(crate) fn wrap<T, F>(
+    state: Rc<HandlerState>,
+    mut event_handler: F,
+) -> impl FnMut(Event<T>, &EventLoopWindowTarget<T>)
+where
+    F: FnMut(Event<T>, &EventLoopWindowTarget<T>),
+{
+    move |event, elwt| {
+        // The borrow is released while dispatching, so that the handler can hide windows.
+        let (before, event, after) = {
+            let mut presence = state.cursor_presence.borrow_mut();
+            let hidden = std::mem::take(&mut *state.hidden_windows.lock().unwrap());
+            presence.hidden.extend(hidden);
+            presence.process(event)
+        };
+        for (window_id, event) in before {
+            event_handler(Event::WindowEvent { window_id, event }, elwt);
+        }
+        if let Some(event) = event {
+            event_handler(event, elwt);
+        }
+        for (window_id, event) in after {
+            event_handler(Event::WindowEvent { window_id, event }, elwt);
+        }
+    }
+}
+
+type Synthesized = Vec<(WindowId, WindowEvent)>;
+
+#[derive(Default)]
+pub(crate) struct CursorPresence {
+    /// The devices whose cursor is inside a window.
+    inside: Vec<(WindowId, DeviceId)>,
+    /// The devices we synthesized a `CursorLeft` for, and whose next leave from the system is
+    /// thus already reported.
+    left: Vec<(WindowId, DeviceId)>,
+    /// The windows hidden since the last event.
+    hidden: Vec<WindowId>,
+}
+
+impl CursorPresence {
+    /// Returns the events to emit before `event`, `event` unless it's dropped, and the events to
+    /// emit after it.
+    fn process<T>(&mut self, event: Event<T>) -> (Synthesized, Option<Event<T>>, Synthesized) {
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for window_id in std::mem::take(&mut self.hidden) {
+            self.leave_window(window_id, &mut before);
+        }
+
+        let (window_id, window_event) = match &event {
+            Event::WindowEvent { window_id, event } => (*window_id, event),
+            _ => return (before, Some(event), after),
+        };
+
+        match *window_event {
+            WindowEvent::CursorEntered { device_id } => {
+                self.left.retain(|&left| left != (window_id, device_id));
+                if self.inside.contains(&(window_id, device_id)) {
+                    return (before, None, after);
+                }
+                self.inside.push((window_id, device_id));
+            }
+            WindowEvent::CursorLeft { device_id } => {
+                self.left.retain(|&left| left != (window_id, device_id));
+                let len = self.inside.len();
+                self.inside
+                    .retain(|&inside| inside != (window_id, device_id));
+                if self.inside.len() == len {
+                    return (before, None, after);
+                }
+            }
+            WindowEvent::CursorMoved { device_id, .. } => {
+                // The cursor is still inside after a synthesized leave.
+                if let Some(index) = self
+                    .left
+                    .iter()
+                    .position(|&left| left == (window_id, device_id))
+                {
+                    self.left.swap_remove(index);
+                    if !self.inside.contains(&(window_id, device_id)) {
+                        self.inside.push((window_id, device_id));
+                        before.push((window_id, WindowEvent::CursorEntered { device_id }));
+                    }
+                }
+            }
+            WindowEvent::Focused(false) => self.leave_window(window_id, &mut after),
+            WindowEvent::Destroyed => {
+                self.leave_window(window_id, &mut before);
+                self.left.retain(|&(left, _)| left != window_id);
+            }
+            _ => (),
+        }
+
+        (before, Some(event), after)
+    }
+
+    /// Synthesizes a `CursorLeft` for every device whose cursor is inside the window.
+    fn leave_window(&mut self, window_id: WindowId, events: &mut Synthesized) {
+        let left = &mut self.left;
+        self.inside.retain(|&(inside, device_id)| {
+            if inside != window_id {
+                return true;
+            }
+            left.push((window_id, device_id));
+            events.push((window_id, WindowEvent::CursorLeft { device_id }));
+            false
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dpi::PhysicalPosition;
+
+    use super::*;
+
+    fn window_event(event: WindowEvent) -> Event<()> {
+        Event::WindowEvent {
+            window_id: WindowId::from(1),
+            event,
+        }
+    }
+
+    /// Processes the events, and returns the window events emitted for them.
+    fn process(presence: &mut CursorPresence, events: Vec<WindowEvent>) -> Vec<WindowEvent> {
+        let mut emitted = Vec::new();
+        for event in events {
+            let (before, event, after) = presence.process(window_event(event));
+            emitted.extend(before.into_iter().map(|(_, event)| event));
+            if let Some(Event::WindowEvent { event, .. }) = event {
+                emitted.push(event);
+            }
+            emitted.extend(after.into_iter().map(|(_, event)| event));
+        }
+        emitted
+    }
+
+    #[test]
+    fn repeated_and_unbalanced_events_are_dropped() {
+        let device_id = unsafe { DeviceId::dummy() };
+        let mut presence = CursorPresence::default();
+
+        let emitted = process(
+            &mut presence,
+            vec![
+                WindowEvent::CursorLeft { device_id },
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::CursorLeft { device_id },
+                WindowEvent::CursorLeft { device_id },
+            ],
+        );
+        assert_eq!(
+            emitted,
+            [
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::CursorLeft { device_id },
+            ]
+        );
+    }
+
+    #[test]
+    fn focus_loss_leaves_until_the_cursor_moves_again() {
+        let device_id = unsafe { DeviceId::dummy() };
+        let moved = WindowEvent::CursorMoved {
+            device_id,
+            position: PhysicalPosition::new(1.0, 1.0),
+        };
+        let mut presence = CursorPresence::default();
+
+        let emitted = process(
+            &mut presence,
+            vec![
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::Focused(false),
+                // The system reports the leave late, when it does.
+                WindowEvent::CursorLeft { device_id },
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::Focused(false),
+                moved.clone(),
+            ],
+        );
+        assert_eq!(
+            emitted,
+            [
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::Focused(false),
+                WindowEvent::CursorLeft { device_id },
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::Focused(false),
+                WindowEvent::CursorLeft { device_id },
+                WindowEvent::CursorEntered { device_id },
+                moved,
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_windows_are_left_before_the_next_event() {
+        let device_id = unsafe { DeviceId::dummy() };
+        let mut presence = CursorPresence::default();
+
+        let emitted = process(
+            &mut presence,
+            vec![WindowEvent::CursorEntered { device_id }],
+        );
+        presence.hidden.push(WindowId::from(1));
+        let emitted = [
+            emitted,
+            process(&mut presence, vec![WindowEvent::Focused(false)]),
+        ]
+        .concat();
+        assert_eq!(
+            emitted,
+            [
+                WindowEvent::CursorEntered { device_id },
+                WindowEvent::CursorLeft { device_id },
+                WindowEvent::Focused(false),
+            ]
+        );
+    }
+}
@@ -582,6 +582,45 @@ impl<P: Pixel> From<LogicalPosition<P>> for Position {
     }
 }
 
+/// A rectangle represented in physical pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicalRect {
+    /// The top-left corner.
+    pub position: PhysicalPosition<i32>,
+    pub size: PhysicalSize<u32>,
+}
+
+impl PhysicalRect {
+    #[inline]
+    pub const fn new(position: PhysicalPosition<i32>, size: PhysicalSize<u32>) -> Self {
+        PhysicalRect { position, size }
+    }
+
+    /// Returns the part of the rectangle which is also in `other`, or `None` if they don't
+    /// overlap.
+    pub fn intersection(&self, other: PhysicalRect) -> Option<PhysicalRect> {
+        let end = |rect: &PhysicalRect| {
+            (
+                rect.position.x as i64 + rect.size.width as i64,
+                rect.position.y as i64 + rect.size.height as i64,
+            )
+        };
+        let (self_end, other_end) = (end(self), end(&other));
+        let x = self.position.x.max(other.position.x);
+        let y = self.position.y.max(other.position.y);
+        let width = self_end.0.min(other_end.0) - x as i64;
+        let height = self_end.1.min(other_end.1) - y as i64;
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        Some(PhysicalRect::new(
+            PhysicalPosition::new(x, y),
+            PhysicalSize::new(width as u32, height as u32),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::dpi;
@@ -980,6 +1019,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rect_intersection() {
+        let rect = dpi::PhysicalRect::new((10, 20).into(), (100, 50).into());
+        assert_eq!(
+            rect.intersection(dpi::PhysicalRect::new((50, 0).into(), (100, 40).into())),
+            Some(dpi::PhysicalRect::new((50, 20).into(), (60, 20).into()))
+        );
+        assert_eq!(rect.intersection(rect), Some(rect));
+        assert_eq!(
+            rect.intersection(dpi::PhysicalRect::new((110, 20).into(), (10, 10).into())),
+            None
+        );
+    }
+
     // Eat coverage for the Debug impls et al
     #[test]
     fn ensure_attrs_do_not_panic() {
@@ -41,6 +41,11 @@ pub enum EventLoopError {
     RecreationAttempt,
     /// Application has exit with an error status.
     ExitFailure(i32),
+    /// The event loop was built off the main thread.
+    ///
+    /// See [`EventLoopBuilder::build`](crate::event_loop::EventLoopBuilder::build) for which
+    /// platforms allow opting out of this check.
+    NotMainThread,
 }
 
 impl From<OsError> for EventLoopError {
@@ -110,6 +115,11 @@ impl fmt::Display for EventLoopError {
             EventLoopError::NotSupported(e) => e.fmt(f),
             EventLoopError::Os(e) => e.fmt(f),
             EventLoopError::ExitFailure(status) => write!(f, "Exit Failure: {status}"),
+            EventLoopError::NotMainThread => write!(
+                f,
+                "EventLoop must be created on the main thread, see `with_any_thread` in the \
+                 platform's `EventLoopBuilderExt` to opt out where that's possible"
+            ),
         }
     }
 }
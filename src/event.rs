@@ -32,6 +32,7 @@
 //!
 //! [`EventLoop::run(...)`]: crate::event_loop::EventLoop::run
 //! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 use std::sync::{Mutex, Weak};
 #[cfg(not(wasm_platform))]
@@ -45,8 +46,8 @@ use crate::error::ExternalError;
 #[cfg(doc)]
 use crate::window::Window;
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize},
-    event_loop::AsyncRequestSerial,
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize},
+    event_loop::{AsyncRequestSerial, PowerState, TimerId},
     keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState},
     platform_impl,
     window::{ActivationToken, Theme, WindowId},
@@ -56,6 +57,7 @@ use crate::{
 ///
 /// See the module-level docs for more information on the event loop manages each event.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Event<T: 'static> {
     /// Emitted when new events arrive from the OS to be processed.
     ///
@@ -63,6 +65,9 @@ pub enum Event<T: 'static> {
     /// processing events, such as updating frame timing information for benchmarking or checking
     /// the [`StartCause`] to see if a timer set by
     /// [`ControlFlow::WaitUntil`](crate::event_loop::ControlFlow::WaitUntil) has elapsed.
+    ///
+    /// This event can't be serialized, since the [`StartCause`] holds [`Instant`]s.
+    #[cfg_attr(feature = "serde", serde(skip))]
     NewEvents(StartCause),
 
     /// Emitted when the OS sends an event to a winit window.
@@ -154,7 +159,8 @@ pub enum Event<T: 'static> {
     /// It's recommended that applications should only initialize their graphics context and create
     /// a window after they have received their first `Resumed` event. Some systems
     /// (specifically Android) won't allow applications to create a render surface until they are
-    /// resumed.
+    /// resumed. `Window::raw_window_handle_ready` tells whether a surface can be created for a
+    /// window.
     ///
     /// Considering that the implementation of [`Suspended`] and `Resumed` events may be internally
     /// driven by multiple platform-specific events, and that there may be subtle differences across
@@ -208,6 +214,22 @@ pub enum Event<T: 'static> {
     /// [`Suspended`]: Self::Suspended
     Resumed,
 
+    /// Emitted when a timer created with [`EventLoopWindowTarget::create_timer`] fires.
+    ///
+    /// [`EventLoopWindowTarget::create_timer`]: crate::event_loop::EventLoopWindowTarget::create_timer
+    Timer(TimerId),
+
+    /// Emitted when the power state returned by [`EventLoopWindowTarget::power_state`] changed.
+    /// It's `None` when the power state became unavailable.
+    ///
+    /// It's only emitted while enabled with [`EventLoopWindowTarget::listen_power_events`]. The
+    /// changes found by checking the state periodically are emitted in loop order right before
+    /// [`Event::AboutToWait`].
+    ///
+    /// [`EventLoopWindowTarget::power_state`]: crate::event_loop::EventLoopWindowTarget::power_state
+    /// [`EventLoopWindowTarget::listen_power_events`]: crate::event_loop::EventLoopWindowTarget::listen_power_events
+    PowerStateChanged(Option<PowerState>),
+
     /// Emitted when the event loop is about to block and wait for new events.
     ///
     /// Most applications shouldn't need to hook into this event since there is no real relationship
@@ -224,6 +246,11 @@ pub enum Event<T: 'static> {
     ///
     /// This is irreversible - if this event is emitted, it is guaranteed to be the last event that
     /// gets emitted. You generally want to treat this as a "do on quit" event.
+    ///
+    /// It's emitted exactly once per run of the event loop, no matter how many times
+    /// [`exit()`](crate::event_loop::EventLoopWindowTarget::exit) was called. Events delivered
+    /// between the call to `exit()` and this event can be told apart with
+    /// [`exiting()`](crate::event_loop::EventLoopWindowTarget::exiting).
     LoopExiting,
 
     /// Emitted when the application has received a memory warning.
@@ -266,6 +293,8 @@ impl<T> Event<T> {
             LoopExiting => Ok(LoopExiting),
             Suspended => Ok(Suspended),
             Resumed => Ok(Resumed),
+            Timer(timer_id) => Ok(Timer(timer_id)),
+            PowerStateChanged(state) => Ok(PowerStateChanged(state)),
             MemoryWarning => Ok(MemoryWarning),
         }
     }
@@ -303,6 +332,7 @@ pub enum StartCause {
 
 /// Describes an event from a [`Window`].
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowEvent {
     /// The activation token was delivered back and now could be used.
     ///
@@ -346,6 +376,25 @@ pub enum WindowEvent {
     /// separately.
     HoveredFile(PathBuf),
 
+    /// The cursor has moved on the window while files are being hovered over it.
+    ///
+    /// This is emitted after the [`HoveredFile`] events and then every time the cursor moves,
+    /// until either [`DroppedFile`] or [`HoveredFileCancelled`] is emitted. Use
+    /// [`Window::set_drop_feedback`] from this event to tell the user whether the files would
+    /// be accepted at this position.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`HoveredFile`]: Self::HoveredFile
+    /// [`DroppedFile`]: Self::DroppedFile
+    /// [`HoveredFileCancelled`]: Self::HoveredFileCancelled
+    HoveredFileMoved {
+        /// (x,y) coords in pixels relative to the top-left corner of the window.
+        position: PhysicalPosition<f64>,
+    },
+
     /// A file was hovered, but has exited the window.
     ///
     /// There will be a single `HoveredFileCancelled` event triggered even if multiple files were
@@ -370,12 +419,17 @@ pub enum WindowEvent {
         /// If `true`, the event was generated synthetically by winit
         /// in one of the following circumstances:
         ///
+        /// * Synthetic key release events are generated for all keys still pressed
+        ///   when a window loses focus, right before [`WindowEvent::Focused(false)`],
+        ///   so that the application never considers a key held forever. The
+        ///   release the system may send later is then dropped.
         /// * Synthetic key press events are generated for all keys pressed
-        ///   when a window gains focus. Likewise, synthetic key release events
-        ///   are generated for all keys pressed when a window goes out of focus.
+        ///   when a window gains focus.
         ///   ***Currently, this is only functional on X11 and Windows***
         ///
         /// Otherwise, this value is always `false`.
+        ///
+        /// [`WindowEvent::Focused(false)`]: WindowEvent::Focused
         is_synthetic: bool,
     },
 
@@ -411,6 +465,18 @@ pub enum WindowEvent {
 
     /// The cursor has entered the window.
     ///
+    /// Winit guarantees that `CursorEntered` and [`CursorLeft`](Self::CursorLeft) alternate for
+    /// every window and device, starting with `CursorEntered`: the repeated notifications some
+    /// systems send around cursor grabs and fullscreen transitions are dropped. When the cursor
+    /// is inside a window which loses focus, gets hidden with [`Window::set_visible`] or is
+    /// destroyed, a `CursorLeft` is emitted right after [`Focused(false)`](Self::Focused), before
+    /// the next event and before [`Destroyed`](Self::Destroyed) respectively, whether the system
+    /// reported the cursor leaving or not. If the cursor then moves inside the window again, a
+    /// `CursorEntered` is emitted before the [`CursorMoved`](Self::CursorMoved).
+    ///
+    /// So hover states tracked with these events are always cleared once the window can't be
+    /// hovered anymore.
+    ///
     /// ## Platform-specific
     ///
     /// - **Web:** Doesn't take into account CSS [`border`], [`padding`], or [`transform`].
@@ -422,6 +488,9 @@ pub enum WindowEvent {
 
     /// The cursor has left the window.
     ///
+    /// This is always preceded by a [`CursorEntered`](Self::CursorEntered) for the same device,
+    /// and may be synthesized by winit, see there.
+    ///
     /// ## Platform-specific
     ///
     /// - **Web:** Doesn't take into account CSS [`border`], [`padding`], or [`transform`].
@@ -439,6 +508,12 @@ pub enum WindowEvent {
     },
 
     /// An mouse button press has been received.
+    ///
+    /// Like the keys, the buttons still pressed when the window loses focus are released right
+    /// before [`WindowEvent::Focused(false)`], and the release the system may send later is
+    /// dropped. These releases can't be told apart from the ones of the system.
+    ///
+    /// [`WindowEvent::Focused(false)`]: WindowEvent::Focused
     MouseInput {
         device_id: DeviceId,
         state: ElementState,
@@ -452,7 +527,9 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS**.
+    /// - **X11:** Requires XInput 2.4. The delta is the change of the scale of the pinch since the
+    ///   last event, and is zero for the [`TouchPhase::Started`] event.
+    /// - **Windows / Wayland / iOS / Android / Web / Orbital:** Unsupported.
     TouchpadMagnify {
         device_id: DeviceId,
         delta: f64,
@@ -478,17 +555,46 @@ pub enum WindowEvent {
     /// - Only available on **macOS 10.8** and later.
     SmartMagnify { device_id: DeviceId },
 
-    /// Touchpad rotation event with two-finger rotation gesture.
+    /// Touchpad rotation event with a two-finger rotation gesture.
     ///
     /// Positive delta values indicate rotation counterclockwise and
     /// negative delta values indicate rotation clockwise.
     ///
+    /// The gesture is only reported by precision touchpads, it starts with a
+    /// [`TouchPhase::Started`] event with a zero delta.
+    ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS**.
-    TouchpadRotate {
+    /// - **X11:** Requires XInput 2.4, which reports rotations as part of pinches with two or
+    ///   more fingers. The event is thus emitted alongside any pinch, with a zero delta when the
+    ///   fingers don't rotate.
+    /// - **Windows / Wayland / iOS / Android / Web / Orbital:** Unsupported.
+    RotationGesture {
         device_id: DeviceId,
-        delta: f32,
+        delta_radians: f32,
+        phase: TouchPhase,
+    },
+
+    /// Touchpad pan event with a swipe gesture of three or more fingers.
+    ///
+    /// `delta` is the distance the fingers moved since the last event, with positive values
+    /// meaning to the right and down.
+    ///
+    /// Two-finger pans are reported as [`WindowEvent::MouseWheel`] instead, so that scrolling and
+    /// swiping can be told apart. The gesture is only reported by precision touchpads.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The swipe is only reported once the system recognized it, as a single step:
+    ///   a [`TouchPhase::Started`] event with a zero delta, a [`TouchPhase::Moved`] event whose
+    ///   delta is the direction of the swipe, one of `-1.0` or `1.0` along one axis, and a
+    ///   [`TouchPhase::Ended`] event with a zero delta. The system only recognizes swipes when "Swipe between pages" or "Swipe between
+    ///   full-screen apps" is set to use three fingers.
+    /// - **X11:** Requires XInput 2.4.
+    /// - **Windows / Wayland / iOS / Android / Web / Orbital:** Unsupported.
+    PanGesture {
+        device_id: DeviceId,
+        delta: PhysicalPosition<f64>,
         phase: TouchPhase,
     },
 
@@ -504,10 +610,23 @@ pub enum WindowEvent {
     },
 
     /// Motion on some analog axis. May report data redundant to other, more specific events.
+    ///
+    /// The axis is described by the [`AxisInfo`] with the same id in [`DeviceInfo::axes`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Reported for the XInput2 valuators that aren't scroll axes, such as the
+    ///   pressure and tilt of a tablet pen.
+    /// - **Wayland / Windows / macOS / iOS / Android / Web / Orbital:** Unsupported.
     AxisMotion {
         device_id: DeviceId,
         axis: AxisId,
+        /// Whether `value` is an absolute position or relative motion.
+        kind: AxisKind,
+        /// The raw value, whose range depends on the device.
         value: f64,
+        /// The value normalized with [`AxisInfo::normalize`], if the range of the axis is known.
+        normalized: Option<f64>,
     },
 
     /// Touch event has been received
@@ -536,6 +655,12 @@ pub enum WindowEvent {
     ///
     /// For more information about DPI in general, see the [`dpi`](crate::dpi) module.
     ScaleFactorChanged {
+        /// The scale factor the window had before this change.
+        ///
+        /// For the first change, this is the scale factor the window was created with. Dividing
+        /// `scale_factor` by it gives the ratio to rescale the sizes computed for the old scale
+        /// factor by, like fonts or a layout.
+        old_scale_factor: f64,
         scale_factor: f64,
         /// Handle to update inner size during scale changes.
         ///
@@ -550,9 +675,27 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
+    /// - **Android:** Emitted when the night mode of the device configuration changes, right
+    ///   after [`WindowEvent::ConfigurationChanged`].
+    /// - **Web:** Emitted when the `prefers-color-scheme` media query changes, e.g. when the user
+    ///   switches the dark mode of the system or of the browser.
+    /// - **iOS / X11 / Wayland / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
+    /// The configuration of the device changed, like its locale, its night mode or the
+    /// availability of a hardware keyboard.
+    ///
+    /// Only emitted when one of the fields of [`Configuration`] changed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Emitted for [`onConfigurationChanged`]. The whole configuration can be read
+    ///   with `WindowExtAndroid::config`.
+    /// - **iOS / macOS / Windows / X11 / Wayland / Web / Orbital:** Unsupported.
+    ///
+    /// [`onConfigurationChanged`]: https://developer.android.com/reference/android/app/Activity#onConfigurationChanged(android.content.res.Configuration)
+    ConfigurationChanged(Configuration),
+
     /// The window has been occluded (completely hidden from view).
     ///
     /// This is different to window visibility as it depends on whether the window is closed,
@@ -573,14 +716,78 @@ pub enum WindowEvent {
     ///
     /// ### Others
     ///
-    /// - **Web:** Doesn't take into account CSS [`border`], [`padding`], or [`transform`].
+    /// - **Web:** `Occluded(true)` is emitted when the canvas is scrolled out of the viewport,
+    ///   observed with an `IntersectionObserver`, or when the tab is hidden, e.g. when it's in the
+    ///   background or the browser is minimized. While occluded, the redraws requested with
+    ///   [`Window::request_redraw`] are deferred until the canvas is visible again, since browsers
+    ///   don't run animation frames in hidden tabs either. Doesn't take into account CSS
+    ///   [`border`], [`padding`], or [`transform`].
     /// - **Android / Wayland / Windows / Orbital:** Unsupported.
     ///
+    /// [`Window::request_redraw`]: crate::window::Window::request_redraw
     /// [`border`]: https://developer.mozilla.org/en-US/docs/Web/CSS/border
     /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Occluded(bool),
 
+    /// The window has been minimized or restored from being minimized.
+    ///
+    /// The parameter is `true` when the window was minimized and `false` when it was restored.
+    /// This matches the value returned by [`Window::is_minimized`] at the time the event is
+    /// delivered, and is also emitted when the change was requested with
+    /// [`Window::set_minimized`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Emitted when either the `WM_STATE` or the `_NET_WM_STATE` property changes.
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`Window::is_minimized`]: crate::window::Window::is_minimized
+    /// [`Window::set_minimized`]: crate::window::Window::set_minimized
+    Minimized(bool),
+
+    /// The window entered or left fullscreen.
+    ///
+    /// The parameter is `true` when the window is now fullscreen, matching
+    /// [`Window::fullscreen`] at the time the event is delivered.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted for the `fullscreenchange` event, which includes the user leaving
+    ///   fullscreen with Escape.
+    /// - **iOS / Android / macOS / Wayland / Windows / X11 / Orbital:** Unsupported.
+    ///
+    /// [`Window::fullscreen`]: crate::window::Window::fullscreen
+    FullscreenChanged(bool),
+
+    /// A request to make the window fullscreen with [`Window::set_fullscreen`] was denied.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted for the `fullscreenerror` event of a request made during a user
+    ///   activation. Requests made without one are retried on the next activation instead.
+    /// - **iOS / Android / macOS / Wayland / Windows / X11 / Orbital:** Unsupported.
+    ///
+    /// [`Window::set_fullscreen`]: crate::window::Window::set_fullscreen
+    FullscreenFailed,
+
+    /// The windowing system decided who draws the decorations of the window.
+    ///
+    /// This is emitted once the window is shown, and again whenever it changes. `server_side` is
+    /// `false` when the windowing system leaves the decorations to the client, in which case
+    /// the application has to draw them unless winit draws its own.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Reports the mode negotiated through `xdg-decoration-unstable-v1`, see
+    ///   [`WindowBuilderExtWayland::with_decoration_mode`]. Winit only draws client-side
+    ///   decorations with [`DecorationMode::PreferServerSide`].
+    /// - **iOS / Android / macOS / Web / Windows / X11 / Orbital:** Unsupported.
+    ///
+    /// [`WindowBuilderExtWayland::with_decoration_mode`]: crate::platform::wayland::WindowBuilderExtWayland::with_decoration_mode
+    /// [`DecorationMode::PreferServerSide`]: crate::platform::wayland::DecorationMode::PreferServerSide
+    DecorationsChanged { server_side: bool },
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in two scenarios:
@@ -588,9 +795,26 @@ pub enum WindowEvent {
     ///   resizing the window).
     /// - The application has explicitly requested a redraw via [`Window::request_redraw`].
     ///
-    /// Winit will aggregate duplicate redraw requests into a single event, to
-    /// help avoid duplicating rendering work.
-    RedrawRequested,
+    /// Winit emits at most one `RedrawRequested` per window in each iteration of the event loop,
+    /// aggregating duplicate redraw requests into a single event to help avoid duplicating
+    /// rendering work.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** `dirty_rects` holds the areas reported by `Expose` events.
+    /// - **Windows:** `dirty_rects` holds the update region of `WM_PAINT`.
+    /// - **macOS / iOS / Android / Web / Wayland / Orbital:** `dirty_rects` is always `None`.
+    RedrawRequested {
+        /// The parts of the window's client area that need to be redrawn, relative to its
+        /// top-left corner.
+        ///
+        /// `None` means the whole window should be assumed to be dirty, which is always the case
+        /// when the redraw was requested with [`Window::request_redraw`]. When several redraws
+        /// are coalesced into one event, their damage is merged.
+        ///
+        /// [`Window::request_redraw`]: crate::window::Window::request_redraw
+        dirty_rects: Option<Vec<PhysicalRect>>,
+    },
 }
 
 /// Identifier of an input device.
@@ -598,7 +822,12 @@ pub enum WindowEvent {
 /// Whenever you receive an event arising from a particular input device, this event contains a `DeviceId` which
 /// identifies its origin. Note that devices may be virtual (representing an on-screen cursor and keyboard focus) or
 /// physical. Virtual devices typically aggregate inputs from multiple physical devices.
+///
+/// With the `serde` feature, a `DeviceId` is serialized without its value and deserialized as
+/// [`DeviceId::dummy`], since it only has a meaning within the running event loop.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "()", into = "()"))]
 pub struct DeviceId(pub(crate) platform_impl::DeviceId);
 
 impl DeviceId {
@@ -615,6 +844,118 @@ impl DeviceId {
         #[allow(unused_unsafe)]
         DeviceId(unsafe { platform_impl::DeviceId::dummy() })
     }
+
+    /// Returns a numeric identifier of the device, which stays the same for as long as the device
+    /// remains connected.
+    ///
+    /// This is best-effort: the value may be reused for another device after this one got
+    /// removed, and doesn't persist across runs of the application.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The XInput device id.
+    /// - **Windows:** The raw input device handle.
+    /// - **Android:** The input device id.
+    /// - **Wayland / macOS / iOS / Web / Orbital:** Unsupported.
+    pub fn persistent_id(&self) -> Option<u64> {
+        self.0.persistent_id()
+    }
+}
+
+// The platform device ids can't be serialized, a deserialized `DeviceId` is always the dummy one.
+#[cfg(feature = "serde")]
+impl From<()> for DeviceId {
+    fn from(_: ()) -> Self {
+        unsafe { DeviceId::dummy() }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<DeviceId> for () {
+    fn from(_: DeviceId) -> Self {}
+}
+
+/// Information about an input device, see
+/// [`EventLoopWindowTarget::device_info`](crate::event_loop::EventLoopWindowTarget::device_info).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceInfo {
+    /// The name of the device as reported by the system.
+    pub name: String,
+    /// The device's kind.
+    pub kind: DeviceKind,
+    /// The USB vendor id of the device, if known.
+    pub vendor_id: Option<u16>,
+    /// The USB product id of the device, if known.
+    pub product_id: Option<u16>,
+    /// The analog axes of the device.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The XInput2 valuators of the device, named after their label.
+    /// - **Windows:** The relative X and Y axes of mice, and the values of the input reports of
+    ///   other HID devices, named after their usage when it is a common one.
+    pub axes: Vec<AxisInfo>,
+}
+
+/// The kind of an input device.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeviceKind {
+    Mouse,
+    Keyboard,
+    Touchpad,
+    Pen,
+    /// Any other kind of device, or one that couldn't be identified.
+    Other,
+}
+
+/// Whether an axis reports absolute positions or relative motion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AxisKind {
+    /// The axis reports a position within its range, like the pressure of a pen.
+    Absolute,
+    /// The axis reports motion since the last value, like a mouse.
+    Relative,
+}
+
+/// Information about an analog axis of a device, see [`DeviceInfo::axes`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AxisInfo {
+    /// The id of the axis in [`WindowEvent::AxisMotion`] and [`DeviceEvent::Motion`].
+    pub id: AxisId,
+    /// The name of the axis as reported by the system, like `"Abs Pressure"`, if known.
+    pub name: Option<String>,
+    pub kind: AxisKind,
+    /// The range of the raw values of the axis, if known.
+    pub range: Option<RangeInclusive<f64>>,
+}
+
+impl AxisInfo {
+    /// Maps a raw `value` of the axis to `0.0..=1.0`, or to `-1.0..=1.0` if its range includes
+    /// negative values.
+    ///
+    /// Returns `None` for relative axes and for axes without a known range.
+    pub fn normalize(&self, value: f64) -> Option<f64> {
+        let range = self
+            .range
+            .as_ref()
+            .filter(|_| self.kind == AxisKind::Absolute)?;
+        let (min, max) = (*range.start(), *range.end());
+        if min >= max {
+            return None;
+        }
+
+        let normalized = ((value - min) / (max - min)).clamp(0.0, 1.0);
+        if min < 0.0 {
+            Some(normalized * 2.0 - 1.0)
+        } else {
+            Some(normalized)
+        }
+    }
 }
 
 /// Represents raw hardware events that are not associated with any particular window.
@@ -626,8 +967,16 @@ impl DeviceId {
 ///
 /// Note that these events are delivered regardless of input focus.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeviceEvent {
-    Added,
+    /// A device got connected.
+    Added {
+        /// The information about the device, as returned by
+        /// [`EventLoopWindowTarget::device_info`](crate::event_loop::EventLoopWindowTarget::device_info)
+        /// when it got connected.
+        info: Option<DeviceInfo>,
+    },
+    /// A device got disconnected, its [`DeviceId`] may be reused for a new device afterwards.
     Removed,
 
     /// Change in physical position of a pointing device.
@@ -677,6 +1026,7 @@ pub struct RawKeyEvent {
 
 /// Describes a keyboard input targeting a window.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEvent {
     /// Represents the position of a key independent of the currently active layout.
     ///
@@ -703,6 +1053,18 @@ pub struct KeyEvent {
     /// `Fn` and `FnLock` key events are *exceedingly unlikely* to be emitted by Winit. These keys
     /// are usually handled at the hardware or OS level, and aren't surfaced to applications. If
     /// you somehow see this in the wild, we'd like to know :)
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Mapped from the [`code`] of the `KeyboardEvent`, which uses the same names as
+    ///   [`KeyCode`](keyboard::KeyCode), so the physical keys match the other platforms. The
+    ///   legacy names still reported by some browsers, like `OSLeft` for `MetaLeft` in older
+    ///   versions of Firefox, are mapped too. Virtual keyboards of mobile browsers often report
+    ///   an empty `code`, which gives [`PhysicalKey::Unidentified`](keyboard::PhysicalKey::Unidentified),
+    ///   and browsers differ on the few keys of the Intl and media clusters whose scancodes
+    ///   aren't standardized.
+    ///
+    /// [`code`]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/code
     pub physical_key: keyboard::PhysicalKey,
 
     // Allowing `broken_intra_doc_links` for `logical_key`, because
@@ -730,6 +1092,12 @@ pub struct KeyEvent {
     /// [`key_without_modifiers`]: crate::platform::modifier_supplement::KeyEventExtModifierSupplement::key_without_modifiers
     pub logical_key: keyboard::Key,
 
+    // Allowing `broken_intra_doc_links` for `text`, because
+    // `text_with_all_modifiers` is not available on all platforms
+    #[cfg_attr(
+        not(any(windows_platform, macos_platform, x11_platform, wayland_platform)),
+        allow(rustdoc::broken_intra_doc_links)
+    )]
     /// Contains the text produced by this keypress.
     ///
     /// In most cases this is identical to the content
@@ -748,7 +1116,13 @@ pub struct KeyEvent {
     /// This is `None` if the current keypress cannot
     /// be interpreted as text.
     ///
-    /// See also: `text_with_all_modifiers()`
+    /// Like `logical_key`, this is affected by all modifiers except <kbd>Ctrl</kbd>, on every
+    /// platform. For example <kbd>Ctrl</kbd>+<kbd>c</kbd> produces `Some("c")` rather than the
+    /// `"\x03"` control character, which makes this suitable for displaying shortcuts.
+    ///
+    /// See also: [`text_with_all_modifiers`].
+    ///
+    /// [`text_with_all_modifiers`]: crate::platform::modifier_supplement::KeyEventExtModifierSupplement::text_with_all_modifiers
     pub text: Option<SmolStr>,
 
     /// Contains the location of this key on the keyboard.
@@ -789,12 +1163,14 @@ pub struct KeyEvent {
 
 /// Describes keyboard modifiers event.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Modifiers {
     pub(crate) state: ModifiersState,
 
     // NOTE: Currently pressed modifiers keys.
     //
     // The field providing a metadata, it shouldn't be used as a source of truth.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) pressed_mods: ModifiersKeys,
 }
 
@@ -944,19 +1320,21 @@ pub enum TouchPhase {
 /// Represents a touch event
 ///
 /// Every time the user touches the screen, a new [`TouchPhase::Started`] event with an unique
-/// identifier for the finger is generated. When the finger is lifted, an [`TouchPhase::Ended`]
-/// event is generated with the same finger id.
+/// identifier for the finger is generated. After that there may be zero or more
+/// [`TouchPhase::Moved`] events when the finger is moved or the touch pressure changes, followed
+/// by exactly one [`TouchPhase::Ended`] or [`TouchPhase::Cancelled`] event with the same id:
 ///
-/// After a `Started` event has been emitted, there may be zero or more `Move`
-/// events when the finger is moved or the touch pressure changes.
+/// ```text
+/// Started -> Moved* -> (Ended | Cancelled)
+/// ```
 ///
-/// The finger id may be reused by the system after an `Ended` event. The user
-/// should assume that a new `Started` event received with the same id has nothing
-/// to do with the old finger and is a new finger.
+/// Winit doesn't hand out an id again while the touch it belongs to is active, and doesn't reuse
+/// ids of finished touches either, so overlapping touches can always be told apart.
 ///
 /// A [`TouchPhase::Cancelled`] event is emitted when the system has canceled tracking this
-/// touch, such as when the window loses focus, or on iOS if the user moves the
-/// device against their face.
+/// touch, such as when the window loses focus, when the contact was rejected as a palm, or on iOS
+/// if the user moves the device against their face. No `Ended` event follows, so any gesture
+/// state associated with the touch must be released when handling `Cancelled` as well.
 ///
 /// ## Platform-specific
 ///
@@ -967,6 +1345,7 @@ pub enum TouchPhase {
 /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
 /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Touch {
     pub device_id: DeviceId,
     pub phase: TouchPhase,
@@ -983,10 +1362,24 @@ pub struct Touch {
     pub force: Option<Force>,
     /// Unique identifier of a finger.
     pub id: u64,
+    /// Whether the platform detected that this contact is a palm rather than a finger.
+    ///
+    /// Applications that don't want palm input should ignore these touches. The platform usually
+    /// cancels a touch once it classifies it as a palm, so expect a [`TouchPhase::Cancelled`]
+    /// event shortly after.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Set for `WM_TOUCH` contacts with `TOUCHEVENTF_PALM`, and for pointer
+    ///   contacts that the system canceled.
+    /// - **Android:** Set for pointers with the palm tool type.
+    /// - **iOS / X11 / Wayland / Web:** Always `false`.
+    pub palm: bool,
 }
 
 /// Describes the force of a touch event
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Force {
     /// On iOS, the force is calibrated so that the same number corresponds to
     /// roughly the same amount of pressure on the screen regardless of the
@@ -1043,11 +1436,28 @@ impl Force {
 }
 
 /// Identifier for a specific analog axis on some device.
+///
+/// This is the index of the axis on the device, see [`AxisInfo`]. Mice report their horizontal
+/// motion on axis `0` and their vertical motion on axis `1`.
 pub type AxisId = u32;
 
 /// Identifier for a specific button on some device.
 pub type ButtonId = u32;
 
+/// The configuration of the device, see [`WindowEvent::ConfigurationChanged`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct Configuration {
+    /// The language of the device, with its region if any, as a BCP 47 tag like `en-US`.
+    pub locale: Option<String>,
+    /// The theme matching the night mode of the device, or `None` if it's undefined.
+    pub theme: Option<Theme>,
+    /// Whether the hardware keyboard is hidden, e.g. because a slide-out keyboard is closed, or
+    /// `None` if it's undefined.
+    pub hardware_keyboard_hidden: Option<bool>,
+}
+
 /// Describes the input state of a key.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1110,7 +1520,9 @@ pub enum MouseScrollDelta {
 /// Handle to synchroniously change the size of the window from the
 /// [`WindowEvent`].
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InnerSizeWriter {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) new_inner_size: Weak<Mutex<PhysicalSize<u32>>>,
 }
 
@@ -1164,6 +1576,11 @@ mod tests {
                 x(LoopExiting);
                 x(Suspended);
                 x(Resumed);
+                x(Timer(crate::event_loop::TimerId::next()));
+                x(PowerStateChanged(Some(crate::event_loop::PowerState {
+                    on_battery: true,
+                    battery_percent: Some(50.0),
+                })));
 
                 // Window events.
                 let with_window_event = |wev| {
@@ -1180,6 +1597,9 @@ mod tests {
                 with_window_event(Resized((0, 0).into()));
                 with_window_event(DroppedFile("x.txt".into()));
                 with_window_event(HoveredFile("x.txt".into()));
+                with_window_event(HoveredFileMoved {
+                    position: (0, 0).into(),
+                });
                 with_window_event(HoveredFileCancelled);
                 with_window_event(Ime(Enabled));
                 with_window_event(CursorMoved {
@@ -1205,9 +1625,14 @@ mod tests {
                     phase: event::TouchPhase::Started,
                 });
                 with_window_event(SmartMagnify { device_id: did });
-                with_window_event(TouchpadRotate {
+                with_window_event(RotationGesture {
                     device_id: did,
-                    delta: 0.0,
+                    delta_radians: 0.0,
+                    phase: event::TouchPhase::Started,
+                });
+                with_window_event(PanGesture {
+                    device_id: did,
+                    delta: (0.0, 0.0).into(),
                     phase: event::TouchPhase::Started,
                 });
                 with_window_event(TouchpadPressure {
@@ -1218,7 +1643,9 @@ mod tests {
                 with_window_event(AxisMotion {
                     device_id: did,
                     axis: 0,
+                    kind: event::AxisKind::Absolute,
                     value: 0.0,
+                    normalized: Some(0.0),
                 });
                 with_window_event(Touch(event::Touch {
                     device_id: did,
@@ -1226,9 +1653,14 @@ mod tests {
                     location: (0.0, 0.0).into(),
                     id: 0,
                     force: Some(event::Force::Normalized(0.0)),
+                    palm: false,
                 }));
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
                 with_window_event(Occluded(true));
+                with_window_event(Minimized(true));
+                with_window_event(FullscreenChanged(true));
+                with_window_event(FullscreenFailed);
+                with_window_event(DecorationsChanged { server_side: true });
             }
 
             #[allow(deprecated)]
@@ -1242,7 +1674,7 @@ mod tests {
                     })
                 };
 
-                with_device_event(Added);
+                with_device_event(Added { info: None });
                 with_device_event(Removed);
                 with_device_event(MouseMotion {
                     delta: (0.0, 0.0).into(),
@@ -1304,6 +1736,40 @@ mod tests {
         assert_eq!(force3.normalized(), 2.0);
     }
 
+    #[test]
+    fn test_axis_normalize() {
+        let pressure = event::AxisInfo {
+            id: 2,
+            name: Some("Abs Pressure".to_owned()),
+            kind: event::AxisKind::Absolute,
+            range: Some(0.0..=65535.0),
+        };
+        assert_eq!(pressure.normalize(0.0), Some(0.0));
+        assert_eq!(pressure.normalize(65535.0 / 4.0), Some(0.25));
+        assert_eq!(pressure.normalize(65535.0), Some(1.0));
+        assert_eq!(pressure.normalize(70000.0), Some(1.0));
+
+        let tilt = event::AxisInfo {
+            range: Some(-64.0..=64.0),
+            ..pressure.clone()
+        };
+        assert_eq!(tilt.normalize(-64.0), Some(-1.0));
+        assert_eq!(tilt.normalize(0.0), Some(0.0));
+        assert_eq!(tilt.normalize(32.0), Some(0.5));
+
+        let unknown = event::AxisInfo {
+            range: None,
+            ..pressure.clone()
+        };
+        assert_eq!(unknown.normalize(1.0), None);
+
+        let relative = event::AxisInfo {
+            kind: event::AxisKind::Relative,
+            ..pressure
+        };
+        assert_eq!(relative.normalize(1.0), None);
+    }
+
     #[allow(clippy::clone_on_copy)]
     #[test]
     fn ensure_attrs_do_not_panic() {
@@ -1330,6 +1796,7 @@ mod tests {
             location: (0.0, 0.0).into(),
             id: 0,
             force: Some(event::Force::Normalized(0.0)),
+            palm: false,
         }
         .clone();
         let _ = event::Force::Calibrated {
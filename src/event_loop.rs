@@ -7,11 +7,14 @@
 //!
 //! See the root-level documentation for information on how to create and use an event loop to
 //! handle events.
+use std::cell::RefCell;
 use std::marker::PhantomData;
 use std::ops::Deref;
 #[cfg(any(x11_platform, wayland_platform))]
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::{error, fmt};
 
 #[cfg(not(wasm_platform))]
@@ -19,8 +22,14 @@ use std::time::{Duration, Instant};
 #[cfg(wasm_platform)]
 use web_time::{Duration, Instant};
 
-use crate::error::EventLoopError;
-use crate::{event::Event, monitor::MonitorHandle, platform_impl};
+use crate::error::{EventLoopError, ExternalError, OsError};
+use crate::window::{Window, WindowAttributes, WindowBuilder};
+use crate::{
+    dpi::PhysicalRect,
+    event::{DeviceId, DeviceInfo, Event},
+    monitor::{MonitorHandle, MonitorId},
+    platform_impl,
+};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -39,9 +48,12 @@ use crate::{event::Event, monitor::MonitorHandle, platform_impl};
 /// [`Window`]: crate::window::Window
 pub struct EventLoop<T: 'static> {
     pub(crate) event_loop: platform_impl::EventLoop<T>,
+    recorder: Option<Recorder<T>>,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
+type Recorder<T> = Rc<RefCell<dyn FnMut(&Event<T>)>>;
+
 /// Target that associates windows with an [`EventLoop`].
 ///
 /// This type exists to allow you to create new windows while Winit executes
@@ -50,6 +62,8 @@ pub struct EventLoop<T: 'static> {
 /// `&EventLoop`.
 pub struct EventLoopWindowTarget<T: 'static> {
     pub(crate) p: platform_impl::EventLoopWindowTarget<T>,
+    /// The state of the wrapper of the event handler, kept across runs.
+    pub(crate) handler_state: Rc<crate::scheduler::HandlerState>,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -91,19 +105,31 @@ impl<T> EventLoopBuilder<T> {
     ///
     /// Calling this function will result in display backend initialisation.
     ///
-    /// ## Panics
+    /// If building fails, no event loop was created and this may be called again, e.g. after
+    /// moving to the main thread.
+    ///
+    /// ## Errors
     ///
-    /// Attempting to create the event loop off the main thread will panic. This
-    /// restriction isn't strictly necessary on all platforms, but is imposed to
-    /// eliminate any nasty surprises when porting to platforms that require it.
-    /// `EventLoopBuilderExt::any_thread` functions are exposed in the relevant
-    /// [`platform`] module if the target platform supports creating an event
-    /// loop on any thread.
+    /// Attempting to create the event loop off the main thread returns
+    /// [`EventLoopError::NotMainThread`]. This restriction isn't strictly necessary on all
+    /// platforms, but is imposed to eliminate any nasty surprises when porting to platforms that
+    /// require it. The `with_any_thread` functions of the `EventLoopBuilderExt*` traits in the
+    /// relevant [`platform`] module lift it where that's possible.
     ///
     /// ## Platform-specific
     ///
+    /// - **macOS:** Always requires the main thread, since `NSApplication` and AppKit in general
+    ///   may only be used from it.
+    /// - **iOS:** Always requires the main thread, since that's where UIKit runs the application.
+    /// - **Windows:** Requires the main thread unless
+    ///   `EventLoopBuilderExtWindows::with_any_thread` is used. Windows created by the event loop
+    ///   belong to the thread it was created on.
+    /// - **Wayland/X11:** Requires the main thread unless `with_any_thread` of
+    ///   `EventLoopBuilderExtWayland` or `EventLoopBuilderExtX11` is used. Neither protocol needs
+    ///   the main thread, the check only exists for portability.
     /// - **Wayland/X11:** to prevent running under `Wayland` or `X11` unset `WAYLAND_DISPLAY`
     ///                    or `DISPLAY` respectively when building the event loop.
+    /// - **Android / Web / Orbital:** No restriction is enforced.
     /// - **Android:** must be configured with an `AndroidApp` from `android_main()` by calling
     ///     [`.with_android_app(app)`] before calling `.build()`, otherwise it'll panic.
     ///
@@ -124,8 +150,17 @@ impl<T> EventLoopBuilder<T> {
 
         // Certain platforms accept a mutable reference in their API.
         #[allow(clippy::unnecessary_mut_passed)]
+        let event_loop = match platform_impl::EventLoop::new(&mut self.platform_specific) {
+            Ok(event_loop) => event_loop,
+            Err(err) => {
+                EVENT_LOOP_CREATED.store(false, Ordering::Relaxed);
+                return Err(err);
+            }
+        };
+
         Ok(EventLoop {
-            event_loop: platform_impl::EventLoop::new(&mut self.platform_specific)?,
+            event_loop,
+            recorder: None,
             _marker: PhantomData,
         })
     }
@@ -246,15 +281,140 @@ impl<T> EventLoop<T> {
     where
         F: FnMut(Event<T>, &EventLoopWindowTarget<T>),
     {
-        self.event_loop.run(event_handler)
+        let event_handler = self.recording(event_handler);
+        let state = self.event_loop.window_target().handler_state.clone();
+        self.event_loop
+            .run(crate::scheduler::wrap(state, event_handler))
+    }
+
+    /// Runs the event loop like [`run()`], but with an `event_handler` that can fail.
+    ///
+    /// The first error returned by `event_handler` makes the event loop exit, as if
+    /// [`EventLoopWindowTarget::exit()`] was called, and is then returned from this function.
+    /// The handler is still called for the remaining events, including
+    /// [`Event::LoopExiting`], to let the application clean up; errors it returns from those are
+    /// dropped.
+    ///
+    /// Errors of the event loop itself are converted into `E`.
+    ///
+    /// ```no_run
+    /// use winit::error::EventLoopError;
+    /// use winit::event::{Event, WindowEvent};
+    /// use winit::event_loop::EventLoop;
+    ///
+    /// #[derive(Debug)]
+    /// enum AppError {
+    ///     EventLoop(EventLoopError),
+    ///     Render(String),
+    /// }
+    ///
+    /// impl From<EventLoopError> for AppError {
+    ///     fn from(error: EventLoopError) -> Self {
+    ///         AppError::EventLoop(error)
+    ///     }
+    /// }
+    ///
+    /// # fn render() -> Result<(), String> { Ok(()) }
+    /// fn main() -> Result<(), AppError> {
+    ///     let event_loop = EventLoop::new()?;
+    ///     event_loop.try_run(|event, _elwt| {
+    ///         if let Event::WindowEvent {
+    ///             event: WindowEvent::RedrawRequested { .. },
+    ///             ..
+    ///         } = event
+    ///         {
+    ///             render().map_err(AppError::Render)?;
+    ///         }
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Web:** Like [`run()`], this never returns, so the error is dropped.
+    ///
+    /// [`run()`]: Self::run()
+    /// [`Event::LoopExiting`]: crate::event::Event::LoopExiting
+    #[inline]
+    #[cfg(not(all(wasm_platform, target_feature = "exception-handling")))]
+    pub fn try_run<E, F>(self, mut event_handler: F) -> Result<(), E>
+    where
+        F: FnMut(Event<T>, &EventLoopWindowTarget<T>) -> Result<(), E>,
+        E: From<EventLoopError>,
+    {
+        let mut error = None;
+        let result = self.run(|event, elwt| {
+            if let Err(err) = event_handler(event, elwt) {
+                if error.is_none() {
+                    error = Some(err);
+                    elwt.exit();
+                }
+            }
+        });
+
+        match error {
+            Some(err) => Err(err),
+            None => result.map_err(Into::into),
+        }
     }
 
     /// Creates an [`EventLoopProxy`] that can be used to dispatch user events to the main event loop.
+    ///
+    /// This can be called before the event loop is run, e.g. to hand out proxies to background
+    /// threads during setup. See [`EventLoopWindowTarget::create_proxy`] to create one from
+    /// within the event loop.
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
         EventLoopProxy {
             event_loop_proxy: self.event_loop.create_proxy(),
         }
     }
+
+    /// Calls `recorder` with every event right before it's passed to the event handler, to
+    /// record a trace of the events.
+    ///
+    /// The recorder sees the events exactly as the handler does, so a trace can be serialized
+    /// with the `serde` feature and replayed by a headless event loop with
+    #[cfg_attr(
+        any(x11_platform, wayland_platform),
+        doc = "[`EventLoopWindowTargetExtHeadless::replay_events`](crate::platform::headless::EventLoopWindowTargetExtHeadless::replay_events),"
+    )]
+    #[cfg_attr(
+        not(any(x11_platform, wayland_platform)),
+        doc = "`EventLoopWindowTargetExtHeadless::replay_events`,"
+    )]
+    /// which explains how the window and device ids are remapped. [`Event::NewEvents`] can't be
+    /// serialized and should be left out, like the other events generated by the event loop
+    /// itself, which aren't replayed.
+    ///
+    /// This is meant for debugging and regression tests, not to drive an application with
+    /// production input. A later call replaces the recorder.
+    ///
+    /// [`Event::NewEvents`]: crate::event::Event::NewEvents
+    pub fn with_recorder<R>(mut self, recorder: R) -> Self
+    where
+        R: FnMut(&Event<T>) + 'static,
+    {
+        self.recorder = Some(Rc::new(RefCell::new(recorder)));
+        self
+    }
+
+    /// Wraps `event_handler` to pass the events to the recorder first.
+    pub(crate) fn recording<F>(
+        &self,
+        mut event_handler: F,
+    ) -> impl FnMut(Event<T>, &EventLoopWindowTarget<T>)
+    where
+        F: FnMut(Event<T>, &EventLoopWindowTarget<T>),
+    {
+        let recorder = self.recorder.clone();
+        move |event, elwt| {
+            if let Some(recorder) = &recorder {
+                (recorder.borrow_mut())(&event);
+            }
+            event_handler(event, elwt);
+        }
+    }
 }
 
 #[cfg(feature = "rwh_06")]
@@ -308,6 +468,115 @@ impl<T> Deref for EventLoop<T> {
 }
 
 impl<T> EventLoopWindowTarget<T> {
+    /// Creates a new window with the given attributes.
+    ///
+    /// This is the preferred way to create windows from the event loop callback, for example in
+    /// response to [`Event::Resumed`]. It's equivalent to building a [`WindowBuilder`] with the
+    /// same attributes and the default platform-specific attributes; use
+    /// [`WindowBuilder::build`] with the platform extension traits to set those.
+    ///
+    /// Returns an error once [`exit()`](Self::exit) has been called.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Returns an error when called before [`Event::Resumed`] or after
+    ///   [`Event::Suspended`], since the native window only exists in between.
+    /// - **iOS:** Windows should only be created after [`Event::Resumed`].
+    ///
+    /// [`Event::Resumed`]: crate::event::Event::Resumed
+    /// [`Event::Suspended`]: crate::event::Event::Suspended
+    #[inline]
+    pub fn create_window(&self, attributes: WindowAttributes) -> Result<Window, OsError> {
+        #[cfg(android_platform)]
+        if !self.p.has_native_window() {
+            return Err(os_error!(platform_impl::OsError::NoNativeWindow));
+        }
+
+        WindowBuilder {
+            window: attributes,
+            platform_specific: Default::default(),
+        }
+        .build(self)
+    }
+
+    /// Creates an [`EventLoopProxy`] that can be used to dispatch user events to the main event loop.
+    ///
+    /// This is the same as [`EventLoop::create_proxy`], for when the [`EventLoop`] itself isn't
+    /// available anymore because it is running.
+    #[inline]
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy {
+            event_loop_proxy: self.p.create_proxy(),
+        }
+    }
+
+    /// Creates a timer which emits [`Event::Timer`] once `interval` elapsed, and then every
+    /// `interval` if `repeat` is `true`, until it's cancelled with
+    /// [`cancel_timer`](Self::cancel_timer).
+    ///
+    /// Winit wakes the event loop up for the timers, so they work with every [`ControlFlow`]: the
+    /// control flow set by the application is only ever shortened to meet the next deadline, and
+    /// [`ControlFlow::WaitUntil`] isn't needed to implement them anymore.
+    ///
+    /// Timers are best-effort, not real-time: the events are emitted on the thread running the
+    /// event loop, in loop order right before [`Event::AboutToWait`], so they're late by however
+    /// long the current iteration takes and by the precision of the system's wait. The ticks of a
+    /// repeating timer missed this way are skipped rather than emitted in a burst. Timers due in
+    /// the same iteration are emitted by deadline, and no timer is emitted once
+    /// [`exit()`](Self::exit) has been called.
+    #[inline]
+    pub fn create_timer(&self, interval: Duration, repeat: bool) -> TimerId {
+        let timer_id = TimerId::next();
+        self.handler_state.scheduler.lock().unwrap().create_timer(
+            timer_id,
+            interval,
+            repeat,
+            Instant::now(),
+        );
+        timer_id
+    }
+
+    /// Cancels a timer created with [`create_timer`](Self::create_timer).
+    ///
+    /// No more [`Event::Timer`] is emitted for it, even if it was already due in the current
+    /// iteration. Does nothing if the timer was a one-shot timer which already fired, or was
+    /// cancelled already.
+    #[inline]
+    pub fn cancel_timer(&self, timer_id: TimerId) {
+        self.handler_state
+            .scheduler
+            .lock()
+            .unwrap()
+            .cancel_timer(timer_id)
+    }
+
+    /// Limits how often each window is redrawn because of [`Window::request_redraw`] to once
+    /// every `throttle`, or removes the limit with `None`, which is the default.
+    ///
+    /// An application which requests a redraw of its windows from every
+    /// [`WindowEvent::RedrawRequested`] otherwise makes the event loop spin as fast as it can
+    /// with [`ControlFlow::Wait`], emitting [`Event::AboutToWait`] and redrawing for nothing
+    /// more than the system shows. With a throttle, the requests made sooner than `throttle`
+    /// after the last redraw of the window are held back, and the event loop waits until they're
+    /// due instead. Setting it to the frame interval of the slowest monitor the windows are on,
+    /// see [`MonitorHandle::refresh_rate_millihertz`], caps the idle CPU usage while keeping the
+    /// animations smooth. Events from the system and redraws it requests are never delayed.
+    ///
+    /// This is orthogonal to [`ControlFlow::WaitUntil`], which still wakes the event loop up at
+    /// its deadline, and is ignored with [`ControlFlow::Poll`]. Requests made from other threads
+    /// while the event loop is waiting are made right away.
+    ///
+    /// [`Window::request_redraw`]: crate::window::Window::request_redraw
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    #[inline]
+    pub fn set_wait_throttle(&self, throttle: Option<Duration>) {
+        self.handler_state
+            .scheduler
+            .lock()
+            .unwrap()
+            .set_wait_throttle(throttle)
+    }
+
     /// Returns the list of all the monitors available on the system.
     #[inline]
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
@@ -318,6 +587,15 @@ impl<T> EventLoopWindowTarget<T> {
             .map(|inner| MonitorHandle { inner })
     }
 
+    /// Returns the available monitor with the given [`MonitorId`], see [`MonitorHandle::id`].
+    ///
+    /// If the monitor moved, for instance because another one was plugged in, the monitor with
+    /// the same name is returned. Returns `None` if there's none.
+    #[inline]
+    pub fn monitor_from_id(&self, id: &MonitorId) -> Option<MonitorHandle> {
+        id.find(self.available_monitors())
+    }
+
     /// Returns the primary monitor of the system.
     ///
     /// Returns `None` if it can't identify any monitor as a primary one.
@@ -332,6 +610,111 @@ impl<T> EventLoopWindowTarget<T> {
             .map(|inner| MonitorHandle { inner })
     }
 
+    /// Returns whether the system runs on battery, and how much charge is left.
+    ///
+    /// This is best-effort: it returns `None` on systems without a battery, such as most
+    /// desktops, and where the information isn't available. Use
+    /// [`listen_power_events`](Self::listen_power_events) to be notified when it changes.
+    ///
+    /// With several batteries, the charge is the one of all of them together.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `GetSystemPowerStatus`.
+    /// - **macOS:** Uses the IOKit power sources.
+    /// - **X11 / Wayland:** With the `upower` feature, queries the display device of UPower over
+    ///   D-Bus, and falls back to reading the power supplies of `/sys/class/power_supply`, which
+    ///   only exists on Linux, when UPower isn't running. Without the feature, only reads the
+    ///   power supplies. The batteries of peripherals, like wireless mice, are ignored.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn power_state(&self) -> Option<PowerState> {
+        let state = self.p.power_state();
+        self.handler_state.power.borrow_mut().queried(state);
+        state
+    }
+
+    /// Whether to emit [`Event::PowerStateChanged`] when the [power state](Self::power_state)
+    /// changes, which is disabled by default.
+    ///
+    /// While enabled, the power state is checked every few seconds, which wakes up the event
+    /// loop even with [`ControlFlow::Wait`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The state is also checked right away when the system notifies a change.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    pub fn listen_power_events(&self, listen: bool) {
+        let state = if listen { self.p.power_state() } else { None };
+        self.handler_state
+            .power
+            .borrow_mut()
+            .listen(listen, state, Instant::now());
+    }
+
+    /// Returns the backend the event loop runs on, to branch on it at runtime.
+    ///
+    /// This is the backend actually selected when the event loop was built, which on X11 and
+    /// Wayland systems depends on the session and on
+    /// [`EventLoopBuilderExtBackend::with_linux_backend_preference`]: an X11 application can run
+    /// on Xwayland in a Wayland session, for instance. It's the same for the whole lifetime of
+    /// the event loop.
+    ///
+    /// [`EventLoopBuilderExtBackend::with_linux_backend_preference`]: crate::platform::backend::EventLoopBuilderExtBackend::with_linux_backend_preference
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        self.p.backend()
+    }
+
+    /// Confines the cursor to a rectangle of the desktop, or releases it with `None`.
+    ///
+    /// The rectangle is in the physical coordinates of the desktop, the ones of
+    /// [`MonitorHandle::position`], and doesn't have to be within a window of the application,
+    /// unlike [`Window::set_cursor_grab`] which confines the cursor to a window. The clip is
+    /// released while no window of the application is focused, and applied again once one of
+    /// them is. A cursor grab of a window replaces the clip while it's active.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `ClipCursor`.
+    /// - **X11:** Grabs the pointer, confined to an input-only window covering the rectangle, so
+    ///   the other applications don't get pointer events while the cursor is clipped.
+    /// - **macOS:** There's no API to confine the cursor, so it's warped back into the rectangle
+    ///   whenever it leaves it, which is checked up to 120 times per second.
+    /// - **Wayland:** Always returns an [`ExternalError::NotSupported`], as the cursor can only be
+    ///   confined to a surface, see [`Window::set_cursor_grab`].
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    ///
+    /// [`MonitorHandle::position`]: crate::monitor::MonitorHandle::position
+    /// [`Window::set_cursor_grab`]: crate::window::Window::set_cursor_grab
+    pub fn clip_cursor(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        // While released, the new rectangle is applied once a window is focused again.
+        if rect.is_none() || !self.handler_state.cursor_clip.borrow().is_released() {
+            self.p.clip_cursor(rect)?;
+        }
+        self.handler_state.cursor_clip.borrow_mut().set(rect);
+        Ok(())
+    }
+
+    /// Returns information about the device with the given id.
+    ///
+    /// This is best-effort and returns `None` if the device is unknown, for example after it got
+    /// removed. The information is also attached to the [`DeviceEvent::Added`] of the device.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The name and kind come from XInput, the vendor and product ids from the
+    ///   `Device Product ID` property that the evdev and libinput drivers set.
+    /// - **Windows:** Uses the raw input device information. For mice and keyboards the vendor
+    ///   and product ids are parsed from the device path.
+    /// - **Wayland / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`DeviceEvent::Added`]: crate::event::DeviceEvent::Added
+    #[inline]
+    pub fn device_info(&self, device_id: DeviceId) -> Option<DeviceInfo> {
+        self.p.device_info(device_id.0)
+    }
+
     /// Change if or when [`DeviceEvent`]s are captured.
     ///
     /// Since the [`DeviceEvent`] capture can lead to high CPU usage for unfocused windows, winit
@@ -353,20 +736,37 @@ impl<T> EventLoopWindowTarget<T> {
     }
 
     /// Gets the current [`ControlFlow`].
+    ///
+    /// This is the control flow requested by the application, even if winit waits for an earlier
+    /// deadline between iterations for the timers created with [`Self::create_timer`] and the
+    /// redraws scheduled with [`Window::set_redraw_hint`](crate::window::Window::set_redraw_hint).
     pub fn control_flow(&self) -> ControlFlow {
         self.p.control_flow()
     }
 
     /// This exits the event loop.
     ///
-    /// See [`LoopExiting`](Event::LoopExiting).
+    /// The event loop doesn't stop immediately: events that were already queued, such as the
+    /// ones of the current iteration, may still be delivered. Then [`LoopExiting`] is emitted
+    /// exactly once, and [`EventLoop::run`] returns. Calling `exit()` more than once, including
+    /// from the [`LoopExiting`] handler, has no further effect.
+    ///
+    /// New windows can't be created once this was called.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Unsupported, iOS apps can't quit programmatically.
+    ///
+    /// [`LoopExiting`]: Event::LoopExiting
     pub fn exit(&self) {
         self.p.exit()
     }
 
     /// Returns if the [`EventLoop`] is about to stop.
     ///
-    /// See [`exit()`](Self::exit).
+    /// This is `true` from the moment [`exit()`](Self::exit) is called, so it can be used to tell
+    /// whether events delivered before [`LoopExiting`](Event::LoopExiting) are part of the
+    /// shutdown.
     pub fn exiting(&self) -> bool {
         self.p.exiting()
     }
@@ -390,6 +790,13 @@ unsafe impl<T> rwh_05::HasRawDisplayHandle for EventLoopWindowTarget<T> {
 }
 
 /// Used to send custom events to [`EventLoop`].
+///
+/// A proxy is [`Send`] as long as `T` is, so it can be moved to other threads. It isn't
+/// guaranteed to be [`Sync`] though: [`Clone`] it for every thread that needs one instead, which
+/// is cheap since it only clones a channel sender and a handle to wake the event loop up.
+///
+/// A proxy can outlive its [`EventLoop`], in which case sending events fails with
+/// [`EventLoopClosed`].
 pub struct EventLoopProxy<T: 'static> {
     event_loop_proxy: platform_impl::EventLoopProxy<T>,
 }
@@ -407,12 +814,68 @@ impl<T: 'static> EventLoopProxy<T> {
     /// `UserEvent(event)` event in the event loop, where `event` is the value passed to this
     /// function.
     ///
-    /// Returns an `Err` if the associated [`EventLoop`] no longer exists.
+    /// Returns an `Err` containing `event` if the associated [`EventLoop`] no longer exists, e.g.
+    /// once [`EventLoop::run`] returned, so that the caller can recover it. Events sent while an
+    /// event loop isn't running, before it starts or in between calls to
+    /// `EventLoopExtRunOnDemand::run_on_demand`, are delivered once it runs again.
+    ///
+    /// The user events are delivered in the order they were sent in, by all the proxies of the
+    /// event loop: the events sent from one thread are never reordered, and the events sent from
+    /// several threads are delivered in the order the calls to this function completed in. There's
+    /// no ordering guarantee relative to the events coming from the system, which are queued by
+    /// the system; see [`send_sequenced`](Self::send_sequenced) to tell when an event was sent.
     ///
     /// [`UserEvent(event)`]: Event::UserEvent
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         self.event_loop_proxy.send_event(event)
     }
+
+    /// Wake up the [`EventLoop`] from which this proxy was created, without emitting a
+    /// [`UserEvent`].
+    ///
+    /// This causes the event loop to run an iteration, ending with [`AboutToWait`], even when
+    /// there's nothing else to process. It's cheaper than sending a dummy event through
+    /// [`send_event`] since it doesn't queue or allocate anything, which makes it the way for an
+    /// `EventLoop<()>` to be woken up from another thread.
+    ///
+    /// Multiple calls made before the event loop gets to run are coalesced into a single wake up.
+    ///
+    /// Does nothing if the associated [`EventLoop`] no longer exists.
+    ///
+    /// [`UserEvent`]: Event::UserEvent
+    /// [`AboutToWait`]: Event::AboutToWait
+    /// [`send_event`]: Self::send_event
+    pub fn wake_up(&self) {
+        self.event_loop_proxy.wake_up()
+    }
+}
+
+impl<T: 'static> EventLoopProxy<Sequenced<T>> {
+    /// Sends `event` like [`send_event`](Self::send_event), along with a sequence number and the
+    /// time it was sent at.
+    ///
+    /// The sequence numbers increase with every event sent with this function, from any thread,
+    /// and the events are delivered in the order of their sequence numbers. The time can be used
+    /// to interleave them with the input events, by comparing it with the time the application
+    /// received those at.
+    ///
+    /// Returns the sequence number of the event.
+    pub fn send_sequenced(&self, event: T) -> Result<u64, EventLoopClosed<T>> {
+        static NEXT_SEQUENCE: Mutex<u64> = Mutex::new(0);
+
+        // Sending with the lock held makes the channel order match the sequence numbers.
+        let mut next_sequence = NEXT_SEQUENCE.lock().unwrap();
+        let sequence = *next_sequence;
+        let sequenced = Sequenced {
+            sequence,
+            sent_at: Instant::now(),
+            event,
+        };
+        self.send_event(sequenced)
+            .map_err(|EventLoopClosed(sequenced)| EventLoopClosed(sequenced.event))?;
+        *next_sequence += 1;
+        Ok(sequence)
+    }
 }
 
 impl<T: 'static> fmt::Debug for EventLoopProxy<T> {
@@ -421,6 +884,27 @@ impl<T: 'static> fmt::Debug for EventLoopProxy<T> {
     }
 }
 
+/// The power source of the system, see [`EventLoopWindowTarget::power_state`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerState {
+    /// Whether the system runs on its battery rather than on external power.
+    pub on_battery: bool,
+    /// The charge left, from `0.0` to `100.0`, if known.
+    pub battery_percent: Option<f32>,
+}
+
+/// A user event sent with [`EventLoopProxy::send_sequenced`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sequenced<T> {
+    /// The order the event was sent in, among the events sent with
+    /// [`EventLoopProxy::send_sequenced`].
+    pub sequence: u64,
+    /// When the event was sent.
+    pub sent_at: Instant,
+    pub event: T,
+}
+
 /// The error that is returned when an [`EventLoopProxy`] attempts to wake up an [`EventLoop`] that
 /// no longer exists.
 ///
@@ -436,6 +920,30 @@ impl<T> fmt::Display for EventLoopClosed<T> {
 
 impl<T: fmt::Debug> error::Error for EventLoopClosed<T> {}
 
+/// The backend an event loop runs on, see [`EventLoopWindowTarget::backend`].
+///
+/// On X11 and Wayland systems, it's picked according to the [`BackendPreference`] of the event
+/// loop builder.
+///
+/// [`BackendPreference`]: crate::platform::backend::BackendPreference
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    X11,
+    Wayland,
+    Windows,
+    MacOS,
+    Android,
+    IOS,
+    Web,
+    Orbital,
+    /// The backend without any display server of
+    /// [`EventLoopBuilderExtHeadless::with_headless`].
+    ///
+    /// [`EventLoopBuilderExtHeadless::with_headless`]: crate::platform::headless::EventLoopBuilderExtHeadless::with_headless
+    Headless,
+}
+
 /// Control when device events are captured.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub enum DeviceEvents {
@@ -448,6 +956,20 @@ pub enum DeviceEvents {
     Never,
 }
 
+/// Identifier of a timer created with [`EventLoopWindowTarget::create_timer`].
+///
+/// Unique for each timer, even once the timer got cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimerId(u64);
+
+impl TimerId {
+    pub(crate) fn next() -> Self {
+        static CURRENT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(CURRENT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 /// A unique identifier of the winit's async request.
 ///
 /// This could be used to identify the async request once it's done
@@ -458,6 +980,7 @@ pub enum DeviceEvents {
 /// Then once event is arriving the working list is being traversed and a job
 /// executed and removed from the list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AsyncRequestSerial {
     serial: usize,
 }
@@ -0,0 +1,269 @@
+//! Releasing of the keys and mouse buttons held when a window loses focus.
+//!
+//! Once a window is unfocused, the system sends the releases of the keys and buttons held to
+//! the window which has the focus now, so the application would consider them held forever.
+//! Some backends already report key releases for the keys held, the others are completed here:
+//! every key and mouse button still held in a window is released right before
+//! [`WindowEvent::Focused(false)`](WindowEvent::Focused), and the late releases from the system
+//! are then dropped.
+
+use std::rc::Rc;
+
+use crate::event::{DeviceId, ElementState, Event, KeyEvent, MouseButton, WindowEvent};
+use crate::event_loop::EventLoopWindowTarget;
+use crate::keyboard::PhysicalKey;
+use crate::scheduler::HandlerState;
+use crate::window::WindowId;
+
+/// Wraps the application's event handler to release the keys and buttons held in the windows
+/// which lose focus.
+pub(crate) fn wrap<T, F>(
+    state: Rc<HandlerState>,
+    mut event_handler: F,
+) -> impl FnMut(Event<T>, &EventLoopWindowTarget<T>)
+where
+    F: FnMut(Event<T>, &EventLoopWindowTarget<T>),
+{
+    move |event, elwt| {
+        let (released, event) = state.held_input.borrow_mut().process(event);
+        for (window_id, event) in released {
+            event_handler(Event::WindowEvent { window_id, event }, elwt);
+        }
+        if let Some(event) = event {
+            event_handler(event, elwt);
+        }
+    }
+}
+
+type Synthesized = Vec<(WindowId, WindowEvent)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Key(PhysicalKey),
+    Button(MouseButton),
+}
+
+#[derive(Default)]
+pub(crate) struct HeldInput {
+    /// The keys and buttons held in a window, with the last press of the keys.
+    held: Vec<(WindowId, DeviceId, Input, Option<KeyEvent>)>,
+    /// The keys and buttons we synthesized a release for, whose next release from the system is
+    /// thus already reported.
+    released: Vec<(WindowId, DeviceId, Input)>,
+}
+
+impl HeldInput {
+    /// Returns the releases to emit before `event`, and `event` unless it's dropped.
+    fn process<T>(&mut self, event: Event<T>) -> (Synthesized, Option<Event<T>>) {
+        let mut released = Vec::new();
+        let (window_id, window_event) = match &event {
+            Event::WindowEvent { window_id, event } => (*window_id, event),
+            _ => return (released, Some(event)),
+        };
+
+        let keep = match window_event {
+            WindowEvent::KeyboardInput {
+                device_id, event, ..
+            } => self.input(
+                window_id,
+                *device_id,
+                Input::Key(event.physical_key),
+                event.state,
+                Some(event),
+            ),
+            WindowEvent::MouseInput {
+                device_id,
+                state,
+                button,
+            } => self.input(window_id, *device_id, Input::Button(*button), *state, None),
+            WindowEvent::Focused(true) => {
+                self.released
+                    .retain(|&(released, ..)| released != window_id);
+                true
+            }
+            WindowEvent::Focused(false) => {
+                self.release_window(window_id, &mut released);
+                true
+            }
+            WindowEvent::Destroyed => {
+                self.held.retain(|&(held, ..)| held != window_id);
+                self.released
+                    .retain(|&(released, ..)| released != window_id);
+                true
+            }
+            _ => true,
+        };
+
+        (released, keep.then_some(event))
+    }
+
+    /// Tracks a press or release, and returns whether to emit it.
+    fn input(
+        &mut self,
+        window_id: WindowId,
+        device_id: DeviceId,
+        input: Input,
+        state: ElementState,
+        key_event: Option<&KeyEvent>,
+    ) -> bool {
+        let id = (window_id, device_id, input);
+        let len = self.released.len();
+        self.released.retain(|&released| released != id);
+        let already_released = self.released.len() != len;
+
+        let held = self
+            .held
+            .iter()
+            .position(|&(window, device, held, _)| (window, device, held) == id);
+        match (state, held) {
+            (ElementState::Pressed, Some(index)) => self.held[index].3 = key_event.cloned(),
+            (ElementState::Pressed, None) => {
+                self.held
+                    .push((window_id, device_id, input, key_event.cloned()));
+            }
+            (ElementState::Released, Some(index)) => {
+                self.held.swap_remove(index);
+            }
+            (ElementState::Released, None) => return !already_released,
+        }
+        true
+    }
+
+    /// Synthesizes a release for every key and button held in the window.
+    fn release_window(&mut self, window_id: WindowId, events: &mut Synthesized) {
+        let released = &mut self.released;
+        self.held
+            .retain_mut(|(window, device_id, input, key_event)| {
+                if *window != window_id {
+                    return true;
+                }
+                released.push((window_id, *device_id, *input));
+                let event = match (*input, key_event.take()) {
+                    (Input::Key(_), Some(mut event)) => {
+                        event.state = ElementState::Released;
+                        event.repeat = false;
+                        event.text = None;
+                        WindowEvent::KeyboardInput {
+                            device_id: *device_id,
+                            event,
+                            is_synthetic: true,
+                        }
+                    }
+                    (Input::Button(button), _) => WindowEvent::MouseInput {
+                        device_id: *device_id,
+                        state: ElementState::Released,
+                        button,
+                    },
+                    (Input::Key(_), None) => unreachable!("keys are held with their press"),
+                };
+                events.push((window_id, event));
+                false
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window_event(event: WindowEvent) -> Event<()> {
+        Event::WindowEvent {
+            window_id: WindowId::from(1),
+            event,
+        }
+    }
+
+    /// Processes the events, and returns the window events emitted for them.
+    fn process(held: &mut HeldInput, events: Vec<WindowEvent>) -> Vec<WindowEvent> {
+        let mut emitted = Vec::new();
+        for event in events {
+            let (released, event) = held.process(window_event(event));
+            emitted.extend(released.into_iter().map(|(_, event)| event));
+            if let Some(Event::WindowEvent { event, .. }) = event {
+                emitted.push(event);
+            }
+        }
+        emitted
+    }
+
+    fn mouse_input(state: ElementState, button: MouseButton) -> WindowEvent {
+        WindowEvent::MouseInput {
+            device_id: unsafe { DeviceId::dummy() },
+            state,
+            button,
+        }
+    }
+
+    #[test]
+    fn held_buttons_are_released_before_focus_loss() {
+        let mut held = HeldInput::default();
+
+        let emitted = process(
+            &mut held,
+            vec![
+                mouse_input(ElementState::Pressed, MouseButton::Left),
+                mouse_input(ElementState::Pressed, MouseButton::Right),
+                mouse_input(ElementState::Released, MouseButton::Right),
+                WindowEvent::Focused(false),
+                // The system reports the release late, when it does.
+                mouse_input(ElementState::Released, MouseButton::Left),
+            ],
+        );
+        assert_eq!(
+            emitted,
+            [
+                mouse_input(ElementState::Pressed, MouseButton::Left),
+                mouse_input(ElementState::Pressed, MouseButton::Right),
+                mouse_input(ElementState::Released, MouseButton::Right),
+                mouse_input(ElementState::Released, MouseButton::Left),
+                WindowEvent::Focused(false),
+            ]
+        );
+    }
+
+    // Key events can only be built with the platform-specific fields of the backend.
+    #[cfg(any(x11_platform, wayland_platform))]
+    #[test]
+    fn held_keys_are_released_synthetically() {
+        use crate::keyboard::{Key, KeyCode, KeyLocation};
+        use crate::platform_impl::KeyEventExtra;
+
+        let key_event = |state, repeat| KeyEvent {
+            physical_key: PhysicalKey::Code(KeyCode::KeyW),
+            logical_key: Key::Character("w".into()),
+            text: (state == ElementState::Pressed).then(|| "w".into()),
+            location: KeyLocation::Standard,
+            state,
+            repeat,
+            platform_specific: KeyEventExtra {
+                key_without_modifiers: Key::Character("w".into()),
+                text_with_all_modifiers: None,
+            },
+        };
+        let keyboard_input = |event, is_synthetic| WindowEvent::KeyboardInput {
+            device_id: unsafe { DeviceId::dummy() },
+            event,
+            is_synthetic,
+        };
+        let mut held = HeldInput::default();
+
+        let emitted = process(
+            &mut held,
+            vec![
+                keyboard_input(key_event(ElementState::Pressed, false), false),
+                keyboard_input(key_event(ElementState::Pressed, true), false),
+                WindowEvent::Focused(false),
+                keyboard_input(key_event(ElementState::Released, false), false),
+            ],
+        );
+        assert_eq!(
+            emitted,
+            [
+                keyboard_input(key_event(ElementState::Pressed, false), false),
+                keyboard_input(key_event(ElementState::Pressed, true), false),
+                keyboard_input(key_event(ElementState::Released, false), true),
+                WindowEvent::Focused(false),
+            ]
+        );
+    }
+}
@@ -12,20 +12,33 @@ pub(crate) struct Pixel {
 
 pub(crate) const PIXEL_SIZE: usize = mem::size_of::<Pixel>();
 
+/// The largest width and height of an icon, see [`Icon::MAX_SIZE`].
+#[cfg(windows_platform)]
+const MAX_SIZE: u32 = 256;
+#[cfg(not(windows_platform))]
+const MAX_SIZE: u32 = 1024;
+
 #[derive(Debug)]
+#[non_exhaustive]
 /// An error produced when using [`Icon::from_rgba`] with invalid arguments.
 pub enum BadIcon {
     /// Produced when the length of the `rgba` argument isn't divisible by 4, thus `rgba` can't be
     /// safely interpreted as 32bpp RGBA pixels.
     ByteCountNotDivisibleBy4 { byte_count: usize },
-    /// Produced when the number of pixels (`rgba.len() / 4`) isn't equal to `width * height`.
+    /// Produced when the length of the `rgba` argument isn't `width * height * 4`.
     /// At least one of your arguments is incorrect.
     DimensionsVsPixelCount {
         width: u32,
         height: u32,
-        width_x_height: usize,
-        pixel_count: usize,
+        /// The length of `rgba`, in bytes.
+        got_len: usize,
+        /// The length `rgba` needs for `width` and `height`, in bytes.
+        expected_len: usize,
     },
+    /// Produced when the width or the height is zero.
+    ZeroSized,
+    /// Produced when the width or the height is larger than [`Icon::MAX_SIZE`].
+    TooLarge { max: u32 },
     /// Produced when underlying OS functionality failed to create the icon
     OsError(io::Error),
 }
@@ -39,10 +52,14 @@ impl fmt::Display for BadIcon {
             BadIcon::DimensionsVsPixelCount {
                 width,
                 height,
-                width_x_height,
-                pixel_count,
+                got_len,
+                expected_len,
             } => write!(f,
-                "The specified dimensions ({width:?}x{height:?}) don't match the number of pixels supplied by the `rgba` argument ({pixel_count:?}). For those dimensions, the expected pixel count is {width_x_height:?}.",
+                "The specified dimensions ({width:?}x{height:?}) don't match the length of the `rgba` argument ({got_len:?} bytes). For those dimensions, the expected length is {expected_len:?} bytes.",
+            ),
+            BadIcon::ZeroSized => write!(f, "The icon has a width or a height of zero."),
+            BadIcon::TooLarge { max } => write!(f,
+                "The icon is larger than the maximum size of {max:?}x{max:?} supported on this platform.",
             ),
             BadIcon::OsError(e) => write!(f, "OS error when instantiating the icon: {e:?}"),
         }
@@ -68,18 +85,25 @@ mod constructors {
 
     impl RgbaIcon {
         pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+            if width == 0 || height == 0 {
+                return Err(BadIcon::ZeroSized);
+            }
+            if width > MAX_SIZE || height > MAX_SIZE {
+                return Err(BadIcon::TooLarge { max: MAX_SIZE });
+            }
             if rgba.len() % PIXEL_SIZE != 0 {
                 return Err(BadIcon::ByteCountNotDivisibleBy4 {
                     byte_count: rgba.len(),
                 });
             }
-            let pixel_count = rgba.len() / PIXEL_SIZE;
-            if pixel_count != (width * height) as usize {
+            // Can't overflow with the size limited to `MAX_SIZE`.
+            let expected_len = width as usize * height as usize * PIXEL_SIZE;
+            if rgba.len() != expected_len {
                 Err(BadIcon::DimensionsVsPixelCount {
                     width,
                     height,
-                    width_x_height: (width * height) as usize,
-                    pixel_count,
+                    got_len: rgba.len(),
+                    expected_len,
                 })
             } else {
                 Ok(RgbaIcon {
@@ -113,13 +137,238 @@ impl fmt::Debug for Icon {
 }
 
 impl Icon {
+    /// The largest width and height of an icon, in pixels.
+    ///
+    /// Larger images have to be scaled down before creating the icon.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** 256, the largest size of the icons of the shell.
+    /// - **Other platforms:** 1024, which keeps the `_NET_WM_ICON` property of X11 within the
+    ///   maximum request length of most servers. The platforms without window icons validate the
+    ///   icon the same way.
+    pub const MAX_SIZE: u32 = MAX_SIZE;
+
     /// Creates an icon from 32bpp RGBA data.
     ///
-    /// The length of `rgba` must be divisible by 4, and `width * height` must equal
-    /// `rgba.len() / 4`. Otherwise, this will return a `BadIcon` error.
+    /// The width and height must be non-zero and at most [`Icon::MAX_SIZE`], and the length of
+    /// `rgba` must be `width * height * 4`. Otherwise, this will return a [`BadIcon`] error naming
+    /// the problem.
     pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
         Ok(Icon {
             inner: PlatformIcon::from_rgba(rgba, width, height)?,
         })
     }
+
+    /// Creates the icon for [`WindowBuilder::with_generated_icon`].
+    ///
+    /// [`WindowBuilder::with_generated_icon`]: crate::window::WindowBuilder::with_generated_icon
+    pub(crate) fn from_title(title: &str) -> Result<Self, BadIcon> {
+        let rgba = generated::rgba(title);
+        Self::from_rgba(rgba, generated::SIZE, generated::SIZE)
+    }
+}
+
+/// A letter on a colored background, drawn with a built-in 5x7 bitmap font so that no font has to
+/// be loaded.
+mod generated {
+    /// The width and height of the icon.
+    pub(super) const SIZE: u32 = 48;
+
+    /// How many pixels of the icon a pixel of the font covers.
+    const SCALE: u32 = 5;
+
+    const GLYPH_WIDTH: u32 = 5;
+    const GLYPH_HEIGHT: u32 = 7;
+
+    /// The background colors, one of which is picked from the title.
+    const BACKGROUNDS: [[u8; 3]; 8] = [
+        [0xc6, 0x28, 0x28],
+        [0xad, 0x14, 0x57],
+        [0x6a, 0x1b, 0x9a],
+        [0x28, 0x35, 0x93],
+        [0x02, 0x77, 0xbd],
+        [0x00, 0x69, 0x5c],
+        [0x2e, 0x7d, 0x32],
+        [0xef, 0x6c, 0x00],
+    ];
+
+    /// The rows of the glyphs of `A` to `Z`, then `0` to `9`, with the leftmost pixel in the
+    /// highest of the 5 bits.
+    #[rustfmt::skip]
+    const GLYPHS: [[u8; GLYPH_HEIGHT as usize]; 36] = [
+        [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+    ];
+
+    /// The glyph of the first character of `title` which the font has, ignoring the case.
+    fn glyph(title: &str) -> Option<&'static [u8; GLYPH_HEIGHT as usize]> {
+        title.chars().find_map(|c| match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => Some(&GLYPHS[c as usize - 'A' as usize]),
+            c @ '0'..='9' => Some(&GLYPHS[26 + c as usize - '0' as usize]),
+            _ => None,
+        })
+    }
+
+    fn background(title: &str) -> [u8; 3] {
+        let hash = title.bytes().fold(0u32, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(byte as u32)
+        });
+        BACKGROUNDS[hash as usize % BACKGROUNDS.len()]
+    }
+
+    /// Renders the icon of `title` as `SIZE`x`SIZE` RGBA pixels.
+    pub(super) fn rgba(title: &str) -> Vec<u8> {
+        let glyph = glyph(title);
+        let [r, g, b] = background(title);
+        let left = (SIZE - GLYPH_WIDTH * SCALE) / 2;
+        let top = (SIZE - GLYPH_HEIGHT * SCALE) / 2;
+
+        let mut rgba = Vec::with_capacity((SIZE * SIZE) as usize * 4);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let lit = glyph.map_or(false, |glyph| {
+                    let (x, y) = (x.wrapping_sub(left) / SCALE, y.wrapping_sub(top) / SCALE);
+                    x < GLYPH_WIDTH
+                        && y < GLYPH_HEIGHT
+                        && glyph[y as usize] & (1 << (GLYPH_WIDTH - 1 - x)) != 0
+                });
+                if lit {
+                    rgba.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+                } else {
+                    rgba.extend_from_slice(&[r, g, b, 0xff]);
+                }
+            }
+        }
+        rgba
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn pixel(pixels: &[u8], x: u32, y: u32) -> &[u8] {
+            let start = (y * SIZE + x) as usize * 4;
+            &pixels[start..start + 4]
+        }
+
+        #[test]
+        fn first_letter_of_the_title() {
+            assert_eq!(glyph("my app"), glyph("M"));
+            assert_eq!(glyph("  42"), glyph("4"));
+            assert_eq!(glyph("ÉTÉ"), glyph("T"));
+            assert_eq!(glyph("日本"), None);
+        }
+
+        #[test]
+        fn letter_on_background() {
+            let pixels = rgba("Tool");
+            assert_eq!(pixels.len(), (SIZE * SIZE * 4) as usize);
+
+            let [r, g, b] = background("Tool");
+            assert_eq!(pixel(&pixels, 0, 0), [r, g, b, 0xff]);
+            // The middle of the top bar of the `T`.
+            assert_eq!(
+                pixel(&pixels, SIZE / 2, (SIZE - GLYPH_HEIGHT * SCALE) / 2),
+                [0xff; 4]
+            );
+
+            // Without any supported character, only the background is drawn.
+            let [r, g, b] = background("日本");
+            assert!(rgba("日本").chunks(4).all(|pixel| pixel == [r, g, b, 0xff]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_icon() {
+        assert!(Icon::from_rgba(vec![0; 16 * 8 * 4], 16, 8).is_ok());
+        let max = Icon::MAX_SIZE;
+        assert!(Icon::from_rgba(vec![0; (max * max * 4) as usize], max, max).is_ok());
+    }
+
+    #[test]
+    fn pixel_count_mismatch() {
+        match Icon::from_rgba(vec![0; 16 * 16 * 4], 16, 8) {
+            Err(BadIcon::DimensionsVsPixelCount {
+                width: 16,
+                height: 8,
+                got_len: 1024,
+                expected_len: 512,
+            }) => (),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn byte_count_not_divisible_by_4() {
+        match Icon::from_rgba(vec![0; 6], 1, 1) {
+            Err(BadIcon::ByteCountNotDivisibleBy4 { byte_count: 6 }) => (),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn zero_sized() {
+        assert!(matches!(
+            Icon::from_rgba(Vec::new(), 0, 16),
+            Err(BadIcon::ZeroSized)
+        ));
+        assert!(matches!(
+            Icon::from_rgba(Vec::new(), 16, 0),
+            Err(BadIcon::ZeroSized)
+        ));
+    }
+
+    #[test]
+    fn too_large() {
+        // The size is checked before the pixels, so `width * height` can't overflow.
+        match Icon::from_rgba(Vec::new(), u32::MAX, u32::MAX) {
+            Err(BadIcon::TooLarge { max }) => assert_eq!(max, Icon::MAX_SIZE),
+            result => panic!("unexpected result: {result:?}"),
+        }
+        let len = (Icon::MAX_SIZE + 1) as usize * 4;
+        assert!(matches!(
+            Icon::from_rgba(vec![0; len], Icon::MAX_SIZE + 1, 1),
+            Err(BadIcon::TooLarge { .. })
+        ));
+    }
 }
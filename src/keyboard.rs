@@ -286,6 +286,11 @@ impl PartialEq<PhysicalKey> for NativeKeyCode {
 ///   "SuperRight" here.
 /// - The key that the specification calls "Super" is reported as `Unidentified` here.
 ///
+/// The media, volume and browser keys, like [`KeyCode::MediaPlayPause`],
+/// [`KeyCode::AudioVolumeUp`] or [`KeyCode::BrowserBack`], are reported like the other keys:
+/// only to the focused window, and only if the system doesn't consume them first. Listening to
+/// them globally, while the application is in the background, is out of scope for Winit.
+///
 /// [`KeyboardEvent.code`]: https://w3c.github.io/uievents-code/#code-value-tables
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -98,7 +98,7 @@
 //!             window.request_redraw();
 //!         },
 //!         Event::WindowEvent {
-//!             event: WindowEvent::RedrawRequested,
+//!             event: WindowEvent::RedrawRequested { .. },
 //!             ..
 //!         } => {
 //!             // Redraw the application.
@@ -169,15 +169,20 @@ extern crate serde;
 #[macro_use]
 extern crate bitflags;
 
+mod cursor_clip;
+mod cursor_presence;
 pub mod dpi;
 #[macro_use]
 pub mod error;
 pub mod event;
 pub mod event_loop;
+mod held_input;
 mod icon;
 pub mod keyboard;
 pub mod monitor;
 mod platform_impl;
+mod power;
+mod scheduler;
 pub mod window;
 
 pub mod platform;
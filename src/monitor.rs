@@ -79,6 +79,23 @@ impl VideoMode {
             inner: self.video_mode.monitor(),
         }
     }
+
+    /// Returns whether this is the [current video mode](MonitorHandle::current_video_mode) of
+    /// its monitor.
+    pub fn is_current(&self) -> bool {
+        self.monitor()
+            .current_video_mode()
+            .map_or(false, |current| current.key() == self.key())
+    }
+
+    /// What tells video modes apart for applications, the platform details aside.
+    fn key(&self) -> (PhysicalSize<u32>, u32, u16) {
+        (
+            self.size(),
+            self.refresh_rate_millihertz(),
+            self.bit_depth(),
+        )
+    }
 }
 
 impl std::fmt::Display for VideoMode {
@@ -98,13 +115,29 @@ impl std::fmt::Display for VideoMode {
 ///
 /// Allows you to retrieve information about a given monitor and can be used in [`Window`] creation.
 ///
+/// A `MonitorHandle` refers to a monitor connected to the running system, so it doesn't implement
+/// `Serialize`/`Deserialize` with the `serde` feature. To persist the monitor a window was on,
+/// store its [`id`](Self::id) instead, and look it up with
+/// [`EventLoopWindowTarget::monitor_from_id`] on the next run.
+///
 /// [`Window`]: crate::window::Window
+/// [`EventLoopWindowTarget::monitor_from_id`]: crate::event_loop::EventLoopWindowTarget::monitor_from_id
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct MonitorHandle {
     pub(crate) inner: platform_impl::MonitorHandle,
 }
 
 impl MonitorHandle {
+    /// Returns an identifier of the monitor which stays the same across runs of the application,
+    /// as long as the monitor keeps its name and position.
+    #[inline]
+    pub fn id(&self) -> MonitorId {
+        MonitorId {
+            name: self.name(),
+            position: self.position(),
+        }
+    }
+
     /// Returns a human-readable name of the monitor.
     ///
     /// Returns `None` if the monitor doesn't exist anymore.
@@ -119,8 +152,22 @@ impl MonitorHandle {
         self.inner.size()
     }
 
-    /// Returns the top-left corner position of the monitor relative to the larger full
-    /// screen area.
+    /// Returns the top-left corner position of the monitor on the desktop.
+    ///
+    /// Desktop coordinates span all monitors and have their origin at the top-left corner of the
+    /// [primary monitor], so a monitor to the left of or above the primary one has a negative
+    /// position. Window positions, like [`Window::outer_position`], use the same space, so
+    /// subtracting the monitor's position from a window's gives the window's offset on that
+    /// monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Windows can't be positioned, and as there's no primary monitor, the origin
+    ///   is defined by the compositor.
+    /// - **Android / Orbital:** Always returns `(0, 0)`.
+    ///
+    /// [primary monitor]: crate::event_loop::EventLoopWindowTarget::primary_monitor
+    /// [`Window::outer_position`]: crate::window::Window::outer_position
     #[inline]
     pub fn position(&self) -> PhysicalPosition<i32> {
         self.inner.position()
@@ -141,14 +188,27 @@ impl MonitorHandle {
     /// Returns the scale factor of the underlying monitor. To map logical pixels to physical
     /// pixels and vice versa, use [`Window::scale_factor`].
     ///
+    /// This can be used to precompute a layout before moving a window to this monitor. Note that
+    /// it may differ from the [`Window::scale_factor`] of a window on this monitor, for example
+    /// while the window is dragged across monitors and hasn't received
+    /// [`WindowEvent::ScaleFactorChanged`] yet.
+    ///
     /// See the [`dpi`](crate::dpi) module for more information.
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Can be overridden using the `WINIT_X11_SCALE_FACTOR` environment variable.
-    /// - **Wayland:** May differ from [`Window::scale_factor`].
+    /// - **Windows:** Uses the effective DPI from `GetDpiForMonitor`, returns 1.0 before Windows
+    ///   8.1.
+    /// - **macOS:** The `backingScaleFactor` of the matching `NSScreen`.
+    /// - **X11:** Derived from the monitor's physical size reported by RandR, or `Xft.dpi` when
+    ///   set. Can be overridden using the `WINIT_X11_SCALE_FACTOR` environment variable.
+    /// - **Wayland:** Always an integer, since that's what `wl_output` reports. Windows may use a
+    ///   fractional scale when the compositor supports `wp_fractional_scale_v1`, so this may differ
+    ///   from [`Window::scale_factor`].
     /// - **Android:** Always returns 1.0.
     ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    ///
     /// [`Window::scale_factor`]: crate::window::Window::scale_factor
     #[inline]
     pub fn scale_factor(&self) -> f64 {
@@ -157,13 +217,178 @@ impl MonitorHandle {
 
     /// Returns all fullscreen video modes supported by this monitor.
     ///
+    /// The modes are sorted from the largest resolution to the smallest, then from the highest
+    /// refresh rate to the lowest, and then from the highest bit depth to the lowest. Modes with
+    /// the same resolution, refresh rate and bit depth are only listed once, even when the system
+    /// reports several of them. The [current video mode](Self::current_video_mode), if any, is
+    /// always part of the list.
+    ///
     /// ## Platform-specific
     ///
     /// - **Web:** Always returns an empty iterator
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
-        self.inner
+        let video_modes = self
+            .inner
             .video_modes()
             .map(|video_mode| VideoMode { video_mode })
+            .collect();
+        sort_video_modes(video_modes).into_iter()
+    }
+
+    /// Returns the video mode the monitor currently uses, out of its [video
+    /// modes](Self::video_modes).
+    ///
+    /// This is the mode with the [size](Self::size) of the monitor and the refresh rate closest to
+    /// [its refresh rate](Self::refresh_rate_millihertz), which the systems may compute slightly
+    /// differently.
+    ///
+    /// Returns `None` when no mode has the size of the monitor, e.g. when the system scales the
+    /// desktop, or when the monitor has no video modes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Always returns `None`.
+    pub fn current_video_mode(&self) -> Option<VideoMode> {
+        let size = self.size();
+        let refresh_rate = self.refresh_rate_millihertz();
+        // The first of the closest modes has the highest bit depth.
+        self.video_modes()
+            .filter(|video_mode| video_mode.size() == size)
+            .min_by_key(|video_mode| {
+                refresh_rate.map_or(0, |refresh_rate| {
+                    video_mode.refresh_rate_millihertz().abs_diff(refresh_rate)
+                })
+            })
+    }
+}
+
+/// Sorts the video modes, keeping only one of those which only differ in platform details.
+fn sort_video_modes(mut video_modes: Vec<VideoMode>) -> Vec<VideoMode> {
+    // The sort puts the modes which only differ in platform details next to each other.
+    video_modes.sort();
+    video_modes.dedup_by_key(|video_mode| video_mode.key());
+    video_modes
+}
+
+/// A persistent identifier of a monitor, see [`MonitorHandle::id`].
+///
+/// Unlike a [`MonitorHandle`], it can be stored with `Serialize`/`Deserialize` with the `serde`
+/// feature, to find the monitor again with
+/// [`EventLoopWindowTarget::monitor_from_id`].
+///
+/// [`EventLoopWindowTarget::monitor_from_id`]: crate::event_loop::EventLoopWindowTarget::monitor_from_id
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MonitorId {
+    name: Option<String>,
+    position: PhysicalPosition<i32>,
+}
+
+impl MonitorId {
+    /// Returns the monitor with this id among `monitors`.
+    ///
+    /// A monitor with the same name is picked if none is at the same position, since monitors
+    /// move when the layout of the desktop changes.
+    pub(crate) fn find(
+        &self,
+        monitors: impl Iterator<Item = MonitorHandle>,
+    ) -> Option<MonitorHandle> {
+        self.find_by(monitors, MonitorHandle::id)
+    }
+
+    fn find_by<T>(
+        &self,
+        items: impl Iterator<Item = T>,
+        id: impl Fn(&T) -> MonitorId,
+    ) -> Option<T> {
+        let mut same_name = None;
+        for item in items {
+            let item_id = id(&item);
+            if item_id == *self {
+                return Some(item);
+            }
+            if same_name.is_none() && item_id.name.is_some() && item_id.name == self.name {
+                same_name = Some(item);
+            }
+        }
+        same_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(x11_platform)]
+    use crate::platform_impl::{self, x11};
+
+    use super::*;
+
+    fn id(name: Option<&str>, x: i32) -> MonitorId {
+        MonitorId {
+            name: name.map(String::from),
+            position: PhysicalPosition::new(x, 0),
+        }
+    }
+
+    #[cfg(x11_platform)]
+    fn video_mode(size: (u32, u32), refresh_rate_millihertz: u32, native_mode: u32) -> VideoMode {
+        VideoMode {
+            video_mode: platform_impl::VideoMode::X(x11::VideoMode {
+                size,
+                bit_depth: 24,
+                refresh_rate_millihertz,
+                native_mode,
+                monitor: Some(x11::MonitorHandle::dummy()),
+            }),
+        }
+    }
+
+    #[test]
+    fn exact_match_wins_over_same_name() {
+        let monitors = vec![id(Some("DP-1"), 1920), id(Some("DP-1"), 0)];
+        let found = id(Some("DP-1"), 0).find_by(monitors.into_iter(), Clone::clone);
+        assert_eq!(found, Some(id(Some("DP-1"), 0)));
+    }
+
+    #[test]
+    fn moved_monitor_is_found_by_name() {
+        let monitors = vec![id(Some("HDMI-1"), 0), id(Some("DP-1"), 1920)];
+        let found = id(Some("DP-1"), 0).find_by(monitors.into_iter(), Clone::clone);
+        assert_eq!(found, Some(id(Some("DP-1"), 1920)));
+    }
+
+    #[test]
+    fn unnamed_monitors_only_match_exactly() {
+        let monitors = vec![id(None, 1920)];
+        assert_eq!(
+            id(None, 0).find_by(monitors.into_iter(), Clone::clone),
+            None
+        );
+    }
+
+    #[cfg(x11_platform)]
+    #[test]
+    fn duplicated_video_modes_are_listed_once() {
+        // The same modes can be listed by several outputs of the monitor, with different ids.
+        let video_modes = vec![
+            video_mode((1280, 720), 60_000, 1),
+            video_mode((1920, 1080), 60_000, 2),
+            video_mode((1280, 720), 60_000, 3),
+            video_mode((1920, 1080), 144_000, 4),
+            video_mode((1920, 1080), 60_000, 5),
+        ];
+
+        let keys: Vec<_> = sort_video_modes(video_modes)
+            .iter()
+            .map(VideoMode::key)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                (PhysicalSize::new(1920, 1080), 144_000, 24),
+                (PhysicalSize::new(1920, 1080), 60_000, 24),
+                (PhysicalSize::new(1280, 720), 60_000, 24),
+            ]
+        );
     }
 }
@@ -1,9 +1,40 @@
+//! Android-specific functionality, on top of [`android_activity`](activity).
+//!
+//! ## Lifecycle
+//!
+//! The [`AndroidApp`] passed to `android_main()` must be given to the event loop with
+//! [`EventLoopBuilderExtAndroid::with_android_app`]. The events of the activity are then
+//! reported in the order `android-activity` delivers them:
+//!
+//! - [`Event::Resumed`] once the native window is created, after which windows can render, and
+//!   [`Event::Suspended`] when it's destroyed, e.g. when the application goes to the background.
+//!   Any surface created for the window has to be dropped then.
+//! - [`WindowEvent::Focused`] when the activity gains or loses the input focus.
+//! - [`WindowEvent::ConfigurationChanged`] for `onConfigurationChanged` when the locale, the
+//!   night mode or the hardware keyboard changed, followed by [`WindowEvent::ThemeChanged`] if
+//!   the night mode did. [`WindowEvent::ScaleFactorChanged`] comes last, and is emitted for any
+//!   configuration change.
+//! - [`Event::MemoryWarning`] for `onLowMemory`.
+//!
+//! The key events are returned to the system as unhandled for the volume keys, unless
+//! [`EventLoopBuilderExtAndroid::handle_volume_keys`] is called, and for the back button when
+//! [`WindowExtAndroid::set_back_button_handled`] is `false`.
+//!
+//! [`Event::Resumed`]: crate::event::Event::Resumed
+//! [`Event::Suspended`]: crate::event::Event::Suspended
+//! [`Event::MemoryWarning`]: crate::event::Event::MemoryWarning
+//! [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
+//! [`WindowEvent::ConfigurationChanged`]: crate::event::WindowEvent::ConfigurationChanged
+//! [`WindowEvent::ThemeChanged`]: crate::event::WindowEvent::ThemeChanged
+//! [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+
 use crate::{
     event_loop::{EventLoop, EventLoopBuilder, EventLoopWindowTarget},
     window::{Window, WindowBuilder},
 };
 
 use android_activity::{AndroidApp, ConfigurationRef, Rect};
+use ndk::native_window::NativeWindow;
 
 /// Additional methods on [`EventLoop`] that are specific to Android.
 pub trait EventLoopExtAndroid {}
@@ -17,7 +48,37 @@ pub trait EventLoopWindowTargetExtAndroid {}
 pub trait WindowExtAndroid {
     fn content_rect(&self) -> Rect;
 
+    /// Returns the configuration of the device.
+    ///
+    /// [`WindowEvent::ConfigurationChanged`] is emitted when the fields it reports change, but
+    /// this is the whole `AConfiguration` of the NDK, read from the activity.
+    ///
+    /// [`WindowEvent::ConfigurationChanged`]: crate::event::WindowEvent::ConfigurationChanged
     fn config(&self) -> ConfigurationRef;
+
+    /// Sets whether the application handles the back button, or back gesture.
+    ///
+    /// The back button is always reported with [`WindowEvent::KeyboardInput`]. When it's not
+    /// handled, winit also returns it to the system, which then navigates back, usually finishing
+    /// the activity. The default is set with
+    /// [`EventLoopBuilderExtAndroid::with_back_button_handled`], and is `true`.
+    ///
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn set_back_button_handled(&self, handled: bool);
+
+    /// Returns a new reference to the `ANativeWindow` backing the window, or `None` while
+    /// [`Window::surface_valid`] is `false`.
+    ///
+    /// The reference keeps the `ANativeWindow` object alive while it's held, but not the surface
+    /// it renders to: any `EGLSurface` created from it has to be destroyed in response to
+    /// [`Event::Suspended`], creating or using one afterwards is undefined behavior.
+    ///
+    /// The buffer geometry can be read with [`NativeWindow::width`], [`NativeWindow::height`]
+    /// and [`NativeWindow::format`], and changed with [`NativeWindow::set_buffers_geometry`],
+    /// which is `ANativeWindow_setBuffersGeometry`.
+    ///
+    /// [`Event::Suspended`]: crate::event::Event::Suspended
+    fn native_window(&self) -> Option<NativeWindow>;
 }
 
 impl WindowExtAndroid for Window {
@@ -28,6 +89,14 @@ impl WindowExtAndroid for Window {
     fn config(&self) -> ConfigurationRef {
         self.window.config()
     }
+
+    fn set_back_button_handled(&self, handled: bool) {
+        self.window.set_back_button_handled(handled)
+    }
+
+    fn native_window(&self) -> Option<NativeWindow> {
+        self.window.native_window()
+    }
 }
 
 impl<T> EventLoopWindowTargetExtAndroid for EventLoopWindowTarget<T> {}
@@ -47,6 +116,13 @@ pub trait EventLoopBuilderExtAndroid {
     ///
     /// Default is to let the operating system handle the volume keys
     fn handle_volume_keys(&mut self) -> &mut Self;
+
+    /// Sets whether the application handles the back button, until it's changed with
+    /// [`WindowExtAndroid::set_back_button_handled`].
+    ///
+    /// Default is `true`: the back button is only reported to the application, which then has
+    /// to finish the activity itself if it wants to.
+    fn with_back_button_handled(&mut self, handled: bool) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtAndroid for EventLoopBuilder<T> {
@@ -59,6 +135,11 @@ impl<T> EventLoopBuilderExtAndroid for EventLoopBuilder<T> {
         self.platform_specific.ignore_volume_keys = false;
         self
     }
+
+    fn with_back_button_handled(&mut self, handled: bool) -> &mut Self {
+        self.platform_specific.back_button_handled = handled;
+        self
+    }
 }
 
 /// Re-export of the `android_activity` API
@@ -0,0 +1,58 @@
+//! Selection of the display server backend the event loop of X11 and Wayland systems uses.
+//!
+//! By default, winit uses Wayland when `WAYLAND_DISPLAY` or `WAYLAND_SOCKET` is set, and X11 when
+//! only `DISPLAY` is, since X11 is often provided by Xwayland in a Wayland session too.
+//! [`EventLoopBuilderExtBackend::with_linux_backend_preference`] changes that order, or forces a
+//! backend. No other environment variable is read: the `WINIT_UNIX_BACKEND` variable of older
+//! versions of winit is ignored, so applications wanting to keep honoring it have to map it to a
+//! [`BackendPreference`] themselves.
+
+use crate::event_loop::EventLoopBuilder;
+use crate::platform_impl;
+
+/// The display server backend to pick, see the [module-level documentation](self).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackendPreference {
+    /// Wayland if it's available, X11 otherwise (default).
+    #[default]
+    Auto,
+    /// X11 if it's available, Wayland otherwise.
+    PreferX11,
+    /// Wayland if it's available, X11 otherwise, which is the same as [`BackendPreference::Auto`].
+    PreferWayland,
+    /// X11 only.
+    ///
+    /// [`EventLoopBuilder::build`] fails if no X server can be connected to, like on a Wayland
+    /// session without Xwayland, or if the `x11` feature of winit is disabled.
+    ForceX11,
+    /// Wayland only.
+    ///
+    /// [`EventLoopBuilder::build`] fails if no Wayland compositor can be connected to, or if the
+    /// `wayland` feature of winit is disabled.
+    ForceWayland,
+}
+
+/// Additional methods on [`EventLoopBuilder`] to pick the display server backend.
+pub trait EventLoopBuilderExtBackend {
+    /// Sets the backend to use, or to prefer when several are available.
+    ///
+    /// This replaces the backend forced by an earlier call to
+    /// [`EventLoopBuilderExtX11::with_x11`] or [`EventLoopBuilderExtWayland::with_wayland`], and
+    /// is replaced by a later one. It has no effect on a headless event loop.
+    ///
+    /// [`EventLoopBuilderExtX11::with_x11`]: crate::platform::x11::EventLoopBuilderExtX11::with_x11
+    /// [`EventLoopBuilderExtWayland::with_wayland`]: crate::platform::wayland::EventLoopBuilderExtWayland::with_wayland
+    fn with_linux_backend_preference(&mut self, preference: BackendPreference) -> &mut Self;
+}
+
+impl<T> EventLoopBuilderExtBackend for EventLoopBuilder<T> {
+    #[inline]
+    fn with_linux_backend_preference(&mut self, preference: BackendPreference) -> &mut Self {
+        let platform_specific = &mut self.platform_specific;
+        if platform_specific.forced_backend != Some(platform_impl::Backend::Headless) {
+            platform_specific.forced_backend = None;
+        }
+        platform_specific.backend_preference = preference;
+        self
+    }
+}
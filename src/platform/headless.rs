@@ -0,0 +1,282 @@
+//! A backend without any display server, to run automated tests.
+//!
+//! [`EventLoopBuilderExtHeadless::with_headless`] creates an event loop which doesn't connect to
+//! X11 or Wayland, so it works in CI and other environments without a display. Its windows only
+//! exist in memory, and their contents can be read and written with
+//! [`WindowExtHeadless::with_pixel_buffer`].
+//!
+//! This backend is meant for tests, not for production: there's no compositor, no input device
+//! and nothing is ever shown on screen. Its limitations are:
+//!
+//! - The only events generated by the backend itself are the ones requested by the application:
+//!   [`WindowEvent::RedrawRequested`] when a window is created visible, is shown, is resized or
+//!   after [`Window::request_redraw`], [`WindowEvent::Resized`] after
+//!   [`Window::request_inner_size`], [`WindowEvent::Minimized`] after [`Window::set_minimized`]
+//!   and [`WindowEvent::Destroyed`] when a window is dropped. Every other event, like the input
+//!   or [`WindowEvent::Focused`], has to be injected with
+//!   [`EventLoopWindowTargetExtHeadless::inject_window_event`] and
+//!   [`EventLoopWindowTargetExtHeadless::inject_device_event`], or replayed from a recorded trace
+//!   with [`EventLoopWindowTargetExtHeadless::replay_events`].
+//! - There's a single monitor, configured with
+//!   [`EventLoopBuilderExtHeadless::with_headless_monitor`], with a single video mode at 60 Hz.
+//!   Moving a window to a monitor with another scale factor is simulated with
+//!   [`EventLoopWindowTargetExtHeadless::inject_scale_factor_change`].
+//! - The window state, like its position, title or maximization, is stored as requested without
+//!   any constraint from a window manager, except the minimum and maximum inner sizes and the
+//!   resize increments.
+//! - Drawing is only possible through the pixel buffer. The raw window and display handles are
+//!   either empty or [`HandleError::NotSupported`], so no graphics API can render to the windows.
+//! - As on every platform, a process can only create one event loop, so tests needing several
+//!   should live in different integration test binaries or reuse it with
+//!   [`EventLoopExtPumpEvents::pump_events`].
+//!
+//! [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+//! [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+//! [`WindowEvent::Minimized`]: crate::event::WindowEvent::Minimized
+//! [`WindowEvent::Destroyed`]: crate::event::WindowEvent::Destroyed
+//! [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
+//! [`Window::request_redraw`]: crate::window::Window::request_redraw
+//! [`Window::request_inner_size`]: crate::window::Window::request_inner_size
+//! [`Window::set_minimized`]: crate::window::Window::set_minimized
+//! [`HandleError::NotSupported`]: https://docs.rs/raw-window-handle/0.6/raw_window_handle/enum.HandleError.html#variant.NotSupported
+//! [`EventLoopExtPumpEvents::pump_events`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_events
+
+use crate::{
+    dpi::{self, PhysicalRect, PhysicalSize},
+    event::{DeviceEvent, DeviceId, Event, WindowEvent},
+    event_loop::{EventLoopBuilder, EventLoopWindowTarget, PowerState},
+    platform_impl::{self, headless::MonitorConfig, Backend},
+    window::{Window, WindowId},
+};
+
+/// Additional methods on [`EventLoopBuilder`] to create a headless event loop.
+pub trait EventLoopBuilderExtHeadless {
+    /// Whether to use the headless backend instead of X11 or Wayland.
+    ///
+    /// A headless event loop can be created off of the main thread, without
+    /// `with_any_thread`, since the test harness doesn't run the tests on the main thread.
+    fn with_headless(&mut self, headless: bool) -> &mut Self;
+
+    /// Sets the size and scale factor of the monitor reported by the headless backend.
+    ///
+    /// The default is a 1920x1080 monitor with a scale factor of 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` isn't [valid](dpi::validate_scale_factor).
+    fn with_headless_monitor(&mut self, size: PhysicalSize<u32>, scale_factor: f64) -> &mut Self;
+}
+
+impl<T> EventLoopBuilderExtHeadless for EventLoopBuilder<T> {
+    #[inline]
+    fn with_headless(&mut self, headless: bool) -> &mut Self {
+        let forced_backend = &mut self.platform_specific.forced_backend;
+        if headless {
+            *forced_backend = Some(Backend::Headless);
+        } else if *forced_backend == Some(Backend::Headless) {
+            *forced_backend = None;
+        }
+        self
+    }
+
+    #[inline]
+    fn with_headless_monitor(&mut self, size: PhysicalSize<u32>, scale_factor: f64) -> &mut Self {
+        assert!(dpi::validate_scale_factor(scale_factor));
+        self.platform_specific.headless_monitor = MonitorConfig { size, scale_factor };
+        self
+    }
+}
+
+/// Additional methods on [`EventLoopWindowTarget`] to drive a headless event loop.
+pub trait EventLoopWindowTargetExtHeadless {
+    /// A type provided by the user that can be passed through [`Event::UserEvent`].
+    type UserEvent: 'static;
+
+    /// True if the [`EventLoopWindowTarget`] uses the headless backend.
+    fn is_headless(&self) -> bool;
+
+    /// Queues a window event, which is dispatched by the next iteration of the event loop.
+    ///
+    /// The window state is updated to match [`WindowEvent::Resized`], [`WindowEvent::Moved`],
+    /// [`WindowEvent::Focused`], [`WindowEvent::ScaleFactorChanged`] and
+    /// [`WindowEvent::ThemeChanged`] right before the event is dispatched. The size written
+    /// through the `inner_size_writer` of [`WindowEvent::ScaleFactorChanged`] is ignored.
+    ///
+    /// Does nothing if the event loop isn't headless.
+    fn inject_window_event(&self, window_id: WindowId, event: WindowEvent);
+
+    /// Queues a device event, which is dispatched by the next iteration of the event loop.
+    ///
+    /// The event is filtered according to [`EventLoopWindowTarget::listen_device_events`], which
+    /// considers a window focused after an injected `WindowEvent::Focused(true)`.
+    /// [`DeviceId::dummy`] can be used as the device.
+    ///
+    /// Does nothing if the event loop isn't headless.
+    fn inject_device_event(&self, device_id: DeviceId, event: DeviceEvent);
+
+    /// Queues a change of the scale factor of a window, like when it's moved to a monitor with
+    /// another scale factor, which is dispatched by the next iteration of the event loop.
+    ///
+    /// The window gets [`WindowEvent::ScaleFactorChanged`], suggesting the inner size which keeps
+    /// its logical size, followed by [`WindowEvent::Resized`] if the size written through the
+    /// `inner_size_writer` isn't the current one. Nothing is dispatched if the scale factor
+    /// doesn't change.
+    ///
+    /// Does nothing if the event loop isn't headless.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` isn't [valid](dpi::validate_scale_factor).
+    fn inject_scale_factor_change(&self, window_id: WindowId, scale_factor: f64);
+
+    /// Sets the state returned by [`EventLoopWindowTarget::power_state`], which is `None` by
+    /// default.
+    ///
+    /// While the application listens to power events, [`Event::PowerStateChanged`] is emitted by
+    /// the next iteration if it changed.
+    ///
+    /// Does nothing if the event loop isn't headless.
+    ///
+    /// [`Event::PowerStateChanged`]: crate::event::Event::PowerStateChanged
+    fn set_power_state(&self, state: Option<PowerState>);
+
+    /// Returns the rectangle the cursor is clipped to with
+    /// [`EventLoopWindowTarget::clip_cursor`], or `None` while it's released.
+    ///
+    /// Returns `None` if the event loop isn't headless.
+    fn cursor_clip(&self) -> Option<PhysicalRect>;
+
+    /// Replays a trace of events, recorded with [`EventLoop::with_recorder`], through the normal
+    /// dispatch.
+    ///
+    /// The trace is split into iterations at every [`Event::AboutToWait`], and each of them is
+    /// replayed by one iteration of the event loop, before the injected events. Only the window,
+    /// device and user events are replayed: the other events, as well as
+    /// [`WindowEvent::RedrawRequested`] and [`WindowEvent::Destroyed`], are generated by the
+    /// event loop again in response to the application.
+    ///
+    /// The ids of the trace belong to the event loop which recorded it, so they're remapped:
+    ///
+    /// - The windows of the trace are mapped, in the order of their first event, to the windows
+    ///   of the application in creation order. The events of a window are dropped until there's
+    ///   a window left to map it to, and once its window is dropped. Applications which create
+    ///   their windows in the same order as when recording don't need to do anything else.
+    /// - The [`DeviceId`]s are kept as they are. Once serialized with the `serde` feature, all of
+    ///   them are deserialized as [`DeviceId::dummy`].
+    ///
+    /// This is meant for debugging and regression tests, not to feed production input to an
+    /// application.
+    ///
+    /// Does nothing if the event loop isn't headless.
+    ///
+    /// [`EventLoop::with_recorder`]: crate::event_loop::EventLoop::with_recorder
+    /// [`Event::AboutToWait`]: crate::event::Event::AboutToWait
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`WindowEvent::Destroyed`]: crate::event::WindowEvent::Destroyed
+    fn replay_events<I>(&self, events: I)
+    where
+        I: IntoIterator<Item = Event<Self::UserEvent>>;
+}
+
+impl<T> EventLoopWindowTargetExtHeadless for EventLoopWindowTarget<T> {
+    type UserEvent = T;
+
+    #[inline]
+    fn is_headless(&self) -> bool {
+        self.p.is_headless()
+    }
+
+    #[inline]
+    fn inject_window_event(&self, window_id: WindowId, event: WindowEvent) {
+        if let platform_impl::EventLoopWindowTarget::Headless(window_target) = &self.p {
+            window_target.inject_window_event(window_id.0, event);
+        }
+    }
+
+    #[inline]
+    fn inject_device_event(&self, device_id: DeviceId, event: DeviceEvent) {
+        if let platform_impl::EventLoopWindowTarget::Headless(window_target) = &self.p {
+            window_target.inject_device_event(device_id, event);
+        }
+    }
+
+    #[inline]
+    fn inject_scale_factor_change(&self, window_id: WindowId, scale_factor: f64) {
+        assert!(dpi::validate_scale_factor(scale_factor));
+        if let platform_impl::EventLoopWindowTarget::Headless(window_target) = &self.p {
+            window_target.inject_scale_factor_change(window_id.0, scale_factor);
+        }
+    }
+
+    #[inline]
+    fn set_power_state(&self, state: Option<PowerState>) {
+        if let platform_impl::EventLoopWindowTarget::Headless(window_target) = &self.p {
+            window_target.set_power_state(state);
+        }
+    }
+
+    #[inline]
+    fn cursor_clip(&self) -> Option<PhysicalRect> {
+        match &self.p {
+            platform_impl::EventLoopWindowTarget::Headless(window_target) => {
+                window_target.cursor_clip()
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn replay_events<I>(&self, events: I)
+    where
+        I: IntoIterator<Item = Event<Self::UserEvent>>,
+    {
+        if let platform_impl::EventLoopWindowTarget::Headless(window_target) = &self.p {
+            window_target.replay_events(events);
+        }
+    }
+}
+
+/// Additional methods on [`Window`] to access the contents of a headless window.
+pub trait WindowExtHeadless {
+    /// Runs `f` with the pixel buffer of the window and its inner size.
+    ///
+    /// The buffer holds one `0RGB` pixel per `u32`, row by row from the top left corner, in the
+    /// same format as [`softbuffer`]. It's zeroed whenever the window is resized.
+    ///
+    /// Returns `None` if the window isn't headless.
+    ///
+    /// [`softbuffer`]: https://docs.rs/softbuffer
+    fn with_pixel_buffer<R>(&self, f: impl FnOnce(&mut [u32], PhysicalSize<u32>) -> R)
+        -> Option<R>;
+
+    /// Returns the rectangle of the desktop the cursor is confined to with
+    /// [`Window::confine_cursor_to_rect`].
+    ///
+    /// The rectangle follows the position and the inner size of the window, as updated by the
+    /// injected [`WindowEvent::Moved`] and [`WindowEvent::Resized`], and is `None` while the
+    /// window isn't focused.
+    ///
+    /// Returns `None` if the window isn't headless.
+    fn cursor_confinement(&self) -> Option<PhysicalRect>;
+}
+
+impl WindowExtHeadless for Window {
+    #[inline]
+    fn with_pixel_buffer<R>(
+        &self,
+        f: impl FnOnce(&mut [u32], PhysicalSize<u32>) -> R,
+    ) -> Option<R> {
+        match &self.window {
+            platform_impl::Window::Headless(window) => Some(window.with_pixel_buffer(f)),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn cursor_confinement(&self) -> Option<PhysicalRect> {
+        match &self.window {
+            platform_impl::Window::Headless(window) => window.cursor_confinement(),
+            _ => None,
+        }
+    }
+}
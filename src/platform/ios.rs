@@ -22,14 +22,40 @@ impl<T: 'static> EventLoopExtIOS for EventLoop<T> {
 }
 
 /// Additional methods on [`Window`] that are specific to iOS.
+///
+/// A fullscreen game usually hides the status bar with
+/// [`set_prefers_status_bar_hidden`](Self::set_prefers_status_bar_hidden), dims the home
+/// indicator with [`set_prefers_home_indicator_hidden`](Self::set_prefers_home_indicator_hidden),
+/// and keeps edge swipes from interrupting it with
+/// [`set_preferred_screen_edges_deferring_system_gestures`](Self::set_preferred_screen_edges_deferring_system_gestures),
+/// or sets them before the window is created with [`WindowBuilderExtIOS`].
+///
+/// UIKit only reads these preferences again once it's told they changed, which winit does right
+/// after storing the new value, so there's no appearance update to request manually. This
+/// trait only exists on iOS; applications targeting other platforms too should call it behind
+/// `#[cfg(target_os = "ios")]`.
 pub trait WindowExtIOS {
     /// Sets the [`contentScaleFactor`] of the underlying [`UIWindow`] to `scale_factor`.
     ///
     /// The default value is device dependent, and it's recommended GLES or Metal applications set
     /// this to [`MonitorHandle::scale_factor()`].
     ///
+    /// The content scale factor is the number of pixels of the drawable per point, and is what
+    /// [`Window::scale_factor`] reports, while [`MonitorHandle::scale_factor()`] keeps reporting
+    /// the native scale of the screen. A value below the native scale, e.g. 2 on a 3x screen,
+    /// renders fewer pixels which the system then scales up, trading sharpness for fill rate.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`] is emitted with the new value, followed by
+    /// [`WindowEvent::Resized`] with the physical size of the window at the new scale factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` isn't [valid](crate::dpi::validate_scale_factor).
+    ///
     /// [`UIWindow`]: https://developer.apple.com/documentation/uikit/uiwindow?language=objc
     /// [`contentScaleFactor`]: https://developer.apple.com/documentation/uikit/uiview/1622657-contentscalefactor?language=objc
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     fn set_scale_factor(&self, scale_factor: f64);
 
     /// Sets the valid orientations for the [`Window`].
@@ -42,6 +68,29 @@ pub trait WindowExtIOS {
     /// [`-[UIViewController attemptRotationToDeviceOrientation]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621400-attemptrotationtodeviceorientati?language=objc).
     fn set_valid_orientations(&self, valid_orientations: ValidOrientations);
 
+    /// Sets the interface orientations the [`Window`] supports, e.g. to lock it to landscape
+    /// during gameplay and allow every orientation in menus.
+    ///
+    /// This replaces the orientations set with [`WindowExtIOS::set_valid_orientations`] or
+    /// [`WindowBuilderExtIOS::with_valid_orientations`], and is applied right away: winit changes
+    /// the value returned by
+    /// [`-[UIViewController supportedInterfaceOrientations]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621435-supportedinterfaceorientations?language=objc),
+    /// then asks UIKit to evaluate it again with
+    /// [`-[UIViewController setNeedsUpdateOfSupportedInterfaceOrientations]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/4047535-setneedsupdateofsupportedinterfa?language=objc)
+    /// on iOS 16.0+, or
+    /// [`+[UIViewController attemptRotationToDeviceOrientation]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621400-attemptrotationtodeviceorientati?language=objc)
+    /// before, so the interface rotates to a supported orientation if the current one isn't.
+    ///
+    /// The orientations must overlap the ones the application declares with
+    /// `UISupportedInterfaceOrientations` in its `Info.plist`, since UIKit only uses the
+    /// orientations both allow, and raises an exception when none is left. An empty
+    /// `orientations` is ignored with a warning for the same reason.
+    ///
+    /// On Android, the equivalent is
+    /// [`Activity.setRequestedOrientation`](https://developer.android.com/reference/android/app/Activity#setRequestedOrientation(int)),
+    /// which winit doesn't wrap.
+    fn set_supported_orientations(&self, orientations: Orientations);
+
     /// Sets whether the [`Window`] prefers the home indicator hidden.
     ///
     /// The default is to prefer showing the home indicator.
@@ -103,6 +152,12 @@ impl WindowExtIOS for Window {
             .maybe_queue_on_main(move |w| w.set_valid_orientations(valid_orientations))
     }
 
+    #[inline]
+    fn set_supported_orientations(&self, orientations: Orientations) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_supported_orientations(orientations))
+    }
+
     #[inline]
     fn set_prefers_home_indicator_hidden(&self, hidden: bool) {
         self.window
@@ -136,8 +191,13 @@ pub trait WindowBuilderExtIOS {
     /// The default value is device dependent, and it's recommended GLES or Metal applications set
     /// this to [`MonitorHandle::scale_factor()`].
     ///
+    /// See [`WindowExtIOS::set_scale_factor`] for how it relates to the native scale of the
+    /// screen. The window is created with this scale factor, so no
+    /// [`WindowEvent::ScaleFactorChanged`] is emitted for it.
+    ///
     /// [`UIWindow`]: https://developer.apple.com/documentation/uikit/uiwindow?language=objc
     /// [`contentScaleFactor`]: https://developer.apple.com/documentation/uikit/uiview/1622657-contentscalefactor?language=objc
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
     fn with_scale_factor(self, scale_factor: f64) -> Self;
 
     /// Sets the valid orientations for the [`Window`].
@@ -265,6 +325,26 @@ pub enum ValidOrientations {
     Portrait,
 }
 
+bitflags! {
+    /// The interface orientations of a [`Window`], see
+    /// [`WindowExtIOS::set_supported_orientations`].
+    ///
+    /// These are [interface orientations]: [`Orientations::LANDSCAPE_LEFT`] has the home button
+    /// or indicator on the left, which is the device rotated clockwise from portrait.
+    ///
+    /// [interface orientations]: https://developer.apple.com/documentation/uikit/uiinterfaceorientationmask?language=objc
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Orientations: u8 {
+        const PORTRAIT             = 1 << 0;
+        const PORTRAIT_UPSIDE_DOWN = 1 << 1;
+        const LANDSCAPE_LEFT       = 1 << 2;
+        const LANDSCAPE_RIGHT      = 1 << 3;
+        const LANDSCAPE = Orientations::LANDSCAPE_LEFT.bits() | Orientations::LANDSCAPE_RIGHT.bits();
+        const ALL = Orientations::PORTRAIT.bits() | Orientations::PORTRAIT_UPSIDE_DOWN.bits()
+            | Orientations::LANDSCAPE.bits();
+    }
+}
+
 /// The device [idiom].
 ///
 /// [idiom]: https://developer.apple.com/documentation/uikit/uidevice/1620037-userinterfaceidiom?language=objc
@@ -69,6 +69,17 @@ pub trait WindowExtMacOS {
     /// Put the window in a state which indicates a file save is required.
     fn set_document_edited(&self, edited: bool);
 
+    /// Sets the subtitle shown below the window title, an empty string removes it.
+    ///
+    /// Together with the title and [`WindowExtMacOS::set_document_edited`], this gives the usual
+    /// look of a document window, for example with the name of the document as the title and its
+    /// location as the subtitle.
+    ///
+    /// Does nothing before macOS 11, and on windows whose title is hidden.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/3608198-subtitle>
+    fn set_subtitle(&self, subtitle: &str);
+
     /// Set option as alt behavior as described in [`OptionAsAlt`].
     ///
     /// This will ignore diacritical marks and accent characters from
@@ -79,6 +90,41 @@ pub trait WindowExtMacOS {
 
     /// Getter for the [`WindowExtMacOS::set_option_as_alt`].
     fn option_as_alt(&self) -> OptionAsAlt;
+
+    /// Sets the level of the window to a raw `NSWindowLevel`, for the levels that
+    /// [`WindowLevel`] can't express.
+    ///
+    /// Windows are stacked by level first, and by order within a level. The named levels of
+    /// AppKit are:
+    ///
+    /// | Level                      | Value   |
+    /// |----------------------------|---------|
+    /// | `NSNormalWindowLevel`      | `0`     |
+    /// | `NSFloatingWindowLevel`    | `3`     |
+    /// | `NSSubmenuWindowLevel`     | `3`     |
+    /// | `NSTornOffMenuWindowLevel` | `3`     |
+    /// | `NSModalPanelWindowLevel`  | `8`     |
+    /// | `NSMainMenuWindowLevel`    | `24`    |
+    /// | `NSStatusWindowLevel`      | `25`    |
+    /// | `NSPopUpMenuWindowLevel`   | `101`   |
+    /// | `NSScreenSaverWindowLevel` | `1000`  |
+    ///
+    /// [`Window::set_window_level`] sets the level to `NSNormalWindowLevel`, to
+    /// `NSFloatingWindowLevel` for [`WindowLevel::AlwaysOnTop`], and to one below
+    /// `NSNormalWindowLevel` for [`WindowLevel::AlwaysOnBottom`], replacing the raw level.
+    /// Changing the fullscreen state also resets the level to `NSNormalWindowLevel`.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/1419511-level>
+    ///
+    /// [`WindowLevel`]: crate::window::WindowLevel
+    /// [`WindowLevel::AlwaysOnTop`]: crate::window::WindowLevel::AlwaysOnTop
+    /// [`WindowLevel::AlwaysOnBottom`]: crate::window::WindowLevel::AlwaysOnBottom
+    /// [`Window::set_window_level`]: crate::window::Window::set_window_level
+    fn set_window_level_raw(&self, level: i64);
+
+    /// Returns the raw `NSWindowLevel` of the window, see
+    /// [`WindowExtMacOS::set_window_level_raw`].
+    fn window_level_raw(&self) -> i64;
 }
 
 impl WindowExtMacOS for Window {
@@ -147,6 +193,11 @@ impl WindowExtMacOS for Window {
             .maybe_queue_on_main(move |w| w.set_document_edited(edited))
     }
 
+    #[inline]
+    fn set_subtitle(&self, subtitle: &str) {
+        self.window.maybe_wait_on_main(|w| w.set_subtitle(subtitle))
+    }
+
     #[inline]
     fn set_option_as_alt(&self, option_as_alt: OptionAsAlt) {
         self.window
@@ -157,6 +208,17 @@ impl WindowExtMacOS for Window {
     fn option_as_alt(&self) -> OptionAsAlt {
         self.window.maybe_wait_on_main(|w| w.option_as_alt())
     }
+
+    #[inline]
+    fn set_window_level_raw(&self, level: i64) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_window_level_raw(level))
+    }
+
+    #[inline]
+    fn window_level_raw(&self) -> i64 {
+        self.window.maybe_wait_on_main(|w| w.window_level_raw())
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -381,6 +443,15 @@ pub trait EventLoopWindowTargetExtMacOS {
     fn set_allows_automatic_window_tabbing(&self, enabled: bool);
     /// Returns whether the system can automatically organize windows into tabs.
     fn allows_automatic_window_tabbing(&self) -> bool;
+    /// Sets the badge shown over the application's Dock icon, such as a count of unread items,
+    /// or removes it with `None`.
+    ///
+    /// The badge belongs to the application rather than to any of its windows. Other platforms
+    /// have no direct equivalent, the closest on Linux is the `com.canonical.Unity.LauncherEntry`
+    /// D-Bus API, which `winit` doesn't implement.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nsdocktile/1524433-badgelabel>
+    fn set_dock_badge(&self, label: Option<&str>);
 }
 
 impl<T> EventLoopWindowTargetExtMacOS for EventLoopWindowTarget<T> {
@@ -399,6 +470,10 @@ impl<T> EventLoopWindowTargetExtMacOS for EventLoopWindowTarget<T> {
     fn allows_automatic_window_tabbing(&self) -> bool {
         self.p.allows_automatic_window_tabbing()
     }
+
+    fn set_dock_badge(&self, label: Option<&str>) {
+        self.p.set_dock_badge(label)
+    }
 }
 
 /// Option as alt behavior.
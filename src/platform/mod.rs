@@ -11,6 +11,8 @@
 //!
 //! And the following platform-specific modules:
 //!
+//! - `backend` (available on `unix`)
+//! - `headless` (available on `unix`)
 //! - `run_on_demand` (available on `windows`, `unix`, `macos`, `android`)
 //! - `pump_events` (available on `windows`, `unix`, `macos`, `android`)
 //!
@@ -18,6 +20,10 @@
 
 #[cfg(android_platform)]
 pub mod android;
+#[cfg(any(x11_platform, wayland_platform))]
+pub mod backend;
+#[cfg(any(x11_platform, wayland_platform))]
+pub mod headless;
 #[cfg(ios_platform)]
 pub mod ios;
 #[cfg(macos_platform)]
@@ -7,7 +7,13 @@ use crate::keyboard::Key;
 pub trait KeyEventExtModifierSupplement {
     /// Identical to `KeyEvent::text` but this is affected by <kbd>Ctrl</kbd>.
     ///
-    /// For example, pressing <kbd>Ctrl</kbd>+<kbd>a</kbd> produces `Some("\x01")`.
+    /// For example, pressing <kbd>Ctrl</kbd>+<kbd>a</kbd> produces `Some("\x01")`, while
+    /// `KeyEvent::text` is `Some("a")`. Together with [`key_without_modifiers`], this gives
+    /// shortcut editors the labels they need regardless of the layout: on AZERTY,
+    /// <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>7</kbd> has a `text` of `Some("7")` and a
+    /// `key_without_modifiers` of `è`.
+    ///
+    /// [`key_without_modifiers`]: Self::key_without_modifiers
     fn text_with_all_modifiers(&self) -> Option<&str>;
 
     /// This value ignores all modifiers including,
@@ -136,6 +136,16 @@ pub trait EventLoopExtPumpEvents {
     /// then handled that later since there would always be a chance that
     /// other lifecycle events occur while the event is buffered.
     ///
+    /// ## Animations and timers
+    ///
+    /// Winit only makes progress while `pump_events` is running, so the external loop is
+    /// responsible for calling it often enough. [`ControlFlow::WaitUntil`] and
+    /// [`ControlFlow::Poll`] only shorten the wait inside of a single call, they don't make
+    /// Winit wake up the external loop, and a `timeout` of `None` may block for as long as no
+    /// events arrive regardless of them. Likewise, anything driven by the platform's own run loop
+    /// while the external loop owns the thread, like OS animations or timers of other libraries,
+    /// is only serviced inside of `pump_events`.
+    ///
     /// ## Supported Platforms
     /// - Windows
     /// - Linux
@@ -144,34 +154,39 @@ pub trait EventLoopExtPumpEvents {
     ///
     /// ## Unsupported Platforms
     /// - **Web:**  This API is fundamentally incompatible with the event-based way in which
-    /// Web browsers work because it's not possible to have a long-running external
-    /// loop that would block the browser and there is nothing that can be
-    /// polled to ask for new new events. Events are delivered via callbacks based
-    /// on an event loop that is internal to the browser itself.
+    ///   Web browsers work because it's not possible to have a long-running external
+    ///   loop that would block the browser and there is nothing that can be
+    ///   polled to ask for new new events. Events are delivered via callbacks based
+    ///   on an event loop that is internal to the browser itself. Use
+    ///   `EventLoopExtWebSys::spawn()` instead, which returns right away and dispatches every
+    ///   event synchronously from the browser callback that produced it, and drive the host's tick
+    ///   from [`Event::AboutToWait`] or through an [`EventLoopProxy`].
     /// - **iOS:** It's not possible to stop and start an `NSApplication` repeatedly on iOS so
-    /// there's no way to support the same approach to polling as on MacOS.
+    ///   there's no way to support the same approach to polling as on MacOS.
     ///
     /// ## Platform-specific
     /// - **Windows**: The implementation will use `PeekMessage` when checking for
     ///   window messages to avoid blocking your external event loop.
     ///
-    /// - **MacOS**: The implementation works in terms of stopping the global `NSApp`
-    ///   whenever the application `RunLoop` indicates that it is preparing to block
-    ///   and wait for new events.
+    /// - **MacOS**: The first call runs the global `NSApp` until it has finished launching.
+    ///   Subsequent calls don't run the `NSApp`, but dispatch its events themselves like
+    ///   `[NSApp run]` would: they wait up to `timeout` for the next event with
+    ///   `nextEventMatchingMask:untilDate:inMode:dequeue:` in `NSDefaultRunLoopMode` (which
+    ///   is `kCFRunLoopDefaultMode`), then dispatch it and all other queued events without
+    ///   waiting, followed by the redraws and [`Event::AboutToWait`].
     ///
-    ///   This is very different to the polling APIs that are available on other
-    ///   platforms (the lower level polling primitives on MacOS are private
-    ///   implementation details for `NSApp` which aren't accessible to application
-    ///   developers)
-    ///
-    ///   It's likely this will be less efficient than polling on other OSs and
-    ///   it also means the `NSApp` is stopped while outside of the Winit
-    ///   event loop - and that's observable (for example to crates like `rfd`)
-    ///   because the `NSApp` is global state.
+    ///   Run loop sources, timers and observers that were only added for other modes, like
+    ///   `NSModalPanelRunLoopMode`, don't run during `pump_events`. Since the `NSApp` isn't
+    ///   running outside of the first call, crates that check whether it is (like `rfd`) may
+    ///   behave differently.
     ///
     ///   If you render outside of Winit you are likely to see window resizing artifacts
     ///   since MacOS expects applications to render synchronously during any `drawRect`
     ///   callback.
+    ///
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    /// [`EventLoopProxy`]: crate::event_loop::EventLoopProxy
     fn pump_events<F>(&mut self, timeout: Option<Duration>, event_handler: F) -> PumpStatus
     where
         F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>);
@@ -184,6 +199,9 @@ impl<T> EventLoopExtPumpEvents for EventLoop<T> {
     where
         F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>),
     {
-        self.event_loop.pump_events(timeout, event_handler)
+        let event_handler = self.recording(event_handler);
+        let state = self.event_loop.window_target().handler_state.clone();
+        self.event_loop
+            .pump_events(timeout, crate::scheduler::wrap(state, event_handler))
     }
 }
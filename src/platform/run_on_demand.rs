@@ -34,11 +34,28 @@ pub trait EventLoopExtRunOnDemand {
     ///
     /// See the [`set_control_flow()`] docs on how to change the event loop's behavior.
     ///
+    /// Each run returns once [`exit()`] has been called and [`Event::LoopExiting`] has been
+    /// dispatched, after which `run_on_demand` can be called again.
+    ///
+    /// # State preserved between runs
+    /// - The connection to the window system.
+    /// - [`EventLoopProxy`]s stay valid, and user events sent while the loop isn't running are
+    ///   delivered during the next run.
+    /// - The [`ControlFlow`] that was set last.
+    /// - [`Window`]s aren't closed when a run returns. A window that's kept alive stays open and
+    ///   can be used again: requests made while the loop isn't running, like
+    ///   [`Window::request_redraw()`], and other pending events are delivered during the next run.
+    ///
+    /// The exit status is reset at the start of each run, so [`exiting()`] is `false` again.
+    ///
     /// # Caveats
     /// - This extension isn't available on all platforms, since it's not always possible to return
     ///   to the caller (specifically this is impossible on iOS and Web - though with the Web
     ///   backend it is possible to use `EventLoopExtWebSys::spawn()`[^1] more than once instead).
-    /// - No [`Window`] state can be carried between separate runs of the event loop.
+    /// - Nothing processes the events of a [`Window`] while the loop isn't running, so it doesn't
+    ///   respond in the meantime and the system may flag it as not responding after a while.
+    ///   Windows that aren't needed by the next run should be dropped before the run returns, for
+    ///   example on [`Event::LoopExiting`].
     ///
     /// You are strongly encouraged to use [`EventLoop::run()`] for portability, unless you specifically need
     /// the ability to re-run a single event loop more than once
@@ -56,6 +73,8 @@ pub trait EventLoopExtRunOnDemand {
     ///   polled to ask for new events. Events are delivered via callbacks based
     ///   on an event loop that is internal to the browser itself.
     /// - **iOS:** It's not possible to stop and start an `NSApplication` repeatedly on iOS.
+    /// - **Orbital:** Not implemented, the event loop can only be run once with
+    ///   [`EventLoop::run()`].
     ///
     #[cfg_attr(
         not(wasm_platform),
@@ -63,7 +82,10 @@ pub trait EventLoopExtRunOnDemand {
     )]
     ///
     /// [`exit()`]: EventLoopWindowTarget::exit()
+    /// [`exiting()`]: EventLoopWindowTarget::exiting()
     /// [`set_control_flow()`]: EventLoopWindowTarget::set_control_flow()
+    /// [`EventLoopProxy`]: crate::event_loop::EventLoopProxy
+    /// [`ControlFlow`]: crate::event_loop::ControlFlow
     fn run_on_demand<F>(&mut self, event_handler: F) -> Result<(), EventLoopError>
     where
         F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>);
@@ -77,7 +99,10 @@ impl<T> EventLoopExtRunOnDemand for EventLoop<T> {
         F: FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>),
     {
         self.event_loop.window_target().clear_exit();
-        self.event_loop.run_on_demand(event_handler)
+        let event_handler = self.recording(event_handler);
+        let state = self.event_loop.window_target().handler_state.clone();
+        self.event_loop
+            .run_on_demand(crate::scheduler::wrap(state, event_handler))
     }
 }
 
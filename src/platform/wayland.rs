@@ -29,7 +29,13 @@ pub trait EventLoopBuilderExtWayland {
     /// Whether to allow the event loop to be created off of the main thread.
     ///
     /// By default, the window is only allowed to be created on the main
-    /// thread, to make platform compatibility easier.
+    /// thread, to make platform compatibility easier, and
+    /// [`EventLoopBuilder::build`] returns [`EventLoopError::NotMainThread`] otherwise.
+    ///
+    /// **Warning:** Code relying on this is not portable. macOS and iOS always require the main
+    /// thread, so an application that works here will fail to start there.
+    ///
+    /// [`EventLoopError::NotMainThread`]: crate::error::EventLoopError::NotMainThread
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
 }
 
@@ -62,6 +68,21 @@ pub trait WindowBuilderExtWayland {
     /// For details about application ID conventions, see the
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
     fn with_name(self, general: impl Into<String>, instance: impl Into<String>) -> Self;
+
+    /// Build window with the given decoration mode, negotiated with the compositor through the
+    /// `xdg-decoration-unstable-v1` protocol.
+    ///
+    /// The mode the compositor granted is reported with [`WindowEvent::DecorationsChanged`],
+    /// after which the application knows whether it has to draw the decorations itself. When it
+    /// does, [`Window::drag_window`], [`Window::drag_resize_window`] and
+    /// [`Window::show_window_menu`] provide what the decorations need to move, resize and open
+    /// the menu of the window.
+    ///
+    /// The default is [`DecorationMode::PreferServerSide`]. Without decorations, as set with
+    /// [`WindowBuilder::with_decorations`], client-side decorations are always requested.
+    ///
+    /// [`WindowEvent::DecorationsChanged`]: crate::event::WindowEvent::DecorationsChanged
+    fn with_decoration_mode(self, decoration_mode: DecorationMode) -> Self;
 }
 
 impl WindowBuilderExtWayland for WindowBuilder {
@@ -70,6 +91,35 @@ impl WindowBuilderExtWayland for WindowBuilder {
         self.platform_specific.name = Some(ApplicationName::new(general.into(), instance.into()));
         self
     }
+
+    #[inline]
+    fn with_decoration_mode(mut self, decoration_mode: DecorationMode) -> Self {
+        self.platform_specific.decoration_mode = decoration_mode;
+        self
+    }
+}
+
+/// Who draws the decorations of a window, see [`WindowBuilderExtWayland::with_decoration_mode`].
+///
+/// The compositor has the final say: it can refuse either mode, and compositors without the
+/// `xdg-decoration-unstable-v1` protocol always leave the decorations to the client.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DecorationMode {
+    /// Request server-side decorations.
+    ///
+    /// When the compositor draws no decorations, the window is left without any, and the
+    /// application is responsible for drawing them.
+    ServerSide,
+
+    /// Request client-side decorations, which the application is responsible for drawing.
+    ///
+    /// Winit doesn't draw any decorations for the window.
+    ClientSide,
+
+    /// Request server-side decorations, and draw client-side decorations instead when the
+    /// compositor doesn't provide them.
+    #[default]
+    PreferServerSide,
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Wayland.
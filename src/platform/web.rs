@@ -30,6 +30,7 @@
 use crate::event::Event;
 use crate::event_loop::EventLoop;
 use crate::event_loop::EventLoopWindowTarget;
+use crate::keyboard::KeyCode;
 use crate::window::{Window, WindowBuilder};
 use crate::SendSyncWrapper;
 
@@ -38,6 +39,78 @@ use web_sys::HtmlCanvasElement;
 pub trait WindowExtWebSys {
     /// Only returns the canvas if called from inside the window.
     fn canvas(&self) -> Option<HtmlCanvasElement>;
+
+    /// Returns whether `event.preventDefault` is called for the events handled by the canvas.
+    ///
+    /// See [`WindowBuilderExtWebSys::with_prevent_default`].
+    fn prevent_default(&self) -> bool;
+
+    /// Sets whether `event.preventDefault` should be called for the events handled by the canvas.
+    ///
+    /// Events are emitted either way, this only decides whether the browser still acts on them.
+    /// Preventing the default of every key blocks browser shortcuts like reloading the page or
+    /// opening the developer tools, so applications should usually let the function keys through
+    /// with [`set_passthrough_keys`](Self::set_passthrough_keys).
+    ///
+    /// See [`WindowBuilderExtWebSys::with_prevent_default`].
+    fn set_prevent_default(&self, prevent_default: bool);
+
+    /// Sets the keys for which `event.preventDefault` is never called, so that the browser keeps
+    /// handling them while [`prevent_default`](Self::prevent_default) is enabled.
+    ///
+    /// The keys are matched against [`KeyEvent::physical_key`]. This replaces any keys passed
+    /// previously, and is empty by default.
+    ///
+    /// [`KeyEvent::physical_key`]: crate::event::KeyEvent::physical_key
+    fn set_passthrough_keys(&self, keys: &[KeyCode]);
+
+    /// Sets whether right-clicking the canvas opens the context menu of the browser.
+    ///
+    /// [`WindowEvent::MouseInput`] is emitted either way.
+    ///
+    /// Disabled by default when [`WindowBuilderExtWebSys::with_prevent_default`] is enabled,
+    /// enabled otherwise. Changing [`prevent_default`](Self::prevent_default) afterwards doesn't
+    /// affect it.
+    ///
+    /// [`WindowEvent::MouseInput`]: crate::event::WindowEvent::MouseInput
+    fn set_context_menu_enabled(&self, enabled: bool);
+
+    /// Sets whether the drawing buffer of the canvas should be kept at the resolution of the
+    /// display, like [`WindowBuilderExtWebSys::with_auto_resize`] does.
+    ///
+    /// Winit only ever sets the CSS `width` and `height` of the canvas, and only when requested
+    /// through [`Window::request_inner_size`], while the `width` and `height` attributes are left
+    /// to the application. When enabled, the attributes are set to the size of the content box
+    /// multiplied by `devicePixelRatio`, which is exactly what [`Window::inner_size`] and
+    /// [`WindowEvent::Resized`] report in physical pixels, so the rendering stays crisp on
+    /// high-DPI displays. [`Window::scale_factor`] is always `devicePixelRatio`, and changes of it,
+    /// e.g. on browser zoom or when moving to another monitor, emit
+    /// [`WindowEvent::ScaleFactorChanged`] followed by the resize of the drawing buffer.
+    ///
+    /// Enabling this applies the last reported size right away. Disabling it leaves the
+    /// attributes at their current value.
+    ///
+    /// [`Window::request_inner_size`]: crate::window::Window::request_inner_size
+    /// [`Window::inner_size`]: crate::window::Window::inner_size
+    /// [`Window::scale_factor`]: crate::window::Window::scale_factor
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn scale_canvas_to_display(&self, enabled: bool);
+
+    /// Sets the CSS `cursor` property of the canvas to `cursor`, to show any cursor of CSS, like
+    /// `"vertical-text"`, `"cell"` or a `url()`, including those without a [`CursorIcon`]
+    /// variant.
+    ///
+    /// The value isn't validated: the application is responsible for passing a valid CSS value,
+    /// browsers ignore invalid ones and keep showing the previous cursor.
+    ///
+    /// This and [`Window::set_cursor`] replace each other, the last one called wins. Like
+    /// the latter, it overrides the [cursor regions](Window::set_cursor_regions) until they're
+    /// set again, and [hiding the cursor](Window::set_cursor_visible) takes precedence over it.
+    ///
+    /// [`CursorIcon`]: crate::window::CursorIcon
+    /// [`Window::set_cursor`]: crate::window::Window::set_cursor
+    fn set_cursor_css(&self, cursor: &str);
 }
 
 impl WindowExtWebSys for Window {
@@ -45,13 +118,51 @@ impl WindowExtWebSys for Window {
     fn canvas(&self) -> Option<HtmlCanvasElement> {
         self.window.canvas()
     }
+
+    #[inline]
+    fn prevent_default(&self) -> bool {
+        self.window.prevent_default()
+    }
+
+    #[inline]
+    fn set_prevent_default(&self, prevent_default: bool) {
+        self.window.set_prevent_default(prevent_default)
+    }
+
+    #[inline]
+    fn set_passthrough_keys(&self, keys: &[KeyCode]) {
+        self.window.set_passthrough_keys(keys.to_vec())
+    }
+
+    #[inline]
+    fn set_context_menu_enabled(&self, enabled: bool) {
+        self.window.set_context_menu_enabled(enabled)
+    }
+
+    #[inline]
+    fn scale_canvas_to_display(&self, enabled: bool) {
+        self.window.scale_canvas_to_display(enabled)
+    }
+
+    #[inline]
+    fn set_cursor_css(&self, cursor: &str) {
+        self.window.set_cursor_css(cursor)
+    }
 }
 
 pub trait WindowBuilderExtWebSys {
     /// Pass an [`HtmlCanvasElement`] to be used for this [`Window`]. If [`None`],
     /// [`WindowBuilder::build()`] will create one.
     ///
-    /// In any case, the canvas won't be automatically inserted into the web page.
+    /// In any case, the canvas won't be automatically inserted into the web page, see
+    /// [`with_append`](Self::with_append).
+    ///
+    /// A provided canvas is adopted as it is: winit attaches the same event listeners and observers
+    /// as to one of its own, but it keeps its CSS size unless
+    /// [`WindowBuilder::with_inner_size`] and related options are set, and its `width` and
+    /// `height` attributes unless [`with_auto_resize`](Self::with_auto_resize) is enabled. It also
+    /// stays in the page when the [`Window`] is dropped, since it belongs to the application,
+    /// while a canvas created by winit is removed from it.
     ///
     /// [`None`] by default.
     fn with_canvas(self, canvas: Option<HtmlCanvasElement>) -> Self;
@@ -65,6 +176,10 @@ pub trait WindowBuilderExtWebSys {
     /// Some events are impossible to prevent. E.g. Firefox allows to access the native browser
     /// context menu with Shift+Rightclick.
     ///
+    /// This includes all key events, which blocks browser shortcuts like F5 or Ctrl+L. Use
+    /// [`WindowExtWebSys::set_passthrough_keys`] to let some of them through, and
+    /// [`WindowExtWebSys::set_prevent_default`] to change this after the window was created.
+    ///
     /// Enabled by default.
     fn with_prevent_default(self, prevent_default: bool) -> Self;
 
@@ -78,6 +193,59 @@ pub trait WindowBuilderExtWebSys {
     ///
     /// Disabled by default.
     fn with_append(self, append: bool) -> Self;
+
+    /// Whether winit should keep the `width` and `height` attributes of the canvas, which
+    /// determine the size of its drawing buffer, equal to the size of its content box in device
+    /// pixels.
+    ///
+    /// The canvas is always observed with a `ResizeObserver`, which emits
+    /// [`WindowEvent::Resized`] in device pixels whenever its CSS box changes, e.g. through flex
+    /// layout or a resize of the browser window. Changes of the `devicePixelRatio` emit
+    /// [`WindowEvent::ScaleFactorChanged`] first. Without this option the attributes are left
+    /// alone, so the application has to update them on `Resized` or the rendering is stretched to
+    /// the CSS box. With it, the attributes are already updated when `Resized` is received, and
+    /// the application should stop setting them itself.
+    ///
+    /// The size of the canvas is still controlled through CSS, see [`Window::request_inner_size`].
+    /// Use [`WindowExtWebSys::scale_canvas_to_display`] to change this after the window was
+    /// created.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`Window::request_inner_size`]: crate::window::Window::request_inner_size
+    fn with_auto_resize(self, auto_resize: bool) -> Self;
+
+    /// Whether a pointer pressed over the canvas should be captured by it until it is released,
+    /// using `setPointerCapture()` and `releasePointerCapture()` of the [Pointer Events API].
+    ///
+    /// While captured, [`WindowEvent::CursorMoved`] and [`WindowEvent::MouseInput`] keep being
+    /// delivered when the pointer leaves the canvas, like on desktop platforms. This also means
+    /// that releasing a button outside of the canvas is still reported, which would otherwise be
+    /// lost.
+    ///
+    /// Enabled by default.
+    ///
+    /// [Pointer Events API]: https://developer.mozilla.org/en-US/docs/Web/API/Pointer_events
+    /// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+    /// [`WindowEvent::MouseInput`]: crate::event::WindowEvent::MouseInput
+    fn with_pointer_capture(self, pointer_capture: bool) -> Self;
+
+    /// Sets the CSS [`touch-action`] of the canvas, which decides which touch gestures the
+    /// browser handles itself, like scrolling the page or pinch-zooming it.
+    ///
+    /// Browsers start handling a gesture as soon as it is recognized and then cancel the touch,
+    /// so [`WindowEvent::Touch`] is only delivered reliably on mobile devices with
+    /// [`TouchAction::None`]. It also disables zooming by double-tapping, which otherwise delays and
+    /// swallows quick consecutive taps. In addition, `touchstart` events have their default action
+    /// prevented when [`with_prevent_default`](Self::with_prevent_default) is enabled.
+    ///
+    /// [`TouchAction::None`] by default.
+    ///
+    /// [`touch-action`]: https://developer.mozilla.org/en-US/docs/Web/CSS/touch-action
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    fn with_touch_action(self, touch_action: TouchAction) -> Self;
 }
 
 impl WindowBuilderExtWebSys for WindowBuilder {
@@ -100,6 +268,21 @@ impl WindowBuilderExtWebSys for WindowBuilder {
         self.platform_specific.append = append;
         self
     }
+
+    fn with_auto_resize(mut self, auto_resize: bool) -> Self {
+        self.platform_specific.auto_resize = auto_resize;
+        self
+    }
+
+    fn with_pointer_capture(mut self, pointer_capture: bool) -> Self {
+        self.platform_specific.pointer_capture = pointer_capture;
+        self
+    }
+
+    fn with_touch_action(mut self, touch_action: TouchAction) -> Self {
+        self.platform_specific.touch_action = touch_action;
+        self
+    }
 }
 
 /// Additional methods on `EventLoop` that are specific to the web.
@@ -119,7 +302,14 @@ pub trait EventLoopExtWebSys {
         doc = "[`run()`]"
     )]
     /// [^1], this returns immediately, and doesn't throw an exception in order to
-    /// satisfy its [`!`] return type.
+    /// satisfy its [`!`] return type. This lets the rest of the `main` function of a larger
+    /// WebAssembly application run after the event loop was set up.
+    ///
+    /// After this returns, the event loop lives on through the callbacks it registered with the
+    /// browser, such as event listeners and `requestAnimationFrame`, which own the event handler.
+    /// There's no handle to keep alive: it keeps running until the event handler calls
+    /// [`EventLoopWindowTarget::exit()`], at which point the callbacks are removed and
+    /// [`Event::LoopExiting`] is the last event delivered.
     ///
     /// Once the event loop has been destroyed, it's possible to reinitialize another event loop
     /// by calling this function again. This can be useful if you want to recreate the event loop
@@ -143,7 +333,10 @@ impl<T> EventLoopExtWebSys for EventLoop<T> {
     where
         F: 'static + FnMut(Event<Self::UserEvent>, &EventLoopWindowTarget<Self::UserEvent>),
     {
-        self.event_loop.spawn(event_handler)
+        let event_handler = self.recording(event_handler);
+        let state = self.event_loop.window_target().handler_state.clone();
+        self.event_loop
+            .spawn(crate::scheduler::wrap(state, event_handler))
     }
 }
 
@@ -200,3 +393,25 @@ pub enum PollStrategy {
     #[default]
     Scheduler,
 }
+
+/// Touch gestures handled by the browser on the canvas, see
+/// [`WindowBuilderExtWebSys::with_touch_action`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TouchAction {
+    /// Leaves the [`touch-action`] of the canvas as set by the page, which is `auto` unless the
+    /// page changed it.
+    ///
+    /// [`touch-action`]: https://developer.mozilla.org/en-US/docs/Web/CSS/touch-action
+    Auto,
+    /// The browser doesn't handle any gesture, all touches are delivered to the application.
+    ///
+    /// This is the default.
+    #[default]
+    None,
+    /// The browser only handles horizontal panning.
+    PanX,
+    /// The browser only handles vertical panning.
+    PanY,
+    /// The browser handles panning and pinch-zooming, but not double-tap zooming.
+    Manipulation,
+}
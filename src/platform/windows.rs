@@ -1,4 +1,4 @@
-use std::{ffi::c_void, path::Path};
+use std::{ffi::c_void, path::Path, time::Duration};
 
 use crate::{
     dpi::PhysicalSize,
@@ -23,7 +23,13 @@ pub trait EventLoopBuilderExtWindows {
     /// Whether to allow the event loop to be created off of the main thread.
     ///
     /// By default, the window is only allowed to be created on the main
-    /// thread, to make platform compatibility easier.
+    /// thread, to make platform compatibility easier, and
+    /// [`EventLoopBuilder::build`] returns [`EventLoopError::NotMainThread`] otherwise.
+    ///
+    /// **Warning:** Code relying on this is not portable. macOS and iOS always require the main
+    /// thread, so an application that works here will fail to start there.
+    ///
+    /// [`EventLoopError::NotMainThread`]: crate::error::EventLoopError::NotMainThread
     ///
     /// # `Window` caveats
     ///
@@ -55,6 +61,25 @@ pub trait EventLoopBuilderExtWindows {
     /// ```
     fn with_dpi_aware(&mut self, dpi_aware: bool) -> &mut Self;
 
+    /// Sets the [AppUserModelID] of the process when the event loop is built.
+    ///
+    /// Windows uses it to group the taskbar buttons of the windows, and to attribute toast
+    /// notifications, jump lists and the like to the application. Without an explicit one, it's
+    /// derived from the executable, so e.g. an application embedded in an interpreter is
+    /// attributed to the interpreter.
+    ///
+    /// The ID applies to the whole process and has to be set before any window is created, which
+    /// building the event loop guarantees. It's unrelated to the application identity set with
+    /// `WindowBuilderExtX11::with_name` and `WindowBuilderExtWayland::with_name`, which only
+    /// apply to their respective platforms.
+    ///
+    /// [`EventLoopBuilder::build`] returns an [`EventLoopError::Os`] if the ID is invalid, e.g.
+    /// when it's empty, contains spaces or is longer than 128 characters.
+    ///
+    /// [AppUserModelID]: https://learn.microsoft.com/en-us/windows/win32/shell/appids
+    /// [`EventLoopError::Os`]: crate::error::EventLoopError::Os
+    fn with_app_id(&mut self, app_id: &str) -> &mut Self;
+
     /// A callback to be executed before dispatching a win32 message to the window procedure.
     /// Return true to disable winit's internal message dispatching.
     ///
@@ -99,6 +124,12 @@ impl<T> EventLoopBuilderExtWindows for EventLoopBuilder<T> {
         self
     }
 
+    #[inline]
+    fn with_app_id(&mut self, app_id: &str) -> &mut Self {
+        self.platform_specific.app_id = Some(app_id.to_owned());
+        self
+    }
+
     #[inline]
     fn with_msg_hook<F>(&mut self, callback: F) -> &mut Self
     where
@@ -109,6 +140,37 @@ impl<T> EventLoopBuilderExtWindows for EventLoopBuilder<T> {
     }
 }
 
+/// Which parts of the window [`WindowExtWindows::flash`] flashes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FlashTarget {
+    /// Flash the window caption, sets `FLASHW_CAPTION`.
+    Caption,
+
+    /// Flash the taskbar button, sets `FLASHW_TRAY`.
+    TaskbarButton,
+
+    /// Flash both the window caption and the taskbar button, sets `FLASHW_ALL`.
+    #[default]
+    All,
+}
+
+/// How [`WindowExtWindows::flash`] flashes the window, mapped to the fields of `FLASHWINFO`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FlashConfig {
+    /// The number of times to flash the window.
+    ///
+    /// `None` flashes it until it comes to the foreground, with `FLASHW_TIMERNOFG`.
+    pub count: Option<u32>,
+
+    /// The time between two flashes.
+    ///
+    /// `None` uses the default cursor blink rate.
+    pub rate: Option<Duration>,
+
+    /// The parts of the window to flash.
+    pub target: FlashTarget,
+}
+
 /// Additional methods on `Window` that are specific to Windows.
 pub trait WindowExtWindows {
     /// Enables or disables mouse and keyboard input to the specified window.
@@ -124,6 +186,8 @@ pub trait WindowExtWindows {
     ///
     /// For more information, see <https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-enablewindow#remarks>
     /// and <https://docs.microsoft.com/en-us/windows/win32/winmsg/window-features#disabled-windows>
+    ///
+    /// This is the same as [`Window::set_enabled`].
     fn set_enable(&self, enabled: bool);
 
     /// This sets `ICON_BIG`. A good ceiling here is 256x256.
@@ -136,12 +200,22 @@ pub trait WindowExtWindows {
     ///
     /// Enabling the shadow causes a thin 1px line to appear on the top of the window.
     fn set_undecorated_shadow(&self, shadow: bool);
+
+    /// Flashes the window caption and/or taskbar button, as configured by [`FlashConfig`].
+    ///
+    /// This is the fine-grained version of [`Window::request_user_attention`], which remains the
+    /// portable way to get the user's attention. Unlike it, this flashes the window even when it
+    /// is already in the foreground. Pass `None` to [`Window::request_user_attention`] to stop
+    /// the flashing.
+    ///
+    /// For more information, see <https://learn.microsoft.com/en-us/windows/win32/api/winuser/ns-winuser-flashwinfo>
+    fn flash(&self, config: FlashConfig);
 }
 
 impl WindowExtWindows for Window {
     #[inline]
     fn set_enable(&self, enabled: bool) {
-        self.window.set_enable(enabled)
+        self.window.set_enabled(enabled)
     }
 
     #[inline]
@@ -158,6 +232,11 @@ impl WindowExtWindows for Window {
     fn set_undecorated_shadow(&self, shadow: bool) {
         self.window.set_undecorated_shadow(shadow)
     }
+
+    #[inline]
+    fn flash(&self, config: FlashConfig) {
+        self.window.flash(config)
+    }
 }
 
 /// Additional methods on `WindowBuilder` that are specific to Windows.
@@ -51,7 +51,7 @@ pub trait EventLoopWindowTargetExtX11 {
 impl<T> EventLoopWindowTargetExtX11 for EventLoopWindowTarget<T> {
     #[inline]
     fn is_x11(&self) -> bool {
-        !self.p.is_wayland()
+        self.p.is_x11()
     }
 }
 
@@ -63,7 +63,13 @@ pub trait EventLoopBuilderExtX11 {
     /// Whether to allow the event loop to be created off of the main thread.
     ///
     /// By default, the window is only allowed to be created on the main
-    /// thread, to make platform compatibility easier.
+    /// thread, to make platform compatibility easier, and
+    /// [`EventLoopBuilder::build`] returns [`EventLoopError::NotMainThread`] otherwise.
+    ///
+    /// **Warning:** Code relying on this is not portable. macOS and iOS always require the main
+    /// thread, so an application that works here will fail to start there.
+    ///
+    /// [`EventLoopError::NotMainThread`]: crate::error::EventLoopError::NotMainThread
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
 }
 
@@ -82,9 +88,24 @@ impl<T> EventLoopBuilderExtX11 for EventLoopBuilder<T> {
 }
 
 /// Additional methods on [`Window`] that are specific to X11.
-pub trait WindowExtX11 {}
+pub trait WindowExtX11 {
+    /// Sets the base size hint of the window, or removes it with `None`.
+    ///
+    /// See [`WindowBuilderExtX11::with_base_size`] for how the base size interacts with
+    /// [`Window::set_resize_increments`].
+    ///
+    /// This is a no-op when the window isn't backed by X11.
+    fn set_base_size<S: Into<Size>>(&self, base_size: Option<S>);
+}
 
-impl WindowExtX11 for Window {}
+impl WindowExtX11 for Window {
+    #[inline]
+    fn set_base_size<S: Into<Size>>(&self, base_size: Option<S>) {
+        let base_size = base_size.map(Into::into);
+        self.window
+            .maybe_queue_on_main(move |w| w.set_base_size(base_size))
+    }
+}
 
 /// Additional methods on [`WindowBuilder`] that are specific to X11.
 pub trait WindowBuilderExtX11 {
@@ -110,6 +131,16 @@ pub trait WindowBuilderExtX11 {
 
     /// Build window with base size hint. Only implemented on X11.
     ///
+    /// The base size is the `base_width` and `base_height` of `WM_NORMAL_HINTS`. Per the ICCCM,
+    /// when resize increments are set with [`WindowBuilder::with_resize_increments`], the window
+    /// manager only allows inner sizes of the form `base + N * increment`, and reports `N` to
+    /// the user instead of the size in pixels. For a terminal emulator, the base size is the
+    /// padding around the cell grid, which makes the window manager display `80x24` for a grid
+    /// of 80 by 24 cells instead of an off-by-one count.
+    ///
+    /// When no base size is set, the window manager uses the minimum size in its place, see
+    /// [`WindowBuilder::with_min_inner_size`].
+    ///
     /// ```
     /// # use winit::dpi::{LogicalSize, PhysicalSize};
     /// # use winit::window::WindowBuilder;
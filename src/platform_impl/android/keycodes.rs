@@ -111,6 +111,14 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::MediaNext => KeyCode::MediaTrackNext,
         Keycode::MediaPrevious => KeyCode::MediaTrackPrevious,
 
+        Keycode::Back => KeyCode::BrowserBack,
+        Keycode::Forward => KeyCode::BrowserForward,
+        Keycode::Home => KeyCode::BrowserHome,
+        Keycode::Search => KeyCode::BrowserSearch,
+        Keycode::Bookmark => KeyCode::BrowserFavorites,
+        Keycode::Envelope => KeyCode::LaunchMail,
+        Keycode::Calculator => KeyCode::LaunchApp2,
+
         Keycode::Plus => KeyCode::Equal,
         Keycode::Minus => KeyCode::Minus,
         // Winit doesn't differentiate both '+' and '=', considering they are usually
@@ -11,28 +11,61 @@ use std::{
     time::{Duration, Instant},
 };
 
-use android_activity::input::{InputEvent, KeyAction, Keycode, MotionAction};
+use android_activity::input::{InputEvent, KeyAction, Keycode, MotionAction, ToolType};
 use android_activity::{
     AndroidApp, AndroidAppWaker, ConfigurationRef, InputStatus, MainEvent, Rect,
 };
+use ndk::configuration::{KeysHidden, UiModeNight};
+use ndk::native_window::NativeWindow;
 use once_cell::sync::Lazy;
 
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size},
     error,
     event::{self, Force, InnerSizeWriter, StartCause},
     event_loop::{self, ControlFlow, DeviceEvents, EventLoopWindowTarget as RootELW},
     platform::pump_events::PumpStatus,
     window::{
-        self, CursorGrabMode, ImePurpose, ResizeDirection, Theme, WindowButtons, WindowLevel,
+        self, CursorGrabMode, ImageBuffer, ImeHints, ImePurpose, ResizeDirection, Theme,
+        WindowButtons, WindowLevel,
     },
 };
-use crate::{error::EventLoopError, platform_impl::Fullscreen};
+use crate::{
+    error::EventLoopError,
+    platform_impl::{Fullscreen, TouchIds},
+};
 
 mod keycodes;
 
 static HAS_FOCUS: Lazy<RwLock<bool>> = Lazy::new(|| RwLock::new(true));
 
+/// Whether the back button is handled by the application, see
+/// `WindowExtAndroid::set_back_button_handled`.
+static BACK_BUTTON_HANDLED: AtomicBool = AtomicBool::new(true);
+
+/// Reads the fields of `WindowEvent::ConfigurationChanged` from the configuration.
+fn configuration(config: &ConfigurationRef) -> event::Configuration {
+    let locale = config.language().map(|language| match config.country() {
+        Some(country) => format!("{language}-{country}"),
+        None => language,
+    });
+    let theme = match config.ui_mode_night() {
+        UiModeNight::Yes => Some(Theme::Dark),
+        UiModeNight::No => Some(Theme::Light),
+        _ => None,
+    };
+    let hardware_keyboard_hidden = match config.keys_hidden() {
+        KeysHidden::Yes | KeysHidden::Soft => Some(true),
+        KeysHidden::No => Some(false),
+        _ => None,
+    };
+    event::Configuration {
+        locale,
+        theme,
+        hardware_keyboard_hidden,
+    }
+}
+
 /// Returns the minimum `Option<Duration>`, taking into account that `None`
 /// equates to an infinite timeout, not a zero timeout (so can't just use
 /// `Option::min`)
@@ -134,26 +167,31 @@ impl RedrawRequester {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEventExtra {}
 
 pub struct EventLoop<T: 'static> {
     android_app: AndroidApp,
     window_target: event_loop::EventLoopWindowTarget<T>,
     redraw_flag: SharedFlag,
-    user_events_sender: mpsc::Sender<T>,
+    wake_up_flag: SharedFlag,
     user_events_receiver: PeekableReceiver<T>, //must wake looper whenever something gets sent
     loop_running: bool,                        // Dispatched `NewEvents<Init>`
     running: bool,
     pending_redraw: bool,
     cause: StartCause,
     ignore_volume_keys: bool,
+    /// The configuration last reported with `WindowEvent::ConfigurationChanged`.
+    configuration: event::Configuration,
     combining_accent: Option<char>,
+    touch_ids: TouchIds<(i32, i32)>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) android_app: Option<AndroidApp>,
     pub(crate) ignore_volume_keys: bool,
+    pub(crate) back_button_handled: bool,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
@@ -161,6 +199,7 @@ impl Default for PlatformSpecificEventLoopAttributes {
         Self {
             android_app: Default::default(),
             ignore_volume_keys: true,
+            back_button_handled: true,
         }
     }
 }
@@ -173,6 +212,8 @@ impl<T: 'static> EventLoop<T> {
 
         let android_app = attributes.android_app.as_ref().expect("An `AndroidApp` as passed to android_main() is required to create an `EventLoop` on Android");
         let redraw_flag = SharedFlag::new();
+        let wake_up_flag = SharedFlag::new();
+        BACK_BUTTON_HANDLED.store(attributes.back_button_handled, Ordering::Relaxed);
 
         Ok(Self {
             android_app: android_app.clone(),
@@ -185,19 +226,23 @@ impl<T: 'static> EventLoop<T> {
                         &redraw_flag,
                         android_app.create_waker(),
                     ),
-                    _marker: std::marker::PhantomData,
+                    user_events_sender,
+                    wake_up_flag: wake_up_flag.setter(),
                 },
+                handler_state: Default::default(),
                 _marker: std::marker::PhantomData,
             },
             redraw_flag,
-            user_events_sender,
+            wake_up_flag,
             user_events_receiver: PeekableReceiver::from_recv(user_events_receiver),
             loop_running: false,
             running: false,
             pending_redraw: false,
             cause: StartCause::Init,
             ignore_volume_keys: attributes.ignore_volume_keys,
+            configuration: configuration(&android_app.config()),
             combining_accent: None,
+            touch_ids: TouchIds::default(),
         })
     }
 
@@ -249,6 +294,29 @@ impl<T: 'static> EventLoop<T> {
                     );
                 }
                 MainEvent::ConfigChanged { .. } => {
+                    let configuration = configuration(&self.android_app.config());
+                    if configuration != self.configuration {
+                        let old_theme = self.configuration.theme;
+                        self.configuration = configuration.clone();
+                        let theme = configuration.theme;
+                        callback(
+                            event::Event::WindowEvent {
+                                window_id: window::WindowId(WindowId),
+                                event: event::WindowEvent::ConfigurationChanged(configuration),
+                            },
+                            self.window_target(),
+                        );
+                        if let Some(theme) = theme.filter(|&theme| Some(theme) != old_theme) {
+                            callback(
+                                event::Event::WindowEvent {
+                                    window_id: window::WindowId(WindowId),
+                                    event: event::WindowEvent::ThemeChanged(theme),
+                                },
+                                self.window_target(),
+                            );
+                        }
+                    }
+
                     let monitor = MonitorHandle::new(self.android_app.clone());
                     let old_scale_factor = monitor.scale_factor();
                     let scale_factor = monitor.scale_factor();
@@ -262,6 +330,7 @@ impl<T: 'static> EventLoop<T> {
                                 inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
                                     &new_inner_size,
                                 )),
+                                old_scale_factor,
                                 scale_factor,
                             },
                         };
@@ -356,7 +425,7 @@ impl<T: 'static> EventLoop<T> {
                 pending_redraw = false;
                 let event = event::Event::WindowEvent {
                     window_id: window::WindowId(WindowId),
-                    event: event::WindowEvent::RedrawRequested,
+                    event: event::WindowEvent::RedrawRequested { dirty_rects: None },
                 };
                 callback(event, self.window_target());
             }
@@ -419,8 +488,11 @@ impl<T: 'static> EventLoop<T> {
                                 device_id,
                                 phase,
                                 location,
-                                id: pointer.pointer_id() as u64,
+                                id: self
+                                    .touch_ids
+                                    .get((motion_event.device_id(), pointer.pointer_id()), phase),
                                 force: Some(Force::Normalized(pointer.pressure() as f64)),
+                                palm: matches!(pointer.tool_type(), ToolType::Palm),
                             }),
                         };
                         callback(event, self.window_target());
@@ -467,6 +539,13 @@ impl<T: 'static> EventLoop<T> {
                             },
                         };
                         callback(event, self.window_target());
+
+                        // The system navigates back when the key isn't handled.
+                        if matches!(keycode, Keycode::Back)
+                            && !BACK_BUTTON_HANDLED.load(Ordering::Relaxed)
+                        {
+                            input_status = InputStatus::Unhandled;
+                        }
                     }
                 }
             }
@@ -575,12 +654,15 @@ impl<T: 'static> EventLoop<T> {
                     // would cause the event loop to run continuously. They handle this by re-checking
                     // for pending events (assuming they cover all valid reasons for a wake up).
                     //
-                    // For now, user_events and redraw_requests are the only reasons to expect
-                    // a wake up here so we can ignore the wake up if there are no events/requests.
-                    // We also ignore wake ups while suspended.
+                    // For now, user_events, redraw_requests and `EventLoopProxy::wake_up` are the
+                    // only reasons to expect a wake up here so we can ignore the wake up if there
+                    // are no events/requests. We also ignore wake ups while suspended.
                     self.pending_redraw |= self.redraw_flag.get_and_reset();
+                    let proxy_wake_up = self.wake_up_flag.get_and_reset();
                     if !self.running
-                        || (!self.pending_redraw && !self.user_events_receiver.has_incoming())
+                        || (!self.pending_redraw
+                            && !proxy_wake_up
+                            && !self.user_events_receiver.has_incoming())
                     {
                         return;
                     }
@@ -624,10 +706,7 @@ impl<T: 'static> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy {
-            user_events_sender: self.user_events_sender.clone(),
-            waker: self.android_app.create_waker(),
-        }
+        self.window_target.p.create_proxy()
     }
 
     fn control_flow(&self) -> ControlFlow {
@@ -641,6 +720,7 @@ impl<T: 'static> EventLoop<T> {
 
 pub struct EventLoopProxy<T: 'static> {
     user_events_sender: mpsc::Sender<T>,
+    wake_up_flag: SharedFlagSetter,
     waker: AndroidAppWaker,
 }
 
@@ -648,6 +728,7 @@ impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         EventLoopProxy {
             user_events_sender: self.user_events_sender.clone(),
+            wake_up_flag: self.wake_up_flag.clone(),
             waker: self.waker.clone(),
         }
     }
@@ -661,6 +742,13 @@ impl<T> EventLoopProxy<T> {
         self.waker.wake();
         Ok(())
     }
+
+    pub fn wake_up(&self) {
+        // Only explicitly try to wake up the main loop when the flag value changes.
+        if self.wake_up_flag.set() {
+            self.waker.wake();
+        }
+    }
 }
 
 pub struct EventLoopWindowTarget<T: 'static> {
@@ -668,10 +756,23 @@ pub struct EventLoopWindowTarget<T: 'static> {
     control_flow: Cell<ControlFlow>,
     exit: Cell<bool>,
     redraw_requester: RedrawRequester,
-    _marker: std::marker::PhantomData<T>,
+    user_events_sender: mpsc::Sender<T>,
+    wake_up_flag: SharedFlagSetter,
 }
 
 impl<T: 'static> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy {
+            user_events_sender: self.user_events_sender.clone(),
+            wake_up_flag: self.wake_up_flag.clone(),
+            waker: self.app.create_waker(),
+        }
+    }
+
+    pub(crate) fn has_native_window(&self) -> bool {
+        self.app.native_window().is_some()
+    }
+
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         Some(MonitorHandle::new(self.app.clone()))
     }
@@ -685,6 +786,28 @@ impl<T: 'static> EventLoopWindowTarget<T> {
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    #[inline]
+    pub fn device_info(&self, _device_id: DeviceId) -> Option<event::DeviceInfo> {
+        None
+    }
+
+    #[inline]
+    pub fn backend(&self) -> event_loop::Backend {
+        event_loop::Backend::Android
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> Option<event_loop::PowerState> {
+        None
+    }
+
+    #[inline]
+    pub fn clip_cursor(&self, _rect: Option<PhysicalRect>) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
     #[cfg(feature = "rwh_05")]
     #[inline]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
@@ -709,6 +832,10 @@ impl<T: 'static> EventLoopWindowTarget<T> {
         self.control_flow.get()
     }
 
+    pub(crate) fn request_redraw(&self, _window_id: WindowId) {
+        self.redraw_requester.request_redraw()
+    }
+
     pub(crate) fn exit(&self) {
         self.exit.set(true)
     }
@@ -750,6 +877,10 @@ impl DeviceId {
     pub const fn dummy() -> Self {
         DeviceId(0)
     }
+
+    pub fn persistent_id(&self) -> Option<u64> {
+        u64::try_from(self.0).ok()
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -766,6 +897,10 @@ impl Window {
         _window_attrs: window::WindowAttributes,
         _: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Self, error::OsError> {
+        if el.exiting() {
+            return Err(os_error!(OsError::EventLoopExiting));
+        }
+
         // FIXME this ignores requested window attributes
 
         Ok(Self {
@@ -796,6 +931,10 @@ impl Window {
         v
     }
 
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        PhysicalRect::new(monitor.position(), monitor.size())
+    }
+
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
         Some(MonitorHandle::new(self.app.clone()))
     }
@@ -834,6 +973,21 @@ impl Window {
         MonitorHandle::new(self.app.clone()).size()
     }
 
+    pub fn set_visible_on_all_workspaces(
+        &self,
+        _visible: bool,
+    ) -> Result<(), error::NotSupportedError> {
+        Err(error::NotSupportedError::new())
+    }
+
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    pub fn frame_insets(&self) -> window::Insets {
+        window::Insets::default()
+    }
+
     pub fn set_min_inner_size(&self, _: Option<Size>) {}
 
     pub fn set_max_inner_size(&self, _: Option<Size>) {}
@@ -856,6 +1010,8 @@ impl Window {
         None
     }
 
+    pub fn set_enabled(&self, _enabled: bool) {}
+
     pub fn set_resizable(&self, _resizeable: bool) {}
 
     pub fn is_resizable(&self) -> bool {
@@ -898,18 +1054,38 @@ impl Window {
 
     pub fn set_window_icon(&self, _window_icon: Option<crate::icon::Icon>) {}
 
+    pub fn set_drop_feedback(&self, _effect: crate::window::DropEffect) {}
+
     pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
     pub fn set_ime_allowed(&self, _allowed: bool) {}
 
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
-    pub fn focus_window(&self) {}
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    pub fn set_above(&self, _sibling: &Window) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    pub fn set_below(&self, _sibling: &Window) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    pub fn focus_window(&self) -> Result<(), error::NotSupportedError> {
+        Err(error::NotSupportedError::new())
+    }
 
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
     pub fn set_cursor_icon(&self, _: window::CursorIcon) {}
 
+    pub fn set_cursor_regions(&self, _: Vec<(PhysicalRect, window::CursorIcon)>) {}
+
     pub fn set_cursor_position(&self, _: Position) -> Result<(), error::ExternalError> {
         Err(error::ExternalError::NotSupported(
             error::NotSupportedError::new(),
@@ -922,6 +1098,17 @@ impl Window {
         ))
     }
 
+    pub fn confine_cursor_to_rect(
+        &self,
+        _: Option<PhysicalRect>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    pub fn set_auto_grab(&self, _: bool) {}
+
     pub fn set_cursor_visible(&self, _: bool) {}
 
     pub fn drag_window(&self) -> Result<(), error::ExternalError> {
@@ -948,6 +1135,15 @@ impl Window {
         ))
     }
 
+    pub fn set_shape(
+        &self,
+        _shape: Option<window::WindowShape>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
     #[cfg(feature = "rwh_04")]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
         use rwh_04::HasRawWindowHandle;
@@ -1002,6 +1198,10 @@ impl Window {
         self.app.config()
     }
 
+    pub fn set_back_button_handled(&self, handled: bool) {
+        BACK_BUTTON_HANDLED.store(handled, Ordering::Relaxed);
+    }
+
     pub fn content_rect(&self) -> Rect {
         self.app.content_rect()
     }
@@ -1009,11 +1209,17 @@ impl Window {
     pub fn set_theme(&self, _theme: Option<Theme>) {}
 
     pub fn theme(&self) -> Option<Theme> {
-        None
+        configuration(&self.app.config()).theme
     }
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn capture(&self) -> Result<ImageBuffer, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
     pub fn has_focus(&self) -> bool {
         *HAS_FOCUS.read().unwrap()
     }
@@ -1023,15 +1229,34 @@ impl Window {
     }
 
     pub fn reset_dead_keys(&self) {}
+
+    pub fn surface_valid(&self) -> bool {
+        self.app.native_window().is_some()
+    }
+
+    pub fn native_window(&self) -> Option<NativeWindow> {
+        self.app.native_window()
+    }
 }
 
-#[derive(Default, Clone, Debug)]
-pub struct OsError;
+#[derive(Clone, Debug)]
+pub enum OsError {
+    /// The native window only exists between `Event::Resumed` and `Event::Suspended`.
+    NoNativeWindow,
+    /// Windows can't be created once [`exit()`](crate::event_loop::EventLoopWindowTarget::exit) was called.
+    EventLoopExiting,
+}
 
 use std::fmt::{self, Display, Formatter};
 impl Display for OsError {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(fmt, "Android OS Error")
+        match self {
+            OsError::NoNativeWindow => write!(
+                fmt,
+                "the native window doesn't exist before `Event::Resumed` and after `Event::Suspended`"
+            ),
+            OsError::EventLoopExiting => write!(fmt, "the event loop is exiting"),
+        }
     }
 }
 
@@ -0,0 +1,82 @@
+//! Releasing the cursor grab while a window is unfocused, see
+//! [`Window::set_auto_grab`](crate::window::Window::set_auto_grab).
+
+use crate::window::CursorGrabMode;
+
+/// Remembers the grab that was released when the window lost focus, to re-apply it once the
+/// window is focused again.
+#[derive(Debug, Default)]
+pub(crate) struct AutoGrab {
+    enabled: bool,
+    released: Option<CursorGrabMode>,
+}
+
+impl AutoGrab {
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.released = None;
+        }
+    }
+
+    /// Returns whether the current grab `mode` should be released.
+    pub fn focus_lost(&mut self, mode: CursorGrabMode) -> bool {
+        if self.enabled && mode != CursorGrabMode::None {
+            self.released = Some(mode);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the grab mode to re-apply, if any.
+    pub fn focus_gained(&mut self) -> Option<CursorGrabMode> {
+        self.released.take()
+    }
+
+    /// Called when the application changes the grab itself, which replaces the released one.
+    pub fn grab_changed(&mut self) {
+        self.released = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_loss_releases_and_restores_the_grab() {
+        let mut auto_grab = AutoGrab::default();
+        auto_grab.set_enabled(true);
+
+        assert!(auto_grab.focus_lost(CursorGrabMode::Locked));
+        assert_eq!(auto_grab.focus_gained(), Some(CursorGrabMode::Locked));
+        assert_eq!(auto_grab.focus_gained(), None);
+
+        assert!(!auto_grab.focus_lost(CursorGrabMode::None));
+        assert_eq!(auto_grab.focus_gained(), None);
+    }
+
+    #[test]
+    fn grab_is_kept_when_disabled() {
+        let mut auto_grab = AutoGrab::default();
+
+        assert!(!auto_grab.focus_lost(CursorGrabMode::Confined));
+        assert_eq!(auto_grab.focus_gained(), None);
+
+        auto_grab.set_enabled(true);
+        assert!(auto_grab.focus_lost(CursorGrabMode::Confined));
+        auto_grab.set_enabled(false);
+        assert_eq!(auto_grab.focus_gained(), None);
+    }
+
+    #[test]
+    fn explicit_grab_replaces_the_released_one() {
+        let mut auto_grab = AutoGrab::default();
+        auto_grab.set_enabled(true);
+
+        assert!(auto_grab.focus_lost(CursorGrabMode::Confined));
+        auto_grab.grab_changed();
+        assert_eq!(auto_grab.focus_gained(), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! Per-region hover cursors, see
+//! [`Window::set_cursor_regions`](crate::window::Window::set_cursor_regions).
+
+use crate::dpi::{PhysicalPosition, PhysicalRect};
+use crate::window::CursorIcon;
+
+/// Picks the cursor to show over the region under the pointer.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct CursorRegions {
+    regions: Vec<(PhysicalRect, CursorIcon)>,
+    /// Whether the application set a cursor itself since the regions were set.
+    overridden: bool,
+    /// The cursor of the region the pointer is over.
+    hovered: Option<CursorIcon>,
+}
+
+impl CursorRegions {
+    /// Replaces the regions, returns whether the shown cursor changed.
+    pub fn set_regions(&mut self, regions: Vec<(PhysicalRect, CursorIcon)>) -> bool {
+        self.regions = regions;
+        self.overridden = false;
+        self.hovered.take().is_some()
+    }
+
+    /// Called when the application replaces the `previous` cursor it selected with `cursor`,
+    /// which overrides the regions.
+    ///
+    /// Returns whether the shown cursor changed. Applications may set the same cursor on every
+    /// frame, so the backends skip the call to the system when it didn't.
+    pub fn cursor_set(&mut self, previous: CursorIcon, cursor: CursorIcon) -> bool {
+        let shown = self.cursor(previous);
+        self.overridden = true;
+        self.hovered = None;
+        shown != cursor
+    }
+
+    /// Returns the cursor of the region at `position`, if any.
+    pub fn cursor_at(&self, position: PhysicalPosition<f64>) -> Option<CursorIcon> {
+        if self.overridden {
+            return None;
+        }
+
+        self.regions
+            .iter()
+            .find(|(rect, _)| {
+                let x = position.x - rect.position.x as f64;
+                let y = position.y - rect.position.y as f64;
+                x >= 0.0 && y >= 0.0 && x < rect.size.width as f64 && y < rect.size.height as f64
+            })
+            .map(|&(_, cursor)| cursor)
+    }
+
+    /// Returns whether the shown cursor changed.
+    pub fn cursor_moved(&mut self, position: PhysicalPosition<f64>) -> bool {
+        let hovered = self.cursor_at(position);
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        changed
+    }
+
+    /// Returns whether the shown cursor changed.
+    pub fn cursor_left(&mut self) -> bool {
+        self.hovered.take().is_some()
+    }
+
+    /// Returns the cursor of the region the pointer is over, if any.
+    pub fn hovered(&self) -> Option<CursorIcon> {
+        self.hovered
+    }
+
+    /// Returns the cursor to show, given the one selected by the application.
+    pub fn cursor(&self, selected: CursorIcon) -> CursorIcon {
+        self.hovered().unwrap_or(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dpi::PhysicalSize;
+
+    use super::*;
+
+    fn regions() -> CursorRegions {
+        let mut regions = CursorRegions::default();
+        regions.set_regions(vec![
+            (
+                PhysicalRect::new(PhysicalPosition::new(0, 0), PhysicalSize::new(10, 100)),
+                CursorIcon::WResize,
+            ),
+            (
+                PhysicalRect::new(PhysicalPosition::new(0, 0), PhysicalSize::new(100, 10)),
+                CursorIcon::NResize,
+            ),
+        ]);
+        regions
+    }
+
+    #[test]
+    fn first_matching_region_wins() {
+        let regions = regions();
+
+        assert_eq!(
+            regions.cursor_at(PhysicalPosition::new(5.0, 5.0)),
+            Some(CursorIcon::WResize)
+        );
+        assert_eq!(
+            regions.cursor_at(PhysicalPosition::new(50.0, 5.0)),
+            Some(CursorIcon::NResize)
+        );
+        assert_eq!(regions.cursor_at(PhysicalPosition::new(10.0, 50.0)), None);
+        assert_eq!(regions.cursor_at(PhysicalPosition::new(-1.0, 5.0)), None);
+    }
+
+    #[test]
+    fn hovered_region_replaces_the_selected_cursor() {
+        let mut regions = regions();
+
+        assert!(regions.cursor_moved(PhysicalPosition::new(5.0, 50.0)));
+        assert_eq!(regions.cursor(CursorIcon::Text), CursorIcon::WResize);
+        assert!(!regions.cursor_moved(PhysicalPosition::new(6.0, 50.0)));
+
+        assert!(regions.cursor_moved(PhysicalPosition::new(50.0, 50.0)));
+        assert_eq!(regions.cursor(CursorIcon::Text), CursorIcon::Text);
+
+        assert!(regions.cursor_moved(PhysicalPosition::new(50.0, 5.0)));
+        assert!(regions.cursor_left());
+        assert_eq!(regions.cursor(CursorIcon::Text), CursorIcon::Text);
+    }
+
+    #[test]
+    fn explicit_cursor_overrides_the_regions_until_they_are_set() {
+        let mut regions = regions();
+        assert!(regions.cursor_moved(PhysicalPosition::new(5.0, 50.0)));
+
+        assert!(regions.cursor_set(CursorIcon::Text, CursorIcon::Text));
+        assert_eq!(regions.cursor(CursorIcon::Text), CursorIcon::Text);
+        assert!(!regions.cursor_moved(PhysicalPosition::new(5.0, 50.0)));
+
+        assert!(!regions.set_regions(Vec::new()));
+        assert!(!regions.cursor_moved(PhysicalPosition::new(5.0, 50.0)));
+    }
+
+    #[test]
+    fn setting_the_same_cursor_again_changes_nothing() {
+        let mut no_regions = CursorRegions::default();
+        let mut selected = CursorIcon::Default;
+        let mut changes = 0;
+        for _ in 0..60 {
+            if no_regions.cursor_set(selected, CursorIcon::Pointer) {
+                changes += 1;
+            }
+            selected = CursorIcon::Pointer;
+        }
+        assert_eq!(changes, 1);
+
+        // A hovered region was shown instead of the selected cursor, which has to be restored.
+        let mut regions = regions();
+        assert!(regions.cursor_moved(PhysicalPosition::new(5.0, 50.0)));
+        assert!(regions.cursor_set(CursorIcon::Pointer, CursorIcon::Pointer));
+        assert!(!regions.cursor_set(CursorIcon::Pointer, CursorIcon::Pointer));
+    }
+}
@@ -0,0 +1,78 @@
+//! The IME cursor area set with
+//! [`Window::set_ime_cursor_area`](crate::window::Window::set_ime_cursor_area), for the backends
+//! which can only place the IME while it's allowed.
+
+use crate::dpi::{Position, Size};
+
+/// Keeps the requested IME cursor area to apply it once the IME gets allowed.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ImeCursorArea {
+    allowed: bool,
+    area: Option<(Position, Size)>,
+}
+
+impl ImeCursorArea {
+    /// Whether the IME is allowed, see
+    /// [`Window::set_ime_allowed`](crate::window::Window::set_ime_allowed).
+    pub fn allowed(&self) -> bool {
+        self.allowed
+    }
+
+    /// Stores the area requested by the application, returning it if it has to be applied now.
+    pub fn set_area(&mut self, position: Position, size: Size) -> Option<(Position, Size)> {
+        self.area = Some((position, size));
+        self.applied()
+    }
+
+    /// Allows or disallows the IME, returning the area to apply once it got allowed.
+    pub fn set_allowed(&mut self, allowed: bool) -> Option<(Position, Size)> {
+        self.allowed = allowed;
+        self.applied()
+    }
+
+    /// Returns the area the IME is placed at, which is none while it's disallowed.
+    pub fn applied(&self) -> Option<(Position, Size)> {
+        self.area.filter(|_| self.allowed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+    use super::*;
+
+    #[test]
+    fn area_set_before_the_ime_is_allowed_is_applied_with_it() {
+        let area = (
+            Position::Logical(LogicalPosition::new(10.0, 20.0)),
+            Size::Logical(LogicalSize::new(1.0, 16.0)),
+        );
+
+        let mut ime = ImeCursorArea::default();
+        assert!(!ime.allowed());
+        assert_eq!(ime.set_area(area.0, area.1), None);
+        assert_eq!(ime.applied(), None);
+
+        assert_eq!(ime.set_allowed(true), Some(area));
+        assert!(ime.allowed());
+        assert_eq!(ime.applied(), Some(area));
+
+        // Later areas are applied right away, and kept while the IME is disallowed.
+        let moved = (
+            Position::Physical(PhysicalPosition::new(30, 40)),
+            Size::Physical(PhysicalSize::new(2, 32)),
+        );
+        assert_eq!(ime.set_area(moved.0, moved.1), Some(moved));
+        assert_eq!(ime.set_allowed(false), None);
+        assert_eq!(ime.applied(), None);
+        assert_eq!(ime.set_allowed(true), Some(moved));
+    }
+
+    #[test]
+    fn allowing_the_ime_without_an_area_applies_nothing() {
+        let mut ime = ImeCursorArea::default();
+        assert_eq!(ime.set_allowed(true), None);
+        assert!(ime.allowed());
+    }
+}
@@ -25,12 +25,13 @@ use objc2::{msg_send, sel};
 use once_cell::sync::Lazy;
 
 use super::event_loop::{EventHandler, Never};
-use super::uikit::UIView;
+use super::uikit::{UITouch, UIView};
 use super::view::WinitUIWindow;
 use crate::{
     dpi::PhysicalSize,
-    event::{Event, InnerSizeWriter, StartCause, WindowEvent},
+    event::{Event, InnerSizeWriter, StartCause, TouchPhase, WindowEvent},
     event_loop::ControlFlow,
+    platform_impl::TouchIds,
     window::WindowId as RootWindowId,
 };
 
@@ -56,6 +57,7 @@ pub enum EventWrapper {
 pub struct ScaleFactorChanged {
     pub(super) window: Id<WinitUIWindow>,
     pub(super) suggested_size: PhysicalSize<u32>,
+    pub(super) old_scale_factor: f64,
     pub(super) scale_factor: f64,
 }
 
@@ -75,7 +77,7 @@ impl Event<Never> {
         matches!(
             self,
             Event::WindowEvent {
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { .. },
                 ..
             }
         )
@@ -126,6 +128,7 @@ pub(crate) struct AppState {
     app_state: Option<AppStateImpl>,
     control_flow: ControlFlow,
     waker: EventLoopWaker,
+    touch_ids: TouchIds<usize>,
 }
 
 impl AppState {
@@ -149,6 +152,7 @@ impl AppState {
                     }),
                     control_flow: ControlFlow::default(),
                     waker,
+                    touch_ids: TouchIds::default(),
                 });
             }
             init_guard(&mut guard);
@@ -460,6 +464,14 @@ impl AppState {
         self.control_flow = control_flow;
     }
 
+    /// Returns the id to report for `touch`.
+    ///
+    /// `UITouch` objects only live for the duration of a touch, so their address may be reused
+    /// for a later one.
+    pub(crate) fn touch_id(&mut self, touch: &UITouch, phase: TouchPhase) -> u64 {
+        self.touch_ids.get(touch as *const UITouch as usize, phase)
+    }
+
     pub(crate) fn control_flow(&self) -> ControlFlow {
         self.control_flow
     }
@@ -763,7 +775,7 @@ pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
         .map(|window| {
             EventWrapper::StaticEvent(Event::WindowEvent {
                 window_id: RootWindowId(window.id()),
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { dirty_rects: None },
             })
         })
         .collect();
@@ -788,6 +800,7 @@ pub fn terminated(mtm: MainThreadMarker) {
 fn handle_hidpi_proxy(event_handler: &mut Box<dyn EventHandler>, event: ScaleFactorChanged) {
     let ScaleFactorChanged {
         suggested_size,
+        old_scale_factor,
         scale_factor,
         window,
     } = event;
@@ -795,6 +808,7 @@ fn handle_hidpi_proxy(event_handler: &mut Box<dyn EventHandler>, event: ScaleFac
     let event = Event::WindowEvent {
         window_id: RootWindowId(window.id()),
         event: WindowEvent::ScaleFactorChanged {
+            old_scale_factor,
             scale_factor,
             inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
         },
@@ -927,6 +941,10 @@ os_capabilities! {
     /// <https://developer.apple.com/documentation/uikit/uiviewcontroller/2887507-setneedsupdateofscreenedgesdefer?language=objc>
     defer_system_gestures_err_msg: "-[UIViewController setNeedsUpdateOfScreenEdgesDeferringSystem]",
     defer_system_gestures: 11-0,
+    /// <https://developer.apple.com/documentation/uikit/uiviewcontroller/4047535-setneedsupdateofsupportedinterfa?language=objc>
+    #[allow(unused)] // error message unused
+    supported_orientations_update_err_msg: "-[UIViewController setNeedsUpdateOfSupportedInterfaceOrientations]",
+    supported_orientations_update: 16-0,
     /// <https://developer.apple.com/documentation/uikit/uiscreen/2806814-maximumframespersecond?language=objc>
     maximum_frames_per_second_err_msg: "-[UIScreen maximumFramesPerSecond]",
     maximum_frames_per_second: 10-3,
@@ -18,16 +18,17 @@ use icrate::Foundation::{MainThreadMarker, NSString};
 use objc2::ClassType;
 
 use crate::{
-    error::EventLoopError,
-    event::Event,
+    dpi::PhysicalRect,
+    error::{EventLoopError, ExternalError, NotSupportedError},
+    event::{DeviceInfo, Event},
     event_loop::{
-        ControlFlow, DeviceEvents, EventLoopClosed,
-        EventLoopWindowTarget as RootEventLoopWindowTarget,
+        Backend, ControlFlow, DeviceEvents, EventLoopClosed,
+        EventLoopWindowTarget as RootEventLoopWindowTarget, PowerState,
     },
     platform::ios::Idiom,
 };
 
-use super::{app_state, monitor, view, MonitorHandle};
+use super::{app_state, monitor, view, MonitorHandle, WindowId};
 use super::{
     app_state::AppState,
     uikit::{UIApplication, UIApplicationMain, UIDevice, UIScreen},
@@ -36,10 +37,14 @@ use super::{
 #[derive(Debug)]
 pub struct EventLoopWindowTarget<T: 'static> {
     pub(super) mtm: MainThreadMarker,
-    p: PhantomData<T>,
+    sender: Sender<T>,
 }
 
 impl<T: 'static> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy::new(self.sender.clone())
+    }
+
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         monitor::uiscreens(self.mtm)
     }
@@ -51,6 +56,26 @@ impl<T: 'static> EventLoopWindowTarget<T> {
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    #[inline]
+    pub fn device_info(&self, _device_id: super::DeviceId) -> Option<DeviceInfo> {
+        None
+    }
+
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        Backend::IOS
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> Option<PowerState> {
+        None
+    }
+
+    #[inline]
+    pub fn clip_cursor(&self, _rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[cfg(feature = "rwh_05")]
     #[inline]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
@@ -75,6 +100,13 @@ impl<T: 'static> EventLoopWindowTarget<T> {
         AppState::get_mut(self.mtm).control_flow()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        // SAFETY: Windows are only scheduled for redraws while they are alive.
+        if let Some(window) = unsafe { window_id.retain_window() } {
+            app_state::queue_gl_or_metal_redraw(self.mtm, window);
+        }
+    }
+
     pub(crate) fn exit(&self) {
         // https://developer.apple.com/library/archive/qa/qa1561/_index.html
         // it is not possible to quit an iOS app gracefully and programatically
@@ -88,7 +120,6 @@ impl<T: 'static> EventLoopWindowTarget<T> {
 
 pub struct EventLoop<T: 'static> {
     mtm: MainThreadMarker,
-    sender: Sender<T>,
     receiver: Receiver<T>,
     window_target: RootEventLoopWindowTarget<T>,
 }
@@ -100,8 +131,10 @@ impl<T: 'static> EventLoop<T> {
     pub(crate) fn new(
         _: &PlatformSpecificEventLoopAttributes,
     ) -> Result<EventLoop<T>, EventLoopError> {
-        let mtm = MainThreadMarker::new()
-            .expect("On iOS, `EventLoop` must be created on the main thread");
+        let mtm = match MainThreadMarker::new() {
+            Some(mtm) => mtm,
+            None => return Err(EventLoopError::NotMainThread),
+        };
 
         static mut SINGLETON_INIT: bool = false;
         unsafe {
@@ -120,13 +153,10 @@ impl<T: 'static> EventLoop<T> {
 
         Ok(EventLoop {
             mtm,
-            sender,
             receiver,
             window_target: RootEventLoopWindowTarget {
-                p: EventLoopWindowTarget {
-                    mtm,
-                    p: PhantomData,
-                },
+                p: EventLoopWindowTarget { mtm, sender },
+                handler_state: Default::default(),
                 _marker: PhantomData,
             },
         })
@@ -172,7 +202,7 @@ impl<T: 'static> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.sender.clone())
+        self.window_target.p.create_proxy()
     }
 
     pub fn window_target(&self) -> &RootEventLoopWindowTarget<T> {
@@ -251,6 +281,14 @@ impl<T> EventLoopProxy<T> {
         }
         Ok(())
     }
+
+    pub fn wake_up(&self) {
+        unsafe {
+            CFRunLoopSourceSignal(self.source);
+            let rl = CFRunLoopGetMain();
+            CFRunLoopWakeUp(rl);
+        }
+    }
 }
 
 fn setup_control_flow_observers() {
@@ -91,12 +91,17 @@ impl DeviceId {
             uiscreen: std::ptr::null(),
         }
     }
+
+    pub fn persistent_id(&self) -> Option<u64> {
+        None
+    }
 }
 
 unsafe impl Send for DeviceId {}
 unsafe impl Sync for DeviceId {}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEventExtra {}
 
 #[derive(Debug)]
@@ -4,6 +4,7 @@ use objc2::rc::Id;
 use objc2::{extern_class, extern_methods, msg_send_id, mutability, ClassType};
 
 use super::{UIResponder, UIView};
+use crate::platform::ios::Orientations;
 
 extern_class!(
     #[derive(Debug, PartialEq, Eq, Hash)]
@@ -30,6 +31,9 @@ extern_methods!(
         #[method(setNeedsUpdateOfScreenEdgesDeferringSystemGestures)]
         pub fn setNeedsUpdateOfScreenEdgesDeferringSystemGestures(&self);
 
+        #[method(setNeedsUpdateOfSupportedInterfaceOrientations)]
+        pub fn setNeedsUpdateOfSupportedInterfaceOrientations(&self);
+
         pub fn view(&self) -> Option<Id<UIView>> {
             unsafe { msg_send_id![self, view] }
         }
@@ -52,6 +56,32 @@ bitflags! {
     }
 }
 
+impl From<Orientations> for UIInterfaceOrientationMask {
+    fn from(orientations: Orientations) -> Self {
+        let mut mask = UIInterfaceOrientationMask::empty();
+        for (orientation, flag) in [
+            (Orientations::PORTRAIT, UIInterfaceOrientationMask::Portrait),
+            (
+                Orientations::PORTRAIT_UPSIDE_DOWN,
+                UIInterfaceOrientationMask::PortraitUpsideDown,
+            ),
+            (
+                Orientations::LANDSCAPE_LEFT,
+                UIInterfaceOrientationMask::LandscapeLeft,
+            ),
+            (
+                Orientations::LANDSCAPE_RIGHT,
+                UIInterfaceOrientationMask::LandscapeRight,
+            ),
+        ] {
+            if orientations.contains(orientation) {
+                mask |= flag;
+            }
+        }
+        mask
+    }
+}
+
 unsafe impl Encode for UIInterfaceOrientationMask {
     const ENCODING: Encoding = NSUInteger::ENCODING;
 }
@@ -46,7 +46,7 @@ declare_class!(
                 mtm,
                 EventWrapper::StaticEvent(Event::WindowEvent {
                     window_id: RootWindowId(window.id()),
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { dirty_rects: None },
                 }),
             );
             let _: () = unsafe { msg_send![super(self), drawRect: rect] };
@@ -89,6 +89,7 @@ declare_class!(
         #[method(setContentScaleFactor:)]
         fn set_content_scale_factor(&self, untrusted_scale_factor: CGFloat) {
             let mtm = MainThreadMarker::new().unwrap();
+            let old_scale_factor = self.contentScaleFactor() as f64;
             let _: () =
                 unsafe { msg_send![super(self), setContentScaleFactor: untrusted_scale_factor] };
 
@@ -125,6 +126,7 @@ declare_class!(
                 std::iter::once(EventWrapper::ScaleFactorChanged(
                     app_state::ScaleFactorChanged {
                         window,
+                        old_scale_factor,
                         scale_factor,
                         suggested_size: size.to_physical(scale_factor),
                     },
@@ -196,6 +198,7 @@ impl WinitView {
     }
 
     fn handle_touches(&self, touches: &NSSet<UITouch>) {
+        let mtm = MainThreadMarker::new().unwrap();
         let window = self.window().unwrap();
         let uiscreen = window.screen();
         let mut touch_events = Vec::new();
@@ -229,7 +232,6 @@ impl WinitView {
             } else {
                 None
             };
-            let touch_id = touch as *const UITouch as u64;
             let phase = touch.phase();
             let phase = match phase {
                 UITouchPhase::Began => TouchPhase::Started,
@@ -239,6 +241,7 @@ impl WinitView {
                 UITouchPhase::Cancelled => TouchPhase::Cancelled,
                 _ => panic!("unexpected touch phase: {:?}", phase as i32),
             };
+            let touch_id = app_state::AppState::get_mut(mtm).touch_id(touch, phase);
 
             let physical_location = {
                 let scale_factor = self.contentScaleFactor();
@@ -257,10 +260,10 @@ impl WinitView {
                     location: physical_location,
                     force,
                     phase,
+                    palm: false,
                 }),
             }));
         }
-        let mtm = MainThreadMarker::new().unwrap();
         app_state::handle_nonuser_events(mtm, touch_events);
     }
 }
@@ -401,8 +404,16 @@ impl WinitViewController {
                     | UIInterfaceOrientationMask::PortraitUpsideDown
             }
         };
+        self.set_supported_orientation_mask(mask);
+    }
+
+    pub(crate) fn set_supported_orientation_mask(&self, mask: UIInterfaceOrientationMask) {
         self.state.supported_orientations.set(mask);
-        UIViewController::attemptRotationToDeviceOrientation();
+        if app_state::os_capabilities().supported_orientations_update {
+            self.setNeedsUpdateOfSupportedInterfaceOrientations();
+        } else {
+            UIViewController::attemptRotationToDeviceOrientation();
+        }
     }
 
     pub(crate) fn new(
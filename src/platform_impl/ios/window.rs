@@ -11,17 +11,21 @@ use super::app_state::EventWrapper;
 use super::uikit::{UIApplication, UIScreen, UIScreenOverscanCompensation};
 use super::view::{WinitUIWindow, WinitView, WinitViewController};
 use crate::{
-    dpi::{self, LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{
+        self, LogicalPosition, LogicalSize, PhysicalPosition, PhysicalRect, PhysicalSize, Position,
+        Size,
+    },
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     event::{Event, WindowEvent},
     icon::Icon,
-    platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations},
+    platform::ios::{Orientations, ScreenEdge, StatusBarStyle, ValidOrientations},
     platform_impl::platform::{
         app_state, monitor, EventLoopWindowTarget, Fullscreen, MonitorHandle,
     },
     window::{
-        CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-        WindowAttributes, WindowButtons, WindowId as RootWindowId, WindowLevel,
+        CursorGrabMode, CursorIcon, ImageBuffer, ImeHints, ImePurpose, Insets, ResizeDirection,
+        Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowId as RootWindowId,
+        WindowLevel, WindowShape,
     },
 };
 
@@ -54,6 +58,10 @@ impl Inner {
         None
     }
 
+    pub fn set_enabled(&self, _enabled: bool) {
+        warn!("`Window::set_enabled` is ignored on iOS")
+    }
+
     pub fn request_redraw(&self) {
         if self.gl_or_metal_backed {
             let mtm = MainThreadMarker::new().unwrap();
@@ -128,6 +136,18 @@ impl Inner {
         size.to_physical(scale_factor)
     }
 
+    pub fn set_visible_on_all_workspaces(&self, _visible: bool) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    pub fn frame_insets(&self) -> Insets {
+        Insets::default()
+    }
+
     pub fn request_inner_size(&self, _size: Size) -> Option<PhysicalSize<u32>> {
         Some(self.inner_size())
     }
@@ -177,6 +197,10 @@ impl Inner {
         debug!("`Window::set_cursor_icon` ignored on iOS")
     }
 
+    pub fn set_cursor_regions(&self, _regions: Vec<(PhysicalRect, CursorIcon)>) {
+        debug!("`Window::set_cursor_regions` ignored on iOS")
+    }
+
     pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
@@ -185,6 +209,12 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn confine_cursor_to_rect(&self, _rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_auto_grab(&self, _auto_grab: bool) {}
+
     pub fn set_cursor_visible(&self, _visible: bool) {
         debug!("`Window::set_cursor_visible` is ignored on iOS")
     }
@@ -204,6 +234,10 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn set_shape(&self, _shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_minimized(&self, _minimized: bool) {
         warn!("`Window::set_minimized` is ignored on iOS")
     }
@@ -288,6 +322,10 @@ impl Inner {
         warn!("`Window::set_window_icon` is ignored on iOS")
     }
 
+    pub fn set_drop_feedback(&self, _effect: crate::window::DropEffect) {
+        warn!("`Window::set_drop_feedback` is ignored on iOS")
+    }
+
     pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {
         warn!("`Window::set_ime_cursor_area` is ignored on iOS")
     }
@@ -300,8 +338,20 @@ impl Inner {
         warn!("`Window::set_ime_allowed` is ignored on iOS")
     }
 
-    pub fn focus_window(&self) {
-        warn!("`Window::set_focus` is ignored on iOS")
+    pub fn set_ime_hints(&self, _hints: ImeHints) {
+        warn!("`Window::set_ime_hints` is ignored on iOS")
+    }
+
+    pub fn set_above(&self, _sibling: &Window) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_below(&self, _sibling: &Window) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
     }
 
     pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
@@ -321,6 +371,10 @@ impl Inner {
         monitor::uiscreens(MainThreadMarker::new().unwrap())
     }
 
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        PhysicalRect::new(monitor.position(), monitor.size())
+    }
+
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         Some(MonitorHandle::new(UIScreen::main(
             MainThreadMarker::new().unwrap(),
@@ -381,6 +435,10 @@ impl Inner {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn has_focus(&self) -> bool {
         self.window.isKeyWindow()
     }
@@ -398,6 +456,11 @@ impl Inner {
     pub fn reset_dead_keys(&self) {
         // Noop
     }
+
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        true
+    }
 }
 
 pub struct Window {
@@ -486,6 +549,7 @@ impl Window {
                 std::iter::once(EventWrapper::ScaleFactorChanged(
                     app_state::ScaleFactorChanged {
                         window: window.clone(),
+                        old_scale_factor: 1.0,
                         scale_factor,
                         suggested_size: size.to_physical(scale_factor),
                     },
@@ -538,6 +602,15 @@ impl Inner {
         );
     }
 
+    pub fn set_supported_orientations(&self, orientations: Orientations) {
+        if orientations.is_empty() {
+            warn!("`set_supported_orientations` called without any orientation, ignoring");
+            return;
+        }
+        self.view_controller
+            .set_supported_orientation_mask(orientations.into());
+    }
+
     pub fn set_prefers_home_indicator_hidden(&self, hidden: bool) {
         self.view_controller
             .set_prefers_home_indicator_auto_hidden(hidden);
@@ -631,6 +704,13 @@ impl WindowId {
             window: std::ptr::null_mut(),
         }
     }
+
+    /// # Safety
+    ///
+    /// The window must not have been deallocated.
+    pub(super) unsafe fn retain_window(self) -> Option<Id<WinitUIWindow>> {
+        unsafe { Id::retain(self.window) }
+    }
 }
 
 impl From<WindowId> for u64 {
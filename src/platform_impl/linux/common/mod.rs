@@ -1 +1,2 @@
+pub mod power;
 pub mod xkb;
@@ -0,0 +1,219 @@
+//! The power state, read from the power supplies the kernel exposes in sysfs, or queried from
+//! UPower over D-Bus with the `upower` feature.
+//!
+//! UPower reads sysfs as well, so both agree; it's still asked first when enabled, since it's
+//! the reference on desktops and may merge in more sources.
+
+use std::fs;
+use std::path::Path;
+
+use crate::event_loop::PowerState;
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+pub fn power_state() -> Option<PowerState> {
+    #[cfg(feature = "upower")]
+    if let Some(state) = upower::power_state() {
+        return state;
+    }
+    read_power_state(Path::new(POWER_SUPPLY_DIR))
+}
+
+#[cfg(feature = "upower")]
+mod upower {
+    use once_cell::sync::Lazy;
+    use zbus::blocking::{Connection, Proxy};
+
+    use crate::event_loop::PowerState;
+
+    const UPOWER: &str = "org.freedesktop.UPower";
+    const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+    /// The device which composes all the batteries of the system.
+    const DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+    const DEVICE: &str = "org.freedesktop.UPower.Device";
+    /// The `Type` of the display device when the system has batteries.
+    const TYPE_BATTERY: u32 = 2;
+
+    /// The system bus, connected to once, if it's there.
+    static CONNECTION: Lazy<Option<Connection>> = Lazy::new(|| Connection::system().ok());
+
+    /// Returns the power state UPower reports, or `None` if it can't be reached.
+    pub fn power_state() -> Option<Option<PowerState>> {
+        let connection = CONNECTION.as_ref()?;
+        let upower = Proxy::new(connection, UPOWER, UPOWER_PATH, UPOWER).ok()?;
+        let device = Proxy::new(connection, UPOWER, DISPLAY_DEVICE_PATH, DEVICE).ok()?;
+
+        let on_battery: bool = upower.get_property("OnBattery").ok()?;
+        let kind: u32 = device.get_property("Type").ok()?;
+        let is_present: bool = device.get_property("IsPresent").ok()?;
+        if kind != TYPE_BATTERY || !is_present {
+            return Some(None);
+        }
+        let percentage: f64 = device.get_property("Percentage").ok()?;
+        Some(Some(PowerState {
+            on_battery,
+            battery_percent: Some(percentage.clamp(0.0, 100.0) as f32),
+        }))
+    }
+}
+
+/// Reads the power state from a directory laid out like `/sys/class/power_supply`.
+fn read_power_state(dir: &Path) -> Option<PowerState> {
+    let mut has_mains = false;
+    let mut on_external = false;
+    let mut batteries = 0;
+    let mut discharging = false;
+    let mut capacities = Vec::new();
+    // The energy left and at full charge of the batteries, if all of them report it.
+    let mut energy = Some((0.0, 0.0));
+
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let supply = entry.path();
+        let read = |attribute: &str| {
+            fs::read_to_string(supply.join(attribute))
+                .ok()
+                .map(|value| value.trim().to_owned())
+        };
+        let read_number = |attribute: &str| read(attribute)?.parse::<f64>().ok();
+
+        // The batteries of mice, keyboards and other peripherals don't power the system.
+        if read("scope").as_deref() == Some("Device") {
+            continue;
+        }
+
+        match read("type").as_deref() {
+            Some("Mains") | Some("USB") => {
+                has_mains = true;
+                on_external |= read("online").as_deref() == Some("1");
+            }
+            Some("Battery") => {
+                batteries += 1;
+                discharging |= read("status").as_deref() == Some("Discharging");
+                if let Some(capacity) = read_number("capacity") {
+                    capacities.push(capacity);
+                }
+                let charge = read_number("energy_now")
+                    .zip(read_number("energy_full"))
+                    .or_else(|| read_number("charge_now").zip(read_number("charge_full")));
+                energy = energy
+                    .zip(charge)
+                    .map(|((now, full), (charge_now, charge_full))| {
+                        (now + charge_now, full + charge_full)
+                    });
+            }
+            _ => (),
+        }
+    }
+
+    if batteries == 0 {
+        return None;
+    }
+
+    let battery_percent = match energy {
+        Some((now, full)) if full > 0.0 => Some(now / full * 100.0),
+        _ if !capacities.is_empty() => {
+            Some(capacities.iter().sum::<f64>() / capacities.len() as f64)
+        }
+        _ => None,
+    };
+    Some(PowerState {
+        // Without a mains supply to look at, the battery status is all there is.
+        on_battery: if has_mains { !on_external } else { discharging },
+        battery_percent: battery_percent.map(|percent| percent.min(100.0) as f32),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn power_supply_dir(name: &str, supplies: &[(&str, &[(&str, &str)])]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("winit-power-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (supply, attributes) in supplies {
+            let supply = dir.join(supply);
+            fs::create_dir_all(&supply).unwrap();
+            for (attribute, value) in *attributes {
+                fs::write(supply.join(attribute), format!("{value}\n")).unwrap();
+            }
+        }
+        dir
+    }
+
+    #[test]
+    fn batteries_are_weighted_by_energy() {
+        let dir = power_supply_dir(
+            "energy",
+            &[
+                ("AC", &[("type", "Mains"), ("online", "0")]),
+                (
+                    "BAT0",
+                    &[
+                        ("type", "Battery"),
+                        ("status", "Discharging"),
+                        ("capacity", "50"),
+                        ("energy_now", "10000000"),
+                        ("energy_full", "20000000"),
+                    ],
+                ),
+                (
+                    "BAT1",
+                    &[
+                        ("type", "Battery"),
+                        ("status", "Full"),
+                        ("capacity", "100"),
+                        ("energy_now", "60000000"),
+                        ("energy_full", "60000000"),
+                    ],
+                ),
+                (
+                    "hid-mouse-battery",
+                    &[("type", "Battery"), ("scope", "Device"), ("capacity", "5")],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            read_power_state(&dir),
+            Some(PowerState {
+                on_battery: true,
+                battery_percent: Some(87.5),
+            })
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn status_is_used_without_mains() {
+        let dir = power_supply_dir(
+            "status",
+            &[(
+                "BAT0",
+                &[
+                    ("type", "Battery"),
+                    ("status", "Charging"),
+                    ("capacity", "42"),
+                ],
+            )],
+        );
+
+        assert_eq!(
+            read_power_state(&dir),
+            Some(PowerState {
+                on_battery: false,
+                battery_percent: Some(42.0),
+            })
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn desktops_have_no_power_state() {
+        let dir = power_supply_dir("desktop", &[("AC", &[("type", "Mains"), ("online", "1")])]);
+
+        assert_eq!(read_power_state(&dir), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
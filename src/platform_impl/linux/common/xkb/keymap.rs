@@ -167,7 +167,7 @@ pub fn scancode_to_keycode(scancode: u32) -> PhysicalKey {
         125 => KeyCode::SuperLeft,
         126 => KeyCode::SuperRight,
         127 => KeyCode::ContextMenu,
-        // 128 => KeyCode::STOP,
+        128 => KeyCode::BrowserStop,
         // 129 => KeyCode::AGAIN,
         // 130 => KeyCode::PROPS,
         // 131 => KeyCode::UNDO,
@@ -179,7 +179,7 @@ pub fn scancode_to_keycode(scancode: u32) -> PhysicalKey {
         // 137 => KeyCode::CUT,
         // 138 => KeyCode::HELP,
         // 139 => KeyCode::MENU,
-        // 140 => KeyCode::CALC,
+        140 => KeyCode::LaunchApp2,
         // 141 => KeyCode::SETUP,
         // 142 => KeyCode::SLEEP,
         // 143 => KeyCode::WAKEUP,
@@ -194,11 +194,11 @@ pub fn scancode_to_keycode(scancode: u32) -> PhysicalKey {
         // 152 => KeyCode::COFFEE,
         // 153 => KeyCode::ROTATE_DISPLAY,
         // 154 => KeyCode::CYCLEWINDOWS,
-        // 155 => KeyCode::MAIL,
-        // 156 => KeyCode::BOOKMARKS,
-        // 157 => KeyCode::COMPUTER,
-        // 158 => KeyCode::BACK,
-        // 159 => KeyCode::FORWARD,
+        155 => KeyCode::LaunchMail,
+        156 => KeyCode::BrowserFavorites,
+        157 => KeyCode::LaunchApp1,
+        158 => KeyCode::BrowserBack,
+        159 => KeyCode::BrowserForward,
         // 160 => KeyCode::CLOSECD,
         // 161 => KeyCode::EJECTCD,
         // 162 => KeyCode::EJECTCLOSECD,
@@ -210,9 +210,9 @@ pub fn scancode_to_keycode(scancode: u32) -> PhysicalKey {
         // 168 => KeyCode::REWIND,
         // 169 => KeyCode::PHONE,
         // 170 => KeyCode::ISO,
-        // 171 => KeyCode::CONFIG,
-        // 172 => KeyCode::HOMEPAGE,
-        // 173 => KeyCode::REFRESH,
+        171 => KeyCode::MediaSelect,
+        172 => KeyCode::BrowserHome,
+        173 => KeyCode::BrowserRefresh,
         // 174 => KeyCode::EXIT,
         // 175 => KeyCode::MOVE,
         // 176 => KeyCode::EDIT,
@@ -251,7 +251,7 @@ pub fn scancode_to_keycode(scancode: u32) -> PhysicalKey {
         // 214 => KeyCode::QUESTION,
         // 215 => KeyCode::EMAIL,
         // 216 => KeyCode::CHAT,
-        // 217 => KeyCode::SEARCH,
+        217 => KeyCode::BrowserSearch,
         // 218 => KeyCode::CONNECT,
         // 219 => KeyCode::FINANCE,
         // 220 => KeyCode::SPORT,
@@ -420,10 +420,20 @@ pub fn physicalkey_to_scancode(key: PhysicalKey) -> Option<u32> {
         KeyCode::SuperLeft => Some(125),
         KeyCode::SuperRight => Some(126),
         KeyCode::ContextMenu => Some(127),
+        KeyCode::BrowserStop => Some(128),
+        KeyCode::LaunchApp2 => Some(140),
+        KeyCode::LaunchMail => Some(155),
+        KeyCode::BrowserFavorites => Some(156),
+        KeyCode::LaunchApp1 => Some(157),
+        KeyCode::BrowserBack => Some(158),
+        KeyCode::BrowserForward => Some(159),
         KeyCode::MediaTrackNext => Some(163),
         KeyCode::MediaPlayPause => Some(164),
         KeyCode::MediaTrackPrevious => Some(165),
         KeyCode::MediaStop => Some(166),
+        KeyCode::MediaSelect => Some(171),
+        KeyCode::BrowserHome => Some(172),
+        KeyCode::BrowserRefresh => Some(173),
         KeyCode::F13 => Some(183),
         KeyCode::F14 => Some(184),
         KeyCode::F15 => Some(185),
@@ -436,6 +446,7 @@ pub fn physicalkey_to_scancode(key: PhysicalKey) -> Option<u32> {
         KeyCode::F22 => Some(192),
         KeyCode::F23 => Some(193),
         KeyCode::F24 => Some(194),
+        KeyCode::BrowserSearch => Some(217),
         _ => None,
     }
 }
@@ -956,6 +967,30 @@ impl XkbKeymap {
         Some(Self::new_inner(keymap, core_keyboard_id))
     }
 
+    /// Compile the keymap for the given layout and variant, as done for the `setxkbmap` names.
+    #[cfg(test)]
+    pub fn from_names(context: &XkbContext, layout: &str, variant: &str) -> Option<Self> {
+        let layout = std::ffi::CString::new(layout).ok()?;
+        let variant = std::ffi::CString::new(variant).ok()?;
+        let names = xkb::xkb_rule_names {
+            rules: ptr::null(),
+            model: ptr::null(),
+            layout: layout.as_ptr(),
+            variant: variant.as_ptr(),
+            options: ptr::null(),
+        };
+
+        let keymap = unsafe {
+            (XKBH.xkb_keymap_new_from_names)(
+                context.as_ptr(),
+                &names,
+                xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            )
+        };
+        let keymap = NonNull::new(keymap)?;
+        Some(Self::new_inner(keymap, 0))
+    }
+
     fn new_inner(keymap: NonNull<xkb_keymap>, _core_keyboard_id: i32) -> Self {
         let mods_indices = ModsIndices {
             shift: mod_index_for_name(keymap, xkb::XKB_MOD_NAME_SHIFT),
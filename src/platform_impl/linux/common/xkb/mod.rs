@@ -153,6 +153,13 @@ impl Context {
         self.keymap = keymap;
     }
 
+    #[cfg(test)]
+    fn set_keymap_from_names(&mut self, layout: &str, variant: &str) {
+        let keymap = XkbKeymap::from_names(&self.context, layout, variant);
+        self.state = keymap.as_ref().and_then(XkbState::new_wayland);
+        self.keymap = keymap;
+    }
+
     /// Key builder context with the user provided xkb state.
     pub fn key_context(&mut self) -> Option<KeyContext<'_>> {
         let state = self.state.as_mut()?;
@@ -463,3 +470,77 @@ fn byte_slice_to_smol_str(bytes: &[u8]) -> Option<SmolStr> {
         })
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    //! Tests for the text reported under modifiers on a few keyboard layouts.
+
+    use super::*;
+
+    use crate::platform::modifier_supplement::KeyEventExtModifierSupplement;
+
+    // X11-style keycodes, the evdev ones offset by 8.
+    const KEY_7: u32 = 16;
+    const KEY_Q: u32 = 24;
+    const KEY_C: u32 = 54;
+
+    fn context(layout: &str) -> Option<Context> {
+        let mut context = Context::new().ok()?;
+        context.set_keymap_from_names(layout, "");
+        context.key_context()?;
+        Some(context)
+    }
+
+    fn press(context: &mut Context, keycode: u32, mods: &[&[u8]]) -> KeyEvent {
+        let mut key_context = context.key_context().unwrap();
+        let mask = mods.iter().fold(0, |mask, name| {
+            let index = unsafe {
+                (XKBH.xkb_keymap_mod_get_index)(
+                    key_context.keymap.as_ptr(),
+                    name.as_ptr() as *const c_char,
+                )
+            };
+            mask | (1 << index)
+        });
+        key_context.state.update_modifiers(mask, 0, 0, 0, 0, 0);
+        key_context.process_key_event(keycode, ElementState::Pressed, false)
+    }
+
+    #[test]
+    fn ctrl_is_ignored_by_text() {
+        let mut context = match context("us") {
+            Some(context) => context,
+            // libxkbcommon or the keyboard layouts are not installed.
+            None => return,
+        };
+
+        let event = press(&mut context, KEY_C, &[xkb::XKB_MOD_NAME_CTRL]);
+        assert_eq!(event.logical_key, Key::Character("c".into()));
+        assert_eq!(event.text.as_deref(), Some("c"));
+        assert_eq!(event.text_with_all_modifiers(), Some("\u{3}"));
+        assert_eq!(event.key_without_modifiers(), Key::Character("c".into()));
+    }
+
+    #[test]
+    fn azerty_layout() {
+        let mut context = match context("fr") {
+            Some(context) => context,
+            None => return,
+        };
+
+        let event = press(&mut context, KEY_Q, &[xkb::XKB_MOD_NAME_CTRL]);
+        assert_eq!(event.text.as_deref(), Some("a"));
+        assert_eq!(event.text_with_all_modifiers(), Some("\u{1}"));
+        assert_eq!(event.key_without_modifiers(), Key::Character("a".into()));
+
+        let event = press(
+            &mut context,
+            KEY_7,
+            &[xkb::XKB_MOD_NAME_CTRL, xkb::XKB_MOD_NAME_SHIFT],
+        );
+        assert_eq!(event.logical_key, Key::Character("7".into()));
+        assert_eq!(event.text.as_deref(), Some("7"));
+        assert_eq!(event.key_without_modifiers(), Key::Character("è".into()));
+        assert_eq!(event.text_with_all_modifiers(), Some("\u{1f}"));
+    }
+}
@@ -22,7 +22,7 @@ pub struct XkbState {
 }
 
 impl XkbState {
-    #[cfg(wayland_platform)]
+    #[cfg(any(wayland_platform, test))]
     pub fn new_wayland(keymap: &XkbKeymap) -> Option<Self> {
         let state = NonNull::new(unsafe { (XKBH.xkb_state_new)(keymap.as_ptr()) })?;
         Some(Self::new_inner(state))
@@ -0,0 +1,900 @@
+//! A backend without any display server, meant for automated tests.
+//!
+//! Windows only exist in memory and, besides the redraws and resizes requested by the
+//! application, events are only injected by the application itself, see
+//! [`crate::platform::headless`].
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use std::{cmp, iter, mem};
+
+use calloop::ping::Ping;
+use calloop::EventLoop as Loop;
+
+use super::{min_timeout, ControlFlow, VideoMode as PlatformVideoMode, WindowId};
+use crate::{
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize},
+    error::{EventLoopError, ExternalError},
+    event::{
+        DeviceEvent, DeviceId as RootDeviceId, Event, InnerSizeWriter, StartCause, WindowEvent,
+    },
+    event_loop::{DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW, PowerState},
+    platform::pump_events::PumpStatus,
+};
+
+mod window;
+
+pub(crate) use window::Window;
+use window::WindowState;
+
+/// The monitor reported by the headless backend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MonitorConfig {
+    pub size: PhysicalSize<u32>,
+    pub scale_factor: f64,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            size: PhysicalSize::new(1920, 1080),
+            scale_factor: 1.0,
+        }
+    }
+}
+
+/// The refresh rate of the headless monitor.
+const REFRESH_RATE_MILLIHERTZ: u32 = 60_000;
+
+#[derive(Debug, Clone)]
+pub struct MonitorHandle(MonitorConfig);
+
+impl MonitorHandle {
+    fn key(&self) -> (u32, u32, u64) {
+        let MonitorConfig { size, scale_factor } = self.0;
+        (size.width, size.height, scale_factor.to_bits())
+    }
+
+    #[inline]
+    pub fn name(&self) -> Option<String> {
+        Some("Headless".to_owned())
+    }
+
+    #[inline]
+    pub fn native_identifier(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.0.size
+    }
+
+    #[inline]
+    pub fn position(&self) -> PhysicalPosition<i32> {
+        PhysicalPosition::new(0, 0)
+    }
+
+    #[inline]
+    pub fn refresh_rate_millihertz(&self) -> Option<u32> {
+        Some(REFRESH_RATE_MILLIHERTZ)
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.0.scale_factor
+    }
+
+    #[inline]
+    pub fn video_modes(&self) -> impl Iterator<Item = PlatformVideoMode> {
+        iter::once(PlatformVideoMode::Headless(VideoMode {
+            monitor: self.clone(),
+        }))
+    }
+}
+
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl PartialOrd for MonitorHandle {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MonitorHandle {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+/// The only video mode of the headless monitor, which matches its size.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    monitor: MonitorHandle,
+}
+
+impl VideoMode {
+    #[inline]
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.monitor.size()
+    }
+
+    #[inline]
+    pub fn bit_depth(&self) -> u16 {
+        32
+    }
+
+    #[inline]
+    pub fn refresh_rate_millihertz(&self) -> u32 {
+        REFRESH_RATE_MILLIHERTZ
+    }
+
+    #[inline]
+    pub fn monitor(&self) -> MonitorHandle {
+        self.monitor.clone()
+    }
+}
+
+/// A request sent to the event loop, by the application or by a window.
+enum Message {
+    WindowEvent(WindowId, WindowEvent),
+    DeviceEvent(RootDeviceId, DeviceEvent),
+    ScaleFactorChanged(WindowId, f64),
+    Redraw(WindowId),
+    PowerStateChanged(Option<PowerState>),
+}
+
+/// An event replayed with `EventLoopWindowTargetExtHeadless::replay_events`.
+enum Replayed<T> {
+    Window(crate::window::WindowId, WindowEvent),
+    Device(RootDeviceId, DeviceEvent),
+    User(T),
+}
+
+/// The events left to replay.
+struct Replay<T> {
+    /// The events of every iteration left to replay.
+    iterations: VecDeque<Vec<Replayed<T>>>,
+    /// The windows of the trace, and the windows of the application they're mapped to.
+    windows: Vec<(crate::window::WindowId, WindowId)>,
+}
+
+impl<T> Replay<T> {
+    fn new() -> Self {
+        Self {
+            iterations: VecDeque::new(),
+            windows: Vec::new(),
+        }
+    }
+
+    /// Maps a window of the trace to the first window of `live` that isn't already mapped.
+    fn map_window(
+        &mut self,
+        recorded: crate::window::WindowId,
+        live: &[WindowId],
+    ) -> Option<WindowId> {
+        let window_id = match self.windows.iter().find(|(id, _)| *id == recorded) {
+            Some(&(_, window_id)) => window_id,
+            None => {
+                let window_id = *live
+                    .iter()
+                    .find(|&&id| self.windows.iter().all(|&(_, mapped)| mapped != id))?;
+                self.windows.push((recorded, window_id));
+                window_id
+            }
+        };
+        // The events of a window which was destroyed are dropped.
+        live.contains(&window_id).then_some(window_id)
+    }
+}
+
+struct WakeSender<T> {
+    sender: Sender<T>,
+    waker: Ping,
+}
+
+impl<T> Clone for WakeSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T> WakeSender<T> {
+    fn send(&self, t: T) -> Result<(), EventLoopClosed<T>> {
+        let res = self.sender.send(t).map_err(|e| EventLoopClosed(e.0));
+        if res.is_ok() {
+            self.waker.ping();
+        }
+        res
+    }
+}
+
+pub struct EventLoopWindowTarget<T> {
+    monitor: MonitorHandle,
+    control_flow: Cell<ControlFlow>,
+    exit: Cell<Option<i32>>,
+    windows: RefCell<HashMap<WindowId, Weak<Mutex<WindowState>>>>,
+    message_sender: WakeSender<Message>,
+    user_sender: WakeSender<T>,
+    proxy_waker: Ping,
+    device_events: Cell<DeviceEvents>,
+    power_state: Cell<Option<PowerState>>,
+    cursor_clip: Cell<Option<PhysicalRect>>,
+    replay: RefCell<Replay<T>>,
+}
+
+pub struct EventLoop<T: 'static> {
+    loop_running: bool,
+    event_loop: Loop<'static, EventLoopState>,
+    window_target: RootELW<T>,
+    message_receiver: Receiver<Message>,
+    user_receiver: Receiver<T>,
+    /// The messages received before the current iteration.
+    pending_messages: VecDeque<Message>,
+    pending_user_events: VecDeque<T>,
+
+    /// The current state of the event loop.
+    state: EventLoopState,
+}
+
+struct EventLoopState {
+    /// Whether `EventLoopProxy::wake_up` was called since the last iteration.
+    proxy_wake_up: bool,
+}
+
+pub struct EventLoopProxy<T: 'static> {
+    user_sender: WakeSender<T>,
+    proxy_waker: Ping,
+}
+
+impl<T: 'static> Clone for EventLoopProxy<T> {
+    fn clone(&self) -> Self {
+        EventLoopProxy {
+            user_sender: self.user_sender.clone(),
+            proxy_waker: self.proxy_waker.clone(),
+        }
+    }
+}
+
+impl<T: 'static> EventLoop<T> {
+    pub(crate) fn new(monitor: MonitorConfig) -> EventLoop<T> {
+        let event_loop =
+            Loop::<EventLoopState>::try_new().expect("Failed to initialize the event loop");
+
+        let (waker, waker_source) =
+            calloop::ping::make_ping().expect("Failed to create event loop waker");
+        event_loop
+            .handle()
+            .insert_source(waker_source, move |_, _, _| {
+                // No extra handling is required, we just need to wake-up.
+            })
+            .expect("Failed to register the event loop waker source");
+
+        // A separate waker for `EventLoopProxy::wake_up`, which must run an iteration even though
+        // nothing was queued.
+        let (proxy_waker, proxy_waker_source) =
+            calloop::ping::make_ping().expect("Failed to create event loop proxy waker");
+        event_loop
+            .handle()
+            .insert_source(
+                proxy_waker_source,
+                move |_, _, state: &mut EventLoopState| {
+                    state.proxy_wake_up = true;
+                },
+            )
+            .expect("Failed to register the event loop proxy waker source");
+
+        let (message_sender, message_receiver) = mpsc::channel();
+        let (user_sender, user_receiver) = mpsc::channel();
+
+        let window_target = EventLoopWindowTarget {
+            monitor: MonitorHandle(monitor),
+            control_flow: Cell::new(ControlFlow::default()),
+            exit: Cell::new(None),
+            windows: Default::default(),
+            message_sender: WakeSender {
+                sender: message_sender,
+                waker: waker.clone(),
+            },
+            user_sender: WakeSender {
+                sender: user_sender,
+                waker,
+            },
+            proxy_waker,
+            device_events: Default::default(),
+            power_state: Cell::new(None),
+            cursor_clip: Cell::new(None),
+            replay: RefCell::new(Replay::new()),
+        };
+
+        EventLoop {
+            loop_running: false,
+            event_loop,
+            window_target: RootELW {
+                p: super::EventLoopWindowTarget::Headless(window_target),
+                handler_state: Default::default(),
+                _marker: PhantomData,
+            },
+            message_receiver,
+            user_receiver,
+            pending_messages: VecDeque::new(),
+            pending_user_events: VecDeque::new(),
+            state: EventLoopState {
+                proxy_wake_up: false,
+            },
+        }
+    }
+
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        self.headless_target().create_proxy()
+    }
+
+    pub(crate) fn window_target(&self) -> &RootELW<T> {
+        &self.window_target
+    }
+
+    fn headless_target(&self) -> &EventLoopWindowTarget<T> {
+        match &self.window_target.p {
+            super::EventLoopWindowTarget::Headless(window_target) => window_target,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn run_on_demand<F>(&mut self, mut event_handler: F) -> Result<(), EventLoopError>
+    where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        if self.loop_running {
+            return Err(EventLoopError::AlreadyRunning);
+        }
+
+        loop {
+            match self.pump_events(None, &mut event_handler) {
+                PumpStatus::Exit(0) => {
+                    break Ok(());
+                }
+                PumpStatus::Exit(code) => {
+                    break Err(EventLoopError::ExitFailure(code));
+                }
+                _ => {
+                    continue;
+                }
+            }
+        }
+    }
+
+    pub fn pump_events<F>(&mut self, timeout: Option<Duration>, mut callback: F) -> PumpStatus
+    where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        if !self.loop_running {
+            self.loop_running = true;
+
+            // run the initial loop iteration
+            self.has_pending();
+            self.single_iteration(&mut callback, StartCause::Init);
+        }
+
+        // Consider the possibility that the `StartCause::Init` iteration could
+        // request to Exit.
+        if !self.exiting() {
+            self.poll_events_with_timeout(timeout, &mut callback);
+        }
+        if let Some(code) = self.exit_code() {
+            self.loop_running = false;
+
+            callback(Event::LoopExiting, self.window_target());
+
+            PumpStatus::Exit(code)
+        } else {
+            PumpStatus::Continue
+        }
+    }
+
+    /// Moves the received messages to the pending queues, returns whether there's anything to
+    /// dispatch.
+    fn has_pending(&mut self) -> bool {
+        self.pending_messages
+            .extend(self.message_receiver.try_iter());
+        self.pending_user_events
+            .extend(self.user_receiver.try_iter());
+        !self.pending_messages.is_empty()
+            || !self.pending_user_events.is_empty()
+            || self.state.proxy_wake_up
+            || !self.headless_target().replay.borrow().iterations.is_empty()
+    }
+
+    fn poll_events_with_timeout<F>(&mut self, mut timeout: Option<Duration>, mut callback: F)
+    where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        let start = Instant::now();
+
+        timeout = if self.has_pending() {
+            // If we already have work to do then we don't want to block on the next poll.
+            Some(Duration::ZERO)
+        } else {
+            let control_flow_timeout = match self.control_flow() {
+                ControlFlow::Wait => None,
+                ControlFlow::Poll => Some(Duration::ZERO),
+                ControlFlow::WaitUntil(wait_deadline) => {
+                    Some(wait_deadline.saturating_duration_since(start))
+                }
+            };
+
+            min_timeout(control_flow_timeout, timeout)
+        };
+
+        if let Err(error) = self
+            .event_loop
+            .dispatch(timeout, &mut self.state)
+            .map_err(std::io::Error::from)
+        {
+            log::error!("Failed to poll for events: {error:?}");
+            let exit_code = error.raw_os_error().unwrap_or(1);
+            self.set_exit_code(exit_code);
+            return;
+        }
+
+        // NB: `StartCause::Init` is handled as a special case and doesn't need
+        // to be considered here
+        let cause = match self.control_flow() {
+            ControlFlow::Poll => StartCause::Poll,
+            ControlFlow::Wait => StartCause::WaitCancelled {
+                start,
+                requested_resume: None,
+            },
+            ControlFlow::WaitUntil(deadline) => {
+                if Instant::now() < deadline {
+                    StartCause::WaitCancelled {
+                        start,
+                        requested_resume: Some(deadline),
+                    }
+                } else {
+                    StartCause::ResumeTimeReached {
+                        start,
+                        requested_resume: deadline,
+                    }
+                }
+            }
+        };
+
+        // Don't run a redundant iteration after a spurious wake up.
+        if !self.has_pending()
+            && !matches!(
+                &cause,
+                StartCause::ResumeTimeReached { .. } | StartCause::Poll
+            )
+        {
+            return;
+        }
+
+        self.state.proxy_wake_up = false;
+        self.single_iteration(&mut callback, cause);
+    }
+
+    fn single_iteration<F>(&mut self, callback: &mut F, cause: StartCause)
+    where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        callback(Event::NewEvents(cause), &self.window_target);
+
+        // NB: For consistency all platforms must emit a 'resumed' event even though headless
+        // applications don't have a suspend/resume lifecycle.
+        if cause == StartCause::Init {
+            callback(Event::Resumed, &self.window_target);
+        }
+
+        // A replayed iteration comes before the injected events.
+        let mut redraws = HashSet::new();
+        for event in self.headless_target().next_replayed_iteration() {
+            match event {
+                Replayed::Window(window_id, event) => {
+                    self.dispatch_window_event(window_id.0, event, &mut redraws, callback);
+                }
+                Replayed::Device(device_id, event) => {
+                    self.dispatch_device_event(device_id, event, callback);
+                }
+                Replayed::User(event) => callback(Event::UserEvent(event), &self.window_target),
+            }
+        }
+
+        // Only dispatch what was received before the iteration, the events injected by the
+        // callback are dispatched by the next one.
+        for message in std::mem::take(&mut self.pending_messages) {
+            match message {
+                Message::WindowEvent(window_id, event) => {
+                    self.dispatch_window_event(window_id, event, &mut redraws, callback);
+                }
+                Message::DeviceEvent(device_id, event) => {
+                    self.dispatch_device_event(device_id, event, callback);
+                }
+                Message::ScaleFactorChanged(window_id, scale_factor) => {
+                    self.dispatch_scale_factor_change(
+                        window_id,
+                        scale_factor,
+                        &mut redraws,
+                        callback,
+                    );
+                }
+                Message::Redraw(window_id) => {
+                    redraws.insert(window_id);
+                }
+                Message::PowerStateChanged(state) => {
+                    callback(Event::PowerStateChanged(state), &self.window_target);
+                }
+            }
+        }
+
+        // Empty the user event buffer
+        for event in std::mem::take(&mut self.pending_user_events) {
+            callback(Event::UserEvent(event), &self.window_target);
+        }
+
+        // Empty the redraw requests, including the ones made during this iteration.
+        for message in self.message_receiver.try_iter() {
+            match message {
+                Message::Redraw(window_id) => {
+                    redraws.insert(window_id);
+                }
+                message => self.pending_messages.push_back(message),
+            }
+        }
+        for window_id in redraws {
+            if self.headless_target().is_alive(window_id) {
+                callback(
+                    Event::WindowEvent {
+                        window_id: crate::window::WindowId(window_id),
+                        event: WindowEvent::RedrawRequested { dirty_rects: None },
+                    },
+                    &self.window_target,
+                );
+            }
+        }
+
+        // This is always the last event we dispatch before poll again
+        callback(Event::AboutToWait, &self.window_target);
+    }
+
+    fn dispatch_window_event<F>(
+        &self,
+        window_id: WindowId,
+        event: WindowEvent,
+        redraws: &mut HashSet<WindowId>,
+        callback: &mut F,
+    ) where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        self.headless_target().apply_window_event(window_id, &event);
+        // Like a real window, the contents are invalidated by a resize.
+        if let WindowEvent::Resized(_) = event {
+            redraws.insert(window_id);
+        }
+        let event = Event::WindowEvent {
+            window_id: crate::window::WindowId(window_id),
+            event,
+        };
+        callback(event, &self.window_target);
+    }
+
+    /// Dispatches `ScaleFactorChanged`, suggesting the size keeping the logical size of the
+    /// window, and `Resized` if the application wrote a size other than the current one.
+    fn dispatch_scale_factor_change<F>(
+        &self,
+        window_id: WindowId,
+        scale_factor: f64,
+        redraws: &mut HashSet<WindowId>,
+        callback: &mut F,
+    ) where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        let (old_scale_factor, inner_size) = match self.headless_target().window_state(window_id) {
+            Some(window_state) => {
+                let window_state = window_state.lock().unwrap();
+                (window_state.scale_factor(), window_state.inner_size())
+            }
+            None => return,
+        };
+        if scale_factor == old_scale_factor {
+            return;
+        }
+
+        let suggested_size = inner_size
+            .to_logical::<f64>(old_scale_factor)
+            .to_physical(scale_factor);
+        let new_inner_size = Arc::new(Mutex::new(suggested_size));
+        let event = WindowEvent::ScaleFactorChanged {
+            old_scale_factor,
+            scale_factor,
+            inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
+        };
+        self.dispatch_window_event(window_id, event, redraws, callback);
+
+        let new_inner_size = *new_inner_size.lock().unwrap();
+        if new_inner_size != inner_size {
+            let event = WindowEvent::Resized(new_inner_size);
+            self.dispatch_window_event(window_id, event, redraws, callback);
+        }
+    }
+
+    fn dispatch_device_event<F>(
+        &self,
+        device_id: RootDeviceId,
+        event: DeviceEvent,
+        callback: &mut F,
+    ) where
+        F: FnMut(Event<T>, &RootELW<T>),
+    {
+        if self.headless_target().device_events_allowed() {
+            let event = Event::DeviceEvent { device_id, event };
+            callback(event, &self.window_target);
+        }
+    }
+
+    fn control_flow(&self) -> ControlFlow {
+        self.headless_target().control_flow()
+    }
+
+    fn exiting(&self) -> bool {
+        self.headless_target().exiting()
+    }
+
+    fn set_exit_code(&self, code: i32) {
+        self.headless_target().set_exit_code(code);
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        self.headless_target().exit_code()
+    }
+}
+
+impl<T> AsFd for EventLoop<T> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.event_loop.as_fd()
+    }
+}
+
+impl<T> AsRawFd for EventLoop<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.event_loop.as_raw_fd()
+    }
+}
+
+impl<T> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy {
+            user_sender: self.user_sender.clone(),
+            proxy_waker: self.proxy_waker.clone(),
+        }
+    }
+
+    /// Queues a window event, which is dispatched by the next iteration of the event loop.
+    pub(crate) fn inject_window_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.inject(Message::WindowEvent(window_id, event));
+    }
+
+    /// Queues a device event, which is dispatched by the next iteration of the event loop.
+    pub(crate) fn inject_device_event(&self, device_id: RootDeviceId, event: DeviceEvent) {
+        self.inject(Message::DeviceEvent(device_id, event));
+    }
+
+    /// Queues a change of the scale factor of a window, which is dispatched by the next
+    /// iteration of the event loop.
+    pub(crate) fn inject_scale_factor_change(&self, window_id: WindowId, scale_factor: f64) {
+        self.inject(Message::ScaleFactorChanged(window_id, scale_factor));
+    }
+
+    /// Queues the events of a recorded trace, which are replayed one recorded iteration per
+    /// iteration of the event loop.
+    pub(crate) fn replay_events(&self, events: impl IntoIterator<Item = Event<T>>)
+    where
+        T: 'static,
+    {
+        let mut replay = self.replay.borrow_mut();
+        let mut iteration = Vec::new();
+        for event in events {
+            match event {
+                Event::AboutToWait => replay.iterations.push_back(mem::take(&mut iteration)),
+                // Generated by the event loop itself, in response to the application.
+                Event::WindowEvent {
+                    event: WindowEvent::RedrawRequested { .. } | WindowEvent::Destroyed,
+                    ..
+                } => (),
+                Event::WindowEvent { window_id, event } => {
+                    iteration.push(Replayed::Window(window_id, event));
+                }
+                Event::DeviceEvent { device_id, event } => {
+                    iteration.push(Replayed::Device(device_id, event));
+                }
+                Event::UserEvent(event) => iteration.push(Replayed::User(event)),
+                _ => (),
+            }
+        }
+        if !iteration.is_empty() {
+            replay.iterations.push_back(iteration);
+        }
+        drop(replay);
+        self.message_sender.waker.ping();
+    }
+
+    /// Takes the events of the next replayed iteration, with the windows of the trace mapped to
+    /// the windows of the application in creation order.
+    fn next_replayed_iteration(&self) -> Vec<Replayed<T>> {
+        let mut replay = self.replay.borrow_mut();
+        let iteration = match replay.iterations.pop_front() {
+            Some(iteration) => iteration,
+            None => return Vec::new(),
+        };
+
+        // The ids are allocated in creation order.
+        let mut live: Vec<WindowId> = self
+            .windows
+            .borrow()
+            .iter()
+            .filter(|(_, window)| window.strong_count() > 0)
+            .map(|(&window_id, _)| window_id)
+            .collect();
+        live.sort_unstable();
+
+        iteration
+            .into_iter()
+            .filter_map(|event| match event {
+                Replayed::Window(window_id, event) => {
+                    let window_id = replay.map_window(window_id, &live)?;
+                    Some(Replayed::Window(crate::window::WindowId(window_id), event))
+                }
+                event => Some(event),
+            })
+            .collect()
+    }
+
+    fn inject(&self, message: Message) {
+        // The receiver lives as long as the event loop, which outlives its window target.
+        let _ = self.message_sender.send(message);
+    }
+
+    fn is_alive(&self, window_id: WindowId) -> bool {
+        self.windows
+            .borrow()
+            .get(&window_id)
+            .map_or(false, |window| window.strong_count() > 0)
+    }
+
+    fn window_state(&self, window_id: WindowId) -> Option<Arc<Mutex<WindowState>>> {
+        self.windows
+            .borrow()
+            .get(&window_id)
+            .and_then(Weak::upgrade)
+    }
+
+    /// Updates the state of the window to match an injected event.
+    fn apply_window_event(&self, window_id: WindowId, event: &WindowEvent) {
+        if let Some(window) = self.window_state(window_id) {
+            window.lock().unwrap().apply_event(event);
+        }
+        if let WindowEvent::Destroyed = event {
+            self.windows
+                .borrow_mut()
+                .retain(|_, window| window.strong_count() > 0);
+        }
+    }
+
+    fn device_events_allowed(&self) -> bool {
+        match self.device_events.get() {
+            DeviceEvents::Always => true,
+            DeviceEvents::WhenFocused => self
+                .windows
+                .borrow()
+                .values()
+                .filter_map(Weak::upgrade)
+                .any(|window| window.lock().unwrap().has_focus()),
+            DeviceEvents::Never => false,
+        }
+    }
+
+    pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
+        iter::once(self.monitor.clone())
+    }
+
+    pub fn primary_monitor(&self) -> Option<MonitorHandle> {
+        Some(self.monitor.clone())
+    }
+
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        self.inject(Message::Redraw(window_id));
+    }
+
+    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+        self.device_events.set(allowed);
+    }
+
+    pub fn power_state(&self) -> Option<PowerState> {
+        self.power_state.get()
+    }
+
+    /// Sets the power state, which is reported as changed by the next iteration.
+    pub(crate) fn set_power_state(&self, state: Option<PowerState>) {
+        self.power_state.set(state);
+        self.inject(Message::PowerStateChanged(state));
+    }
+
+    pub fn clip_cursor(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        self.cursor_clip.set(rect);
+        Ok(())
+    }
+
+    pub(crate) fn cursor_clip(&self) -> Option<PhysicalRect> {
+        self.cursor_clip.get()
+    }
+
+    #[cfg(feature = "rwh_05")]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::XlibDisplayHandle::empty().into()
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+
+    pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.control_flow.set(control_flow)
+    }
+
+    pub(crate) fn control_flow(&self) -> ControlFlow {
+        self.control_flow.get()
+    }
+
+    pub(crate) fn exit(&self) {
+        self.exit.set(Some(0))
+    }
+
+    pub(crate) fn clear_exit(&self) {
+        self.exit.set(None)
+    }
+
+    pub(crate) fn exiting(&self) -> bool {
+        self.exit.get().is_some()
+    }
+
+    pub(crate) fn set_exit_code(&self, code: i32) {
+        self.exit.set(Some(code))
+    }
+
+    pub(crate) fn exit_code(&self) -> Option<i32> {
+        self.exit.get()
+    }
+}
+
+impl<T: 'static> EventLoopProxy<T> {
+    pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
+        self.user_sender.send(event)
+    }
+
+    pub fn wake_up(&self) {
+        self.proxy_waker.ping();
+    }
+}
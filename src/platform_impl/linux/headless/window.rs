@@ -0,0 +1,711 @@
+//! The headless window.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::{EventLoopWindowTarget, Message, MonitorHandle, WakeSender, WindowId};
+use crate::dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size};
+use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
+use crate::event::WindowEvent;
+use crate::event_loop::AsyncRequestSerial;
+use crate::platform_impl::{
+    Fullscreen, PlatformIcon, PlatformSpecificWindowBuilderAttributes as PlatformAttributes,
+    SizeConstraints,
+};
+use crate::window::{
+    CursorGrabMode, CursorIcon, DropEffect, ImageBuffer, ImeHints, ImePurpose, Insets,
+    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+    WindowShape,
+};
+
+/// The size of windows created without an explicit one.
+const DEFAULT_INNER_SIZE: PhysicalSize<u32> = PhysicalSize::new(800, 600);
+
+static NEXT_WINDOW_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The headless window.
+pub struct Window {
+    window_id: WindowId,
+
+    /// The state of the window.
+    window_state: Arc<Mutex<WindowState>>,
+
+    /// The monitor of the event loop.
+    monitor: MonitorHandle,
+}
+
+/// The state of a headless window, shared with the event loop to apply the injected events.
+pub struct WindowState {
+    title: String,
+    visible: bool,
+    position: PhysicalPosition<i32>,
+    inner_size: PhysicalSize<u32>,
+    min_inner_size: Option<Size>,
+    max_inner_size: Option<Size>,
+    resize_increments: Option<Size>,
+    scale_factor: f64,
+    resizable: bool,
+    enabled_buttons: WindowButtons,
+    decorations: bool,
+    maximized: bool,
+    minimized: bool,
+    fullscreen: Option<Fullscreen>,
+    theme: Option<Theme>,
+    has_focus: bool,
+
+    /// The rectangle of the client area set with `Window::confine_cursor_to_rect`.
+    cursor_confinement: Option<PhysicalRect>,
+
+    /// The `0RGB` contents of the window, one `u32` per pixel, row by row.
+    pixels: Vec<u32>,
+
+    /// Sends the redraw and resize requests to the event loop.
+    message_sender: WakeSender<Message>,
+}
+
+impl WindowState {
+    fn new(
+        attributes: WindowAttributes,
+        scale_factor: f64,
+        message_sender: WakeSender<Message>,
+    ) -> Self {
+        let mut window_state = WindowState {
+            title: attributes.title,
+            visible: attributes.visible,
+            position: attributes
+                .position
+                .map(|position| position.to_physical(scale_factor))
+                .unwrap_or_default(),
+            inner_size: PhysicalSize::new(0, 0),
+            min_inner_size: attributes.min_inner_size,
+            max_inner_size: attributes.max_inner_size,
+            resize_increments: attributes.resize_increments,
+            scale_factor,
+            resizable: attributes.resizable,
+            enabled_buttons: attributes.enabled_buttons,
+            decorations: attributes.decorations,
+            maximized: attributes.maximized,
+            minimized: false,
+            fullscreen: attributes.fullscreen.0.map(Into::into),
+            theme: attributes.preferred_theme,
+            has_focus: false,
+            cursor_confinement: None,
+            pixels: Vec::new(),
+            message_sender,
+        };
+        let inner_size = attributes
+            .inner_size
+            .map(|size| size.to_physical(scale_factor))
+            .unwrap_or(DEFAULT_INNER_SIZE);
+        window_state.set_inner_size(window_state.clamp_inner_size(inner_size));
+        window_state
+    }
+
+    fn set_inner_size(&mut self, size: PhysicalSize<u32>) {
+        self.inner_size = size;
+        self.pixels.clear();
+        self.pixels
+            .resize(size.width as usize * size.height as usize, 0);
+    }
+
+    /// Applies the size limits of the window to `size`.
+    fn clamp_inner_size(&self, size: PhysicalSize<u32>) -> PhysicalSize<u32> {
+        let to_physical = |size: Size| size.to_physical::<u32>(self.scale_factor).into();
+        let constraints = SizeConstraints {
+            min: self.min_inner_size.map(to_physical),
+            max: self.max_inner_size.map(to_physical),
+            increments: self.resize_increments.map(to_physical),
+            base: None,
+        };
+        constraints.clamp(size)
+    }
+
+    /// Updates the state to match an event injected by the application.
+    pub(super) fn apply_event(&mut self, event: &WindowEvent) {
+        match *event {
+            WindowEvent::Resized(size) => self.set_inner_size(size),
+            WindowEvent::Moved(position) => self.position = position,
+            WindowEvent::Focused(focused) => self.has_focus = focused,
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = scale_factor
+            }
+            WindowEvent::ThemeChanged(theme) => self.theme = Some(theme),
+            WindowEvent::Minimized(minimized) => self.minimized = minimized,
+            _ => (),
+        }
+    }
+
+    pub(super) fn has_focus(&self) -> bool {
+        self.has_focus
+    }
+
+    pub(super) fn inner_size(&self) -> PhysicalSize<u32> {
+        self.inner_size
+    }
+
+    pub(super) fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// The rectangle of the desktop the cursor is confined to, while the window is focused.
+    fn cursor_confinement(&self) -> Option<PhysicalRect> {
+        if !self.has_focus {
+            return None;
+        }
+        let rect = self.cursor_confinement?;
+        let client_area = PhysicalRect::new(self.position, self.inner_size);
+        let position = PhysicalPosition::new(
+            client_area.position.x + rect.position.x,
+            client_area.position.y + rect.position.y,
+        );
+        client_area.intersection(PhysicalRect::new(position, rect.size))
+    }
+
+    /// Returns the pixel buffer, whose pixels are `0RGB`, as an opaque RGBA image.
+    fn capture(&self) -> ImageBuffer {
+        let rgba = self
+            .pixels
+            .iter()
+            .flat_map(|&pixel| {
+                let [b, g, r, _] = pixel.to_le_bytes();
+                [r, g, b, 0xff]
+            })
+            .collect();
+        ImageBuffer::from_rgba(rgba, self.inner_size)
+    }
+
+    fn send(&self, message: Message) {
+        // The event loop could already be gone.
+        let _ = self.message_sender.send(message);
+    }
+}
+
+impl Window {
+    pub(crate) fn new<T>(
+        event_loop_window_target: &EventLoopWindowTarget<T>,
+        attributes: WindowAttributes,
+        _platform_attributes: PlatformAttributes,
+    ) -> Result<Self, RootOsError> {
+        let window_id = WindowId(NEXT_WINDOW_ID.fetch_add(1, Ordering::Relaxed));
+        let monitor = event_loop_window_target.monitor.clone();
+        let visible = attributes.visible;
+        let window_state = WindowState::new(
+            attributes,
+            monitor.scale_factor(),
+            event_loop_window_target.message_sender.clone(),
+        );
+
+        let window_state = Arc::new(Mutex::new(window_state));
+        event_loop_window_target
+            .windows
+            .borrow_mut()
+            .insert(window_id, Arc::downgrade(&window_state));
+
+        let window = Window {
+            window_id,
+            window_state,
+            monitor,
+        };
+
+        // Like a real window, a visible one needs to be drawn once it's created.
+        if visible {
+            window.request_redraw();
+        }
+
+        Ok(window)
+    }
+
+    /// Runs `f` with the pixels of the window and its inner size.
+    pub(crate) fn with_pixel_buffer<R>(
+        &self,
+        f: impl FnOnce(&mut [u32], PhysicalSize<u32>) -> R,
+    ) -> R {
+        let mut window_state = self.window_state.lock().unwrap();
+        let inner_size = window_state.inner_size;
+        f(&mut window_state.pixels, inner_size)
+    }
+}
+
+impl Window {
+    #[inline]
+    pub fn id(&self) -> WindowId {
+        self.window_id
+    }
+
+    #[inline]
+    pub fn set_title(&self, title: &str) {
+        self.window_state.lock().unwrap().title = title.to_owned();
+    }
+
+    #[inline]
+    pub fn set_transparent(&self, _transparent: bool) {}
+
+    #[inline]
+    pub fn set_blur(&self, _blur: bool) {}
+
+    #[inline]
+    pub fn set_visible(&self, visible: bool) {
+        let was_visible =
+            std::mem::replace(&mut self.window_state.lock().unwrap().visible, visible);
+        if visible && !was_visible {
+            self.request_redraw();
+        }
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        Some(self.window_state.lock().unwrap().visible)
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, _enabled: bool) {}
+
+    #[inline]
+    pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        Ok(self.window_state.lock().unwrap().position)
+    }
+
+    #[inline]
+    pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        // There are no decorations around the window.
+        self.outer_position()
+    }
+
+    #[inline]
+    pub fn set_outer_position(&self, position: Position) {
+        let mut window_state = self.window_state.lock().unwrap();
+        window_state.position = position.to_physical(window_state.scale_factor);
+    }
+
+    #[inline]
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        self.window_state.lock().unwrap().inner_size
+    }
+
+    #[inline]
+    pub fn outer_size(&self) -> PhysicalSize<u32> {
+        self.inner_size()
+    }
+
+    /// Resizes the window right away, the `Resized` event is still delivered by the next
+    /// iteration of the event loop.
+    #[inline]
+    pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let mut window_state = self.window_state.lock().unwrap();
+        let size = window_state.clamp_inner_size(size.to_physical(window_state.scale_factor));
+        if size != window_state.inner_size {
+            window_state.set_inner_size(size);
+            window_state.send(Message::WindowEvent(
+                self.window_id,
+                WindowEvent::Resized(size),
+            ));
+        }
+        Some(size)
+    }
+
+    #[inline]
+    pub fn request_activation_token(&self) -> Result<AsyncRequestSerial, NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn set_min_inner_size(&self, dimensions: Option<Size>) {
+        self.window_state.lock().unwrap().min_inner_size = dimensions;
+    }
+
+    #[inline]
+    pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
+        self.window_state.lock().unwrap().max_inner_size = dimensions;
+    }
+
+    #[inline]
+    pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        let window_state = self.window_state.lock().unwrap();
+        window_state
+            .resize_increments
+            .map(|size| size.to_physical(window_state.scale_factor))
+    }
+
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, _visible: bool) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        Insets::default()
+    }
+
+    #[inline]
+    pub fn set_resize_increments(&self, increments: Option<Size>) {
+        self.window_state.lock().unwrap().resize_increments = increments;
+    }
+
+    #[inline]
+    pub fn set_resizable(&self, resizable: bool) {
+        self.window_state.lock().unwrap().resizable = resizable;
+    }
+
+    #[inline]
+    pub fn is_resizable(&self) -> bool {
+        self.window_state.lock().unwrap().resizable
+    }
+
+    #[inline]
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.window_state.lock().unwrap().enabled_buttons = buttons;
+    }
+
+    #[inline]
+    pub fn enabled_buttons(&self) -> WindowButtons {
+        self.window_state.lock().unwrap().enabled_buttons
+    }
+
+    #[inline]
+    pub fn set_cursor_icon(&self, _cursor: CursorIcon) {}
+
+    #[inline]
+    pub fn set_cursor_regions(&self, _regions: Vec<(PhysicalRect, CursorIcon)>) {}
+
+    #[inline]
+    pub fn set_cursor_grab(&self, _mode: CursorGrabMode) -> Result<(), ExternalError> {
+        // There's no cursor to grab, so any grab trivially holds.
+        Ok(())
+    }
+
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        self.window_state.lock().unwrap().cursor_confinement = rect;
+        Ok(())
+    }
+
+    /// The rectangle of the desktop the cursor is confined to, which follows the window.
+    pub(crate) fn cursor_confinement(&self) -> Option<PhysicalRect> {
+        self.window_state.lock().unwrap().cursor_confinement()
+    }
+
+    #[inline]
+    pub fn set_auto_grab(&self, _auto_grab: bool) {}
+
+    #[inline]
+    pub fn set_cursor_visible(&self, _visible: bool) {}
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: Position) {}
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), ExternalError> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_shape(&self, _shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.window_state.lock().unwrap().scale_factor
+    }
+
+    #[inline]
+    pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_maximized(&self, maximized: bool) {
+        self.window_state.lock().unwrap().maximized = maximized;
+    }
+
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.window_state.lock().unwrap().maximized
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        let mut window_state = self.window_state.lock().unwrap();
+        if std::mem::replace(&mut window_state.minimized, minimized) != minimized {
+            window_state.send(Message::WindowEvent(
+                self.window_id,
+                WindowEvent::Minimized(minimized),
+            ));
+        }
+    }
+
+    #[inline]
+    pub fn is_minimized(&self) -> Option<bool> {
+        Some(self.window_state.lock().unwrap().minimized)
+    }
+
+    #[inline]
+    pub(crate) fn fullscreen(&self) -> Option<Fullscreen> {
+        self.window_state.lock().unwrap().fullscreen.clone()
+    }
+
+    #[inline]
+    pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.window_state.lock().unwrap().fullscreen = fullscreen;
+    }
+
+    #[inline]
+    pub fn set_decorations(&self, decorations: bool) {
+        self.window_state.lock().unwrap().decorations = decorations;
+    }
+
+    #[inline]
+    pub fn is_decorated(&self) -> bool {
+        self.window_state.lock().unwrap().decorations
+    }
+
+    #[inline]
+    pub fn set_window_level(&self, _level: WindowLevel) {}
+
+    #[inline]
+    pub(crate) fn set_window_icon(&self, _window_icon: Option<PlatformIcon>) {}
+
+    #[inline]
+    pub fn set_drop_feedback(&self, _effect: DropEffect) {}
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
+
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {}
+
+    #[inline]
+    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {}
+
+    #[inline]
+    pub fn request_redraw(&self) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .send(Message::Redraw(self.window_id));
+    }
+
+    #[inline]
+    pub fn pre_present_notify(&self) {}
+
+    #[inline]
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
+        Some(self.monitor.clone())
+    }
+
+    #[inline]
+    pub fn available_monitors(&self) -> Vec<MonitorHandle> {
+        vec![self.monitor.clone()]
+    }
+
+    #[inline]
+    pub fn primary_monitor(&self) -> Option<MonitorHandle> {
+        Some(self.monitor.clone())
+    }
+
+    #[cfg(feature = "rwh_04")]
+    #[inline]
+    pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
+        rwh_04::RawWindowHandle::Xlib(rwh_04::XlibHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        rwh_05::RawWindowHandle::Xlib(rwh_05::XlibWindowHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        rwh_05::RawDisplayHandle::Xlib(rwh_05::XlibDisplayHandle::empty())
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+
+    #[inline]
+    pub fn set_theme(&self, theme: Option<Theme>) {
+        self.window_state.lock().unwrap().theme = theme;
+    }
+
+    #[inline]
+    pub fn theme(&self) -> Option<Theme> {
+        self.window_state.lock().unwrap().theme
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {}
+
+    #[inline]
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        Ok(self.window_state.lock().unwrap().capture())
+    }
+
+    #[inline]
+    pub fn has_focus(&self) -> bool {
+        self.window_state.lock().unwrap().has_focus
+    }
+
+    #[inline]
+    pub fn title(&self) -> String {
+        self.window_state.lock().unwrap().title.clone()
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .send(Message::WindowEvent(self.window_id, WindowEvent::Destroyed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{self, Receiver};
+
+    use super::*;
+    use crate::dpi::LogicalSize;
+
+    fn window_state(attributes: WindowAttributes) -> (WindowState, Receiver<Message>) {
+        let (sender, receiver) = mpsc::channel();
+        let (waker, _) = calloop::ping::make_ping().unwrap();
+        let window_state = WindowState::new(attributes, 2.0, WakeSender { sender, waker });
+        (window_state, receiver)
+    }
+
+    #[test]
+    fn initial_inner_size_is_clamped_to_the_size_limits() {
+        let attributes = WindowAttributes {
+            inner_size: Some(LogicalSize::new(1000, 10).into()),
+            min_inner_size: Some(LogicalSize::new(100, 50).into()),
+            max_inner_size: Some(PhysicalSize::new(1280, 720).into()),
+            ..Default::default()
+        };
+        let (window_state, _) = window_state(attributes);
+
+        assert_eq!(window_state.inner_size, PhysicalSize::new(1280, 100));
+        assert_eq!(window_state.pixels.len(), 1280 * 100);
+    }
+
+    #[test]
+    fn cursor_confinement_follows_the_window() {
+        let attributes = WindowAttributes {
+            position: Some(PhysicalPosition::new(100, 100).into()),
+            inner_size: Some(PhysicalSize::new(800, 600).into()),
+            ..Default::default()
+        };
+        let (mut window_state, _) = window_state(attributes);
+        window_state.cursor_confinement =
+            Some(PhysicalRect::new((10, 20).into(), (300, 200).into()));
+
+        // The confinement only applies while the window is focused.
+        assert_eq!(window_state.cursor_confinement(), None);
+        window_state.apply_event(&WindowEvent::Focused(true));
+        assert_eq!(
+            window_state.cursor_confinement(),
+            Some(PhysicalRect::new((110, 120).into(), (300, 200).into()))
+        );
+
+        window_state.apply_event(&WindowEvent::Moved((500, 300).into()));
+        assert_eq!(
+            window_state.cursor_confinement(),
+            Some(PhysicalRect::new((510, 320).into(), (300, 200).into()))
+        );
+
+        // The rectangle is clipped to the client area.
+        window_state.apply_event(&WindowEvent::Resized((200, 100).into()));
+        assert_eq!(
+            window_state.cursor_confinement(),
+            Some(PhysicalRect::new((510, 320).into(), (190, 80).into()))
+        );
+    }
+
+    #[test]
+    fn captured_pixels_are_converted_to_rgba() {
+        let attributes = WindowAttributes {
+            inner_size: Some(PhysicalSize::new(2, 1).into()),
+            ..Default::default()
+        };
+        let (mut window_state, _) = window_state(attributes);
+        window_state.pixels.copy_from_slice(&[0xff0000, 0x00ff80]);
+
+        let image = window_state.capture();
+        assert_eq!(image.size(), PhysicalSize::new(2, 1));
+        assert_eq!(image.rgba(), [0xff, 0, 0, 0xff, 0, 0xff, 0x80, 0xff]);
+    }
+
+    #[test]
+    fn requested_inner_size_is_clamped_to_the_constraints() {
+        let attributes = WindowAttributes {
+            min_inner_size: Some(PhysicalSize::new(640, 480).into()),
+            max_inner_size: Some(PhysicalSize::new(1280, 720).into()),
+            ..Default::default()
+        };
+        let (mut window_state, _) = window_state(attributes);
+
+        let size = window_state.clamp_inner_size(PhysicalSize::new(100, 100));
+        assert_eq!(size, PhysicalSize::new(640, 480));
+
+        // Above the maximum, and then rounded down to the increments from the minimum.
+        window_state.resize_increments = Some(PhysicalSize::new(100, 100).into());
+        let size = window_state.clamp_inner_size(PhysicalSize::new(2000, 555));
+        assert_eq!(size, PhysicalSize::new(1240, 480));
+    }
+
+    #[test]
+    fn injected_events_update_the_state() {
+        let (mut window_state, _) = window_state(WindowAttributes::default());
+        assert_eq!(window_state.inner_size, DEFAULT_INNER_SIZE);
+
+        window_state.apply_event(&WindowEvent::Resized(PhysicalSize::new(30, 20)));
+        window_state.apply_event(&WindowEvent::Focused(true));
+        window_state.apply_event(&WindowEvent::Minimized(true));
+        window_state.apply_event(&WindowEvent::ThemeChanged(Theme::Light));
+
+        assert_eq!(window_state.inner_size, PhysicalSize::new(30, 20));
+        assert_eq!(window_state.pixels, vec![0; 30 * 20]);
+        assert!(window_state.has_focus());
+        assert!(window_state.minimized);
+        assert_eq!(window_state.theme, Some(Theme::Light));
+    }
+}
@@ -17,22 +17,23 @@ use smol_str::SmolStr;
 #[cfg(x11_platform)]
 use crate::platform::x11::XlibErrorHook;
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size},
     error::{EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError},
-    event::KeyEvent,
+    event::{DeviceInfo, KeyEvent},
     event_loop::{
-        AsyncRequestSerial, ControlFlow, DeviceEvents, EventLoopClosed,
-        EventLoopWindowTarget as RootELW,
+        AsyncRequestSerial, Backend as RootBackend, ControlFlow, DeviceEvents, EventLoopClosed,
+        EventLoopWindowTarget as RootELW, PowerState,
     },
     icon::Icon,
     keyboard::{Key, PhysicalKey},
     platform::{
-        modifier_supplement::KeyEventExtModifierSupplement, pump_events::PumpStatus,
-        scancode::PhysicalKeyExtScancode,
+        backend::BackendPreference, modifier_supplement::KeyEventExtModifierSupplement,
+        pump_events::PumpStatus, scancode::PhysicalKeyExtScancode,
     },
     window::{
-        ActivationToken, CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme,
-        UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+        ActivationToken, CursorGrabMode, CursorIcon, DropEffect, ImageBuffer, ImeHints, ImePurpose,
+        Insets, ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons,
+        WindowLevel, WindowShape,
     },
 };
 #[cfg(x11_platform)]
@@ -44,6 +45,7 @@ pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
 pub(crate) use crate::platform_impl::Fullscreen;
 
 pub mod common;
+pub mod headless;
 #[cfg(wayland_platform)]
 pub mod wayland;
 #[cfg(x11_platform)]
@@ -55,12 +57,15 @@ pub(crate) enum Backend {
     X,
     #[cfg(wayland_platform)]
     Wayland,
+    Headless,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
+    pub(crate) backend_preference: BackendPreference,
     pub(crate) any_thread: bool,
+    pub(crate) headless_monitor: headless::MonitorConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -79,6 +84,8 @@ impl ApplicationName {
 pub struct PlatformSpecificWindowBuilderAttributes {
     pub name: Option<ApplicationName>,
     pub activation_token: Option<ActivationToken>,
+    #[cfg(wayland_platform)]
+    pub decoration_mode: crate::platform::wayland::DecorationMode,
     #[cfg(x11_platform)]
     pub x11: X11WindowBuilderAttributes,
 }
@@ -101,6 +108,8 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
         Self {
             name: None,
             activation_token: None,
+            #[cfg(wayland_platform)]
+            decoration_mode: Default::default(),
             #[cfg(x11_platform)]
             x11: X11WindowBuilderAttributes {
                 visual_id: None,
@@ -144,6 +153,7 @@ pub(crate) enum Window {
     X(x11::Window),
     #[cfg(wayland_platform)]
     Wayland(wayland::Window),
+    Headless(headless::Window),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -182,6 +192,15 @@ impl DeviceId {
         #[cfg(all(not(wayland_platform), x11_platform))]
         return DeviceId::X(unsafe { x11::DeviceId::dummy() });
     }
+
+    pub fn persistent_id(&self) -> Option<u64> {
+        match self {
+            #[cfg(x11_platform)]
+            DeviceId::X(device_id) => device_id.persistent_id(),
+            #[cfg(wayland_platform)]
+            DeviceId::Wayland(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -190,6 +209,7 @@ pub enum MonitorHandle {
     X(x11::MonitorHandle),
     #[cfg(wayland_platform)]
     Wayland(wayland::MonitorHandle),
+    Headless(headless::MonitorHandle),
 }
 
 /// `x11_or_wayland!(match expr; Enum(foo) => foo.something())`
@@ -198,6 +218,7 @@ pub enum MonitorHandle {
 /// match self {
 ///    Enum::X(foo) => foo.something(),
 ///    Enum::Wayland(foo) => foo.something(),
+///    Enum::Headless(foo) => foo.something(),
 /// }
 /// ```
 /// The result can be converted to another enum by adding `; as AnotherEnum`
@@ -208,6 +229,7 @@ macro_rules! x11_or_wayland {
             $enum::X($($c1)*) => $enum2::X($x),
             #[cfg(wayland_platform)]
             $enum::Wayland($($c1)*) => $enum2::Wayland($x),
+            $enum::Headless($($c1)*) => $enum2::Headless($x),
         }
     };
     (match $what:expr; $enum:ident ( $($c1:tt)* ) => $x:expr) => {
@@ -216,6 +238,7 @@ macro_rules! x11_or_wayland {
             $enum::X($($c1)*) => $x,
             #[cfg(wayland_platform)]
             $enum::Wayland($($c1)*) => $x,
+            $enum::Headless($($c1)*) => $x,
         }
     };
 }
@@ -263,6 +286,7 @@ pub enum VideoMode {
     X(x11::VideoMode),
     #[cfg(wayland_platform)]
     Wayland(wayland::VideoMode),
+    Headless(headless::VideoMode),
 }
 
 impl VideoMode {
@@ -294,6 +318,10 @@ impl Window {
         attribs: WindowAttributes,
         pl_attribs: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Self, RootOsError> {
+        if window_target.exiting() {
+            return Err(os_error!(OsError::Misc("the event loop is exiting")));
+        }
+
         match *window_target {
             #[cfg(wayland_platform)]
             EventLoopWindowTarget::Wayland(ref window_target) => {
@@ -303,6 +331,9 @@ impl Window {
             EventLoopWindowTarget::X(ref window_target) => {
                 x11::Window::new(window_target, attribs, pl_attribs).map(Window::X)
             }
+            EventLoopWindowTarget::Headless(ref window_target) => {
+                headless::Window::new(window_target, attribs, pl_attribs).map(Window::Headless)
+            }
         }
     }
 
@@ -344,6 +375,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.is_visible())
     }
 
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        x11_or_wayland!(match self; Window(w) => w.set_enabled(enabled))
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         x11_or_wayland!(match self; Window(w) => w.outer_position())
@@ -394,11 +430,36 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.resize_increments())
     }
 
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, visible: bool) -> Result<(), NotSupportedError> {
+        x11_or_wayland!(match self; Window(w) => w.set_visible_on_all_workspaces(visible))
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        x11_or_wayland!(match self; Window(w) => w.is_visible_on_all_workspaces())
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        x11_or_wayland!(match self; Window(w) => w.frame_insets())
+    }
+
     #[inline]
     pub fn set_resize_increments(&self, increments: Option<Size>) {
         x11_or_wayland!(match self; Window(w) => w.set_resize_increments(increments))
     }
 
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn set_base_size(&self, base_size: Option<Size>) {
+        match self {
+            #[cfg(x11_platform)]
+            Window::X(ref w) => w.set_base_size(base_size),
+            _ => (),
+        }
+    }
+
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         x11_or_wayland!(match self; Window(w) => w.set_resizable(resizable))
@@ -424,16 +485,49 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_cursor_icon(cursor))
     }
 
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        x11_or_wayland!(match self; Window(w) => w.set_cursor_regions(regions))
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         x11_or_wayland!(match self; Window(window) => window.set_cursor_grab(mode))
     }
 
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(window) => window.confine_cursor_to_rect(rect))
+    }
+
+    #[inline]
+    pub fn set_auto_grab(&self, auto_grab: bool) {
+        x11_or_wayland!(match self; Window(window) => window.set_auto_grab(auto_grab))
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         x11_or_wayland!(match self; Window(window) => window.set_cursor_visible(visible))
     }
 
+    #[inline]
+    pub fn set_above(&self, sibling: &Window) -> Result<(), ExternalError> {
+        match (self, sibling) {
+            #[cfg(x11_platform)]
+            (Window::X(window), Window::X(sibling)) => window.set_above(sibling),
+            _ => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
+    #[inline]
+    pub fn set_below(&self, sibling: &Window) -> Result<(), ExternalError> {
+        match (self, sibling) {
+            #[cfg(x11_platform)]
+            (Window::X(window), Window::X(sibling)) => window.set_below(sibling),
+            _ => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         x11_or_wayland!(match self; Window(window) => window.drag_window())
@@ -454,6 +548,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_cursor_hittest(hittest))
     }
 
+    #[inline]
+    pub fn set_shape(&self, shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(w) => w.set_shape(shape))
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; Window(w) => w.scale_factor())
@@ -514,6 +613,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_window_icon(window_icon.map(|icon| icon.inner)))
     }
 
+    #[inline]
+    pub fn set_drop_feedback(&self, effect: DropEffect) {
+        x11_or_wayland!(match self; Window(w) => w.set_drop_feedback(effect))
+    }
+
     #[inline]
     pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
         x11_or_wayland!(match self; Window(w) => w.set_ime_cursor_area(position, size))
@@ -524,6 +628,11 @@ impl Window {
         common::xkb::reset_dead_keys()
     }
 
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        true
+    }
+
     #[inline]
     pub fn set_ime_allowed(&self, allowed: bool) {
         x11_or_wayland!(match self; Window(w) => w.set_ime_allowed(allowed))
@@ -535,7 +644,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn set_ime_hints(&self, hints: ImeHints) {
+        x11_or_wayland!(match self; Window(w) => w.set_ime_hints(hints))
+    }
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
         x11_or_wayland!(match self; Window(w) => w.focus_window())
     }
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
@@ -572,6 +686,20 @@ impl Window {
                 .into_iter()
                 .map(MonitorHandle::Wayland)
                 .collect(),
+            Window::Headless(ref window) => window
+                .available_monitors()
+                .into_iter()
+                .map(MonitorHandle::Headless)
+                .collect(),
+        }
+    }
+
+    #[inline]
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        match (self, monitor) {
+            #[cfg(x11_platform)]
+            (Window::X(window), MonitorHandle::X(monitor)) => window.work_area(monitor),
+            _ => PhysicalRect::new(monitor.position(), monitor.size()),
         }
     }
 
@@ -626,6 +754,11 @@ impl Window {
         x11_or_wayland!(match self; Window(window) => window.set_content_protected(protected))
     }
 
+    #[inline]
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        x11_or_wayland!(match self; Window(window) => window.capture())
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         x11_or_wayland!(match self; Window(window) => window.has_focus())
@@ -637,6 +770,7 @@ impl Window {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEventExtra {
     pub key_without_modifiers: Key,
     pub text_with_all_modifiers: Option<SmolStr>,
@@ -724,6 +858,7 @@ pub enum EventLoop<T: 'static> {
     Wayland(Box<wayland::EventLoop<T>>),
     #[cfg(x11_platform)]
     X(x11::EventLoop<T>),
+    Headless(headless::EventLoop<T>),
 }
 
 pub enum EventLoopProxy<T: 'static> {
@@ -731,6 +866,7 @@ pub enum EventLoopProxy<T: 'static> {
     X(x11::EventLoopProxy<T>),
     #[cfg(wayland_platform)]
     Wayland(wayland::EventLoopProxy<T>),
+    Headless(headless::EventLoopProxy<T>),
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
@@ -739,51 +875,77 @@ impl<T: 'static> Clone for EventLoopProxy<T> {
     }
 }
 
+/// Picks the backend to use from whether a Wayland and an X11 display are set.
+fn select_backend(
+    preference: BackendPreference,
+    wayland_display: bool,
+    x11_display: bool,
+) -> Result<Backend, EventLoopError> {
+    match (preference, wayland_display, x11_display) {
+        // The connection fails instead if the display server isn't present.
+        #[cfg(x11_platform)]
+        (BackendPreference::ForceX11, _, _) => Ok(Backend::X),
+        #[cfg(wayland_platform)]
+        (BackendPreference::ForceWayland, _, _) => Ok(Backend::Wayland),
+        #[cfg(not(x11_platform))]
+        (BackendPreference::ForceX11, _, _) => Err(EventLoopError::Os(os_error!(OsError::Misc(
+            "X11 is forced; note: enable the `winit/x11` feature to support X11"
+        )))),
+        #[cfg(not(wayland_platform))]
+        (BackendPreference::ForceWayland, _, _) => {
+            Err(EventLoopError::Os(os_error!(OsError::Misc(
+                "Wayland is forced; note: enable the `winit/wayland` feature to support Wayland"
+            ))))
+        }
+        #[cfg(x11_platform)]
+        (BackendPreference::PreferX11, _, true) => Ok(Backend::X),
+        // Wayland is present, and first because X11 could be present under Wayland as well.
+        #[cfg(wayland_platform)]
+        (_, true, _) => Ok(Backend::Wayland),
+        // X11 is present.
+        #[cfg(x11_platform)]
+        (_, _, true) => Ok(Backend::X),
+        // No backend is present.
+        (_, wayland_display, x11_display) => {
+            let msg = if wayland_display && !cfg!(wayland_platform) {
+                "DISPLAY is not set; note: enable the `winit/wayland` feature to support Wayland"
+            } else if x11_display && !cfg!(x11_platform) {
+                "neither WAYLAND_DISPLAY nor WAYLAND_SOCKET is set; note: enable the `winit/x11` feature to support X11"
+            } else {
+                "neither WAYLAND_DISPLAY nor WAYLAND_SOCKET nor DISPLAY is set."
+            };
+            Err(EventLoopError::Os(os_error!(OsError::Misc(msg))))
+        }
+    }
+}
+
 impl<T: 'static> EventLoop<T> {
     pub(crate) fn new(
         attributes: &PlatformSpecificEventLoopAttributes,
     ) -> Result<Self, EventLoopError> {
-        if !attributes.any_thread && !is_main_thread() {
-            panic!(
-                "Initializing the event loop outside of the main thread is a significant \
-                 cross-platform compatibility hazard. If you absolutely need to create an \
-                 EventLoop on a different thread, you can use the \
-                 `EventLoopBuilderExtUnix::any_thread` function."
-            );
+        // The headless backend doesn't talk to a display server, so it can run on the threads of
+        // the test harness.
+        if !attributes.any_thread
+            && attributes.forced_backend != Some(Backend::Headless)
+            && !is_main_thread()
+        {
+            return Err(EventLoopError::NotMainThread);
         }
 
-        // NOTE: Wayland first because of X11 could be present under Wayland as well. Empty
-        // variables are also treated as not set.
-        let backend = match (
-            attributes.forced_backend,
-            env::var("WAYLAND_DISPLAY")
-                .ok()
-                .filter(|var| !var.is_empty())
-                .or_else(|| env::var("WAYLAND_SOCKET").ok())
-                .filter(|var| !var.is_empty())
-                .is_some(),
-            env::var("DISPLAY")
-                .map(|var| !var.is_empty())
-                .unwrap_or(false),
-        ) {
-            // User is forcing a backend.
-            (Some(backend), _, _) => backend,
-            // Wayland is present.
-            #[cfg(wayland_platform)]
-            (None, true, _) => Backend::Wayland,
-            // X11 is present.
-            #[cfg(x11_platform)]
-            (None, _, true) => Backend::X,
-            // No backend is present.
-            (_, wayland_display, x11_display) => {
-                let msg = if wayland_display && !cfg!(wayland_platform) {
-                    "DISPLAY is not set; note: enable the `winit/wayland` feature to support Wayland"
-                } else if x11_display && !cfg!(x11_platform) {
-                    "neither WAYLAND_DISPLAY nor WAYLAND_SOCKET is set; note: enable the `winit/x11` feature to support X11"
-                } else {
-                    "neither WAYLAND_DISPLAY nor WAYLAND_SOCKET nor DISPLAY is set."
-                };
-                return Err(EventLoopError::Os(os_error!(OsError::Misc(msg))));
+        let backend = match attributes.forced_backend {
+            Some(backend) => backend,
+            None => {
+                // Empty variables are treated as not set.
+                let wayland_display = env::var("WAYLAND_DISPLAY")
+                    .ok()
+                    .filter(|var| !var.is_empty())
+                    .or_else(|| env::var("WAYLAND_SOCKET").ok())
+                    .filter(|var| !var.is_empty())
+                    .is_some();
+                let x11_display = env::var("DISPLAY")
+                    .map(|var| !var.is_empty())
+                    .unwrap_or(false);
+                select_backend(attributes.backend_preference, wayland_display, x11_display)?
             }
         };
 
@@ -793,6 +955,9 @@ impl<T: 'static> EventLoop<T> {
             Backend::Wayland => EventLoop::new_wayland_any_thread().map_err(Into::into),
             #[cfg(x11_platform)]
             Backend::X => EventLoop::new_x11_any_thread().map_err(Into::into),
+            Backend::Headless => Ok(EventLoop::Headless(headless::EventLoop::new(
+                attributes.headless_monitor,
+            ))),
         }
     }
 
@@ -857,6 +1022,10 @@ impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         x11_or_wayland!(match self; EventLoopProxy(proxy) => proxy.send_event(event))
     }
+
+    pub fn wake_up(&self) {
+        x11_or_wayland!(match self; EventLoopProxy(proxy) => proxy.wake_up())
+    }
 }
 
 pub enum EventLoopWindowTarget<T> {
@@ -864,16 +1033,40 @@ pub enum EventLoopWindowTarget<T> {
     Wayland(wayland::EventLoopWindowTarget<T>),
     #[cfg(x11_platform)]
     X(x11::EventLoopWindowTarget<T>),
+    Headless(headless::EventLoopWindowTarget<T>),
 }
 
 impl<T> EventLoopWindowTarget<T> {
+    #[inline]
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        x11_or_wayland!(match self; EventLoopWindowTarget(evlp) => evlp.create_proxy(); as EventLoopProxy)
+    }
+
+    #[cfg(wayland_platform)]
     #[inline]
     pub fn is_wayland(&self) -> bool {
-        match *self {
+        matches!(self, EventLoopWindowTarget::Wayland(_))
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn is_x11(&self) -> bool {
+        matches!(self, EventLoopWindowTarget::X(_))
+    }
+
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        matches!(self, EventLoopWindowTarget::Headless(_))
+    }
+
+    #[inline]
+    pub fn backend(&self) -> RootBackend {
+        match self {
             #[cfg(wayland_platform)]
-            EventLoopWindowTarget::Wayland(_) => true,
+            EventLoopWindowTarget::Wayland(_) => RootBackend::Wayland,
             #[cfg(x11_platform)]
-            _ => false,
+            EventLoopWindowTarget::X(_) => RootBackend::X11,
+            EventLoopWindowTarget::Headless(_) => RootBackend::Headless,
         }
     }
 
@@ -889,6 +1082,10 @@ impl<T> EventLoopWindowTarget<T> {
             EventLoopWindowTarget::X(ref evlp) => {
                 evlp.available_monitors().map(MonitorHandle::X).collect()
             }
+            EventLoopWindowTarget::Headless(ref evlp) => evlp
+                .available_monitors()
+                .map(MonitorHandle::Headless)
+                .collect(),
         }
     }
 
@@ -899,6 +1096,36 @@ impl<T> EventLoopWindowTarget<T> {
         )
     }
 
+    #[inline]
+    pub fn device_info(&self, device_id: DeviceId) -> Option<DeviceInfo> {
+        match (self, device_id) {
+            #[cfg(x11_platform)]
+            (EventLoopWindowTarget::X(evlp), DeviceId::X(device_id)) => evlp.device_info(device_id),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> Option<PowerState> {
+        match self {
+            EventLoopWindowTarget::Headless(evlp) => evlp.power_state(),
+            _ => common::power::power_state(),
+        }
+    }
+
+    #[inline]
+    pub fn clip_cursor(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        match self {
+            #[cfg(wayland_platform)]
+            EventLoopWindowTarget::Wayland(_) => {
+                Err(ExternalError::NotSupported(NotSupportedError::new()))
+            }
+            #[cfg(x11_platform)]
+            EventLoopWindowTarget::X(evlp) => evlp.clip_cursor(rect),
+            EventLoopWindowTarget::Headless(evlp) => evlp.clip_cursor(rect),
+        }
+    }
+
     #[inline]
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
         x11_or_wayland!(match self; Self(evlp) => evlp.listen_device_events(allowed))
@@ -926,6 +1153,10 @@ impl<T> EventLoopWindowTarget<T> {
         x11_or_wayland!(match self; Self(evlp) => evlp.control_flow())
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        x11_or_wayland!(match self; Self(evlp) => evlp.request_redraw(window_id))
+    }
+
     pub(crate) fn clear_exit(&self) {
         x11_or_wayland!(match self; Self(evlp) => evlp.clear_exit())
     }
@@ -974,3 +1205,43 @@ fn is_main_thread() -> bool {
 fn is_main_thread() -> bool {
     std::thread::current().name() == Some("main")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(x11_platform, wayland_platform))]
+    #[test]
+    fn backend_preference_orders_the_present_backends() {
+        let select = |preference, wayland_display, x11_display| {
+            select_backend(preference, wayland_display, x11_display).ok()
+        };
+
+        assert_eq!(
+            select(BackendPreference::Auto, true, true),
+            Some(Backend::Wayland)
+        );
+        assert_eq!(
+            select(BackendPreference::PreferX11, true, true),
+            Some(Backend::X)
+        );
+        assert_eq!(
+            select(BackendPreference::PreferX11, true, false),
+            Some(Backend::Wayland)
+        );
+        assert_eq!(
+            select(BackendPreference::PreferWayland, false, true),
+            Some(Backend::X)
+        );
+        // Forced backends are used even if they aren't present, to fail to connect.
+        assert_eq!(
+            select(BackendPreference::ForceX11, true, false),
+            Some(Backend::X)
+        );
+        assert_eq!(
+            select(BackendPreference::ForceWayland, false, false),
+            Some(Backend::Wayland)
+        );
+        assert_eq!(select(BackendPreference::Auto, false, false), None);
+    }
+}
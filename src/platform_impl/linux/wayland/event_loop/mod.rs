@@ -46,9 +46,6 @@ pub struct EventLoop<T: 'static> {
     compositor_updates: Vec<WindowCompositorUpdate>,
     window_ids: Vec<WindowId>,
 
-    /// Sender of user events.
-    user_events_sender: calloop::channel::Sender<T>,
-
     // XXX can't remove RefCell out of here, unless we can plumb generics into the `Window`, which
     // we don't really want, since it'll break public API by a lot.
     /// Pending events from the user.
@@ -162,11 +159,11 @@ impl<T: 'static> EventLoop<T> {
             connection: connection.clone(),
             wayland_dispatcher: wayland_dispatcher.clone(),
             event_loop_awakener,
+            user_events_sender,
             queue_handle,
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             state: RefCell::new(winit_state),
-            _marker: PhantomData,
         };
 
         let event_loop = Self {
@@ -176,11 +173,11 @@ impl<T: 'static> EventLoop<T> {
             window_ids: Vec::new(),
             connection,
             wayland_dispatcher,
-            user_events_sender,
             pending_user_events,
             event_loop,
             window_target: RootEventLoopWindowTarget {
                 p: PlatformEventLoopWindowTarget::Wayland(window_target),
+                handler_state: Default::default(),
                 _marker: PhantomData,
             },
         };
@@ -355,7 +352,7 @@ impl<T: 'static> EventLoop<T> {
 
         for mut compositor_update in compositor_updates.drain(..) {
             let window_id = compositor_update.window_id;
-            if compositor_update.scale_changed {
+            if let Some(old_scale_factor) = compositor_update.old_scale_factor {
                 let (physical_size, scale_factor) = self.with_state(|state| {
                     let windows = state.windows.get_mut();
                     let window = windows.get(&window_id).unwrap().lock().unwrap();
@@ -372,6 +369,7 @@ impl<T: 'static> EventLoop<T> {
                     Event::WindowEvent {
                         window_id: crate::window::WindowId(window_id),
                         event: WindowEvent::ScaleFactorChanged {
+                            old_scale_factor,
                             scale_factor,
                             inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
                                 &new_inner_size,
@@ -402,7 +400,7 @@ impl<T: 'static> EventLoop<T> {
 
             // NOTE: Rescale changed the physical size which winit operates in, thus we should
             // resize.
-            if compositor_update.resized || compositor_update.scale_changed {
+            if compositor_update.resized || compositor_update.old_scale_factor.is_some() {
                 let physical_size = self.with_state(|state| {
                     let windows = state.windows.get_mut();
                     let window = windows.get(&window_id).unwrap().lock().unwrap();
@@ -486,17 +484,20 @@ impl<T: 'static> EventLoop<T> {
                     return None;
                 }
 
+                // Drawing would map the window, so keep the request pending until it's shown.
+                let window_requests = window_requests.get(window_id).unwrap();
+                if !window_requests.visible.load(Ordering::Relaxed) {
+                    return None;
+                }
+
                 // Reset the frame callbacks state.
                 window.frame_callback_reset();
-                let mut redraw_requested = window_requests
-                    .get(window_id)
-                    .unwrap()
-                    .take_redraw_requested();
+                let mut redraw_requested = window_requests.take_redraw_requested();
 
                 // Redraw the frame while at it.
                 redraw_requested |= window.refresh_frame();
 
-                redraw_requested.then_some(WindowEvent::RedrawRequested)
+                redraw_requested.then_some(WindowEvent::RedrawRequested { dirty_rects: None })
             });
 
             if let Some(event) = event {
@@ -549,8 +550,7 @@ impl<T: 'static> EventLoop<T> {
                 PlatformEventLoopWindowTarget::Wayland(window_target) => {
                     window_target.event_loop_awakener.ping();
                 }
-                #[cfg(x11_platform)]
-                PlatformEventLoopWindowTarget::X(_) => unreachable!(),
+                _ => unreachable!(),
             }
         }
 
@@ -561,7 +561,10 @@ impl<T: 'static> EventLoop<T> {
 
     #[inline]
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.user_events_sender.clone())
+        match &self.window_target.p {
+            PlatformEventLoopWindowTarget::Wayland(window_target) => window_target.create_proxy(),
+            _ => unreachable!(),
+        }
     }
 
     #[inline]
@@ -572,7 +575,6 @@ impl<T: 'static> EventLoop<T> {
     fn with_state<'a, U: 'a, F: FnOnce(&'a mut WinitState) -> U>(&'a mut self, callback: F) -> U {
         let state = match &mut self.window_target.p {
             PlatformEventLoopWindowTarget::Wayland(window_target) => window_target.state.get_mut(),
-            #[cfg(x11_platform)]
             _ => unreachable!(),
         };
 
@@ -582,7 +584,6 @@ impl<T: 'static> EventLoop<T> {
     fn loop_dispatch<D: Into<Option<std::time::Duration>>>(&mut self, timeout: D) -> IOResult<()> {
         let state = match &mut self.window_target.p {
             PlatformEventLoopWindowTarget::Wayland(window_target) => window_target.state.get_mut(),
-            #[cfg(feature = "x11")]
             _ => unreachable!(),
         };
 
@@ -595,7 +596,6 @@ impl<T: 'static> EventLoop<T> {
     fn roundtrip(&mut self) -> Result<usize, RootOsError> {
         let state = match &mut self.window_target.p {
             PlatformEventLoopWindowTarget::Wayland(window_target) => window_target.state.get_mut(),
-            #[cfg(feature = "x11")]
             _ => unreachable!(),
         };
 
@@ -641,6 +641,9 @@ pub struct EventLoopWindowTarget<T> {
     /// The event loop wakeup source.
     pub event_loop_awakener: calloop::ping::Ping,
 
+    /// Sender of user events.
+    user_events_sender: calloop::channel::Sender<T>,
+
     /// The main queue used by the event loop.
     pub queue_handle: QueueHandle<WinitState>,
 
@@ -659,11 +662,16 @@ pub struct EventLoopWindowTarget<T> {
 
     /// Connection to the wayland server.
     pub connection: Connection,
-
-    _marker: std::marker::PhantomData<T>,
 }
 
 impl<T> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy::new(
+            self.user_events_sender.clone(),
+            self.event_loop_awakener.clone(),
+        )
+    }
+
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -672,6 +680,20 @@ impl<T> EventLoopWindowTarget<T> {
         self.control_flow.get()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        let state = self.state.borrow();
+        let window_requests = state.window_requests.borrow();
+        if let Some(window_requests) = window_requests.get(&window_id) {
+            if window_requests
+                .redraw_requested
+                .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                self.event_loop_awakener.ping();
+            }
+        }
+    }
+
     pub(crate) fn exit(&self) {
         self.exit.set(Some(0))
     }
@@ -3,25 +3,31 @@
 use std::sync::mpsc::SendError;
 
 use sctk::reexports::calloop::channel::Sender;
+use sctk::reexports::calloop::ping::Ping;
 
 use crate::event_loop::EventLoopClosed;
 
 /// A handle that can be sent across the threads and used to wake up the `EventLoop`.
 pub struct EventLoopProxy<T: 'static> {
     user_events_sender: Sender<T>,
+    event_loop_awakener: Ping,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         EventLoopProxy {
             user_events_sender: self.user_events_sender.clone(),
+            event_loop_awakener: self.event_loop_awakener.clone(),
         }
     }
 }
 
 impl<T: 'static> EventLoopProxy<T> {
-    pub fn new(user_events_sender: Sender<T>) -> Self {
-        Self { user_events_sender }
+    pub fn new(user_events_sender: Sender<T>, event_loop_awakener: Ping) -> Self {
+        Self {
+            user_events_sender,
+            event_loop_awakener,
+        }
     }
 
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
@@ -29,4 +35,8 @@ impl<T: 'static> EventLoopProxy<T> {
             .send(event)
             .map_err(|SendError(error)| EventLoopClosed(error))
     }
+
+    pub fn wake_up(&self) {
+        self.event_loop_awakener.ping();
+    }
 }
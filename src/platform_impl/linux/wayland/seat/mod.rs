@@ -17,6 +17,7 @@ use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 use crate::event::WindowEvent;
 use crate::keyboard::ModifiersState;
 use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::TouchIds;
 
 mod keyboard;
 mod pointer;
@@ -42,6 +43,9 @@ pub struct WinitSeatState {
     /// The mapping from touched points to the surfaces they're present.
     touch_map: AHashMap<i32, TouchPoint>,
 
+    /// The ids reported for the touched points.
+    touch_ids: TouchIds<i32>,
+
     /// The text input bound on the seat.
     text_input: Option<Arc<ZwpTextInputV3>>,
 
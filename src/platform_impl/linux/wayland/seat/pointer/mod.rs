@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use sctk::reexports::client::delegate_dispatch;
 use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_region::WlRegion;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
@@ -118,6 +119,7 @@ impl PointerHandler for WinitState {
                     self.events_sink
                         .push_window_event(WindowEvent::CursorEntered { device_id }, window_id);
 
+                    window.pointer_moved(position);
                     if let Some(pointer) = seat_state.pointer.as_ref().map(Arc::downgrade) {
                         window.pointer_entered(pointer);
                     }
@@ -145,6 +147,7 @@ impl PointerHandler for WinitState {
                         .push_window_event(WindowEvent::CursorLeft { device_id }, window_id);
                 }
                 PointerEventKind::Motion { .. } => {
+                    window.pointer_moved(position);
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved {
                             device_id,
@@ -285,12 +288,13 @@ impl WinitPointerData {
         pointer_constraints: &PointerConstraintsState,
         surface: &WlSurface,
         pointer: &WlPointer,
+        region: Option<&WlRegion>,
         queue_handle: &QueueHandle<WinitState>,
     ) {
         self.inner.lock().unwrap().confined_pointer = Some(pointer_constraints.confine_pointer(
             surface,
             pointer,
-            None,
+            region,
             Lifetime::Persistent,
             queue_handle,
             GlobalData,
@@ -16,7 +16,7 @@ use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::
 use crate::event::{Ime, WindowEvent};
 use crate::platform_impl::wayland;
 use crate::platform_impl::wayland::state::WinitState;
-use crate::window::ImePurpose;
+use crate::window::{ImeHints, ImePurpose};
 
 pub struct TextInputState {
     text_input_manager: ZwpTextInputManagerV3,
@@ -74,8 +74,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 };
 
                 if window.ime_allowed() {
-                    text_input.enable();
-                    text_input.set_content_type_by_purpose(window.ime_purpose());
+                    window.enable_text_input(text_input);
                     text_input.commit();
                     state
                         .events_sink
@@ -169,20 +168,39 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 }
 
 pub trait ZwpTextInputV3Ext {
-    fn set_content_type_by_purpose(&self, purpose: ImePurpose);
+    fn set_content_type_for(&self, purpose: ImePurpose, hints: ImeHints);
 }
 
 impl ZwpTextInputV3Ext for ZwpTextInputV3 {
-    fn set_content_type_by_purpose(&self, purpose: ImePurpose) {
-        let (hint, purpose) = match purpose {
-            ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
-            ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
-            ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
-        };
+    fn set_content_type_for(&self, purpose: ImePurpose, hints: ImeHints) {
+        let (hint, purpose) = content_type(purpose, hints);
         self.set_content_type(hint, purpose);
     }
 }
 
+fn content_type(purpose: ImePurpose, hints: ImeHints) -> (ContentHint, ContentPurpose) {
+    let (mut hint, purpose) = match purpose {
+        ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
+        ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
+        ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
+    };
+
+    if hints.contains(ImeHints::AUTO_CAPITALIZE) {
+        hint |= ContentHint::AutoCapitalization;
+    }
+    if hints.contains(ImeHints::AUTO_CORRECT) {
+        hint |= ContentHint::Spellcheck;
+        if !hints.contains(ImeHints::NO_SUGGESTIONS) {
+            hint |= ContentHint::Completion;
+        }
+    }
+    if hints.contains(ImeHints::MULTILINE) {
+        hint |= ContentHint::Multiline;
+    }
+
+    (hint, purpose)
+}
+
 /// The Data associated with the text input.
 #[derive(Default)]
 pub struct TextInputData {
@@ -210,3 +228,24 @@ struct Preedit {
 
 delegate_dispatch!(WinitState: [ZwpTextInputManagerV3: GlobalData] => TextInputState);
 delegate_dispatch!(WinitState: [ZwpTextInputV3: TextInputData] => TextInputState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_combine_with_the_purpose() {
+        let (hint, purpose) = content_type(
+            ImePurpose::Password,
+            ImeHints::AUTO_CORRECT | ImeHints::NO_SUGGESTIONS | ImeHints::MULTILINE,
+        );
+        assert_eq!(
+            hint,
+            ContentHint::SensitiveData | ContentHint::Spellcheck | ContentHint::Multiline
+        );
+        assert_eq!(purpose, ContentPurpose::Password);
+
+        let (hint, _) = content_type(ImePurpose::Normal, ImeHints::AUTO_CORRECT);
+        assert_eq!(hint, ContentHint::Spellcheck | ContentHint::Completion);
+    }
+}
@@ -39,6 +39,7 @@ impl TouchHandler for WinitState {
         seat_state
             .touch_map
             .insert(id, TouchPoint { surface, location });
+        let touch_id = seat_state.touch_ids.get(id, TouchPhase::Started);
 
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
@@ -48,7 +49,8 @@ impl TouchHandler for WinitState {
                 phase: TouchPhase::Started,
                 location: location.to_physical(scale_factor),
                 force: None,
-                id: id as u64,
+                id: touch_id,
+                palm: false,
             }),
             window_id,
         );
@@ -70,6 +72,7 @@ impl TouchHandler for WinitState {
             Some(touch_point) => touch_point,
             None => return,
         };
+        let touch_id = seat_state.touch_ids.get(id, TouchPhase::Ended);
 
         let window_id = wayland::make_wid(&touch_point.surface);
         let scale_factor = match self.windows.get_mut().get(&window_id) {
@@ -85,7 +88,8 @@ impl TouchHandler for WinitState {
                 phase: TouchPhase::Ended,
                 location: touch_point.location.to_physical(scale_factor),
                 force: None,
-                id: id as u64,
+                id: touch_id,
+                palm: false,
             }),
             window_id,
         );
@@ -107,6 +111,7 @@ impl TouchHandler for WinitState {
             Some(touch_point) => touch_point,
             None => return,
         };
+        let touch_id = seat_state.touch_ids.get(id, TouchPhase::Moved);
 
         let window_id = wayland::make_wid(&touch_point.surface);
         let scale_factor = match self.windows.get_mut().get(&window_id) {
@@ -124,7 +129,8 @@ impl TouchHandler for WinitState {
                 phase: TouchPhase::Moved,
                 location: touch_point.location.to_physical(scale_factor),
                 force: None,
-                id: id as u64,
+                id: touch_id,
+                palm: false,
             }),
             window_id,
         );
@@ -134,10 +140,13 @@ impl TouchHandler for WinitState {
         let seat_state = self.seats.get_mut(&touch.seat().id()).unwrap();
 
         for (id, touch_point) in seat_state.touch_map.drain() {
+            let touch_id = seat_state.touch_ids.get(id, TouchPhase::Cancelled);
+
             let window_id = wayland::make_wid(&touch_point.surface);
             let scale_factor = match self.windows.get_mut().get(&window_id) {
                 Some(window) => window.lock().unwrap().scale_factor(),
-                None => return,
+                // Keep cancelling the remaining points.
+                None => continue,
             };
 
             let location = touch_point.location.to_physical(scale_factor);
@@ -150,7 +159,8 @@ impl TouchHandler for WinitState {
                     phase: TouchPhase::Cancelled,
                     location,
                     force: None,
-                    id: id as u64,
+                    id: touch_id,
+                    palm: false,
                 }),
                 window_id,
             );
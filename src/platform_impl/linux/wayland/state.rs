@@ -16,12 +16,13 @@ use sctk::output::{OutputHandler, OutputState};
 use sctk::registry::{ProvidesRegistryState, RegistryState};
 use sctk::seat::pointer::ThemedPointer;
 use sctk::seat::SeatState;
-use sctk::shell::xdg::window::{Window, WindowConfigure, WindowHandler};
+use sctk::shell::xdg::window::{DecorationMode, Window, WindowConfigure, WindowHandler};
 use sctk::shell::xdg::XdgShell;
 use sctk::shell::WaylandSurface;
 use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 
+use crate::event::WindowEvent;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::platform_impl::wayland::seat::{
@@ -216,8 +217,10 @@ impl WinitState {
             };
 
             // Update the scale factor right away.
-            window.lock().unwrap().set_scale_factor(scale_factor);
-            self.window_compositor_updates[pos].scale_changed = true;
+            let mut window = window.lock().unwrap();
+            let old_scale_factor = window.scale_factor();
+            window.set_scale_factor(scale_factor);
+            self.window_compositor_updates[pos].scale_changed(old_scale_factor);
         } else if let Some(pointer) = self.pointer_surfaces.get(&surface.id()) {
             // Get the window, where the pointer resides right now.
             let focused_window = match pointer.pointer().winit_data().focused_window() {
@@ -280,15 +283,29 @@ impl WindowHandler for WinitState {
             self.window_compositor_updates.len() - 1
         };
 
-        // Populate the configure to the window.
-        self.window_compositor_updates[pos].resized |= self
+        let mut window = self
             .windows
             .get_mut()
             .get_mut(&window_id)
             .expect("got configure for dead window.")
             .lock()
-            .unwrap()
-            .configure(configure, &self.shm, &self.subcompositor_state);
+            .unwrap();
+
+        // Tell the application who draws the decorations, the first time and when it changes.
+        let decoration_mode = configure.decoration_mode;
+        if window.decoration_mode() != Some(decoration_mode) {
+            self.window_events_sink.lock().unwrap().push_window_event(
+                WindowEvent::DecorationsChanged {
+                    server_side: decoration_mode == DecorationMode::Server,
+                },
+                window_id,
+            );
+        }
+
+        // Populate the configure to the window.
+        self.window_compositor_updates[pos].resized |=
+            window.configure(configure, &self.shm, &self.subcompositor_state);
+        drop(window);
 
         // NOTE: configure demands wl_surface::commit, however winit doesn't commit on behalf of the
         // users, since it can break a lot of things, thus it'll ask users to redraw instead.
@@ -396,8 +413,8 @@ pub struct WindowCompositorUpdate {
     /// New window size.
     pub resized: bool,
 
-    /// New scale factor.
-    pub scale_changed: bool,
+    /// The scale factor before it changed, if it did.
+    pub old_scale_factor: Option<f64>,
 
     /// Close the window.
     pub close_window: bool,
@@ -408,10 +425,16 @@ impl WindowCompositorUpdate {
         Self {
             window_id,
             resized: false,
-            scale_changed: false,
+            old_scale_factor: None,
             close_window: false,
         }
     }
+
+    /// Records a change of the scale factor from `old_scale_factor`, keeping the scale factor
+    /// from before the first change of the iteration for the event.
+    fn scale_changed(&mut self, old_scale_factor: f64) {
+        self.old_scale_factor.get_or_insert(old_scale_factor);
+    }
 }
 
 sctk::delegate_subcompositor!(WinitState);
@@ -421,3 +444,18 @@ sctk::delegate_registry!(WinitState);
 sctk::delegate_shm!(WinitState);
 sctk::delegate_xdg_shell!(WinitState);
 sctk::delegate_xdg_window!(WinitState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_changes_report_the_scale_factor_before_the_first() {
+        let mut update = WindowCompositorUpdate::new(WindowId(1));
+        assert_eq!(update.old_scale_factor, None);
+
+        update.scale_changed(1.0);
+        update.scale_changed(2.0);
+        assert_eq!(update.old_scale_factor, Some(1.0));
+    }
+}
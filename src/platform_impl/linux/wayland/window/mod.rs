@@ -14,17 +14,21 @@ use sctk::shell::xdg::window::Window as SctkWindow;
 use sctk::shell::xdg::window::WindowDecorations;
 use sctk::shell::WaylandSurface;
 
-use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{
+    LogicalPosition, LogicalSize, PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size,
+};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::{Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::platform::wayland::DecorationMode;
 use crate::platform_impl::{
     Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformIcon,
-    PlatformSpecificWindowBuilderAttributes as PlatformAttributes,
+    PlatformSpecificWindowBuilderAttributes as PlatformAttributes, SizeConstraints,
 };
 use crate::window::{
-    CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowLevel,
+    CursorGrabMode, CursorIcon, DropEffect, ImageBuffer, ImeHints, ImePurpose, Insets,
+    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+    WindowShape,
 };
 
 use super::event_loop::sink::EventSink;
@@ -96,17 +100,24 @@ impl Window {
             .map(|activation_state| activation_state.global().clone());
         let display = event_loop_window_target.connection.display();
 
-        let size: Size = attributes
+        // Like the min and max sizes below, the initial size is snapped to the resize increments
+        // with the default `1.` scaling.
+        let size = attributes
             .inner_size
             .unwrap_or(LogicalSize::new(800., 600.).into());
+        let size: LogicalSize<u32> =
+            SizeConstraints::from_attributes(&attributes, None, 1.).clamp(size.to_logical(1.));
+        let size: Size = size.into();
 
         // We prefer server side decorations, however to not have decorations we ask for client
         // side decorations instead.
-        let default_decorations = if attributes.decorations {
-            WindowDecorations::RequestServer
-        } else {
-            WindowDecorations::RequestClient
-        };
+        let decoration_mode = platform_attributes.decoration_mode;
+        let default_decorations =
+            if attributes.decorations && decoration_mode != DecorationMode::ClientSide {
+                WindowDecorations::RequestServer
+            } else {
+                WindowDecorations::RequestClient
+            };
 
         let window =
             state
@@ -120,6 +131,7 @@ impl Window {
             size,
             window.clone(),
             attributes.preferred_theme,
+            decoration_mode,
         );
 
         // Set transparency hint.
@@ -156,8 +168,7 @@ impl Window {
             Some(Fullscreen::Borderless(monitor)) => {
                 let output = monitor.and_then(|monitor| match monitor {
                     PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                    #[cfg(x11_platform)]
-                    PlatformMonitorHandle::X(_) => None,
+                    _ => None,
                 });
 
                 window.set_fullscreen(output.as_ref())
@@ -188,6 +199,7 @@ impl Window {
         let window_requests = WindowRequests {
             redraw_requested: AtomicBool::new(true),
             closed: AtomicBool::new(false),
+            visible: AtomicBool::new(attributes.visible),
         };
         let window_requests = Arc::new(window_requests);
         state
@@ -251,8 +263,16 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_visible(&self, _visible: bool) {
-        // Not possible on Wayland.
+    pub fn set_visible(&self, visible: bool) {
+        // Hiding a mapped window is not possible on Wayland, however we can delay the mapping of
+        // a window created with `with_visible(false)`, since the surface is only mapped by the
+        // first buffer commit, which the user does in response to `RedrawRequested`.
+        if visible && !self.window_requests.visible.swap(true, Ordering::Relaxed) {
+            self.window_requests
+                .redraw_requested
+                .store(true, Ordering::Relaxed);
+            self.event_loop_awakener.ping();
+        }
     }
 
     #[inline]
@@ -260,6 +280,9 @@ impl Window {
         None
     }
 
+    #[inline]
+    pub fn set_enabled(&self, _enabled: bool) {}
+
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         Err(NotSupportedError::new())
@@ -310,6 +333,21 @@ impl Window {
         super::logical_to_physical_rounded(window_state.outer_size(), scale_factor)
     }
 
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, _visible: bool) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        self.window_state.lock().unwrap().frame_insets()
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let mut window_state = self.window_state.lock().unwrap();
@@ -438,6 +476,9 @@ impl Window {
     #[inline]
     pub(crate) fn set_window_icon(&self, _window_icon: Option<PlatformIcon>) {}
 
+    #[inline]
+    pub fn set_drop_feedback(&self, _effect: DropEffect) {}
+
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
         // You can't unminimize the window on Wayland.
@@ -497,8 +538,7 @@ impl Window {
             Some(Fullscreen::Borderless(monitor)) => {
                 let output = monitor.and_then(|monitor| match monitor {
                     PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                    #[cfg(x11_platform)]
-                    PlatformMonitorHandle::X(_) => None,
+                    _ => None,
                 });
 
                 self.window.set_fullscreen(output.as_ref())
@@ -512,6 +552,14 @@ impl Window {
         self.window_state.lock().unwrap().set_cursor(cursor);
     }
 
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_cursor_regions(regions);
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         self.window_state
@@ -567,6 +615,19 @@ impl Window {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
     }
 
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .confine_cursor_to_rect(rect)
+    }
+
+    /// The pointer constraints are persistent, so the compositor already deactivates them while
+    /// the window is unfocused.
+    #[inline]
+    pub fn set_auto_grab(&self, _auto_grab: bool) {}
+
     #[inline]
     pub fn set_cursor_position(&self, position: Position) -> Result<(), ExternalError> {
         let scale_factor = self.scale_factor();
@@ -602,14 +663,36 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
-        let window_state = self.window_state.lock().unwrap();
-        if window_state.ime_allowed() {
-            let scale_factor = window_state.scale_factor();
-            let position = position.to_logical(scale_factor);
-            let size = size.to_logical(scale_factor);
-            window_state.set_ime_cursor_area(position, size);
+    pub fn set_shape(&self, shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        let surface = self.window.wl_surface();
+
+        let shape = match shape {
+            Some(shape) => shape,
+            None => {
+                surface.set_input_region(None);
+                return Ok(());
+            }
+        };
+
+        let region = Region::new(&*self.compositor).map_err(|_| {
+            ExternalError::Os(os_error!(OsError::Misc("failed to set input region.")))
+        })?;
+        // The input region is in surface local coordinates.
+        let scale_factor = self.scale_factor();
+        for (position, size) in shape.rectangles() {
+            let position: LogicalPosition<i32> = position.to_logical(scale_factor);
+            let size: LogicalSize<i32> = size.to_logical(scale_factor);
+            region.add(position.x, position.y, size.width, size.height);
         }
+        surface.set_input_region(Some(region.wl_region()));
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        let mut window_state = self.window_state.lock().unwrap();
+        window_state.set_ime_cursor_area(position, size);
     }
 
     #[inline]
@@ -632,7 +715,14 @@ impl Window {
     }
 
     #[inline]
-    pub fn focus_window(&self) {}
+    pub fn set_ime_hints(&self, hints: ImeHints) {
+        self.window_state.lock().unwrap().set_ime_hints(hints);
+    }
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
 
     #[inline]
     pub fn surface(&self) -> &WlSurface {
@@ -715,6 +805,11 @@ impl Window {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    #[inline]
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn title(&self) -> String {
         self.window_state.lock().unwrap().title().to_owned()
@@ -736,6 +831,9 @@ pub struct WindowRequests {
 
     /// Redraw Requested.
     pub redraw_requested: AtomicBool,
+
+    /// The window is allowed to be mapped, `RedrawRequested` is withheld until then.
+    pub visible: AtomicBool,
 }
 
 impl WindowRequests {
@@ -29,12 +29,17 @@ use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
-use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
+use crate::dpi::{
+    LogicalPosition, LogicalSize, PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size,
+};
 use crate::error::{ExternalError, NotSupportedError};
+use crate::platform::wayland::DecorationMode as RequestedDecorationMode;
 use crate::platform_impl::wayland::logical_to_physical_rounded;
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
-use crate::platform_impl::WindowId;
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme};
+use crate::platform_impl::{CursorRegions, ImeCursorArea, SizeConstraints, WindowId};
+use crate::window::{
+    CursorGrabMode, CursorIcon, ImeHints, ImePurpose, Insets, ResizeDirection, Theme,
+};
 
 use crate::platform_impl::wayland::seat::{
     PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
@@ -72,6 +77,9 @@ pub struct WindowState {
     /// Wether the cursor is visible.
     pub cursor_visible: bool,
 
+    /// The cursors shown over the regions of the window.
+    cursor_regions: CursorRegions,
+
     /// Pointer constraints to lock/confine pointer.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
@@ -104,12 +112,20 @@ pub struct WindowState {
     /// The current cursor grabbing mode.
     cursor_grab_mode: GrabState,
 
-    /// Whether the IME input is allowed for that window.
-    ime_allowed: bool,
+    /// The rectangle of the surface set with `Window::confine_cursor_to_rect`.
+    cursor_confinement: Option<PhysicalRect>,
 
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// The hints refining the IME purpose.
+    ime_hints: ImeHints,
+
+    /// Whether the IME input is allowed for that window, and the last IME cursor area in the
+    /// units it was requested in, which is also applied when the IME gets enabled or the scale
+    /// factor changes.
+    ime_cursor_area: ImeCursorArea,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
@@ -122,6 +138,9 @@ pub struct WindowState {
     /// Whether we should decorate the frame.
     decorate: bool,
 
+    /// The decoration mode requested by the application.
+    requested_decoration_mode: RequestedDecorationMode,
+
     /// Min size.
     min_inner_size: LogicalSize<u32>,
     max_inner_size: Option<LogicalSize<u32>>,
@@ -161,6 +180,7 @@ impl WindowState {
         initial_size: Size,
         window: Window,
         theme: Option<Theme>,
+        requested_decoration_mode: RequestedDecorationMode,
     ) -> Self {
         let compositor = winit_state.compositor_state.clone();
         let pointer_constraints = winit_state.pointer_constraints.clone();
@@ -180,16 +200,20 @@ impl WindowState {
             connection,
             csd_fails: false,
             cursor_grab_mode: GrabState::new(),
+            cursor_confinement: None,
             cursor_icon: CursorIcon::Default,
             cursor_visible: true,
+            cursor_regions: Default::default(),
             decorate: true,
+            requested_decoration_mode,
             fractional_scale,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
             seat_focus: Default::default(),
             has_pending_move: None,
-            ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_hints: ImeHints::empty(),
+            ime_cursor_area: ImeCursorArea::default(),
             last_configure: None,
             max_inner_size: None,
             min_inner_size: MIN_WINDOW_SIZE,
@@ -268,6 +292,7 @@ impl WindowState {
 
         if let Some(subcompositor) = subcompositor.as_ref().filter(|_| {
             configure.decoration_mode == DecorationMode::Client
+                && self.requested_decoration_mode == RequestedDecorationMode::PreferServerSide
                 && self.frame.is_none()
                 && !self.csd_fails
         }) {
@@ -282,7 +307,7 @@ impl WindowState {
                 into_sctk_adwaita_config(self.theme),
             ) {
                 Ok(mut frame) => {
-                    frame.set_title(&self.title);
+                    frame.set_title(truncate_title(&self.title));
                     frame.set_scaling_factor(self.scale_factor);
                     // Hide the frame if we were asked to not decorate.
                     frame.set_hidden(!self.decorate);
@@ -335,6 +360,13 @@ impl WindowState {
                 .unwrap_or(new_size.height);
         }
 
+        // Compositors don't have to respect the min and max sizes while the user resizes the
+        // window, so they're enforced here, and the clamped size is what the window geometry is
+        // set to. The size is mandatory when maximized, fullscreen or tiled though.
+        if stateless {
+            new_size = self.clamp_inner_size(new_size);
+        }
+
         let new_state = configure.state;
         let old_state = self
             .last_configure
@@ -532,7 +564,7 @@ impl WindowState {
     /// Whether the IME is allowed.
     #[inline]
     pub fn ime_allowed(&self) -> bool {
-        self.ime_allowed
+        self.ime_cursor_area.allowed()
     }
 
     /// Get the size of the window.
@@ -547,6 +579,14 @@ impl WindowState {
         self.last_configure.is_some()
     }
 
+    /// The decoration mode granted with the last configure.
+    #[inline]
+    pub fn decoration_mode(&self) -> Option<DecorationMode> {
+        self.last_configure
+            .as_ref()
+            .map(|configure| configure.decoration_mode)
+    }
+
     #[inline]
     pub fn is_decorated(&mut self) -> bool {
         let csd = self
@@ -571,6 +611,33 @@ impl WindowState {
             .unwrap_or(self.size)
     }
 
+    /// Get the thickness of the client-side decorations on each side of the window.
+    pub fn frame_insets(&self) -> Insets {
+        let frame = match self.frame.as_ref() {
+            Some(frame) => frame,
+            None => return Insets::default(),
+        };
+
+        // The location is the offset of the frame relative to the surface.
+        let (x, y) = frame.location();
+        let (left, top) = (
+            x.saturating_neg().max(0) as u32,
+            y.saturating_neg().max(0) as u32,
+        );
+        let (outer_width, outer_height) = frame.add_borders(self.size.width, self.size.height);
+        let right = outer_width.saturating_sub(self.size.width.saturating_add(left));
+        let bottom = outer_height.saturating_sub(self.size.height.saturating_add(top));
+
+        let scale_factor = self.scale_factor();
+        let to_physical = |inset: u32| (inset as f64 * scale_factor).round() as u32;
+        Insets {
+            top: to_physical(top),
+            bottom: to_physical(bottom),
+            left: to_physical(left),
+            right: to_physical(right),
+        }
+    }
+
     /// Register pointer on the top-level.
     pub fn pointer_entered(&mut self, added: Weak<ThemedPointer<WinitPointerData>>) {
         self.pointers.push(added);
@@ -592,6 +659,9 @@ impl WindowState {
         }
 
         self.pointers = new_pointers;
+        if self.pointers.is_empty() {
+            self.cursor_regions.cursor_left();
+        }
     }
 
     /// Refresh the decorations frame if it's present returning whether the client should redraw.
@@ -608,7 +678,7 @@ impl WindowState {
     /// Reload the cursor style on the given window.
     pub fn reload_cursor_style(&mut self) {
         if self.cursor_visible {
-            self.set_cursor(self.cursor_icon);
+            self.apply_cursor();
         } else {
             self.set_cursor_visible(self.cursor_visible);
         }
@@ -636,12 +706,30 @@ impl WindowState {
             .map(Self::is_stateless)
             .unwrap_or(true)
         {
-            self.resize(inner_size.to_logical(self.scale_factor()))
+            let inner_size = self.clamp_inner_size(inner_size.to_logical(self.scale_factor()));
+            self.resize(inner_size)
         }
 
         logical_to_physical_rounded(self.inner_size(), self.scale_factor())
     }
 
+    /// Clamp an inner size to the minimum and maximum sizes, which include the frame borders.
+    fn clamp_inner_size(&self, size: LogicalSize<u32>) -> LogicalSize<u32> {
+        let borders = self
+            .frame
+            .as_ref()
+            .map(|frame| {
+                let (width, height) = frame.add_borders(size.width, size.height);
+                (
+                    width.saturating_sub(size.width),
+                    height.saturating_sub(size.height),
+                )
+            })
+            .unwrap_or((0, 0));
+
+        clamp_inner_size(size, self.min_inner_size, self.max_inner_size, borders)
+    }
+
     /// Resize the window to the new inner size.
     fn resize(&mut self, inner_size: LogicalSize<u32>) {
         self.size = inner_size;
@@ -697,12 +785,33 @@ impl WindowState {
     ///
     /// Providing `None` will hide the cursor.
     pub fn set_cursor(&mut self, cursor_icon: CursorIcon) {
-        self.cursor_icon = cursor_icon;
+        let previous = std::mem::replace(&mut self.cursor_icon, cursor_icon);
+        if self.cursor_regions.cursor_set(previous, cursor_icon) {
+            self.apply_cursor();
+        }
+    }
+
+    /// Set the cursors shown over the regions of the window.
+    pub fn set_cursor_regions(&mut self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        if self.cursor_regions.set_regions(regions) {
+            self.apply_cursor();
+        }
+    }
+
+    /// Show the cursor of the region under the pointer.
+    pub fn pointer_moved(&mut self, position: PhysicalPosition<f64>) {
+        if self.cursor_regions.cursor_moved(position) {
+            self.apply_cursor();
+        }
+    }
 
+    /// Show the cursor of the region under the pointer, or the selected one.
+    fn apply_cursor(&self) {
         if !self.cursor_visible {
             return;
         }
 
+        let cursor_icon = self.cursor_regions.cursor(self.cursor_icon);
         self.apply_on_poiner(|pointer, _| {
             if pointer.set_cursor(&self.connection, cursor_icon).is_err() {
                 warn!("Failed to set cursor to {:?}", cursor_icon);
@@ -768,6 +877,16 @@ impl WindowState {
         Ok(())
     }
 
+    /// Confine the pointer to a rectangle of the surface.
+    pub fn confine_cursor_to_rect(
+        &mut self,
+        rect: Option<PhysicalRect>,
+    ) -> Result<(), ExternalError> {
+        self.cursor_confinement = rect;
+        let mode = self.cursor_grab_mode.user_grab_mode;
+        self.set_cursor_grab_inner(mode)
+    }
+
     /// Reload the hints for minimum and maximum sizes.
     pub fn reload_min_max_hints(&mut self) {
         self.set_min_inner_size(Some(self.min_inner_size));
@@ -776,6 +895,12 @@ impl WindowState {
 
     /// Set the grabbing state on the surface.
     fn set_cursor_grab_inner(&mut self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        // Without a grab mode, the pointer is still confined to the rectangle, if any.
+        let mode = match (mode, self.cursor_confinement) {
+            (CursorGrabMode::None, Some(_)) => CursorGrabMode::Confined,
+            (mode, _) => mode,
+        };
+
         let pointer_constraints = match self.pointer_constraints.as_ref() {
             Some(pointer_constraints) => pointer_constraints,
             None if mode == CursorGrabMode::None => return Ok(()),
@@ -795,6 +920,22 @@ impl WindowState {
             }
         }
 
+        // The region is in surface local coordinates.
+        let region = match self.cursor_confinement {
+            Some(rect) if mode == CursorGrabMode::Confined => {
+                let region = Region::new(&*self.compositor).map_err(|_| {
+                    ExternalError::Os(os_error!(crate::platform_impl::OsError::Misc(
+                        "failed to create confine region."
+                    )))
+                })?;
+                let position: LogicalPosition<i32> = rect.position.to_logical(self.scale_factor);
+                let size: LogicalSize<i32> = rect.size.to_logical(self.scale_factor);
+                region.add(position.x, position.y, size.width, size.height);
+                Some(region)
+            }
+            _ => None,
+        };
+
         let surface = self.window.wl_surface();
         match mode {
             CursorGrabMode::Locked => self.apply_on_poiner(|pointer, data| {
@@ -803,7 +944,14 @@ impl WindowState {
             }),
             CursorGrabMode::Confined => self.apply_on_poiner(|pointer, data| {
                 let pointer = pointer.pointer();
-                data.confine_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
+                let region = region.as_ref().map(Region::wl_region);
+                data.confine_pointer(
+                    pointer_constraints,
+                    surface,
+                    pointer,
+                    region,
+                    &self.queue_handle,
+                )
             }),
             CursorGrabMode::None => {
                 // Current lock/confine was already removed.
@@ -849,7 +997,7 @@ impl WindowState {
         self.cursor_visible = cursor_visible;
 
         if self.cursor_visible {
-            self.set_cursor(self.cursor_icon);
+            self.apply_cursor();
         } else {
             for pointer in self.pointers.iter().filter_map(|pointer| pointer.upgrade()) {
                 let latest_enter_serial = pointer.pointer().winit_data().latest_enter_serial();
@@ -880,9 +1028,12 @@ impl WindowState {
                 self.window
                     .request_decoration_mode(Some(DecorationMode::Client))
             }
-            _ if self.decorate => self
-                .window
-                .request_decoration_mode(Some(DecorationMode::Server)),
+            _ if self.decorate
+                && self.requested_decoration_mode != RequestedDecorationMode::ClientSide =>
+            {
+                self.window
+                    .request_decoration_mode(Some(DecorationMode::Server))
+            }
             _ => (),
         }
 
@@ -907,14 +1058,14 @@ impl WindowState {
 
     /// Returns `true` if the requested state was applied.
     pub fn set_ime_allowed(&mut self, allowed: bool) -> bool {
-        self.ime_allowed = allowed;
+        // Enabling the text inputs sends the area.
+        self.ime_cursor_area.set_allowed(allowed);
 
         let mut applied = false;
         for text_input in &self.text_inputs {
             applied = true;
             if allowed {
-                text_input.enable();
-                text_input.set_content_type_by_purpose(self.ime_purpose);
+                self.enable_text_input(text_input);
             } else {
                 text_input.disable();
             }
@@ -924,13 +1075,47 @@ impl WindowState {
         applied
     }
 
+    /// Enable the IME on `text_input`, sending the IME state as enabling resets it.
+    ///
+    /// The caller has to commit the `text_input`.
+    pub fn enable_text_input(&self, text_input: &ZwpTextInputV3) {
+        text_input.enable();
+        text_input.set_content_type_for(self.ime_purpose, self.ime_hints);
+        if let Some((x, y, width, height)) = self.ime_cursor_rectangle() {
+            text_input.set_cursor_rectangle(x, y, width, height);
+        }
+    }
+
+    /// The IME cursor area in surface coordinates, at the current scale factor.
+    fn ime_cursor_rectangle(&self) -> Option<(i32, i32, i32, i32)> {
+        let (position, size) = self.ime_cursor_area.applied()?;
+        let position: LogicalPosition<u32> = position.to_logical(self.scale_factor);
+        let size: LogicalSize<u32> = size.to_logical(self.scale_factor);
+        Some((
+            position.x as i32,
+            position.y as i32,
+            size.width as i32,
+            size.height as i32,
+        ))
+    }
+
     /// Set the IME position.
-    pub fn set_ime_cursor_area(&self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+    pub fn set_ime_cursor_area(&mut self, position: Position, size: Size) {
+        if self.ime_cursor_area.set_area(position, size).is_some() {
+            self.send_ime_cursor_area();
+        }
+    }
+
+    /// Sends the IME cursor area to the enabled text inputs.
+    fn send_ime_cursor_area(&self) {
+        let (x, y, width, height) = match self.ime_cursor_rectangle() {
+            Some(rectangle) => rectangle,
+            None => return,
+        };
+
         // FIXME: This won't fly unless user will have a way to request IME window per seat, since
         // the ime windows will be overlapping, but winit doesn't expose API to specify for
         // which seat we're setting IME position.
-        let (x, y) = (position.x as i32, position.y as i32);
-        let (width, height) = (size.width as i32, size.height as i32);
         for text_input in self.text_inputs.iter() {
             text_input.set_cursor_rectangle(x, y, width, height);
             text_input.commit();
@@ -942,7 +1127,17 @@ impl WindowState {
         self.ime_purpose = purpose;
 
         for text_input in &self.text_inputs {
-            text_input.set_content_type_by_purpose(purpose);
+            text_input.set_content_type_for(purpose, self.ime_hints);
+            text_input.commit();
+        }
+    }
+
+    /// Set the IME hints.
+    pub fn set_ime_hints(&mut self, hints: ImeHints) {
+        self.ime_hints = hints;
+
+        for text_input in &self.text_inputs {
+            text_input.set_content_type_for(self.ime_purpose, hints);
             text_input.commit();
         }
     }
@@ -965,6 +1160,20 @@ impl WindowState {
         if let Some(frame) = self.frame.as_mut() {
             frame.set_scaling_factor(scale_factor);
         }
+
+        // A physical cursor area maps to another logical one at the new scale.
+        if matches!(
+            self.ime_cursor_area.applied(),
+            Some((Position::Physical(_), _) | (_, Size::Physical(_)))
+        ) {
+            self.send_ime_cursor_area();
+        }
+
+        // The confinement rectangle maps to another region of the surface too.
+        if self.cursor_confinement.is_some() {
+            let mode = self.cursor_grab_mode.user_grab_mode;
+            let _ = self.set_cursor_grab_inner(mode);
+        }
     }
 
     /// Make window background blurred
@@ -989,24 +1198,16 @@ impl WindowState {
 
     /// Set the window title to a new value.
     ///
-    /// This will autmatically truncate the title to something meaningfull.
-    pub fn set_title(&mut self, mut title: String) {
-        // Truncate the title to at most 1024 bytes, so that it does not blow up the protocol
-        // messages
-        if title.len() > 1024 {
-            let mut new_len = 1024;
-            while !title.is_char_boundary(new_len) {
-                new_len -= 1;
-            }
-            title.truncate(new_len);
-        }
+    /// The compositor only receives a truncated title, but the full title is kept.
+    pub fn set_title(&mut self, title: String) {
+        let truncated = truncate_title(&title);
 
         // Update the CSD title.
         if let Some(frame) = self.frame.as_mut() {
-            frame.set_title(&title);
+            frame.set_title(truncated);
         }
 
-        self.window.set_title(&title);
+        self.window.set_title(truncated);
         self.title = title;
     }
 
@@ -1114,3 +1315,89 @@ fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
         None => sctk_adwaita::FrameConfig::auto(),
     }
 }
+
+/// Truncate the title to at most 1024 bytes, so that it does not blow up the protocol messages.
+///
+/// The title is only ever cut on a character boundary.
+fn truncate_title(title: &str) -> &str {
+    const MAX_LEN: usize = 1024;
+
+    if title.len() <= MAX_LEN {
+        return title;
+    }
+
+    let mut len = MAX_LEN;
+    while !title.is_char_boundary(len) {
+        len -= 1;
+    }
+    &title[..len]
+}
+
+/// Clamp an inner size to the `min` and `max` outer sizes, given the size of the `borders` added
+/// by the frame.
+fn clamp_inner_size(
+    size: LogicalSize<u32>,
+    min: LogicalSize<u32>,
+    max: Option<LogicalSize<u32>>,
+    (border_width, border_height): (u32, u32),
+) -> LogicalSize<u32> {
+    let without_borders = |size: LogicalSize<u32>| {
+        (
+            size.width.saturating_sub(border_width),
+            size.height.saturating_sub(border_height),
+        )
+    };
+
+    SizeConstraints {
+        min: Some(without_borders(min)),
+        max: max.map(without_borders),
+        ..Default::default()
+    }
+    .clamp(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp_inner_size, truncate_title};
+    use crate::dpi::LogicalSize;
+
+    #[test]
+    fn undersized_configure_is_clamped_to_the_min_size() {
+        // The min and max sizes are stored with the borders of the frame.
+        let borders = (2, 37);
+        let min = LogicalSize::new(202, 137);
+        let max = Some(LogicalSize::new(802, 637));
+
+        // The compositor let the user drag the window smaller than its min size.
+        assert_eq!(
+            clamp_inner_size(LogicalSize::new(50, 20), min, max, borders),
+            LogicalSize::new(200, 100)
+        );
+        assert_eq!(
+            clamp_inner_size(LogicalSize::new(1000, 300), min, max, borders),
+            LogicalSize::new(800, 300)
+        );
+        // Without a frame, the sizes are only the inner sizes.
+        assert_eq!(
+            clamp_inner_size(LogicalSize::new(50, 20), min, None, (0, 0)),
+            LogicalSize::new(202, 137)
+        );
+    }
+
+    #[test]
+    fn long_emoji_title_is_truncated_on_a_char_boundary() {
+        let title = "\u{1F600}".repeat(500);
+        let truncated = truncate_title(&title);
+
+        assert_eq!(truncated.len(), 1024);
+        assert_eq!(truncated.chars().count(), 256);
+        assert!(title.starts_with(truncated));
+
+        let title = format!("a{}", "\u{1F600}".repeat(500));
+        let truncated = truncate_title(&title);
+        assert_eq!(truncated.len(), 1021);
+        assert!(title.starts_with(truncated));
+
+        assert_eq!(truncate_title("short"), "short");
+    }
+}
@@ -55,6 +55,9 @@ atom_manager! {
     _NET_WM_STATE_HIDDEN,
     _NET_WM_STATE_MAXIMIZED_HORZ,
     _NET_WM_STATE_MAXIMIZED_VERT,
+    _NET_WM_STATE_STICKY,
+    _NET_WM_SYNC_REQUEST,
+    _NET_WM_SYNC_REQUEST_COUNTER,
     _NET_WM_WINDOW_TYPE,
 
     // Activation atoms.
@@ -85,21 +88,28 @@ atom_manager! {
     XdndDrop,
     XdndPosition,
     XdndStatus,
-    XdndActionPrivate,
+    XdndActionCopy,
+    XdndActionMove,
+    XdndActionLink,
     XdndSelection,
     XdndFinished,
     XdndTypeList,
     TextUriList: b"text/uri-list",
+    DEVICE_PRODUCT_ID: b"Device Product ID",
     None: b"None",
 
     // Miscellaneous Atoms
     _GTK_THEME_VARIANT,
+    _KDE_NET_WM_BLUR_BEHIND_REGION,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
+    _NET_CURRENT_DESKTOP,
     _NET_FRAME_EXTENTS,
+    _NET_RESTACK_WINDOW,
     _NET_SUPPORTED,
     _NET_SUPPORTING_WM_CHECK,
+    _NET_WORKAREA,
     _XEMBED,
     _XSETTINGS_SETTINGS
 }
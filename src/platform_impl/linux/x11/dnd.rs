@@ -13,13 +13,23 @@ use super::{
     atoms::{AtomName::None as DndNone, *},
     util, CookieResultExt, X11Error, XConnection,
 };
+use crate::{dpi::PhysicalPosition, window::DropEffect};
 
 #[derive(Debug, Clone, Copy)]
 pub enum DndState {
-    Accepted,
+    Accepted(DropEffect),
     Rejected,
 }
 
+impl From<DropEffect> for DndState {
+    fn from(effect: DropEffect) -> Self {
+        match effect {
+            DropEffect::None => DndState::Rejected,
+            effect => DndState::Accepted(effect),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum DndDataParseError {
     EmptyData,
@@ -48,6 +58,8 @@ pub struct Dnd {
     pub type_list: Option<Vec<xproto::Atom>>,
     // Populated by XdndPosition event handler
     pub source_window: Option<xproto::Window>,
+    // Populated by XdndPosition event handler, in window coordinates
+    pub position: Option<PhysicalPosition<f64>>,
     // Populated by SelectionNotify event handler (triggered by XdndPosition event handler)
     pub result: Option<Result<Vec<PathBuf>, DndDataParseError>>,
 }
@@ -59,6 +71,7 @@ impl Dnd {
             version: None,
             type_list: None,
             source_window: None,
+            position: None,
             result: None,
         })
     }
@@ -67,9 +80,20 @@ impl Dnd {
         self.version = None;
         self.type_list = None;
         self.source_window = None;
+        self.position = None;
         self.result = None;
     }
 
+    fn state_to_action(&self, state: DndState) -> (u32, xproto::Atom) {
+        let atoms = self.xconn.atoms();
+        match state {
+            DndState::Accepted(DropEffect::Move) => (1, atoms[XdndActionMove]),
+            DndState::Accepted(DropEffect::Link) => (1, atoms[XdndActionLink]),
+            DndState::Accepted(_) => (1, atoms[XdndActionCopy]),
+            DndState::Rejected => (0, atoms[DndNone]),
+        }
+    }
+
     pub unsafe fn send_status(
         &self,
         this_window: xproto::Window,
@@ -77,10 +101,7 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), X11Error> {
         let atoms = self.xconn.atoms();
-        let (accepted, action) = match state {
-            DndState::Accepted => (1, atoms[XdndActionPrivate]),
-            DndState::Rejected => (0, atoms[DndNone]),
-        };
+        let (accepted, action) = self.state_to_action(state);
         self.xconn
             .send_client_msg(
                 target_window,
@@ -101,10 +122,7 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), X11Error> {
         let atoms = self.xconn.atoms();
-        let (accepted, action) = match state {
-            DndState::Accepted => (1, atoms[XdndActionPrivate]),
-            DndState::Rejected => (0, atoms[DndNone]),
-        };
+        let (accepted, action) = self.state_to_action(state);
         self.xconn
             .send_client_msg(
                 target_window,
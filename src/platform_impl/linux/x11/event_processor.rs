@@ -13,6 +13,7 @@ use x11_dl::xlib::{
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
     XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
 };
+use x11rb::protocol::sync;
 use x11rb::protocol::xinput;
 use x11rb::protocol::xkb::ID as XkbId;
 use x11rb::protocol::xproto::{self, ConnectionExt as _, ModMask};
@@ -20,10 +21,10 @@ use x11rb::x11_utils::ExtensionInformation;
 use x11rb::x11_utils::Serialize;
 use xkbcommon_dl::xkb_mod_mask_t;
 
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::dpi::{PhysicalPosition, PhysicalRect, PhysicalSize};
 use crate::event::{
-    DeviceEvent, ElementState, Event, Ime, MouseScrollDelta, RawKeyEvent, Touch, TouchPhase,
-    WindowEvent,
+    AxisId, AxisKind, DeviceEvent, ElementState, Event, Ime, MouseScrollDelta, RawKeyEvent, Touch,
+    TouchPhase, WindowEvent,
 };
 use crate::event::{InnerSizeWriter, MouseButton};
 use crate::event_loop::EventLoopWindowTarget as RootELW;
@@ -33,11 +34,14 @@ use crate::platform_impl::platform::common::xkb::Context;
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventReceiver, ImeRequest};
 use crate::platform_impl::platform::x11::EventLoopWindowTarget;
 use crate::platform_impl::platform::EventLoopWindowTarget as PlatformEventLoopWindowTarget;
+use crate::platform_impl::x11::ffi::{self, XIGesturePinchEvent, XIGestureSwipeEvent};
 use crate::platform_impl::x11::util::cookie::GenericEventCookie;
 use crate::platform_impl::x11::{
     atoms::*, mkdid, mkwid, util, CookieResultExt, Device, DeviceId, DeviceInfo, Dnd, DndState,
     ImeReceiver, ScrollOrientation, UnownedWindow, WindowId,
 };
+use crate::platform_impl::TouchIds;
+use crate::window::DropEffect;
 
 /// The maximum amount of X modifiers to replay.
 pub const MAX_MOD_REPLAY_LEN: usize = 32;
@@ -63,6 +67,8 @@ pub struct EventProcessor<T: 'static> {
     // Used to detect key repeats.
     pub held_key_press: Option<u32>,
     pub first_touch: Option<u64>,
+    // Maps the XInput touch ids to the ones we report.
+    pub touch_ids: TouchIds<i32>,
     // Currently focused window belonging to this process
     pub active_window: Option<xproto::Window>,
     /// Latest modifiers we've sent for the user to trigger change in event.
@@ -70,6 +76,8 @@ pub struct EventProcessor<T: 'static> {
     pub xfiltered_modifiers: VecDeque<c_ulong>,
     pub xmodmap: util::ModifierKeymap,
     pub is_composing: bool,
+    // The scale of the ongoing pinch gesture, which XInput reports relative to its start.
+    pub pinch_scale: f64,
 }
 
 impl<T: 'static> EventProcessor<T> {
@@ -253,6 +261,48 @@ impl<T: 'static> EventProcessor<T> {
                         let xev: &XIDeviceEvent = unsafe { xev.as_event() };
                         self.xinput2_touch(xev, phase, &mut callback);
                     }
+                    ffi::XI_GesturePinchBegin
+                    | ffi::XI_GesturePinchUpdate
+                    | ffi::XI_GesturePinchEnd => {
+                        let xev: &XIGesturePinchEvent = unsafe { xev.as_event() };
+                        let phase = match evtype {
+                            ffi::XI_GesturePinchBegin => TouchPhase::Started,
+                            ffi::XI_GesturePinchUpdate => TouchPhase::Moved,
+                            _ if xev.flags & ffi::XIGesturePinchEventCancelled != 0 => {
+                                TouchPhase::Cancelled
+                            }
+                            _ => TouchPhase::Ended,
+                        };
+
+                        self.update_mods_from_xinput2_event(
+                            &xev.mods,
+                            &xev.group,
+                            false,
+                            &mut callback,
+                        );
+                        self.xinput2_pinch_gesture(xev, phase, &mut callback);
+                    }
+                    ffi::XI_GestureSwipeBegin
+                    | ffi::XI_GestureSwipeUpdate
+                    | ffi::XI_GestureSwipeEnd => {
+                        let xev: &XIGestureSwipeEvent = unsafe { xev.as_event() };
+                        let phase = match evtype {
+                            ffi::XI_GestureSwipeBegin => TouchPhase::Started,
+                            ffi::XI_GestureSwipeUpdate => TouchPhase::Moved,
+                            _ if xev.flags & ffi::XIGestureSwipeEventCancelled != 0 => {
+                                TouchPhase::Cancelled
+                            }
+                            _ => TouchPhase::Ended,
+                        };
+
+                        self.update_mods_from_xinput2_event(
+                            &xev.mods,
+                            &xev.group,
+                            false,
+                            &mut callback,
+                        );
+                        self.xinput2_swipe_gesture(xev, phase, &mut callback);
+                    }
                     xinput2::XI_RawButtonPress | xinput2::XI_RawButtonRelease => {
                         let state = match evtype {
                             xinput2::XI_RawButtonPress => ElementState::Pressed,
@@ -374,7 +424,6 @@ impl<T: 'static> EventProcessor<T> {
     pub fn window_target(window_target: &RootELW<T>) -> &EventLoopWindowTarget<T> {
         match &window_target.p {
             PlatformEventLoopWindowTarget::X(target) => target,
-            #[cfg(wayland_platform)]
             _ => unreachable!(),
         }
     }
@@ -383,7 +432,6 @@ impl<T: 'static> EventProcessor<T> {
     pub fn window_target_mut(window_target: &mut RootELW<T>) -> &mut EventLoopWindowTarget<T> {
         match &mut window_target.p {
             PlatformEventLoopWindowTarget::X(target) => target,
-            #[cfg(wayland_platform)]
             _ => unreachable!(),
         }
     }
@@ -433,6 +481,19 @@ impl<T: 'static> EventProcessor<T> {
             return;
         }
 
+        if xev.data.get_long(0) as xproto::Atom == atoms[_NET_WM_SYNC_REQUEST] {
+            // The value to set the counter to once the frame for the following
+            // `ConfigureNotify` is presented.
+            let value = sync::Int64 {
+                lo: xev.data.get_long(2) as u32,
+                hi: xev.data.get_long(3) as i32,
+            };
+            self.with_window(window, |window| {
+                window.shared_state_lock().sync_request = Some((value, false));
+            });
+            return;
+        }
+
         if xev.message_type == atoms[XdndEnter] as c_ulong {
             let source_window = xev.data.get_long(0) as xproto::Window;
             let flags = xev.data.get_long(1);
@@ -457,20 +518,20 @@ impl<T: 'static> EventProcessor<T> {
             // over our window. We emit HoveredFile in response; while the macOS backend
             // does that upon a drag entering, XDND doesn't have access to the actual drop
             // data until this event. For parity with other platforms, we only emit
-            // `HoveredFile` the first time, though if winit's API is later extended to
-            // supply position updates with `HoveredFile` or another event, implementing
-            // that here would be trivial.
+            // `HoveredFile` the first time, and `HoveredFileMoved` for every position
+            // update once the files are known.
 
             let source_window = xev.data.get_long(0) as xproto::Window;
 
-            // Equivalent to `(x << shift) | y`
-            // where `shift = mem::size_of::<c_short>() * 8`
+            // Equivalent to `(x << 16) | y`.
             // Note that coordinates are in "desktop space", not "window space"
             // (in X11 parlance, they're root window coordinates)
-            //let packed_coordinates = xev.data.get_long(2);
-            //let shift = mem::size_of::<libc::c_short>() * 8;
-            //let x = packed_coordinates >> shift;
-            //let y = packed_coordinates & !(x << shift);
+            let packed_coordinates = xev.data.get_long(2);
+            let root_x = ((packed_coordinates >> 16) & 0xffff) as i16 as i32;
+            let root_y = (packed_coordinates & 0xffff) as i16 as i32;
+            let position = self
+                .with_window(window, |window| window.inner_position_physical())
+                .map(|(x, y)| PhysicalPosition::new((root_x - x) as f64, (root_y - y) as f64));
 
             // By our own state flow, `version` should never be `None` at this point.
             let version = self.dnd.version.unwrap_or(5);
@@ -495,6 +556,15 @@ impl<T: 'static> EventProcessor<T> {
             }
 
             self.dnd.source_window = Some(source_window);
+            self.dnd.position = position;
+            if let (Some(Ok(_)), Some(position)) = (&self.dnd.result, position) {
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::HoveredFileMoved { position },
+                };
+                callback(&self.target, event);
+            }
+
             if self.dnd.result.is_none() {
                 let time = if version >= 1 {
                     xev.data.get_long(3) as xproto::Timestamp
@@ -512,31 +582,42 @@ impl<T: 'static> EventProcessor<T> {
                 }
             }
 
+            // The drop effect could be changed by the user while handling the events above.
+            let state = self
+                .with_window(window, |window| window.drop_effect())
+                .unwrap_or_default()
+                .into();
             unsafe {
                 self.dnd
-                    .send_status(window, source_window, DndState::Accepted)
+                    .send_status(window, source_window, state)
                     .expect("Failed to send `XdndStatus` message.");
             }
             return;
         }
 
         if xev.message_type == atoms[XdndDrop] as c_ulong {
-            let (source_window, state) = if let Some(source_window) = self.dnd.source_window {
-                if let Some(Ok(ref path_list)) = self.dnd.result {
-                    for path in path_list {
-                        let event = Event::WindowEvent {
-                            window_id,
-                            event: WindowEvent::DroppedFile(path.clone()),
-                        };
-                        callback(&self.target, event);
+            let effect = self
+                .with_window(window, |window| window.drop_effect())
+                .unwrap_or_default();
+            let (source_window, state) = match self.dnd.source_window {
+                Some(source_window) if effect != DropEffect::None => {
+                    if let Some(Ok(ref path_list)) = self.dnd.result {
+                        for path in path_list {
+                            let event = Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::DroppedFile(path.clone()),
+                            };
+                            callback(&self.target, event);
+                        }
                     }
+                    (source_window, DndState::Accepted(effect))
+                }
+                _ => {
+                    // `source_window` won't be part of our DND state if we already rejected the
+                    // drop in our `XdndPosition` handler.
+                    let source_window = xev.data.get_long(0) as xproto::Window;
+                    (source_window, DndState::Rejected)
                 }
-                (source_window, DndState::Accepted)
-            } else {
-                // `source_window` won't be part of our DND state if we already rejected the drop in our
-                // `XdndPosition` handler.
-                let source_window = xev.data.get_long(0) as xproto::Window;
-                (source_window, DndState::Rejected)
             };
 
             unsafe {
@@ -588,6 +669,14 @@ impl<T: 'static> EventProcessor<T> {
                     };
                     callback(&self.target, event);
                 }
+
+                if let Some(position) = self.dnd.position {
+                    let event = Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::HoveredFileMoved { position },
+                    };
+                    callback(&self.target, event);
+                }
             }
             self.dnd.result = Some(parse_result);
         }
@@ -623,6 +712,11 @@ impl<T: 'static> EventProcessor<T> {
         let (mut resized, moved) = {
             let mut shared_state_lock = window.shared_state_lock();
 
+            // The frame drawn from now on is the one the pending sync request waits for.
+            if let Some((_, configured)) = shared_state_lock.sync_request.as_mut() {
+                *configured = true;
+            }
+
             let resized = util::maybe_change(&mut shared_state_lock.size, new_inner_size);
             let moved = if is_synthetic {
                 util::maybe_change(&mut shared_state_lock.inner_position, new_inner_position)
@@ -672,7 +766,7 @@ impl<T: 'static> EventProcessor<T> {
                     &self.target,
                     Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::Moved(outer.into()),
+                        event: WindowEvent::Moved(wt.xconn.screen_to_desktop(outer).into()),
                     },
                 );
             }
@@ -689,10 +783,20 @@ impl<T: 'static> EventProcessor<T> {
 
             let last_scale_factor = shared_state_lock.last_monitor.scale_factor;
             let new_scale_factor = {
-                let window_rect = util::AaRect::new(new_outer_position, new_inner_size);
+                // Use the outer geometry, like `Window::current_monitor`.
+                let new_outer_size = shared_state_lock
+                    .frame_extents
+                    .as_ref()
+                    .map_or(new_inner_size, |extents| {
+                        extents.inner_size_to_outer(new_inner_size.0, new_inner_size.1)
+                    });
+                let window_rect = util::AaRect::new(new_outer_position, new_outer_size);
                 let monitor = wt
                     .xconn
-                    .get_monitor_for_window(Some(window_rect))
+                    .get_monitor_for_window(
+                        Some(window_rect),
+                        Some(&shared_state_lock.last_monitor),
+                    )
                     .expect("Failed to find monitor for window");
 
                 if monitor.is_dummy() {
@@ -724,6 +828,7 @@ impl<T: 'static> EventProcessor<T> {
                     Event::WindowEvent {
                         window_id,
                         event: WindowEvent::ScaleFactorChanged {
+                            old_scale_factor: last_scale_factor,
                             scale_factor: new_scale_factor,
                             inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&inner_size)),
                         },
@@ -774,6 +879,11 @@ impl<T: 'static> EventProcessor<T> {
             let _ = window.set_cursor_hittest(true);
         }
 
+        // Keep a confined cursor within the new client area.
+        if resized || moved {
+            window.refresh_cursor_confinement();
+        }
+
         if resized {
             callback(
                 &self.target,
@@ -866,6 +976,25 @@ impl<T: 'static> EventProcessor<T> {
         {
             self.process_dpi_change(&mut callback);
         }
+
+        if atom == atoms[WM_STATE] || atom == atoms[_NET_WM_STATE] {
+            let xwindow = xev.window as xproto::Window;
+            let minimized = self
+                .with_window(xwindow, |window| {
+                    if atom == atoms[_NET_WM_STATE] {
+                        window.update_maximized();
+                    }
+                    window.update_minimized()
+                })
+                .flatten();
+            if let Some(minimized) = minimized {
+                let event = Event::WindowEvent {
+                    window_id: mkwid(xwindow),
+                    event: WindowEvent::Minimized(minimized),
+                };
+                callback(&self.target, event);
+            }
+        }
     }
 
     fn visibility_notify<F>(&self, xev: &XVisibilityEvent, mut callback: F)
@@ -889,19 +1018,23 @@ impl<T: 'static> EventProcessor<T> {
     where
         F: FnMut(&RootELW<T>, Event<T>),
     {
-        // Multiple Expose events may be received for subareas of a window.
-        // We issue `RedrawRequested` only for the last event of such a series.
-        if xev.count == 0 {
-            let window = xev.window as xproto::Window;
-            let window_id = mkwid(window);
+        // Multiple Expose events may be received for subareas of a window. The event loop merges
+        // them, and any redraw requests, into a single `RedrawRequested`.
+        let window = xev.window as xproto::Window;
+        let window_id = mkwid(window);
 
-            let event = Event::WindowEvent {
-                window_id,
-                event: WindowEvent::RedrawRequested,
-            };
+        let rect = PhysicalRect::new(
+            PhysicalPosition::new(xev.x, xev.y),
+            PhysicalSize::new(xev.width as u32, xev.height as u32),
+        );
+        let event = Event::WindowEvent {
+            window_id,
+            event: WindowEvent::RedrawRequested {
+                dirty_rects: Some(vec![rect]),
+            },
+        };
 
-            callback(&self.target, event);
-        }
+        callback(&self.target, event);
     }
 
     fn xinput_key_input<F>(&mut self, xev: &mut XKeyEvent, state: ElementState, mut callback: F)
@@ -1154,9 +1287,27 @@ impl<T: 'static> EventProcessor<T> {
 
         let cursor_moved = self.with_window(window, |window| {
             let mut shared_state_lock = window.shared_state_lock();
-            util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
+            // The first motion onto the exact position we warped the cursor to was caused by the
+            // warp, so we don't report it. A warp to where the cursor already is causes no motion,
+            // so the next one, wherever it lands, ends the wait for it.
+            let warped = shared_state_lock
+                .expected_cursor_warp
+                .take()
+                .map_or(false, |(x, y)| new_cursor_pos == (x as f64, y as f64));
+            let changed = util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos);
+            drop(shared_state_lock);
+            if changed {
+                window.update_hovered_cursor(new_cursor_pos.into());
+            }
+            (changed, warped)
         });
 
+        let cursor_moved = match cursor_moved {
+            Some((_, true)) => return,
+            Some((changed, false)) => Some(changed),
+            None => None,
+        };
+
         if cursor_moved == Some(true) {
             let position = PhysicalPosition::new(event.event_x, event.event_y);
 
@@ -1212,10 +1363,16 @@ impl<T: 'static> EventProcessor<T> {
                     phase: TouchPhase::Moved,
                 }
             } else {
+                let axis = physical_device
+                    .axes
+                    .iter()
+                    .find(|axis| axis.id == i as AxisId);
                 WindowEvent::AxisMotion {
                     device_id,
-                    axis: i as u32,
-                    value: unsafe { *value },
+                    axis: i as AxisId,
+                    kind: axis.map_or(AxisKind::Absolute, |axis| axis.kind),
+                    value: x,
+                    normalized: axis.and_then(|axis| axis.normalize(x)),
                 }
             };
 
@@ -1292,7 +1449,10 @@ impl<T: 'static> EventProcessor<T> {
 
         // Leave, FocusIn, and FocusOut can be received by a window that's already
         // been destroyed, which the user presumably doesn't want to deal with.
-        if self.window_exists(window) {
+        if self
+            .with_window(window, |window| window.clear_hovered_cursor())
+            .is_some()
+        {
             let event = Event::WindowEvent {
                 window_id: mkwid(window),
                 event: WindowEvent::CursorLeft {
@@ -1332,6 +1492,7 @@ impl<T: 'static> EventProcessor<T> {
 
         if let Some(window) = self.with_window(window, Arc::clone) {
             window.shared_state_lock().has_focus = true;
+            window.update_auto_grab(true);
         }
 
         let event = Event::WindowEvent {
@@ -1417,6 +1578,7 @@ impl<T: 'static> EventProcessor<T> {
 
             if let Some(window) = self.with_window(window, Arc::clone) {
                 window.shared_state_lock().has_focus = false;
+                window.update_auto_grab(false);
             }
 
             let event = Event::WindowEvent {
@@ -1439,7 +1601,7 @@ impl<T: 'static> EventProcessor<T> {
         let window = xev.event as xproto::Window;
         if self.window_exists(window) {
             let window_id = mkwid(window);
-            let id = xev.detail as u64;
+            let id = self.touch_ids.get(xev.detail, phase);
             let location = PhysicalPosition::new(xev.event_x, xev.event_y);
 
             // Mouse cursor position changes when touch events are received.
@@ -1463,12 +1625,83 @@ impl<T: 'static> EventProcessor<T> {
                     location,
                     force: None, // TODO
                     id,
+                    palm: false,
                 }),
             };
             callback(&self.target, event)
         }
     }
 
+    fn xinput2_pinch_gesture<F>(
+        &mut self,
+        xev: &XIGesturePinchEvent,
+        phase: TouchPhase,
+        mut callback: F,
+    ) where
+        F: FnMut(&RootELW<T>, Event<T>),
+    {
+        let wt = Self::window_target(&self.target);
+
+        // Set the timestamp.
+        wt.xconn.set_timestamp(xev.time as xproto::Timestamp);
+
+        let window = xev.event as xproto::Window;
+        if self.window_exists(window) {
+            let previous_scale = match phase {
+                TouchPhase::Started => xev.scale,
+                _ => self.pinch_scale,
+            };
+            self.pinch_scale = xev.scale;
+            let event = Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::TouchpadMagnify {
+                    device_id: mkdid(xev.deviceid as xinput::DeviceId),
+                    delta: xev.scale - previous_scale,
+                    phase,
+                },
+            };
+            callback(&self.target, event);
+
+            // The angle is in degrees, clockwise.
+            let event = Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::RotationGesture {
+                    device_id: mkdid(xev.deviceid as xinput::DeviceId),
+                    delta_radians: -(xev.delta_angle as f32).to_radians(),
+                    phase,
+                },
+            };
+            callback(&self.target, event);
+        }
+    }
+
+    fn xinput2_swipe_gesture<F>(
+        &self,
+        xev: &XIGestureSwipeEvent,
+        phase: TouchPhase,
+        mut callback: F,
+    ) where
+        F: FnMut(&RootELW<T>, Event<T>),
+    {
+        let wt = Self::window_target(&self.target);
+
+        // Set the timestamp.
+        wt.xconn.set_timestamp(xev.time as xproto::Timestamp);
+
+        let window = xev.event as xproto::Window;
+        if self.window_exists(window) {
+            let event = Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::PanGesture {
+                    device_id: mkdid(xev.deviceid as xinput::DeviceId),
+                    delta: PhysicalPosition::new(xev.delta_x, xev.delta_y),
+                    phase,
+                },
+            };
+            callback(&self.target, event);
+        }
+    }
+
     fn xinput2_raw_button_input<F>(&self, xev: &XIRawEvent, state: ElementState, mut callback: F)
     where
         F: FnMut(&RootELW<T>, Event<T>),
@@ -1593,11 +1826,12 @@ impl<T: 'static> EventProcessor<T> {
         for info in infos {
             if 0 != info.flags & (xinput2::XISlaveAdded | xinput2::XIMasterAdded) {
                 self.init_device(info.deviceid as xinput::DeviceId);
+                let device_info = wt.device_info(DeviceId(info.deviceid as xinput::DeviceId));
                 callback(
                     &self.target,
                     Event::DeviceEvent {
                         device_id: mkdid(info.deviceid as xinput::DeviceId),
-                        event: DeviceEvent::Added,
+                        event: DeviceEvent::Added { info: device_info },
                     },
                 );
             } else if 0 != info.flags & (xinput2::XISlaveRemoved | xinput2::XIMasterRemoved) {
@@ -1 +1,86 @@
 pub use x11_dl::{error::OpenError, xcursor::*, xinput2::*, xlib::*, xlib_xcb::*};
+
+// The touchpad gesture events of XInput 2.4, which `x11_dl` doesn't define yet.
+#[allow(non_upper_case_globals)]
+mod gestures {
+    use std::os::raw::{c_double, c_int, c_ulong};
+
+    use x11_dl::xinput2::{XIGroupState, XIModifierState};
+    use x11_dl::xlib::{Display, Time, Window};
+
+    pub const XI_GesturePinchBegin: c_int = 27;
+    pub const XI_GesturePinchUpdate: c_int = 28;
+    pub const XI_GesturePinchEnd: c_int = 29;
+    pub const XI_GestureSwipeBegin: c_int = 30;
+    pub const XI_GestureSwipeUpdate: c_int = 31;
+    pub const XI_GestureSwipeEnd: c_int = 32;
+
+    pub const XIGesturePinchEventCancelled: c_int = 1 << 0;
+    pub const XIGestureSwipeEventCancelled: c_int = 1 << 0;
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    pub struct XIGesturePinchEvent {
+        pub _type: c_int,
+        pub serial: c_ulong,
+        pub send_event: c_int,
+        pub display: *mut Display,
+        pub extension: c_int,
+        pub evtype: c_int,
+        pub time: Time,
+        pub deviceid: c_int,
+        pub sourceid: c_int,
+        /// The number of touches.
+        pub detail: c_int,
+        pub root: Window,
+        pub event: Window,
+        pub child: Window,
+        pub root_x: c_double,
+        pub root_y: c_double,
+        pub event_x: c_double,
+        pub event_y: c_double,
+        pub delta_x: c_double,
+        pub delta_y: c_double,
+        pub delta_unaccel_x: c_double,
+        pub delta_unaccel_y: c_double,
+        pub scale: c_double,
+        pub delta_scale: c_double,
+        /// In degrees, clockwise.
+        pub delta_angle: c_double,
+        pub flags: c_int,
+        pub mods: XIModifierState,
+        pub group: XIGroupState,
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    pub struct XIGestureSwipeEvent {
+        pub _type: c_int,
+        pub serial: c_ulong,
+        pub send_event: c_int,
+        pub display: *mut Display,
+        pub extension: c_int,
+        pub evtype: c_int,
+        pub time: Time,
+        pub deviceid: c_int,
+        pub sourceid: c_int,
+        /// The number of touches.
+        pub detail: c_int,
+        pub root: Window,
+        pub event: Window,
+        pub child: Window,
+        pub root_x: c_double,
+        pub root_y: c_double,
+        pub event_x: c_double,
+        pub event_y: c_double,
+        pub delta_x: c_double,
+        pub delta_y: c_double,
+        pub delta_unaccel_x: c_double,
+        pub delta_unaccel_y: c_double,
+        pub flags: c_int,
+        pub mods: XIModifierState,
+        pub group: XIGroupState,
+    }
+}
+
+pub use gestures::*;
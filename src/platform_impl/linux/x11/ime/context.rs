@@ -355,12 +355,17 @@ impl ImeContext {
     // window and couldn't be changed.
     //
     // For me see: https://bugs.freedesktop.org/show_bug.cgi?id=1580.
+    //
+    // The spot is remembered while the IME isn't allowed, so that it can be applied once it is.
     pub(crate) fn set_spot(&mut self, xconn: &Arc<XConnection>, x: c_short, y: c_short) {
-        if !self.is_allowed() || self.ic_spot.x == x && self.ic_spot.y == y {
+        if self.ic_spot.x == x && self.ic_spot.y == y {
             return;
         }
 
         self.ic_spot = ffi::XPoint { x, y };
+        if !self.is_allowed() {
+            return;
+        }
 
         unsafe {
             let preedit_attr = util::memory::XSmartPointer::new(
@@ -119,6 +119,7 @@ impl Ime {
         &mut self,
         window: ffi::Window,
         with_preedit: bool,
+        spot: Option<ffi::XPoint>,
     ) -> Result<bool, ImeContextCreationError> {
         let context = if self.is_destroyed() {
             // Create empty entry in map, so that when IME is rebuilt, this window has a context.
@@ -137,7 +138,7 @@ impl Ime {
                     im.im,
                     style,
                     window,
-                    None,
+                    spot,
                     self.inner.event_sender.clone(),
                 )?
             };
@@ -225,17 +226,22 @@ impl Ime {
             return;
         }
 
+        let mut spot = None;
         if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
             if allowed == context.is_allowed() {
                 return;
             }
+
+            // Carry the spot set while the IME wasn't allowed over, so that the candidate window
+            // is placed correctly on the first composition.
+            spot = Some(context.ic_spot);
         }
 
         // Remove context for that window.
         let _ = self.remove_context(window);
 
         // Create new context supporting IME input.
-        let _ = self.create_context(window, allowed);
+        let _ = self.create_context(window, allowed, spot);
     }
 }
 
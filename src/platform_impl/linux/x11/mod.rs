@@ -22,8 +22,11 @@ use calloop::{ping::Ping, Readiness};
 use libc::{setlocale, LC_CTYPE};
 use log::warn;
 
-use x11rb::connection::RequestConnection;
+use x11rb::connection::{Connection as _, RequestConnection};
 use x11rb::errors::{ConnectError, ConnectionError, IdsExhausted, ReplyError};
+use x11rb::protocol::shape::SK;
+use x11rb::protocol::sync::ConnectionExt as _;
+use x11rb::protocol::xfixes::{ConnectionExt as _, RegionWrapper};
 use x11rb::protocol::xinput::{self, ConnectionExt as _};
 use x11rb::protocol::xkb;
 use x11rb::protocol::xproto::{self, ConnectionExt as _};
@@ -32,14 +35,18 @@ use x11rb::xcb_ffi::ReplyOrIdError;
 
 use super::{ControlFlow, OsError};
 use crate::{
-    error::{EventLoopError, OsError as RootOsError},
-    event::{Event, StartCause, WindowEvent},
+    dpi::PhysicalRect,
+    error::{EventLoopError, ExternalError, OsError as RootOsError},
+    event::{
+        AxisId, AxisInfo, AxisKind, DeviceInfo as RootDeviceInfo, DeviceKind, Event, StartCause,
+        WindowEvent,
+    },
     event_loop::{DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW},
     platform::pump_events::PumpStatus,
     platform_impl::common::xkb::Context,
     platform_impl::{
         platform::{min_timeout, WindowId},
-        PlatformSpecificWindowBuilderAttributes,
+        PlatformSpecificWindowBuilderAttributes, TouchIds,
     },
     window::WindowAttributes,
 };
@@ -68,6 +75,13 @@ pub(crate) use xdisplay::XConnection;
 const ALL_DEVICES: u16 = 0;
 const ALL_MASTER_DEVICES: u16 = 1;
 const ICONIC_STATE: u32 = 3;
+// The touchpad gesture events of XInput 2.4, which don't fit in a 32-bit mask.
+const GESTURE_EVENT_MASK: u64 = (1 << xinput::GESTURE_PINCH_BEGIN_EVENT)
+    | (1 << xinput::GESTURE_PINCH_UPDATE_EVENT)
+    | (1 << xinput::GESTURE_PINCH_END_EVENT)
+    | (1 << xinput::GESTURE_SWIPE_BEGIN_EVENT)
+    | (1 << xinput::GESTURE_SWIPE_UPDATE_EVENT)
+    | (1 << xinput::GESTURE_SWIPE_END_EVENT);
 
 /// The underlying x11rb connection that we are using.
 type X11rbConnection = x11rb::xcb_ffi::XCBConnection;
@@ -144,19 +158,26 @@ pub struct EventLoopWindowTarget<T> {
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<ActivationToken>,
+    cursor_warp_sender: WakeSender<WindowId>,
+    user_sender: WakeSender<T>,
+    proxy_waker: Ping,
     device_events: Cell<DeviceEvents>,
-    _marker: ::std::marker::PhantomData<T>,
+    // Whether the server supports XInput 2.4, and thus touchpad gestures.
+    xinput_gestures: bool,
+    // The input-only window the pointer is confined to by `clip_cursor`.
+    cursor_clip_window: Cell<Option<xproto::Window>>,
 }
 
 pub struct EventLoop<T: 'static> {
     loop_running: bool,
     event_loop: Loop<'static, EventLoopState>,
-    waker: calloop::ping::Ping,
     event_processor: EventProcessor<T>,
     redraw_receiver: PeekableReceiver<WindowId>,
+    /// The areas exposed since the last redraw of each window.
+    redraw_damage: HashMap<WindowId, Vec<PhysicalRect>>,
     user_receiver: PeekableReceiver<T>,
     activation_receiver: PeekableReceiver<ActivationToken>,
-    user_sender: Sender<T>,
+    cursor_warp_receiver: PeekableReceiver<WindowId>,
 
     /// The current state of the event loop.
     state: EventLoopState,
@@ -167,16 +188,21 @@ type ActivationToken = (WindowId, crate::event_loop::AsyncRequestSerial);
 struct EventLoopState {
     /// The latest readiness state for the x11 file descriptor
     x11_readiness: Readiness,
+
+    /// Whether `EventLoopProxy::wake_up` was called since the last iteration.
+    proxy_wake_up: bool,
 }
 
 pub struct EventLoopProxy<T: 'static> {
     user_sender: WakeSender<T>,
+    proxy_waker: Ping,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         EventLoopProxy {
             user_sender: self.user_sender.clone(),
+            proxy_waker: self.proxy_waker.clone(),
         }
     }
 }
@@ -243,12 +269,14 @@ impl<T: 'static> EventLoop<T> {
             .expect("X server missing XKB extension");
 
         // Check for XInput2 support.
-        xconn
+        let xinput_version = xconn
             .xcb_connection()
-            .xinput_xi_query_version(2, 3)
+            .xinput_xi_query_version(2, 4)
             .expect("Failed to send XInput2 query version request")
             .reply()
             .expect("Error while checking for XInput2 query version reply");
+        let xinput_gestures =
+            (xinput_version.major_version, xinput_version.minor_version) >= (2, 4);
 
         xconn.update_cached_wm_info(root);
 
@@ -280,12 +308,29 @@ impl<T: 'static> EventLoop<T> {
             })
             .expect("Failed to register the event loop waker source");
 
+        // A separate waker for `EventLoopProxy::wake_up`, which must run an iteration even though
+        // nothing was queued.
+        let (proxy_waker, proxy_waker_source) =
+            calloop::ping::make_ping().expect("Failed to create event loop proxy waker");
+        event_loop
+            .handle()
+            .insert_source(
+                proxy_waker_source,
+                move |_, _, state: &mut EventLoopState| {
+                    state.proxy_wake_up = true;
+                },
+            )
+            .expect("Failed to register the event loop proxy waker source");
+
         // Create a channel for handling redraw requests.
         let (redraw_sender, redraw_channel) = mpsc::channel();
 
         // Create a channel for sending activation tokens.
         let (activation_token_sender, activation_token_channel) = mpsc::channel();
 
+        // Create a channel for windows with a queued cursor warp.
+        let (cursor_warp_sender, cursor_warp_channel) = mpsc::channel();
+
         // Create a channel for sending user events.
         let (user_sender, user_channel) = mpsc::channel();
 
@@ -301,7 +346,6 @@ impl<T: 'static> EventLoop<T> {
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             windows: Default::default(),
-            _marker: ::std::marker::PhantomData,
             ime_sender,
             xconn,
             wm_delete_window,
@@ -314,7 +358,18 @@ impl<T: 'static> EventLoop<T> {
                 sender: activation_token_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            cursor_warp_sender: WakeSender {
+                sender: cursor_warp_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
+            user_sender: WakeSender {
+                sender: user_sender, // not used again so no clone
+                waker,
+            },
+            proxy_waker,
             device_events: Default::default(),
+            xinput_gestures,
+            cursor_clip_window: Cell::new(None),
         };
 
         // Set initial device event filter.
@@ -322,6 +377,7 @@ impl<T: 'static> EventLoop<T> {
 
         let root_window_target = RootELW {
             p: super::EventLoopWindowTarget::X(window_target),
+            handler_state: Default::default(),
             _marker: PhantomData,
         };
 
@@ -340,9 +396,11 @@ impl<T: 'static> EventLoop<T> {
             num_touch: 0,
             held_key_press: None,
             first_touch: None,
+            touch_ids: TouchIds::default(),
             active_window: None,
             modifiers: Default::default(),
             is_composing: false,
+            pinch_scale: 1.0,
         };
 
         // Register for device hotplug events
@@ -371,25 +429,21 @@ impl<T: 'static> EventLoop<T> {
         EventLoop {
             loop_running: false,
             event_loop,
-            waker,
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
+            cursor_warp_receiver: PeekableReceiver::from_recv(cursor_warp_channel),
+            redraw_damage: HashMap::new(),
             user_receiver: PeekableReceiver::from_recv(user_channel),
-            user_sender,
             state: EventLoopState {
                 x11_readiness: Readiness::EMPTY,
+                proxy_wake_up: false,
             },
         }
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy {
-            user_sender: WakeSender {
-                sender: self.user_sender.clone(),
-                waker: self.waker.clone(),
-            },
-        }
+        EventProcessor::window_target(&self.event_processor.target).create_proxy()
     }
 
     pub(crate) fn window_target(&self) -> &RootELW<T> {
@@ -459,6 +513,7 @@ impl<T: 'static> EventLoop<T> {
 
     fn has_pending(&mut self) -> bool {
         self.event_processor.poll()
+            || self.state.proxy_wake_up
             || self.user_receiver.has_incoming()
             || self.redraw_receiver.has_incoming()
     }
@@ -498,6 +553,9 @@ impl<T: 'static> EventLoop<T> {
             return;
         }
 
+        // Warps queued from other threads while we were waiting.
+        self.flush_cursor_warps();
+
         // NB: `StartCause::Init` is handled as a special case and doesn't need
         // to be considered here
         let cause = match self.control_flow() {
@@ -538,6 +596,7 @@ impl<T: 'static> EventLoop<T> {
             return;
         }
 
+        self.state.proxy_wake_up = false;
         self.single_iteration(&mut callback, cause);
     }
 
@@ -591,21 +650,27 @@ impl<T: 'static> EventLoop<T> {
 
         // Empty the redraw requests
         {
-            let mut windows = HashSet::new();
-
-            while let Ok(window_id) = self.redraw_receiver.try_recv() {
-                windows.insert(window_id);
-            }
+            let requests = std::iter::from_fn(|| self.redraw_receiver.try_recv().ok());
+            let windows = coalesce_redraws(self.redraw_damage.drain(), requests);
 
-            for window_id in windows {
+            for (window_id, dirty_rects) in windows {
                 let window_id = crate::window::WindowId(window_id);
                 callback(
                     Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::RedrawRequested,
+                        event: WindowEvent::RedrawRequested { dirty_rects },
                     },
                     &self.event_processor.target,
                 );
+
+                // Acknowledge the pending sync request for the applications which don't call
+                // `pre_present_notify`, as the frame has been drawn by now.
+                self.event_processor.with_window(
+                    u64::from(window_id.0) as xproto::Window,
+                    |window| {
+                        window.update_sync_counter();
+                    },
+                );
             }
         }
 
@@ -613,6 +678,37 @@ impl<T: 'static> EventLoop<T> {
         {
             callback(Event::AboutToWait, &self.event_processor.target);
         }
+
+        self.flush_cursor_warps();
+    }
+
+    /// Sends the cursor warps queued by `Window::set_cursor_position`, only keeping the latest
+    /// one of each window, and flushes them all at once.
+    fn flush_cursor_warps(&mut self) {
+        let mut windows = HashSet::new();
+        while let Ok(window_id) = self.cursor_warp_receiver.try_recv() {
+            windows.insert(window_id);
+        }
+
+        if windows.is_empty() {
+            return;
+        }
+
+        for window_id in windows {
+            let result = self
+                .event_processor
+                .with_window(window_id.0 as xproto::Window, |window| {
+                    window.send_queued_cursor_warp()
+                });
+            if let Some(Err(err)) = result {
+                warn!("Failed to warp the cursor: {err}");
+            }
+        }
+
+        let wt = EventProcessor::window_target(&self.event_processor.target);
+        if let Err(err) = wt.xconn.flush_requests() {
+            warn!("Failed to flush the cursor warps: {err}");
+        }
     }
 
     fn drain_events<F>(&mut self, callback: &mut F)
@@ -627,11 +723,18 @@ impl<T: 'static> EventLoop<T> {
                 .process_event(&mut xev, |window_target, event| {
                     if let Event::WindowEvent {
                         window_id: crate::window::WindowId(wid),
-                        event: WindowEvent::RedrawRequested,
+                        event: WindowEvent::RedrawRequested { dirty_rects },
                     } = event
                     {
-                        let window_target = EventProcessor::window_target(window_target);
-                        window_target.redraw_sender.send(wid).unwrap();
+                        // Hold the damage back until the end of the iteration, to merge it with
+                        // the other exposures and requests.
+                        match dirty_rects {
+                            Some(rects) => self.redraw_damage.entry(wid).or_default().extend(rects),
+                            None => {
+                                let window_target = EventProcessor::window_target(window_target);
+                                window_target.redraw_sender.send(wid).unwrap();
+                            }
+                        }
                     } else {
                         callback(event, window_target);
                     }
@@ -679,18 +782,148 @@ impl<T> EventLoopWindowTarget<T> {
         &self.xconn
     }
 
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy {
+            user_sender: self.user_sender.clone(),
+            proxy_waker: self.proxy_waker.clone(),
+        }
+    }
+
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
         self.xconn.available_monitors().into_iter().flatten()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        self.redraw_sender.send(window_id).unwrap();
+    }
+
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         self.xconn.primary_monitor().ok()
     }
 
+    pub fn device_info(&self, device_id: DeviceId) -> Option<RootDeviceInfo> {
+        let info = DeviceInfo::get(&self.xconn, device_id.0 as c_int)?;
+        let info = info.first()?;
+        let name = unsafe { CStr::from_ptr(info.name) }
+            .to_string_lossy()
+            .into_owned();
+
+        // Touchpads report touches relative to the device rather than the screen.
+        let is_touchpad = Device::classes(info).iter().any(|&class_ptr| unsafe {
+            (*class_ptr)._type == ffi::XITouchClass
+                && (*(class_ptr as *const ffi::XITouchClassInfo)).mode == ffi::XIDependentTouch
+        });
+        // XInput doesn't tell pens apart from other pointers, so guess from the name.
+        let lowercase_name = name.to_lowercase();
+        let is_pen = ["pen", "stylus", "eraser"]
+            .iter()
+            .any(|pen| lowercase_name.contains(pen));
+        let kind = match info._use {
+            ffi::XIMasterKeyboard | ffi::XISlaveKeyboard => DeviceKind::Keyboard,
+            _ if is_touchpad || lowercase_name.contains("touchpad") => DeviceKind::Touchpad,
+            _ if is_pen => DeviceKind::Pen,
+            ffi::XIMasterPointer | ffi::XISlavePointer => DeviceKind::Mouse,
+            _ => DeviceKind::Other,
+        };
+
+        // Set by the evdev and libinput drivers, as the vendor id followed by the product id.
+        let product_id_atom = self.xconn.atoms()[DEVICE_PRODUCT_ID];
+        let ids = self
+            .xconn
+            .xcb_connection()
+            .xinput_xi_get_property(
+                device_id.0,
+                false,
+                product_id_atom,
+                xproto::Atom::from(xproto::AtomEnum::ANY),
+                0,
+                2,
+            )
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| match reply.items {
+                xinput::XIGetPropertyItems::Data32(ids) if ids.len() == 2 => {
+                    Some((ids[0] as u16, ids[1] as u16))
+                }
+                _ => None,
+            });
+
+        let axes = Device::valuators(info)
+            .map(|valuator| {
+                let name = (valuator.label != 0)
+                    .then(|| {
+                        self.xconn
+                            .xcb_connection()
+                            .get_atom_name(valuator.label as _)
+                    })
+                    .and_then(|cookie| cookie.ok()?.reply().ok())
+                    .map(|reply| String::from_utf8_lossy(&reply.name).into_owned());
+                valuator_axis(valuator, name)
+            })
+            .collect();
+
+        Some(RootDeviceInfo {
+            name,
+            kind,
+            vendor_id: ids.map(|(vendor_id, _)| vendor_id),
+            product_id: ids.map(|(_, product_id)| product_id),
+            axes,
+        })
+    }
+
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
         self.device_events.set(allowed);
     }
 
+    pub fn clip_cursor(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        let window = rect
+            .map(|rect| self.create_cursor_clip_window(rect))
+            .transpose()
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        let grab = window.map(|window| util::PointerGrab {
+            window: self.root,
+            confine_to: window,
+            event_mask: xproto::EventMask::NO_EVENT,
+        });
+        let result = self.xconn.set_clip_pointer_grab(grab);
+
+        // The previous window isn't confined to anymore.
+        if let Some(window) = self.cursor_clip_window.replace(window) {
+            self.xconn
+                .xcb_connection()
+                .destroy_window(window)
+                .expect_then_ignore_error("Failed to destroy the cursor clip window");
+        }
+        result
+    }
+
+    /// Creates and maps a window covering `rect`, to confine the pointer to.
+    fn create_cursor_clip_window(&self, rect: PhysicalRect) -> Result<xproto::Window, X11Error> {
+        let conn = self.xconn.xcb_connection();
+        let window = conn.generate_id()?;
+        let (x, y, width, height) = clip_window_geometry(rect, self.xconn.desktop_origin());
+        conn.create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            window,
+            self.root,
+            x,
+            y,
+            width,
+            height,
+            0,
+            xproto::WindowClass::INPUT_ONLY,
+            x11rb::COPY_FROM_PARENT,
+            &xproto::CreateWindowAux::new().override_redirect(1),
+        )?;
+
+        // The window confines the pointer with its bounding shape, while an empty input shape
+        // lets the input through to the windows below it.
+        let region = RegionWrapper::create_region(conn, &[])?;
+        conn.xfixes_set_window_shape_region(window, SK::INPUT, 0, 0, region.region())?;
+        conn.map_window(window)?;
+        Ok(window)
+    }
+
     /// Update the device event based on window focus.
     pub fn update_listen_device_events(&self, focus: bool) {
         let device_events = self.device_events.get() == DeviceEvents::Always
@@ -768,6 +1001,10 @@ impl<T: 'static> EventLoopProxy<T> {
             .send(event)
             .map_err(|e| EventLoopClosed(e.0))
     }
+
+    pub fn wake_up(&self) {
+        self.proxy_waker.ping();
+    }
 }
 
 struct DeviceInfo<'a> {
@@ -818,6 +1055,10 @@ impl DeviceId {
     pub const unsafe fn dummy() -> Self {
         DeviceId(0)
     }
+
+    pub fn persistent_id(&self) -> Option<u64> {
+        Some(self.0 as u64)
+    }
 }
 
 pub(crate) struct Window(Arc<UnownedWindow>);
@@ -850,6 +1091,17 @@ impl Drop for Window {
         let window = self.deref();
         let xconn = &window.xconn;
 
+        if let Some(counter) = window.sync_counter {
+            if let Ok(c) = xconn.xcb_connection().sync_destroy_counter(counter) {
+                c.ignore_error();
+            }
+        }
+
+        // The clip of `clip_cursor` applies again once the window doesn't hold the pointer.
+        if let Err(err) = xconn.release_window_pointer_grab(window.id().0 as xproto::Window) {
+            warn!("Failed to release the cursor grab of a destroyed window: {err}");
+        }
+
         if let Ok(c) = xconn
             .xcb_connection()
             .destroy_window(window.id().0 as xproto::Window)
@@ -1017,6 +1269,23 @@ impl<'a, E: fmt::Debug> CookieResultExt for Result<VoidCookie<'a>, E> {
     }
 }
 
+/// Merges the areas exposed in each window with the redraw requests, into the dirty rectangles
+/// of a single `RedrawRequested` per window.
+fn coalesce_redraws(
+    damage: impl Iterator<Item = (WindowId, Vec<PhysicalRect>)>,
+    requests: impl Iterator<Item = WindowId>,
+) -> HashMap<WindowId, Option<Vec<PhysicalRect>>> {
+    let mut windows: HashMap<_, _> = damage
+        .map(|(window_id, rects)| (window_id, Some(rects)))
+        .collect();
+
+    // An explicit request means everything has to be redrawn.
+    for window_id in requests {
+        windows.insert(window_id, None);
+    }
+    windows
+}
+
 fn mkwid(w: xproto::Window) -> crate::window::WindowId {
     crate::window::WindowId(crate::platform_impl::platform::WindowId(w as _))
 }
@@ -1028,6 +1297,7 @@ fn mkdid(w: xinput::DeviceId) -> crate::event::DeviceId {
 pub struct Device {
     _name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    axes: Vec<AxisInfo>,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
@@ -1050,6 +1320,7 @@ impl Device {
     fn new(info: &ffi::XIDeviceInfo) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut axes = Vec::new();
 
         if Device::physical_device(info) {
             // Identify scroll axes
@@ -1071,11 +1342,16 @@ impl Device {
                     ));
                 }
             }
+
+            axes = Device::valuators(info)
+                .map(|valuator| valuator_axis(valuator, None))
+                .collect();
         }
 
         let mut device = Device {
             _name: name.into_owned(),
             scroll_axes,
+            axes,
             attachment: info.attachment,
         };
         device.reset_scroll_position(info);
@@ -1116,6 +1392,41 @@ impl Device {
             )
         }
     }
+
+    fn valuators(info: &ffi::XIDeviceInfo) -> impl Iterator<Item = &ffi::XIValuatorClassInfo> {
+        Device::classes(info)
+            .iter()
+            .filter(|&&class_ptr| unsafe { (*class_ptr)._type } == ffi::XIValuatorClass)
+            .map(|&class_ptr| unsafe { &*(class_ptr as *const ffi::XIValuatorClassInfo) })
+    }
+}
+
+/// Describes a valuator, whose range is unknown when the driver reports it as `0..=0`.
+fn valuator_axis(valuator: &ffi::XIValuatorClassInfo, name: Option<String>) -> AxisInfo {
+    AxisInfo {
+        id: valuator.number as AxisId,
+        name,
+        kind: match valuator.mode {
+            ffi::XIModeRelative => AxisKind::Relative,
+            _ => AxisKind::Absolute,
+        },
+        range: (valuator.min < valuator.max).then_some(valuator.min..=valuator.max),
+    }
+}
+
+/// Returns the geometry of the window covering the cursor clip `rect`, which is in desktop
+/// coordinates, in the X screen, where it's clamped to the range of the X11 coordinates.
+fn clip_window_geometry(rect: PhysicalRect, desktop_origin: (i32, i32)) -> (i16, i16, u16, u16) {
+    let (x, y) = (
+        rect.position.x.saturating_add(desktop_origin.0),
+        rect.position.y.saturating_add(desktop_origin.1),
+    );
+    let clamp = |value: i32| value.clamp(i16::MIN.into(), i16::MAX.into()) as i16;
+    let (width, height) = (
+        rect.size.width.clamp(1, u16::MAX.into()) as u16,
+        rect.size.height.clamp(1, u16::MAX.into()) as u16,
+    );
+    (clamp(x), clamp(y), width, height)
 }
 
 /// Convert the raw X11 representation for a 32-bit floating point to a double.
@@ -1123,3 +1434,82 @@ impl Device {
 fn xinput_fp1616_to_float(fp: xinput::Fp1616) -> f64 {
     (fp as f64) / ((1 << 16) as f64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpi::{PhysicalPosition, PhysicalSize};
+
+    #[test]
+    fn redraws_are_coalesced_per_window() {
+        let rect = |x| PhysicalRect::new(PhysicalPosition::new(x, 0), PhysicalSize::new(10, 10));
+        let (exposed, requested) = (WindowId(1), WindowId(2));
+        let damage = vec![(exposed, vec![rect(0), rect(10)])];
+
+        let windows = coalesce_redraws(damage.into_iter(), [requested, requested].into_iter());
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[&exposed], Some(vec![rect(0), rect(10)]));
+        assert_eq!(windows[&requested], None);
+    }
+
+    #[test]
+    fn requested_redraws_include_the_damage() {
+        let rect = PhysicalRect::new(PhysicalPosition::new(0, 0), PhysicalSize::new(10, 10));
+        let damage = vec![(WindowId(1), vec![rect])];
+
+        let windows = coalesce_redraws(damage.into_iter(), [WindowId(1)].into_iter());
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[&WindowId(1)], None);
+    }
+
+    #[test]
+    fn clip_window_is_placed_in_the_screen() {
+        let rect = |x, y, width, height| {
+            PhysicalRect::new(
+                PhysicalPosition::new(x, y),
+                PhysicalSize::new(width, height),
+            )
+        };
+
+        assert_eq!(
+            clip_window_geometry(rect(-100, 20, 640, 480), (1920, 0)),
+            (1820, 20, 640, 480)
+        );
+        assert_eq!(
+            clip_window_geometry(rect(i32::MIN, 40_000, u32::MAX, 0), (0, 0)),
+            (i16::MIN, i16::MAX, u16::MAX, 1)
+        );
+    }
+
+    #[test]
+    fn tablet_pressure_valuator_is_normalized() {
+        let pressure = ffi::XIValuatorClassInfo {
+            _type: ffi::XIValuatorClass,
+            number: 2,
+            min: 0.0,
+            max: 65535.0,
+            mode: ffi::XIModeAbsolute,
+            ..Default::default()
+        };
+        let axis = valuator_axis(&pressure, Some("Abs Pressure".to_owned()));
+
+        assert_eq!(axis.id, 2);
+        assert_eq!(axis.kind, AxisKind::Absolute);
+        assert_eq!(axis.normalize(65535.0 / 2.0), Some(0.5));
+    }
+
+    #[test]
+    fn relative_valuator_is_not_normalized() {
+        let motion = ffi::XIValuatorClassInfo {
+            _type: ffi::XIValuatorClass,
+            number: 0,
+            mode: ffi::XIModeRelative,
+            ..Default::default()
+        };
+        let axis = valuator_axis(&motion, None);
+
+        assert_eq!(axis.kind, AxisKind::Relative);
+        assert_eq!(axis.range, None);
+        assert_eq!(axis.normalize(3.0), None);
+    }
+}
@@ -19,6 +19,42 @@ impl XConnection {
         // We update this lazily.
         self.monitor_handles.lock().unwrap().take()
     }
+
+    /// Returns the position of the primary monitor in the X screen, which is the origin of the
+    /// desktop coordinates winit reports positions in.
+    pub fn desktop_origin(&self) -> (i32, i32) {
+        self.primary_monitor()
+            .map_or((0, 0), |monitor| monitor.screen_position())
+    }
+
+    /// Converts a position in the X screen to desktop coordinates.
+    pub fn screen_to_desktop(&self, position: (i32, i32)) -> (i32, i32) {
+        screen_to_desktop(position, self.desktop_origin())
+    }
+
+    /// Converts a position in desktop coordinates to the X screen.
+    pub fn desktop_to_screen(&self, position: (i32, i32)) -> (i32, i32) {
+        desktop_to_screen(position, self.desktop_origin())
+    }
+}
+
+fn screen_to_desktop((x, y): (i32, i32), (origin_x, origin_y): (i32, i32)) -> (i32, i32) {
+    (x - origin_x, y - origin_y)
+}
+
+fn desktop_to_screen((x, y): (i32, i32), (origin_x, origin_y): (i32, i32)) -> (i32, i32) {
+    (x + origin_x, y + origin_y)
+}
+
+/// Moves the monitors' desktop positions so that the primary monitor is at the origin.
+fn place_on_desktop(monitors: &mut [MonitorHandle]) {
+    let origin = monitors
+        .iter()
+        .find(|monitor| monitor.primary)
+        .map_or((0, 0), |monitor| monitor.position);
+    for monitor in monitors {
+        monitor.desktop_position = screen_to_desktop(monitor.position, origin);
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -62,6 +98,8 @@ pub struct MonitorHandle {
     dimensions: (u32, u32),
     /// The position of the monitor in the X screen
     position: (i32, i32),
+    /// The position of the monitor relative to the primary one
+    desktop_position: (i32, i32),
     /// If the monitor is the primary one
     primary: bool,
     /// The refresh rate used by monitor.
@@ -139,6 +177,7 @@ impl MonitorHandle {
             scale_factor,
             dimensions,
             position,
+            desktop_position: position,
             primary,
             rect,
             video_modes,
@@ -152,6 +191,7 @@ impl MonitorHandle {
             scale_factor: 1.0,
             dimensions: (1, 1),
             position: (0, 0),
+            desktop_position: (0, 0),
             refresh_rate_millihertz: None,
             primary: true,
             rect: util::AaRect::new((0, 0), (1, 1)),
@@ -178,7 +218,11 @@ impl MonitorHandle {
     }
 
     pub fn position(&self) -> PhysicalPosition<i32> {
-        self.position.into()
+        self.desktop_position.into()
+    }
+
+    pub(crate) fn screen_position(&self) -> (i32, i32) {
+        self.position
     }
 
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
@@ -200,10 +244,39 @@ impl MonitorHandle {
     }
 }
 
+/// Pick the monitor that the window overlaps the most, or the first one if it doesn't overlap
+/// any of them.
+///
+/// The `current` monitor of the window is kept on ties, so that it only changes once another
+/// monitor is overlapped strictly more: for a window dragged across the edge between two
+/// monitors, this is when its center crosses the edge.
+///
+/// `monitors` must not be empty.
+fn monitor_with_largest_overlap<'a>(
+    monitors: &'a [MonitorHandle],
+    window_rect: &util::AaRect,
+    current: Option<&MonitorHandle>,
+) -> &'a MonitorHandle {
+    let mut matched_monitor = current
+        .and_then(|current| monitors.iter().find(|monitor| *monitor == current))
+        .unwrap_or(&monitors[0]);
+    let mut largest_overlap = window_rect.get_overlapping_area(&matched_monitor.rect);
+    for monitor in monitors {
+        let overlapping_area = window_rect.get_overlapping_area(&monitor.rect);
+        if overlapping_area > largest_overlap {
+            largest_overlap = overlapping_area;
+            matched_monitor = monitor;
+        }
+    }
+
+    matched_monitor
+}
+
 impl XConnection {
     pub fn get_monitor_for_window(
         &self,
         window_rect: Option<util::AaRect>,
+        current: Option<&MonitorHandle>,
     ) -> Result<MonitorHandle, X11Error> {
         let monitors = self.available_monitors()?;
 
@@ -212,23 +285,11 @@ impl XConnection {
             return Ok(MonitorHandle::dummy());
         }
 
-        let default = monitors.first().unwrap();
-
-        let window_rect = match window_rect {
-            Some(rect) => rect,
-            None => return Ok(default.to_owned()),
+        let matched_monitor = match window_rect {
+            Some(window_rect) => monitor_with_largest_overlap(&monitors, &window_rect, current),
+            None => monitors.first().unwrap(),
         };
 
-        let mut largest_overlap = 0;
-        let mut matched_monitor = default;
-        for monitor in &monitors {
-            let overlapping_area = window_rect.get_overlapping_area(&monitor.rect);
-            if overlapping_area > largest_overlap {
-                largest_overlap = overlapping_area;
-                matched_monitor = monitor;
-            }
-        }
-
         Ok(matched_monitor.to_owned())
     }
 
@@ -280,6 +341,8 @@ impl XConnection {
             }
         }
 
+        place_on_desktop(&mut available_monitors);
+
         Ok(available_monitors)
     }
 
@@ -373,3 +436,92 @@ impl ScreenResources {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(id: randr::Crtc, position: (i32, i32), dimensions: (u32, u32)) -> MonitorHandle {
+        MonitorHandle {
+            id,
+            name: format!("monitor {id}"),
+            scale_factor: 1.0,
+            dimensions,
+            position,
+            desktop_position: position,
+            refresh_rate_millihertz: None,
+            primary: id == 1,
+            rect: util::AaRect::new(position, dimensions),
+            video_modes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn picks_monitor_with_largest_overlap() {
+        // The primary monitor on the left, a second one on its right.
+        let monitors = [
+            monitor(1, (0, 0), (1920, 1080)),
+            monitor(2, (1920, 0), (2560, 1440)),
+        ];
+
+        let pick = |position, size| {
+            monitor_with_largest_overlap(&monitors, &util::AaRect::new(position, size), None).id
+        };
+
+        // Fully on one monitor.
+        assert_eq!(pick((100, 100), (800, 600)), 1);
+        assert_eq!(pick((2000, 100), (800, 600)), 2);
+        // Straddling both monitors, with most of the window on the second one.
+        assert_eq!(pick((1800, 100), (800, 600)), 2);
+        // Straddling both monitors, with most of the window on the primary one.
+        assert_eq!(pick((1500, 100), (800, 600)), 1);
+        // Off all monitors falls back to the first one.
+        assert_eq!(pick((-5000, -5000), (800, 600)), 1);
+    }
+
+    #[test]
+    fn monitor_flips_when_the_center_crosses_the_edge() {
+        let monitors = [
+            monitor(1, (0, 0), (1920, 1080)),
+            monitor(2, (1920, 0), (2560, 1440)),
+        ];
+
+        // Drag an 800 pixels wide window to the right and back, its center crossing the edge
+        // between the monitors at `x = 1520`.
+        let mut current = monitors[0].clone();
+        let mut flips = Vec::new();
+        for x in (1000..2000).chain((1000..2000).rev()) {
+            let window_rect = util::AaRect::new((x, 100), (800, 600));
+            let monitor = monitor_with_largest_overlap(&monitors, &window_rect, Some(&current));
+            if *monitor != current {
+                flips.push((x, monitor.id));
+                current = monitor.clone();
+            }
+        }
+
+        // At exactly half the window on each monitor, the monitor doesn't change.
+        assert_eq!(flips, [(1521, 2), (1519, 1)]);
+    }
+
+    #[test]
+    fn desktop_origin_is_the_primary_monitor() {
+        // A second monitor on the left of the primary one, which starts the X screen.
+        let mut monitors = [
+            monitor(2, (0, 0), (2560, 1440)),
+            monitor(1, (2560, 200), (1920, 1080)),
+        ];
+        place_on_desktop(&mut monitors);
+
+        assert_eq!(monitors[0].position(), PhysicalPosition::new(-2560, -200));
+        assert_eq!(monitors[1].position(), PhysicalPosition::new(0, 0));
+
+        // A window on the left monitor has a negative position on the desktop, but its offset
+        // within the monitor stays the same.
+        let origin = monitors[1].screen_position();
+        let window = screen_to_desktop((100, 50), origin);
+        assert_eq!(window, (-2460, -150));
+        let monitor = monitors[0].position();
+        assert_eq!((window.0 - monitor.x, window.1 - monitor.y), (100, 50));
+        assert_eq!(desktop_to_screen(window, origin), (100, 50));
+    }
+}
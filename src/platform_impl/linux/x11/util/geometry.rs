@@ -83,30 +83,19 @@ impl FrameExtents {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum FrameExtentsHeuristicPath {
-    Supported,
-    UnsupportedNested,
-    UnsupportedBordered,
-}
-
 #[derive(Debug, Clone)]
 pub struct FrameExtentsHeuristic {
     pub frame_extents: FrameExtents,
-    pub heuristic_path: FrameExtentsHeuristicPath,
 }
 
 impl FrameExtentsHeuristic {
     pub fn inner_pos_to_outer(&self, x: i32, y: i32) -> (i32, i32) {
-        use self::FrameExtentsHeuristicPath::*;
-        if self.heuristic_path != UnsupportedBordered {
-            (
-                x - self.frame_extents.left as i32,
-                y - self.frame_extents.top as i32,
-            )
-        } else {
-            (x, y)
-        }
+        // For un-nested windows with a border, the translated coordinates are inside the border,
+        // so it has to be subtracted like a frame as well.
+        (
+            x - self.frame_extents.left as i32,
+            y - self.frame_extents.top as i32,
+        )
     }
 
     pub fn inner_size_to_outer(&self, width: u32, height: u32) -> (u32, u32) {
@@ -123,6 +112,21 @@ impl FrameExtentsHeuristic {
             ),
         )
     }
+
+    pub fn outer_size_to_inner(&self, width: u32, height: u32) -> (u32, u32) {
+        (
+            width.saturating_sub(
+                self.frame_extents
+                    .left
+                    .saturating_add(self.frame_extents.right) as _,
+            ),
+            height.saturating_sub(
+                self.frame_extents
+                    .top
+                    .saturating_add(self.frame_extents.bottom) as _,
+            ),
+        )
+    }
 }
 
 impl XConnection {
@@ -182,6 +186,35 @@ impl XConnection {
         })
     }
 
+    /// Returns the work area of the current desktop in the X screen, as `(x, y, width, height)`.
+    pub fn get_work_area(&self, root: xproto::Window) -> Option<(i32, i32, u32, u32)> {
+        let atoms = self.atoms();
+        let work_area_atom = atoms[_NET_WORKAREA];
+
+        if !hint_is_supported(work_area_atom) {
+            return None;
+        }
+
+        let cardinal = xproto::Atom::from(xproto::AtomEnum::CARDINAL);
+        let current_desktop = self
+            .get_property::<u32>(root, atoms[_NET_CURRENT_DESKTOP], cardinal)
+            .ok()
+            .and_then(|desktop| desktop.first().copied())
+            .unwrap_or(0) as usize;
+        // There's one work area per desktop.
+        let work_areas: Vec<u32> = self.get_property(root, work_area_atom, cardinal).ok()?;
+        let work_area = work_areas
+            .chunks_exact(4)
+            .nth(current_desktop)
+            .or_else(|| work_areas.chunks_exact(4).next())?;
+        Some((
+            work_area[0] as i32,
+            work_area[1] as i32,
+            work_area[2],
+            work_area[3],
+        ))
+    }
+
     pub fn is_top_level(&self, window: xproto::Window, root: xproto::Window) -> Option<bool> {
         let atoms = self.atoms();
         let client_list_atom = atoms[_NET_CLIENT_LIST];
@@ -227,8 +260,6 @@ impl XConnection {
         window: xproto::Window,
         root: xproto::Window,
     ) -> FrameExtentsHeuristic {
-        use self::FrameExtentsHeuristicPath::*;
-
         // Position relative to root window.
         // With rare exceptions, this is the position of a nested window. Cases where the window
         // isn't nested are outlined in the comments throghout this function, but in addition to
@@ -286,10 +317,7 @@ impl XConnection {
             //   positions given to XMoveWindow as a client area position rather than a position
             //   of the overall window.
 
-            FrameExtentsHeuristic {
-                frame_extents,
-                heuristic_path: Supported,
-            }
+            FrameExtentsHeuristic { frame_extents }
         } else if nested {
             // If the position value we have is for a nested window used as the client area, we'll
             // just climb up the hierarchy and get the geometry of the outermost window we're
@@ -320,18 +348,43 @@ impl XConnection {
             let bottom = diff_y.saturating_sub(offset_y);
 
             let frame_extents = FrameExtents::new(left, right, top, bottom);
-            FrameExtentsHeuristic {
-                frame_extents,
-                heuristic_path: UnsupportedNested,
-            }
+            FrameExtentsHeuristic { frame_extents }
         } else {
             // This is the case for xmonad and dwm, AKA the only WMs tested that supplied a
             // border value. This is convenient, since we can use it to get an accurate frame.
             let frame_extents = FrameExtents::from_border(border.into());
-            FrameExtentsHeuristic {
-                frame_extents,
-                heuristic_path: UnsupportedBordered,
-            }
+            FrameExtentsHeuristic { frame_extents }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bordered_outer_position_is_inner_position_minus_border() {
+        let heuristic = FrameExtentsHeuristic {
+            frame_extents: FrameExtents::from_border(2),
+        };
+        assert_eq!(heuristic.inner_pos_to_outer(102, 52), (100, 50));
+        assert_eq!(heuristic.inner_size_to_outer(640, 480), (644, 484));
+    }
+
+    #[test]
+    fn framed_outer_position_is_inner_position_minus_insets() {
+        let heuristic = FrameExtentsHeuristic {
+            frame_extents: FrameExtents::new(1, 1, 30, 1),
+        };
+        assert_eq!(heuristic.inner_pos_to_outer(101, 80), (100, 50));
+    }
+
+    #[test]
+    fn inner_size_is_outer_size_minus_insets() {
+        let heuristic = FrameExtentsHeuristic {
+            frame_extents: FrameExtents::new(1, 1, 30, 1),
+        };
+        assert_eq!(heuristic.outer_size_to_inner(800, 600), (798, 569));
+        assert_eq!(heuristic.outer_size_to_inner(1, 10), (0, 0));
+    }
+}
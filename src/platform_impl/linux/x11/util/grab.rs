@@ -0,0 +1,170 @@
+//! Coordination of the pointer grabs of the application.
+//!
+//! An X11 client only has one pointer grab, while both the cursor grab of a window and the clip
+//! of `EventLoopWindowTarget::clip_cursor` need one. They're recorded here, and the pointer is
+//! always grabbed for the one which applies: the cursor grab of a window replaces the clip while
+//! it's active, and the clip applies again once it's released.
+
+use super::*;
+use crate::error::ExternalError;
+use crate::platform_impl::x11::CookieResultExt;
+use crate::platform_impl::OsError;
+
+/// A grab of the pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointerGrab {
+    /// The window the pointer events are reported to.
+    pub window: xproto::Window,
+    /// The window the pointer is confined to.
+    pub confine_to: xproto::Window,
+    /// The pointer events reported to `window`, beside the ones of the application's windows.
+    pub event_mask: xproto::EventMask,
+}
+
+/// The pointer grabs of the application.
+#[derive(Debug, Default)]
+pub struct PointerGrabs {
+    /// The cursor grab of a window.
+    window: Option<PointerGrab>,
+    /// The grab of `EventLoopWindowTarget::clip_cursor`.
+    clip: Option<PointerGrab>,
+}
+
+impl PointerGrabs {
+    /// Returns the grab the pointer has to be grabbed for.
+    fn owner(&self) -> Option<PointerGrab> {
+        self.window.or(self.clip)
+    }
+
+    /// Releases the cursor grab of `window`, returning whether it had one.
+    fn release_window(&mut self, window: xproto::Window) -> bool {
+        match self.window {
+            Some(grab) if grab.window == window => {
+                self.window = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl XConnection {
+    /// Sets the cursor grab of a window, which replaces the one of any other window.
+    pub fn set_window_pointer_grab(&self, grab: PointerGrab) -> Result<(), ExternalError> {
+        let mut grabs = self.pointer_grabs.lock().unwrap();
+        let previous = grabs.window.replace(grab);
+        let result = self.apply_pointer_grab(&grabs);
+        if result.is_err() {
+            grabs.window = previous;
+        }
+        result
+    }
+
+    /// Releases the cursor grab of `window`, if it's the one holding the pointer, which confines
+    /// the pointer to the clip again.
+    pub fn release_window_pointer_grab(&self, window: xproto::Window) -> Result<(), ExternalError> {
+        let mut grabs = self.pointer_grabs.lock().unwrap();
+        if !grabs.release_window(window) {
+            return Ok(());
+        }
+        self.apply_pointer_grab(&grabs)
+    }
+
+    /// Forgets the cursor grab of `window` after the pointer got ungrabbed, without grabbing it
+    /// again.
+    pub fn forget_window_pointer_grab(&self, window: xproto::Window) {
+        self.pointer_grabs.lock().unwrap().release_window(window);
+    }
+
+    /// Sets or releases the grab of `EventLoopWindowTarget::clip_cursor`.
+    pub fn set_clip_pointer_grab(&self, grab: Option<PointerGrab>) -> Result<(), ExternalError> {
+        let mut grabs = self.pointer_grabs.lock().unwrap();
+        grabs.clip = grab;
+        self.apply_pointer_grab(&grabs)
+    }
+
+    fn apply_pointer_grab(&self, grabs: &PointerGrabs) -> Result<(), ExternalError> {
+        // We ungrab before grabbing to prevent passive grabs from causing `AlreadyGrabbed`.
+        self.xcb_connection()
+            .ungrab_pointer(x11rb::CURRENT_TIME)
+            .expect_then_ignore_error("Failed to call `xcb_ungrab_pointer`");
+
+        let grab = match grabs.owner() {
+            Some(grab) => grab,
+            None => {
+                return self.flush_requests().map_err(|err| {
+                    ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
+                })
+            }
+        };
+
+        // Our own windows keep getting their events, thanks to `owner_events`.
+        let result = self
+            .xcb_connection()
+            .grab_pointer(
+                true,
+                grab.window,
+                grab.event_mask,
+                xproto::GrabMode::ASYNC,
+                xproto::GrabMode::ASYNC,
+                grab.confine_to,
+                x11rb::NONE,
+                x11rb::CURRENT_TIME,
+            )
+            .map_err(X11Error::from)
+            .and_then(|cookie| Ok(cookie.reply()?))
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+
+        match result.status {
+            xproto::GrabStatus::SUCCESS => Ok(()),
+            xproto::GrabStatus::ALREADY_GRABBED => {
+                Err("Cursor could not be confined: already confined by another client")
+            }
+            xproto::GrabStatus::INVALID_TIME => Err("Cursor could not be confined: invalid time"),
+            xproto::GrabStatus::NOT_VIEWABLE => {
+                Err("Cursor could not be confined: confine location not viewable")
+            }
+            xproto::GrabStatus::FROZEN => {
+                Err("Cursor could not be confined: frozen by another client")
+            }
+            _ => unreachable!(),
+        }
+        .map_err(|err| ExternalError::Os(os_error!(OsError::Misc(err))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grab(window: xproto::Window) -> PointerGrab {
+        PointerGrab {
+            window,
+            confine_to: window,
+            event_mask: xproto::EventMask::NO_EVENT,
+        }
+    }
+
+    #[test]
+    fn window_grab_replaces_the_clip_while_active() {
+        let mut grabs = PointerGrabs {
+            window: None,
+            clip: Some(grab(1)),
+        };
+        assert_eq!(grabs.owner(), Some(grab(1)));
+
+        grabs.window = Some(grab(2));
+        assert_eq!(grabs.owner(), Some(grab(2)));
+
+        // Only the window holding the grab releases it.
+        assert!(!grabs.release_window(3));
+        assert_eq!(grabs.owner(), Some(grab(2)));
+        assert!(grabs.release_window(2));
+        assert_eq!(grabs.owner(), Some(grab(1)));
+
+        // Releasing the clip leaves the window grab in place.
+        grabs.window = Some(grab(2));
+        grabs.clip = None;
+        assert_eq!(grabs.owner(), Some(grab(2)));
+    }
+}
@@ -32,6 +32,33 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// Like `select_xinput_events`, but with a mask that may not fit in 32 bits.
+    pub fn select_xinput_events_wide(
+        &self,
+        window: xproto::Window,
+        device_id: u16,
+        mask: u64,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let mut mask = vec![
+            xinput::XIEventMask::from(mask as u32),
+            xinput::XIEventMask::from((mask >> 32) as u32),
+        ];
+        // Servers without the events of the high bits may not accept a longer mask.
+        if u32::from(mask[1]) == 0 {
+            mask.pop();
+        }
+
+        self.xcb_connection()
+            .xinput_xi_select_events(
+                window,
+                &[xinput::EventMask {
+                    deviceid: device_id,
+                    mask,
+                }],
+            )
+            .map_err(Into::into)
+    }
+
     pub fn select_xkb_events(
         &self,
         device_id: xkb::DeviceSpec,
@@ -11,6 +11,7 @@ mod client_msg;
 pub mod cookie;
 mod cursor;
 mod geometry;
+mod grab;
 mod hint;
 mod icon;
 mod input;
@@ -23,7 +24,8 @@ mod wm;
 mod xmodmap;
 
 pub use self::{
-    geometry::*, hint::*, input::*, mouse::*, window_property::*, wm::*, xmodmap::ModifierKeymap,
+    geometry::*, grab::*, hint::*, input::*, mouse::*, window_property::*, wm::*,
+    xmodmap::ModifierKeymap,
 };
 
 use super::{atoms::*, ffi, VoidCookie, X11Error, XConnection, XError};
@@ -1,5 +1,5 @@
 use std::{
-    cmp, env,
+    env,
     ffi::CString,
     mem::replace,
     os::raw::*,
@@ -13,6 +13,7 @@ use x11rb::{
     protocol::{
         randr,
         shape::SK,
+        sync::{self, ConnectionExt as _},
         xfixes::{ConnectionExt, RegionWrapper},
         xinput,
         xproto::{self, ConnectionExt as _, Rectangle},
@@ -20,7 +21,7 @@ use x11rb::{
 };
 
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     event::{Event, InnerSizeWriter, WindowEvent},
     event_loop::AsyncRequestSerial,
@@ -29,12 +30,14 @@ use crate::{
             atoms::*, xinput_fp1616_to_float, MonitorHandle as X11MonitorHandle, WakeSender,
             X11Error,
         },
-        Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformIcon,
-        PlatformSpecificWindowBuilderAttributes, VideoMode as PlatformVideoMode,
+        AutoGrab, CursorRegions, Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError,
+        PlatformIcon, PlatformSpecificWindowBuilderAttributes, SizeConstraints,
+        VideoMode as PlatformVideoMode,
     },
     window::{
-        CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-        WindowAttributes, WindowButtons, WindowLevel,
+        CursorGrabMode, CursorIcon, DropEffect, ImageBuffer, ImeHints, ImePurpose, Insets,
+        ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+        WindowShape,
     },
 };
 
@@ -46,6 +49,10 @@ use super::{
 #[derive(Debug)]
 pub struct SharedState {
     pub cursor_pos: Option<(f64, f64)>,
+    /// The cursor position requested with `set_cursor_position` since the last flush.
+    pub queued_cursor_warp: Option<(i32, i32)>,
+    /// The position of the last cursor warp, until the next motion event.
+    pub expected_cursor_warp: Option<(i32, i32)>,
     pub size: Option<(u32, u32)>,
     pub position: Option<(i32, i32)>,
     pub inner_position: Option<(i32, i32)>,
@@ -62,6 +69,8 @@ pub struct SharedState {
     // Used to restore video mode after exiting fullscreen
     pub desktop_video_mode: Option<(randr::Crtc, randr::Mode)>,
     pub frame_extents: Option<util::FrameExtentsHeuristic>,
+    // The outer size requested at creation, until the frame extents are known.
+    pub pending_outer_size: Option<Size>,
     pub min_inner_size: Option<Size>,
     pub max_inner_size: Option<Size>,
     pub resize_increments: Option<Size>,
@@ -70,6 +79,86 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub drop_effect: DropEffect,
+    // The minimized state last reported with `WindowEvent::Minimized`.
+    pub minimized: bool,
+    // The maximized state requested, until the window manager confirms it in `_NET_WM_STATE`.
+    pub pending_maximized: Option<bool>,
+    // The theme last requested, which `_GTK_THEME_VARIANT` defaults to dark when unset.
+    pub theme: Option<Theme>,
+    // The custom shape set with `set_shape`, if any.
+    pub shape: Option<Vec<Rectangle>>,
+    // The full title last set, as the window manager may only ever display part of it.
+    pub title: String,
+    // The value of the last `_NET_WM_SYNC_REQUEST`, and whether its `ConfigureNotify` arrived.
+    pub sync_request: Option<(sync::Int64, bool)>,
+    // The IME cursor area as requested, to place the IME again when the scale factor changes.
+    pub ime_cursor_area: Option<(Position, Size)>,
+}
+
+/// Whether a window is minimized according to its `WM_STATE` and `_NET_WM_STATE` properties.
+fn is_minimized_state(
+    wm_state: &[u32],
+    net_wm_state: &[xproto::Atom],
+    hidden_atom: xproto::Atom,
+) -> bool {
+    // The ICCCM `WM_STATE` is set by every WM, its first value is the state and the second one is
+    // the icon window.
+    wm_state.first() == Some(&super::ICONIC_STATE) || net_wm_state.contains(&hidden_atom)
+}
+
+/// Records the minimized state, and returns it if it differs from the one `reported` before.
+fn minimized_changed(reported: &mut bool, minimized: bool) -> Option<bool> {
+    if *reported == minimized {
+        return None;
+    }
+    *reported = minimized;
+    Some(minimized)
+}
+
+/// Forgets the `pending` maximized state once the window manager's state matches it.
+fn confirm_maximized(pending: &mut Option<bool>, wm_maximized: bool) {
+    if *pending == Some(wm_maximized) {
+        *pending = None;
+    }
+}
+
+/// The `_GTK_THEME_VARIANT` written for the requested theme, which is dark by default.
+fn gtk_theme_variant(theme: Option<Theme>) -> &'static str {
+    match theme {
+        Some(Theme::Light) => "light",
+        Some(Theme::Dark) | None => "dark",
+    }
+}
+
+/// The core and XInput2 events selected on a window, with the input events only when it's
+/// `enabled`.
+fn event_masks(enabled: bool) -> (xproto::EventMask, xinput::XIEventMask) {
+    use xproto::EventMask;
+
+    let mut event_mask = EventMask::EXPOSURE
+        | EventMask::STRUCTURE_NOTIFY
+        | EventMask::VISIBILITY_CHANGE
+        | EventMask::KEYMAP_STATE
+        | EventMask::PROPERTY_CHANGE;
+    let mut xinput_mask = xinput::XIEventMask::ENTER
+        | xinput::XIEventMask::LEAVE
+        | xinput::XIEventMask::FOCUS_IN
+        | xinput::XIEventMask::FOCUS_OUT;
+    if enabled {
+        event_mask |= EventMask::KEY_PRESS
+            | EventMask::KEY_RELEASE
+            | EventMask::BUTTON_PRESS
+            | EventMask::BUTTON_RELEASE
+            | EventMask::POINTER_MOTION;
+        xinput_mask |= xinput::XIEventMask::MOTION
+            | xinput::XIEventMask::BUTTON_PRESS
+            | xinput::XIEventMask::BUTTON_RELEASE
+            | xinput::XIEventMask::TOUCH_BEGIN
+            | xinput::XIEventMask::TOUCH_UPDATE
+            | xinput::XIEventMask::TOUCH_END;
+    }
+    (event_mask, xinput_mask)
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -80,6 +169,9 @@ pub enum Visibility {
     YesWait,
 }
 
+/// A width and height as stored in `WM_NORMAL_HINTS`.
+type SizeHint = (i32, i32);
+
 impl SharedState {
     fn new(last_monitor: X11MonitorHandle, window_attributes: &WindowAttributes) -> Mutex<Self> {
         let visibility = if window_attributes.visible {
@@ -95,6 +187,8 @@ impl SharedState {
             is_resizable: window_attributes.resizable,
             is_decorated: window_attributes.decorations,
             cursor_pos: None,
+            queued_cursor_warp: None,
+            expected_cursor_warp: None,
             size: None,
             position: None,
             inner_position: None,
@@ -105,14 +199,52 @@ impl SharedState {
             restore_position: None,
             desktop_video_mode: None,
             frame_extents: None,
+            pending_outer_size: None,
             min_inner_size: None,
             max_inner_size: None,
             resize_increments: None,
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            drop_effect: DropEffect::default(),
+            minimized: false,
+            pending_maximized: None,
+            theme: window_attributes.preferred_theme,
+            shape: None,
+            title: window_attributes.title.clone(),
+            sync_request: None,
+            ime_cursor_area: None,
         })
     }
+
+    /// The minimum and maximum size hints at the given scale factor.
+    ///
+    /// The constraints keep the unit they were requested in: a logical size is converted with
+    /// the current scale factor, while a physical size is used as is.
+    fn min_max_size_hints(&self, scale_factor: f64) -> (Option<SizeHint>, Option<SizeHint>) {
+        let hint = |size: Size| cast_size_to_hint(size, scale_factor);
+        (self.min_inner_size.map(hint), self.max_inner_size.map(hint))
+    }
+
+    /// The position of the IME cursor area in physical pixels at the given scale factor.
+    ///
+    /// Like the size constraints, the area keeps the unit it was requested in.
+    fn ime_position(&self, scale_factor: f64) -> Option<(i16, i16)> {
+        let (spot, _) = self.ime_cursor_area?;
+        Some(spot.to_physical::<i32>(scale_factor).into())
+    }
+
+    /// The constraints on the inner size at the given scale factor, like the window manager
+    /// applies them from the normal hints.
+    fn size_constraints(&self, scale_factor: f64) -> SizeConstraints {
+        let to_physical = |size: Size| size.to_physical::<u32>(scale_factor).into();
+        SizeConstraints {
+            min: self.min_inner_size.map(to_physical),
+            max: self.max_inner_size.map(to_physical),
+            increments: self.resize_increments.map(to_physical),
+            base: self.base_size.map(to_physical),
+        }
+    }
 }
 
 unsafe impl Send for UnownedWindow {}
@@ -121,19 +253,27 @@ unsafe impl Sync for UnownedWindow {}
 pub struct UnownedWindow {
     pub(crate) xconn: Arc<XConnection>, // never changes
     xwindow: xproto::Window,            // never changes
-    #[allow(dead_code)]
-    visual: u32, // never changes
+    visual: u32,                        // never changes
     root: xproto::Window,               // never changes
     #[allow(dead_code)]
     screen_id: i32, // never changes
     cursor: Mutex<CursorIcon>,
     cursor_grabbed_mode: Mutex<CursorGrabMode>,
+    /// The child window covering the rectangle of `confine_cursor_to_rect`.
+    cursor_confinement: Mutex<Option<xproto::Window>>,
+    auto_grab: Mutex<AutoGrab>,
+    cursor_regions: Mutex<CursorRegions>,
     #[allow(clippy::mutex_atomic)]
     cursor_visible: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<super::ActivationToken>,
+    cursor_warp_sender: WakeSender<WindowId>,
+    // The counter of the `_NET_WM_SYNC_REQUEST` protocol, if the server supports it.
+    pub(super) sync_counter: Option<sync::Counter>,
+    // Whether to select the touchpad gesture events of XInput 2.4.
+    xinput_gestures: bool, // never changes
 }
 
 macro_rules! leap {
@@ -188,34 +328,32 @@ impl UnownedWindow {
 
         info!("Guessed window scale factor: {}", scale_factor);
 
-        let max_inner_size: Option<(u32, u32)> = window_attrs
-            .max_inner_size
-            .map(|size| size.to_physical::<u32>(scale_factor).into());
-        let min_inner_size: Option<(u32, u32)> = window_attrs
-            .min_inner_size
-            .map(|size| size.to_physical::<u32>(scale_factor).into());
-
-        let position = window_attrs
-            .position
-            .map(|position| position.to_physical::<i32>(scale_factor));
+        // Embedded windows are positioned relative to their parent rather than the desktop.
+        let position = window_attrs.position.map(|position| {
+            let position = position.to_physical::<i32>(scale_factor).into();
+            if pl_attribs.x11.embed_window.is_some() {
+                PhysicalPosition::from(position)
+            } else {
+                PhysicalPosition::from(xconn.desktop_to_screen(position))
+            }
+        });
 
         let dimensions = {
             // x11 only applies constraints when the window is actively resized
-            // by the user, so we have to manually apply the initial constraints
-            let mut dimensions: (u32, u32) = window_attrs
+            // by the user, so we have to manually apply the initial constraints,
+            // including the snapping to the resize increments
+            let dimensions: (u32, u32) = window_attrs
                 .inner_size
                 .map(|size| size.to_physical::<u32>(scale_factor))
                 .or_else(|| Some((800, 600).into()))
                 .map(Into::into)
                 .unwrap();
-            if let Some(max) = max_inner_size {
-                dimensions.0 = cmp::min(dimensions.0, max.0);
-                dimensions.1 = cmp::min(dimensions.1, max.1);
-            }
-            if let Some(min) = min_inner_size {
-                dimensions.0 = cmp::max(dimensions.0, min.0);
-                dimensions.1 = cmp::max(dimensions.1, min.1);
-            }
+            let dimensions = SizeConstraints::from_attributes(
+                &window_attrs,
+                pl_attribs.x11.base_size,
+                scale_factor,
+            )
+            .clamp(dimensions);
             debug!(
                 "Calculated physical dimensions: {}x{}",
                 dimensions.0, dimensions.1
@@ -269,19 +407,8 @@ impl UnownedWindow {
         let mut visual = visualtype.map_or(x11rb::COPY_FROM_PARENT, |v| v.visual_id);
 
         let window_attributes = {
-            use xproto::EventMask;
-
             let mut aux = xproto::CreateWindowAux::new();
-            let event_mask = EventMask::EXPOSURE
-                | EventMask::STRUCTURE_NOTIFY
-                | EventMask::VISIBILITY_CHANGE
-                | EventMask::KEY_PRESS
-                | EventMask::KEY_RELEASE
-                | EventMask::KEYMAP_STATE
-                | EventMask::BUTTON_PRESS
-                | EventMask::BUTTON_RELEASE
-                | EventMask::POINTER_MOTION
-                | EventMask::PROPERTY_CHANGE;
+            let (event_mask, _) = event_masks(true);
 
             aux = aux.event_mask(event_mask).border_pixel(0);
 
@@ -348,6 +475,19 @@ impl UnownedWindow {
             .visual;
         }
 
+        // The counter the window manager waits on after a resize, to only show the window once
+        // the resized frame is presented.
+        let sync_counter = if xconn.sync_supported() {
+            let counter = leap!(xconn.xcb_connection().generate_id());
+            leap!(xconn
+                .xcb_connection()
+                .sync_create_counter(counter, sync::Int64 { hi: 0, lo: 0 }))
+            .ignore_error();
+            Some(counter)
+        } else {
+            None
+        };
+
         #[allow(clippy::mutex_atomic)]
         let mut window = UnownedWindow {
             xconn: Arc::clone(xconn),
@@ -357,11 +497,17 @@ impl UnownedWindow {
             screen_id,
             cursor: Default::default(),
             cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
+            cursor_confinement: Mutex::new(None),
+            auto_grab: Default::default(),
+            cursor_regions: Default::default(),
             cursor_visible: Mutex::new(true),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
             activation_sender: event_loop.activation_sender.clone(),
+            cursor_warp_sender: event_loop.cursor_warp_sender.clone(),
+            sync_counter,
+            xinput_gestures: event_loop.xinput_gestures,
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -431,29 +577,32 @@ impl UnownedWindow {
 
             leap!(window.set_window_types(pl_attribs.x11.x11_window_types)).ignore_error();
 
-            // Set size hints.
-            let mut min_inner_size = window_attrs
-                .min_inner_size
-                .map(|size| size.to_physical::<u32>(scale_factor));
-            let mut max_inner_size = window_attrs
-                .max_inner_size
-                .map(|size| size.to_physical::<u32>(scale_factor));
+            // Set the blur before mapping the window, so it's already applied to the first frame.
+            if window_attrs.blur {
+                leap!(window.set_blur_inner(true)).ignore_error();
+            }
+
+            // Set size hints. The constraints are stored as requested, so that logical sizes
+            // can be converted again when the scale factor changes.
+            let shared_state = window.shared_state.get_mut().unwrap();
+            shared_state.pending_outer_size = window_attrs.outer_size;
+            shared_state.min_inner_size = window_attrs.min_inner_size;
+            shared_state.max_inner_size = window_attrs.max_inner_size;
+            shared_state.resize_increments = window_attrs.resize_increments;
+            shared_state.base_size = pl_attribs.x11.base_size;
 
+            let (mut min_inner_size, mut max_inner_size) =
+                shared_state.min_max_size_hints(scale_factor);
             if !window_attrs.resizable {
                 if util::wm_name_is_one_of(&["Xfwm4"]) {
                     warn!("To avoid a WM bug, disabling resizing has no effect on Xfwm4");
                 } else {
-                    max_inner_size = Some(dimensions.into());
-                    min_inner_size = Some(dimensions.into());
+                    let size = cast_physical_size_to_hint(dimensions.into());
+                    max_inner_size = Some(size);
+                    min_inner_size = Some(size);
                 }
             }
 
-            let shared_state = window.shared_state.get_mut().unwrap();
-            shared_state.min_inner_size = min_inner_size.map(Into::into);
-            shared_state.max_inner_size = max_inner_size.map(Into::into);
-            shared_state.resize_increments = window_attrs.resize_increments;
-            shared_state.base_size = pl_attribs.x11.base_size;
-
             let normal_hints = WmSizeHints {
                 position: position.map(|PhysicalPosition { x, y }| {
                     (WmSizeHintsSpecification::UserSpecified, x, y)
@@ -463,8 +612,8 @@ impl UnownedWindow {
                     cast_dimension_to_hint(dimensions.0),
                     cast_dimension_to_hint(dimensions.1),
                 )),
-                max_size: max_inner_size.map(cast_physical_size_to_hint),
-                min_size: min_inner_size.map(cast_physical_size_to_hint),
+                max_size: max_inner_size,
+                min_size: min_inner_size,
                 size_increment: window_attrs
                     .resize_increments
                     .map(|size| cast_size_to_hint(size, scale_factor)),
@@ -487,18 +636,27 @@ impl UnownedWindow {
                 leap!(window.set_icon_inner(icon.inner)).ignore_error();
             }
 
-            // Opt into handling window close
+            // Opt into handling window close, and into synchronizing resizes when possible
+            let mut protocols = vec![atoms[WM_DELETE_WINDOW], atoms[_NET_WM_PING]];
+            if let Some(counter) = window.sync_counter {
+                protocols.push(atoms[_NET_WM_SYNC_REQUEST]);
+                leap!(xconn.change_property(
+                    window.xwindow,
+                    atoms[_NET_WM_SYNC_REQUEST_COUNTER],
+                    xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                    xproto::PropMode::REPLACE,
+                    &[counter],
+                ))
+                .ignore_error();
+            }
             let result = xconn.xcb_connection().change_property(
                 xproto::PropMode::REPLACE,
                 window.xwindow,
                 atoms[WM_PROTOCOLS],
                 xproto::AtomEnum::ATOM,
                 32,
-                2,
-                bytemuck::cast_slice::<xproto::Atom, u8>(&[
-                    atoms[WM_DELETE_WINDOW],
-                    atoms[_NET_WM_PING],
-                ]),
+                protocols.len() as u32,
+                bytemuck::cast_slice::<xproto::Atom, u8>(&protocols),
             );
             leap!(result).ignore_error();
 
@@ -528,24 +686,14 @@ impl UnownedWindow {
             }
 
             // Select XInput2 events
-            let mask = xinput::XIEventMask::MOTION
-                | xinput::XIEventMask::BUTTON_PRESS
-                | xinput::XIEventMask::BUTTON_RELEASE
-                | xinput::XIEventMask::ENTER
-                | xinput::XIEventMask::LEAVE
-                | xinput::XIEventMask::FOCUS_IN
-                | xinput::XIEventMask::FOCUS_OUT
-                | xinput::XIEventMask::TOUCH_BEGIN
-                | xinput::XIEventMask::TOUCH_UPDATE
-                | xinput::XIEventMask::TOUCH_END;
-            leap!(xconn.select_xinput_events(window.xwindow, super::ALL_MASTER_DEVICES, mask))
-                .ignore_error();
+            let (_, mask) = event_masks(true);
+            leap!(window.select_xinput_events(mask, true)).ignore_error();
 
             // Try to create input context for the window.
             if let Some(ime) = event_loop.ime.as_ref() {
-                let result = ime
-                    .borrow_mut()
-                    .create_context(window.xwindow as ffi::Window, false);
+                let result =
+                    ime.borrow_mut()
+                        .create_context(window.xwindow as ffi::Window, false, None);
                 leap!(result);
             }
 
@@ -653,12 +801,9 @@ impl UnownedWindow {
         let atoms = self.xconn.atoms();
         let hint_atom = atoms[_GTK_THEME_VARIANT];
         let utf8_atom = atoms[UTF8_STRING];
-        let variant = match theme {
-            Some(Theme::Dark) => "dark",
-            Some(Theme::Light) => "light",
-            None => "dark",
-        };
-        let variant = CString::new(variant).expect("`_GTK_THEME_VARIANT` contained null byte");
+        self.shared_state_lock().theme = theme;
+        let variant = CString::new(gtk_theme_variant(theme))
+            .expect("`_GTK_THEME_VARIANT` contained null byte");
         self.xconn.change_property(
             self.xwindow,
             hint_atom,
@@ -793,10 +938,7 @@ impl UnownedWindow {
                     Fullscreen::Borderless(Some(PlatformMonitorHandle::X(monitor))) => {
                         (None, monitor)
                     }
-                    Fullscreen::Borderless(None) => {
-                        (None, self.shared_state_lock().last_monitor.clone())
-                    }
-                    #[cfg(wayland_platform)]
+                    Fullscreen::Borderless(None) => (None, self.monitor_for_current_position()),
                     _ => unreachable!(),
                 };
 
@@ -838,7 +980,7 @@ impl UnownedWindow {
 
                 let window_position = self.outer_position_physical();
                 self.shared_state_lock().restore_position = Some(window_position);
-                let monitor_origin: (i32, i32) = monitor.position().into();
+                let monitor_origin = monitor.screen_position();
                 self.set_position_inner(monitor_origin.0, monitor_origin.1)
                     .expect_then_ignore_error("Failed to set window position");
                 self.set_fullscreen_hint(true).map(Some)
@@ -887,12 +1029,33 @@ impl UnownedWindow {
                     drop(shared_state);
                     self.set_fullscreen(fullscreen);
                 }
+                self.apply_pending_outer_size();
             }
         }
     }
 
     pub fn current_monitor(&self) -> Option<X11MonitorHandle> {
-        Some(self.shared_state_lock().last_monitor.clone())
+        Some(self.monitor_for_current_position())
+    }
+
+    /// The monitor that the window overlaps the most at its current position.
+    ///
+    /// `last_monitor` is only updated on synthetic `ConfigureNotify` events, so it may still hold
+    /// the monitor guessed at creation or lag behind a drag, thus look it up from the current
+    /// geometry instead. This uses the same rule as `ConfigureNotify`, so both agree once the
+    /// event for the current geometry is processed.
+    fn monitor_for_current_position(&self) -> X11MonitorHandle {
+        let (x, y) = self.outer_position_physical();
+        let outer_size = self.outer_size();
+        let window_rect = util::AaRect::new((x, y), outer_size.into());
+        let last_monitor = self.shared_state_lock().last_monitor.clone();
+        match self
+            .xconn
+            .get_monitor_for_window(Some(window_rect), Some(&last_monitor))
+        {
+            Ok(monitor) if !monitor.is_dummy() => monitor,
+            _ => last_monitor,
+        }
     }
 
     pub fn available_monitors(&self) -> Vec<X11MonitorHandle> {
@@ -901,6 +1064,29 @@ impl UnownedWindow {
             .expect("Failed to get available monitors")
     }
 
+    /// Returns the part of the monitor left to windows by the panels, which `_NET_WORKAREA`
+    /// only reports for the whole X screen.
+    pub fn work_area(&self, monitor: &X11MonitorHandle) -> PhysicalRect {
+        let (x, y) = monitor.screen_position();
+        let (width, height): (u32, u32) = monitor.size().into();
+        let area = match self.xconn.get_work_area(self.root) {
+            Some(area) => area,
+            None => return PhysicalRect::new(monitor.position(), monitor.size()),
+        };
+
+        let left = x.max(area.0);
+        let top = y.max(area.1);
+        let right = (x + width as i32).min(area.0 + area.2 as i32);
+        let bottom = (y + height as i32).min(area.1 + area.3 as i32);
+        if right <= left || bottom <= top {
+            return PhysicalRect::new(monitor.position(), monitor.size());
+        }
+        PhysicalRect::new(
+            self.xconn.screen_to_desktop((left, top)).into(),
+            PhysicalSize::new((right - left) as u32, (bottom - top) as u32),
+        )
+    }
+
     pub fn primary_monitor(&self) -> Option<X11MonitorHandle> {
         Some(
             self.xconn
@@ -912,20 +1098,41 @@ impl UnownedWindow {
     #[inline]
     pub fn is_minimized(&self) -> Option<bool> {
         let atoms = self.xconn.atoms();
-        let state_atom = atoms[_NET_WM_STATE];
-        let state = self.xconn.get_property(
-            self.xwindow,
-            state_atom,
-            xproto::Atom::from(xproto::AtomEnum::ATOM),
-        );
-        let hidden_atom = atoms[_NET_WM_STATE_HIDDEN];
+        let wm_state = self
+            .xconn
+            .get_property::<u32>(self.xwindow, atoms[WM_STATE], atoms[CARD32])
+            .unwrap_or_default();
+        let net_wm_state = self
+            .xconn
+            .get_property::<xproto::Atom>(
+                self.xwindow,
+                atoms[_NET_WM_STATE],
+                xproto::Atom::from(xproto::AtomEnum::ATOM),
+            )
+            .unwrap_or_default();
 
-        Some(match state {
-            Ok(atoms) => atoms
-                .iter()
-                .any(|atom: &xproto::Atom| *atom as xproto::Atom == hidden_atom),
-            _ => false,
-        })
+        Some(is_minimized_state(
+            &wm_state,
+            &net_wm_state,
+            atoms[_NET_WM_STATE_HIDDEN],
+        ))
+    }
+
+    /// Forgets the requested maximized state once `_NET_WM_STATE` reflects it.
+    pub(crate) fn update_maximized(&self) {
+        if self.shared_state_lock().pending_maximized.is_some() {
+            let wm_maximized = self.wm_maximized();
+            confirm_maximized(
+                &mut self.shared_state_lock().pending_maximized,
+                wm_maximized,
+            );
+        }
+    }
+
+    /// Returns the new minimized state if it changed since the last call.
+    pub(crate) fn update_minimized(&self) -> Option<bool> {
+        let minimized = self.is_minimized().unwrap_or(false);
+        minimized_changed(&mut self.shared_state_lock().minimized, minimized)
     }
 
     /// Refresh the API for the given monitor.
@@ -940,12 +1147,13 @@ impl UnownedWindow {
         let monitor = self.shared_state_lock().last_monitor.clone();
         if monitor.name == new_monitor.name {
             let (width, height) = self.inner_size_physical();
+            // If we couldn't determine the previous scale
+            // factor (e.g., because all monitors were closed
+            // before), just pick whatever the current monitor
+            // has set as a baseline.
+            let old_scale_factor = maybe_prev_scale_factor.unwrap_or(monitor.scale_factor);
             let (new_width, new_height) = self.adjust_for_dpi(
-                // If we couldn't determine the previous scale
-                // factor (e.g., because all monitors were closed
-                // before), just pick whatever the current monitor
-                // has set as a baseline.
-                maybe_prev_scale_factor.unwrap_or(monitor.scale_factor),
+                old_scale_factor,
                 new_monitor.scale_factor,
                 width,
                 height,
@@ -958,6 +1166,7 @@ impl UnownedWindow {
             callback(Event::WindowEvent {
                 window_id,
                 event: WindowEvent::ScaleFactorChanged {
+                    old_scale_factor,
                     scale_factor: new_monitor.scale_factor,
                     inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&inner_size)),
                 },
@@ -1015,6 +1224,13 @@ impl UnownedWindow {
 
     #[inline]
     pub fn is_maximized(&self) -> bool {
+        // The window manager applies the request asynchronously.
+        let pending_maximized = self.shared_state_lock().pending_maximized;
+        pending_maximized.unwrap_or_else(|| self.wm_maximized())
+    }
+
+    /// Returns the maximized state currently set by the window manager.
+    fn wm_maximized(&self) -> bool {
         let atoms = self.xconn.atoms();
         let state_atom = atoms[_NET_WM_STATE];
         let state = self.xconn.get_property(
@@ -1039,6 +1255,7 @@ impl UnownedWindow {
         let horz_atom = atoms[_NET_WM_STATE_MAXIMIZED_HORZ];
         let vert_atom = atoms[_NET_WM_STATE_MAXIMIZED_VERT];
 
+        self.shared_state_lock().pending_maximized = Some(maximized);
         self.set_netwm(maximized.into(), (horz_atom, vert_atom, 0, 0))
     }
 
@@ -1078,6 +1295,7 @@ impl UnownedWindow {
     pub fn set_title(&self, title: &str) {
         self.set_title_inner(title)
             .expect_then_ignore_error("Failed to set window title");
+        self.shared_state_lock().title = title.to_owned();
 
         self.xconn
             .flush_requests()
@@ -1087,8 +1305,33 @@ impl UnownedWindow {
     #[inline]
     pub fn set_transparent(&self, _transparent: bool) {}
 
+    fn set_blur_inner(&self, blur: bool) -> Result<VoidCookie<'_>, X11Error> {
+        let blur_atom = self.xconn.atoms()[_KDE_NET_WM_BLUR_BEHIND_REGION];
+        if blur {
+            // An empty region blurs behind the whole window.
+            self.xconn.change_property(
+                self.xwindow,
+                blur_atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[] as &[u32],
+            )
+        } else {
+            self.xconn
+                .xcb_connection()
+                .delete_property(self.xwindow, blur_atom)
+                .map_err(Into::into)
+        }
+    }
+
     #[inline]
-    pub fn set_blur(&self, _blur: bool) {}
+    pub fn set_blur(&self, blur: bool) {
+        self.set_blur_inner(blur)
+            .expect_then_ignore_error("Failed to set window blur");
+        self.xconn
+            .flush_requests()
+            .expect("Failed to set window blur");
+    }
 
     fn set_decorations_inner(&self, decorations: bool) -> Result<VoidCookie<'_>, X11Error> {
         self.shared_state_lock().is_decorated = decorations;
@@ -1143,6 +1386,80 @@ impl UnownedWindow {
             .expect("Failed to set window-level state");
     }
 
+    #[inline]
+    pub fn set_above(&self, sibling: &UnownedWindow) -> Result<(), ExternalError> {
+        self.restack(sibling, xproto::StackMode::ABOVE)
+    }
+
+    #[inline]
+    pub fn set_below(&self, sibling: &UnownedWindow) -> Result<(), ExternalError> {
+        self.restack(sibling, xproto::StackMode::BELOW)
+    }
+
+    fn restack(
+        &self,
+        sibling: &UnownedWindow,
+        mode: xproto::StackMode,
+    ) -> Result<(), ExternalError> {
+        let atoms = self.xconn.atoms();
+        // Top-level windows are usually reparented by the window manager, which makes them no
+        // longer siblings, so ask it to restack them instead when it supports that.
+        let cookie = if util::hint_is_supported(atoms[_NET_RESTACK_WINDOW]) {
+            self.xconn.send_client_msg(
+                self.xwindow,
+                self.root,
+                atoms[_NET_RESTACK_WINDOW],
+                Some(
+                    xproto::EventMask::SUBSTRUCTURE_REDIRECT
+                        | xproto::EventMask::SUBSTRUCTURE_NOTIFY,
+                ),
+                [2, sibling.xwindow, u32::from(mode), 0, 0],
+            )
+        } else {
+            self.xconn
+                .xcb_connection()
+                .configure_window(
+                    self.xwindow,
+                    &xproto::ConfigureWindowAux::new()
+                        .sibling(sibling.xwindow)
+                        .stack_mode(mode),
+                )
+                .map_err(Into::into)
+        };
+        cookie
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?
+            .ignore_error();
+
+        self.xconn.flush_requests().map_err(|err| {
+            ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
+        })
+    }
+
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, visible: bool) -> Result<(), NotSupportedError> {
+        self.toggle_atom(_NET_WM_STATE_STICKY, visible)
+            .expect_then_ignore_error("Failed to set sticky state");
+        self.xconn
+            .flush_requests()
+            .expect("Failed to set sticky state");
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        let atoms = self.xconn.atoms();
+        let state = self.xconn.get_property::<xproto::Atom>(
+            self.xwindow,
+            atoms[_NET_WM_STATE],
+            xproto::Atom::from(xproto::AtomEnum::ATOM),
+        );
+        let sticky_atom = atoms[_NET_WM_STATE_STICKY];
+        Some(match state {
+            Ok(atoms) => atoms.contains(&sticky_atom),
+            _ => false,
+        })
+    }
+
     fn set_icon_inner(&self, icon: PlatformIcon) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let icon_atom = atoms[_NET_WM_ICON];
@@ -1180,6 +1497,15 @@ impl UnownedWindow {
         self.xconn.flush_requests().expect("Failed to set icons");
     }
 
+    #[inline]
+    pub fn set_drop_feedback(&self, effect: DropEffect) {
+        self.shared_state_lock().drop_effect = effect;
+    }
+
+    pub(crate) fn drop_effect(&self) -> DropEffect {
+        self.shared_state_lock().drop_effect
+    }
+
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         let mut shared_state = self.shared_state_lock();
@@ -1221,7 +1547,63 @@ impl UnownedWindow {
 
     #[inline]
     pub fn is_visible(&self) -> Option<bool> {
-        Some(self.shared_state_lock().visibility == Visibility::Yes)
+        // Ask the server for the map state, since the window manager unmaps the window when it
+        // gets minimized without us being involved.
+        let attributes = self
+            .xconn
+            .xcb_connection()
+            .get_window_attributes(self.xwindow)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some(attributes.map_state == xproto::MapState::VIEWABLE)
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        // Stop selecting the input events, so the server doesn't deliver them to us, but keep
+        // the ones we need to track the state of the window.
+        let (event_mask, xinput_mask) = event_masks(enabled);
+        self.xconn
+            .xcb_connection()
+            .change_window_attributes(
+                self.xwindow,
+                &xproto::ChangeWindowAttributesAux::new().event_mask(event_mask),
+            )
+            .expect_then_ignore_error("Failed to call `xcb_change_window_attributes`");
+        self.select_xinput_events(xinput_mask, enabled)
+            .expect_then_ignore_error("Failed to select XInput2 events");
+
+        // Ask the WM to not give the keyboard focus to the disabled window.
+        let mut wm_hints =
+            WmHints::get(self.xconn.xcb_connection(), self.xwindow as xproto::Window)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .flatten()
+                .unwrap_or_default();
+        wm_hints.input = Some(enabled);
+        wm_hints
+            .set(self.xconn.xcb_connection(), self.xwindow as xproto::Window)
+            .expect_then_ignore_error("Failed to set WM hints");
+
+        self.xconn
+            .flush_requests()
+            .expect("Failed to update the window input");
+    }
+
+    /// Selects the XInput2 events of the window, along with the touchpad gestures if `gestures`
+    /// is set and the server supports them.
+    fn select_xinput_events(
+        &self,
+        mask: xinput::XIEventMask,
+        gestures: bool,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let mut mask = u64::from(u32::from(mask));
+        if gestures && self.xinput_gestures {
+            mask |= super::GESTURE_EVENT_MASK;
+        }
+        self.xconn
+            .select_xinput_events_wide(self.xwindow, super::ALL_MASTER_DEVICES, mask)
     }
 
     fn update_cached_frame_extents(&self) {
@@ -1235,6 +1617,34 @@ impl UnownedWindow {
         self.shared_state_lock().frame_extents.take();
     }
 
+    /// Fits the window in the outer size requested at creation, once the window manager has
+    /// framed the window, which it has when the window first becomes visible.
+    pub(crate) fn apply_pending_outer_size(&self) {
+        let outer_size = {
+            let mut shared_state = self.shared_state_lock();
+            if shared_state.visibility != Visibility::Yes {
+                return;
+            }
+            match shared_state.pending_outer_size.take() {
+                Some(outer_size) => outer_size,
+                None => return,
+            }
+        };
+
+        self.update_cached_frame_extents();
+        let (width, height) = outer_size.to_physical::<u32>(self.scale_factor()).into();
+        let inner_size = self
+            .shared_state_lock()
+            .frame_extents
+            .as_ref()
+            .map_or((width, height), |extents| {
+                extents.outer_size_to_inner(width, height)
+            });
+        if inner_size != self.inner_size_physical() {
+            self.request_inner_size(PhysicalSize::<u32>::from(inner_size).into());
+        }
+    }
+
     pub(crate) fn outer_position_physical(&self) -> (i32, i32) {
         let extents = self.shared_state_lock().frame_extents.clone();
         if let Some(extents) = extents {
@@ -1251,7 +1661,8 @@ impl UnownedWindow {
         let extents = self.shared_state_lock().frame_extents.clone();
         if let Some(extents) = extents {
             let (x, y) = self.inner_position_physical();
-            Ok(extents.inner_pos_to_outer(x, y).into())
+            let position = extents.inner_pos_to_outer(x, y);
+            Ok(self.xconn.screen_to_desktop(position).into())
         } else {
             self.update_cached_frame_extents();
             self.outer_position()
@@ -1269,7 +1680,8 @@ impl UnownedWindow {
 
     #[inline]
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
-        Ok(self.inner_position_physical().into())
+        let position = self.inner_position_physical();
+        Ok(self.xconn.screen_to_desktop(position).into())
     }
 
     pub(crate) fn set_position_inner(
@@ -1297,13 +1709,24 @@ impl UnownedWindow {
     }
 
     pub(crate) fn set_position_physical(&self, x: i32, y: i32) {
+        // Most WMs pick the initial position from the size hints when the window gets mapped and
+        // ignore the geometry of the unmapped window, so keep the hints in sync to not show the
+        // window at the old position first.
+        if self.shared_state_lock().visibility == Visibility::No {
+            self.update_normal_hints(|normal_hints| {
+                normal_hints.position = Some((WmSizeHintsSpecification::UserSpecified, x, y))
+            })
+            .expect("Failed to call `XSetWMNormalHints`");
+        }
+
         self.set_position_inner(x, y)
             .expect_then_ignore_error("Failed to call `XMoveWindow`");
     }
 
     #[inline]
     pub fn set_outer_position(&self, position: Position) {
-        let (x, y) = position.to_physical::<i32>(self.scale_factor()).into();
+        let position = position.to_physical::<i32>(self.scale_factor()).into();
+        let (x, y) = self.xconn.desktop_to_screen(position);
         self.set_position_physical(x, y);
     }
 
@@ -1333,7 +1756,36 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        let extents = self.shared_state_lock().frame_extents.clone();
+        if let Some(extents) = extents {
+            let extents = extents.frame_extents;
+            Insets {
+                top: extents.top,
+                bottom: extents.bottom,
+                left: extents.left,
+                right: extents.right,
+            }
+        } else {
+            self.update_cached_frame_extents();
+            self.frame_insets()
+        }
+    }
+
     pub(crate) fn request_inner_size_physical(&self, width: u32, height: u32) {
+        // See `set_position_physical`.
+        if self.shared_state_lock().visibility == Visibility::No {
+            self.update_normal_hints(|normal_hints| {
+                normal_hints.size = Some((
+                    WmSizeHintsSpecification::UserSpecified,
+                    cast_dimension_to_hint(width),
+                    cast_dimension_to_hint(height),
+                ))
+            })
+            .expect("Failed to call `XSetWMNormalHints`");
+        }
+
         self.xconn
             .xcb_connection()
             .configure_window(
@@ -1355,15 +1807,27 @@ impl UnownedWindow {
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let scale_factor = self.scale_factor();
-        let size = size.to_physical::<u32>(scale_factor).into();
-        if !self.shared_state_lock().is_resizable {
+        let size = size.to_physical::<u32>(scale_factor);
+        let (is_resizable, constraints) = {
+            let shared_state = self.shared_state_lock();
+            (
+                shared_state.is_resizable,
+                shared_state.size_constraints(scale_factor),
+            )
+        };
+        let size = if is_resizable {
+            // Apply the constraints as the window manager would, since some ignore part of them.
+            constraints.clamp(size)
+        } else {
+            let hint = cast_physical_size_to_hint(size);
             self.update_normal_hints(|normal_hints| {
-                normal_hints.min_size = Some(size);
-                normal_hints.max_size = Some(size);
+                normal_hints.min_size = Some(hint);
+                normal_hints.max_size = Some(hint);
             })
             .expect("Failed to call `XSetWMNormalHints`");
-        }
-        self.request_inner_size_physical(size.0 as u32, size.1 as u32);
+            size
+        };
+        self.request_inner_size_physical(size.width, size.height);
 
         None
     }
@@ -1390,36 +1854,20 @@ impl UnownedWindow {
         Ok(())
     }
 
-    pub(crate) fn set_min_inner_size_physical(&self, dimensions: Option<(u32, u32)>) {
-        self.update_normal_hints(|normal_hints| {
-            normal_hints.min_size =
-                dimensions.map(|(w, h)| (cast_dimension_to_hint(w), cast_dimension_to_hint(h)))
-        })
-        .expect("Failed to call `XSetWMNormalHints`");
-    }
-
     #[inline]
     pub fn set_min_inner_size(&self, dimensions: Option<Size>) {
         self.shared_state_lock().min_inner_size = dimensions;
-        let physical_dimensions =
-            dimensions.map(|dimensions| dimensions.to_physical::<u32>(self.scale_factor()).into());
-        self.set_min_inner_size_physical(physical_dimensions);
-    }
-
-    pub(crate) fn set_max_inner_size_physical(&self, dimensions: Option<(u32, u32)>) {
-        self.update_normal_hints(|normal_hints| {
-            normal_hints.max_size =
-                dimensions.map(|(w, h)| (cast_dimension_to_hint(w), cast_dimension_to_hint(h)))
-        })
-        .expect("Failed to call `XSetWMNormalHints`");
+        let min_size = dimensions.map(|size| cast_size_to_hint(size, self.scale_factor()));
+        self.update_normal_hints(|normal_hints| normal_hints.min_size = min_size)
+            .expect("Failed to call `XSetWMNormalHints`");
     }
 
     #[inline]
     pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
         self.shared_state_lock().max_inner_size = dimensions;
-        let physical_dimensions =
-            dimensions.map(|dimensions| dimensions.to_physical::<u32>(self.scale_factor()).into());
-        self.set_max_inner_size_physical(physical_dimensions);
+        let max_size = dimensions.map(|size| cast_size_to_hint(size, self.scale_factor()));
+        self.update_normal_hints(|normal_hints| normal_hints.max_size = max_size)
+            .expect("Failed to call `XSetWMNormalHints`");
     }
 
     #[inline]
@@ -1445,6 +1893,15 @@ impl UnownedWindow {
             .expect("Failed to call `XSetWMNormalHints`");
     }
 
+    #[inline]
+    pub fn set_base_size(&self, base_size: Option<Size>) {
+        self.shared_state_lock().base_size = base_size;
+        let physical_base_size =
+            base_size.map(|base_size| cast_size_to_hint(base_size, self.scale_factor()));
+        self.update_normal_hints(|hints| hints.base_size = physical_base_size)
+            .expect("Failed to call `XSetWMNormalHints`");
+    }
+
     pub(crate) fn adjust_for_dpi(
         &self,
         old_scale_factor: f64,
@@ -1454,22 +1911,30 @@ impl UnownedWindow {
         shared_state: &SharedState,
     ) -> (u32, u32) {
         let scale_factor = new_scale_factor / old_scale_factor;
+        let new_width = (width as f64 * scale_factor).round() as u32;
+        let new_height = (height as f64 * scale_factor).round() as u32;
+
         self.update_normal_hints(|normal_hints| {
-            let dpi_adjuster = |size: Size| -> (i32, i32) { cast_size_to_hint(size, scale_factor) };
-            let max_size = shared_state.max_inner_size.map(dpi_adjuster);
-            let min_size = shared_state.min_inner_size.map(dpi_adjuster);
-            let resize_increments = shared_state.resize_increments.map(dpi_adjuster);
-            let base_size = shared_state.base_size.map(dpi_adjuster);
+            // Derive the constraints from the sizes they were requested with, so that e.g. a
+            // logical minimum keeps its logical size on the new monitor.
+            let (min_size, max_size) = if shared_state.is_resizable {
+                shared_state.min_max_size_hints(new_scale_factor)
+            } else {
+                let size = Some(cast_physical_size_to_hint((new_width, new_height).into()));
+                (size, size)
+            };
+            let hint = |size: Size| cast_size_to_hint(size, new_scale_factor);
 
             normal_hints.max_size = max_size;
             normal_hints.min_size = min_size;
-            normal_hints.size_increment = resize_increments;
-            normal_hints.base_size = base_size;
+            normal_hints.size_increment = shared_state.resize_increments.map(hint);
+            normal_hints.base_size = shared_state.base_size.map(hint);
         })
         .expect("Failed to update normal hints");
 
-        let new_width = (width as f64 * scale_factor).round() as u32;
-        let new_height = (height as f64 * scale_factor).round() as u32;
+        if let Some((x, y)) = shared_state.ime_position(new_scale_factor) {
+            self.send_ime_position(x, y);
+        }
 
         (new_width, new_height)
     }
@@ -1483,14 +1948,11 @@ impl UnownedWindow {
             return;
         }
 
-        let (min_size, max_size) = if resizable {
-            let shared_state_lock = self.shared_state_lock();
-            (
-                shared_state_lock.min_inner_size,
-                shared_state_lock.max_inner_size,
-            )
+        let (min_inner_size, max_inner_size) = if resizable {
+            let scale_factor = self.scale_factor();
+            self.shared_state_lock().min_max_size_hints(scale_factor)
         } else {
-            let window_size = Some(Size::from(self.inner_size()));
+            let window_size = Some(cast_physical_size_to_hint(self.inner_size()));
             (window_size, window_size)
         };
         self.shared_state_lock().is_resizable = resizable;
@@ -1498,9 +1960,6 @@ impl UnownedWindow {
         self.set_maximizable_inner(resizable)
             .expect_then_ignore_error("Failed to call `XSetWMNormalHints`");
 
-        let scale_factor = self.scale_factor();
-        let min_inner_size = min_size.map(|size| cast_size_to_hint(size, scale_factor));
-        let max_inner_size = max_size.map(|size| cast_size_to_hint(size, scale_factor));
         self.update_normal_hints(|normal_hints| {
             normal_hints.min_size = min_inner_size;
             normal_hints.max_size = max_inner_size;
@@ -1535,89 +1994,212 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
-        let old_cursor = replace(&mut *self.cursor.lock().unwrap(), cursor);
+        let mut cursor_regions = self.cursor_regions.lock().unwrap();
+        let previous = replace(&mut *self.cursor.lock().unwrap(), cursor);
+        let changed = cursor_regions.cursor_set(previous, cursor);
+        drop(cursor_regions);
+        #[allow(clippy::mutex_atomic)]
+        if changed && *self.cursor_visible.lock().unwrap() {
+            self.xconn.set_cursor_icon(self.xwindow, Some(cursor));
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        let changed = self.cursor_regions.lock().unwrap().set_regions(regions);
+        if changed {
+            self.show_cursor();
+        }
+    }
+
+    /// Shows the cursor of the region under the pointer, see [`CursorRegions`].
+    pub(super) fn update_hovered_cursor(&self, position: PhysicalPosition<f64>) {
+        let changed = self.cursor_regions.lock().unwrap().cursor_moved(position);
+        if changed {
+            self.show_cursor();
+        }
+    }
+
+    pub(super) fn clear_hovered_cursor(&self) {
+        let changed = self.cursor_regions.lock().unwrap().cursor_left();
+        if changed {
+            self.show_cursor();
+        }
+    }
+
+    fn show_cursor(&self) {
+        let cursor_regions = self.cursor_regions.lock().unwrap();
+        let cursor = cursor_regions.cursor(*self.cursor.lock().unwrap());
+        drop(cursor_regions);
         #[allow(clippy::mutex_atomic)]
-        if cursor != old_cursor && *self.cursor_visible.lock().unwrap() {
+        if *self.cursor_visible.lock().unwrap() {
             self.xconn.set_cursor_icon(self.xwindow, Some(cursor));
         }
     }
 
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.auto_grab.lock().unwrap().grab_changed();
+        self.grab_cursor(mode)
+    }
+
+    #[inline]
+    pub fn set_auto_grab(&self, auto_grab: bool) {
+        self.auto_grab.lock().unwrap().set_enabled(auto_grab);
+    }
+
+    /// Releases or re-applies the cursor grab with [`AutoGrab`] when the focus changed.
+    pub(crate) fn update_auto_grab(&self, focused: bool) {
+        let mode = if focused {
+            self.auto_grab.lock().unwrap().focus_gained()
+        } else {
+            let mode = *self.cursor_grabbed_mode.lock().unwrap();
+            self.auto_grab
+                .lock()
+                .unwrap()
+                .focus_lost(mode)
+                .then_some(CursorGrabMode::None)
+        };
+
+        if let Some(mode) = mode {
+            if let Err(err) = self.grab_cursor(mode) {
+                warn!("Failed to update the cursor grab after a focus change: {err}");
+            }
+        }
+
+        // Without a grab mode, the confinement only holds while the window is focused.
+        let grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
+        if *grabbed_lock == CursorGrabMode::None
+            && self.cursor_confinement.lock().unwrap().is_some()
+        {
+            if let Err(err) = self.apply_cursor_grab(CursorGrabMode::None) {
+                warn!("Failed to update the cursor confinement after a focus change: {err}");
+            }
+        }
+    }
+
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        let grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
+        {
+            let mut confinement = self.cursor_confinement.lock().unwrap();
+            let conn = self.xconn.xcb_connection();
+            let result = match (rect, *confinement) {
+                (Some(rect), Some(window)) => conn
+                    .configure_window(
+                        window,
+                        &xproto::ConfigureWindowAux::new()
+                            .x(rect.position.x)
+                            .y(rect.position.y)
+                            .width(rect.size.width.max(1))
+                            .height(rect.size.height.max(1)),
+                    )
+                    .map(|cookie| cookie.ignore_error())
+                    .map_err(X11Error::from),
+                (Some(rect), None) => self
+                    .create_cursor_confinement_window(rect)
+                    .map(|window| *confinement = Some(window)),
+                (None, Some(window)) => {
+                    *confinement = None;
+                    conn.destroy_window(window)
+                        .map(|cookie| cookie.ignore_error())
+                        .map_err(X11Error::from)
+                }
+                (None, None) => return Ok(()),
+            };
+            result.map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        }
+
+        self.apply_cursor_grab(*grabbed_lock)
+    }
+
+    /// Creates and maps an input-only child window covering `rect`, to confine the cursor to.
+    ///
+    /// Being a child of the window, it follows the window when it's moved.
+    fn create_cursor_confinement_window(
+        &self,
+        rect: PhysicalRect,
+    ) -> Result<xproto::Window, X11Error> {
+        let conn = self.xconn.xcb_connection();
+        let window = conn.generate_id()?;
+        conn.create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            window,
+            self.xwindow,
+            rect.position.x as i16,
+            rect.position.y as i16,
+            rect.size.width.max(1) as u16,
+            rect.size.height.max(1) as u16,
+            0,
+            xproto::WindowClass::INPUT_ONLY,
+            x11rb::COPY_FROM_PARENT,
+            &xproto::CreateWindowAux::new(),
+        )?;
+
+        // An empty input shape lets the input through to the window.
+        let region = RegionWrapper::create_region(conn, &[])?;
+        conn.xfixes_set_window_shape_region(window, SK::INPUT, 0, 0, region.region())?;
+        conn.map_window(window)?;
+        Ok(window)
+    }
+
+    /// Re-applies a `Confined` grab, so that it follows the client area after a move or resize.
+    pub(crate) fn refresh_cursor_confinement(&self) {
+        let grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
+        if *grabbed_lock != CursorGrabMode::Confined {
+            return;
+        }
+
+        if let Err(err) = self.apply_cursor_grab(CursorGrabMode::Confined) {
+            warn!("Failed to update the cursor confinement: {err}");
+        }
+    }
+
+    fn grab_cursor(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
         if mode == *grabbed_lock {
             return Ok(());
         }
 
-        // We ungrab before grabbing to prevent passive grabs from causing `AlreadyGrabbed`.
-        // Therefore, this is common to both codepaths.
-        self.xconn
-            .xcb_connection()
-            .ungrab_pointer(x11rb::CURRENT_TIME)
-            .expect_then_ignore_error("Failed to call `xcb_ungrab_pointer`");
+        let result = self.apply_cursor_grab(mode);
+        if result.is_ok() {
+            *grabbed_lock = mode;
+        }
 
-        let result = match mode {
-            CursorGrabMode::None => self.xconn.flush_requests().map_err(|err| {
-                ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
-            }),
-            CursorGrabMode::Confined => {
-                let result = {
-                    self.xconn
-                        .xcb_connection()
-                        .grab_pointer(
-                            true as _,
-                            self.xwindow,
-                            xproto::EventMask::BUTTON_PRESS
-                                | xproto::EventMask::BUTTON_RELEASE
-                                | xproto::EventMask::ENTER_WINDOW
-                                | xproto::EventMask::LEAVE_WINDOW
-                                | xproto::EventMask::POINTER_MOTION
-                                | xproto::EventMask::POINTER_MOTION_HINT
-                                | xproto::EventMask::BUTTON1_MOTION
-                                | xproto::EventMask::BUTTON2_MOTION
-                                | xproto::EventMask::BUTTON3_MOTION
-                                | xproto::EventMask::BUTTON4_MOTION
-                                | xproto::EventMask::BUTTON5_MOTION
-                                | xproto::EventMask::KEYMAP_STATE,
-                            xproto::GrabMode::ASYNC,
-                            xproto::GrabMode::ASYNC,
-                            self.xwindow,
-                            0u32,
-                            x11rb::CURRENT_TIME,
-                        )
-                        .expect("Failed to call `grab_pointer`")
-                        .reply()
-                        .expect("Failed to receive reply from `grab_pointer`")
-                };
+        result
+    }
 
-                match result.status {
-                    xproto::GrabStatus::SUCCESS => Ok(()),
-                    xproto::GrabStatus::ALREADY_GRABBED => {
-                        Err("Cursor could not be confined: already confined by another client")
-                    }
-                    xproto::GrabStatus::INVALID_TIME => {
-                        Err("Cursor could not be confined: invalid time")
-                    }
-                    xproto::GrabStatus::NOT_VIEWABLE => {
-                        Err("Cursor could not be confined: confine location not viewable")
-                    }
-                    xproto::GrabStatus::FROZEN => {
-                        Err("Cursor could not be confined: frozen by another client")
-                    }
-                    _ => unreachable!(),
-                }
-                .map_err(|err| ExternalError::Os(os_error!(OsError::Misc(err))))
-            }
+    fn apply_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        // The rectangle of `confine_cursor_to_rect` replaces the window as the confinement, and
+        // is confined to without a grab mode too while the window is focused.
+        let confinement = *self.cursor_confinement.lock().unwrap();
+        let confine_to = match mode {
+            CursorGrabMode::None => confinement.filter(|_| self.has_focus()),
+            CursorGrabMode::Confined => Some(confinement.unwrap_or(self.xwindow)),
             CursorGrabMode::Locked => {
-                return Err(ExternalError::NotSupported(NotSupportedError::new()));
+                return Err(ExternalError::NotSupported(NotSupportedError::new()))
             }
         };
 
-        if result.is_ok() {
-            *grabbed_lock = mode;
+        match confine_to {
+            None => self.xconn.release_window_pointer_grab(self.xwindow),
+            Some(confine_to) => self.xconn.set_window_pointer_grab(util::PointerGrab {
+                window: self.xwindow,
+                confine_to,
+                event_mask: xproto::EventMask::BUTTON_PRESS
+                    | xproto::EventMask::BUTTON_RELEASE
+                    | xproto::EventMask::ENTER_WINDOW
+                    | xproto::EventMask::LEAVE_WINDOW
+                    | xproto::EventMask::POINTER_MOTION
+                    | xproto::EventMask::POINTER_MOTION_HINT
+                    | xproto::EventMask::BUTTON1_MOTION
+                    | xproto::EventMask::BUTTON2_MOTION
+                    | xproto::EventMask::BUTTON3_MOTION
+                    | xproto::EventMask::BUTTON4_MOTION
+                    | xproto::EventMask::BUTTON5_MOTION
+                    | xproto::EventMask::KEYMAP_STATE,
+            }),
         }
-
-        result
     }
 
     #[inline]
@@ -1628,7 +2210,8 @@ impl UnownedWindow {
             return;
         }
         let cursor = if visible {
-            Some(*self.cursor.lock().unwrap())
+            let cursor_regions = self.cursor_regions.lock().unwrap();
+            Some(cursor_regions.cursor(*self.cursor.lock().unwrap()))
         } else {
             None
         };
@@ -1643,17 +2226,42 @@ impl UnownedWindow {
     }
 
     pub fn set_cursor_position_physical(&self, x: i32, y: i32) -> Result<(), ExternalError> {
-        {
-            self.xconn
-                .xcb_connection()
-                .warp_pointer(x11rb::NONE, self.xwindow, 0, 0, 0, 0, x as _, y as _)
-                .map_err(|e| {
-                    ExternalError::Os(os_error!(OsError::XError(X11Error::from(e).into())))
-                })?;
-            self.xconn.flush_requests().map_err(|e| {
-                ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(e).into())))
-            })
+        // Only the latest warp of an iteration is sent, so warping every frame doesn't cost a
+        // flush per call.
+        let already_queued = self
+            .shared_state_lock()
+            .queued_cursor_warp
+            .replace((x, y))
+            .is_some();
+        if !already_queued {
+            self.cursor_warp_sender
+                .send(self.id())
+                .map_err(|_| ExternalError::Ignored)?;
         }
+        Ok(())
+    }
+
+    /// Sends the warp queued by `set_cursor_position_physical`, without flushing it.
+    pub(super) fn send_queued_cursor_warp(&self) -> Result<(), X11Error> {
+        let mut shared_state_lock = self.shared_state_lock();
+        let (x, y) = match shared_state_lock.queued_cursor_warp.take() {
+            Some(position) => position,
+            None => return Ok(()),
+        };
+        shared_state_lock.expected_cursor_warp = Some((x, y));
+        drop(shared_state_lock);
+
+        self.xconn.xcb_connection().warp_pointer(
+            x11rb::NONE,
+            self.xwindow,
+            0,
+            0,
+            0,
+            0,
+            x as _,
+            y as _,
+        )?;
+        Ok(())
     }
 
     #[inline]
@@ -1666,13 +2274,17 @@ impl UnownedWindow {
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
         let mut rectangles: Vec<Rectangle> = Vec::new();
         if hittest {
-            let size = self.inner_size();
-            rectangles.push(Rectangle {
-                x: 0,
-                y: 0,
-                width: size.width as u16,
-                height: size.height as u16,
-            })
+            if let Some(shape) = self.shared_state_lock().shape.clone() {
+                rectangles = shape;
+            } else {
+                let size = self.inner_size();
+                rectangles.push(Rectangle {
+                    x: 0,
+                    y: 0,
+                    width: size.width as u16,
+                    height: size.height as u16,
+                })
+            }
         }
         let region = RegionWrapper::create_region(self.xconn.xcb_connection(), &rectangles)
             .map_err(|_e| ExternalError::Ignored)?;
@@ -1684,6 +2296,52 @@ impl UnownedWindow {
         Ok(())
     }
 
+    #[inline]
+    pub fn set_shape(&self, shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        let rectangles = shape.map(|shape| {
+            shape
+                .rectangles()
+                .into_iter()
+                .map(|(position, size)| Rectangle {
+                    x: position.x as i16,
+                    y: position.y as i16,
+                    width: size.width as u16,
+                    height: size.height as u16,
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let conn = self.xconn.xcb_connection();
+        // Without a region the server restores the default, rectangular, shape.
+        let region = match rectangles {
+            Some(ref rectangles) => Some(
+                RegionWrapper::create_region(conn, rectangles)
+                    .map_err(|_e| ExternalError::Ignored)?,
+            ),
+            None => None,
+        };
+        let region_id = region
+            .as_ref()
+            .map_or(x11rb::NONE, |region| region.region());
+
+        conn.xfixes_set_window_shape_region(self.xwindow, SK::BOUNDING, 0, 0, region_id)
+            .map_err(|_e| ExternalError::Ignored)?;
+
+        let mut shared_state_lock = self.shared_state_lock();
+        // Keep the input shape empty while hittest is disabled.
+        if shared_state_lock.cursor_hittest != Some(false) {
+            conn.xfixes_set_window_shape_region(self.xwindow, SK::INPUT, 0, 0, region_id)
+                .map_err(|_e| ExternalError::Ignored)?;
+        }
+        shared_state_lock.shape = rectangles;
+        drop(shared_state_lock);
+
+        self.xconn
+            .flush_requests()
+            .map_err(|_e| ExternalError::Ignored)?;
+        Ok(())
+    }
+
     /// Moves the window while it is being dragged.
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         self.drag_initiate(util::MOVERESIZE_MOVE)
@@ -1713,7 +2371,7 @@ impl UnownedWindow {
             .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
             .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
 
-        let window = self.inner_position().map_err(ExternalError::NotSupported)?;
+        let (window_x, window_y) = self.inner_position_physical();
 
         let atoms = self.xconn.atoms();
         let message = atoms[_NET_WM_MOVERESIZE];
@@ -1731,6 +2389,8 @@ impl UnownedWindow {
         self.xconn.flush_requests().map_err(|err| {
             ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
         })?;
+        // The window manager grabs the pointer for the drag, so the clip isn't grabbed again.
+        self.xconn.forget_window_pointer_grab(self.xwindow);
         *grabbed_lock = CursorGrabMode::None;
 
         // we keep the lock until we are done
@@ -1744,8 +2404,8 @@ impl UnownedWindow {
                         | xproto::EventMask::SUBSTRUCTURE_NOTIFY,
                 ),
                 [
-                    (window.x as u32 + xinput_fp1616_to_float(pointer.win_x) as u32),
-                    (window.y as u32 + xinput_fp1616_to_float(pointer.win_y) as u32),
+                    (window_x as u32 + xinput_fp1616_to_float(pointer.win_x) as u32),
+                    (window_y as u32 + xinput_fp1616_to_float(pointer.win_y) as u32),
                     action.try_into().unwrap(),
                     1, // Button 1
                     1,
@@ -1759,8 +2419,16 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_ime_cursor_area(&self, spot: Position, _size: Size) {
-        let (x, y) = spot.to_physical::<i32>(self.scale_factor()).into();
+    pub fn set_ime_cursor_area(&self, spot: Position, size: Size) {
+        let scale_factor = self.scale_factor();
+        let mut shared_state = self.shared_state_lock();
+        shared_state.ime_cursor_area = Some((spot, size));
+        if let Some((x, y)) = shared_state.ime_position(scale_factor) {
+            self.send_ime_position(x, y);
+        }
+    }
+
+    fn send_ime_position(&self, x: i16, y: i16) {
         let _ = self.ime_sender.lock().unwrap().send(ImeRequest::Position(
             self.xwindow as ffi::Window,
             x,
@@ -1781,18 +2449,12 @@ impl UnownedWindow {
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
         let atoms = self.xconn.atoms();
-        let state_atom = atoms[WM_STATE];
-        let state_type_atom = atoms[CARD32];
-        let is_minimized = if let Ok(state) =
-            self.xconn
-                .get_property::<u32>(self.xwindow, state_atom, state_type_atom)
-        {
-            state.contains(&super::ICONIC_STATE)
-        } else {
-            false
-        };
+        let is_minimized = self.is_minimized().unwrap_or(false);
         let is_visible = match self.shared_state_lock().visibility {
             Visibility::Yes => true,
             Visibility::YesWait | Visibility::No => false,
@@ -1818,6 +2480,7 @@ impl UnownedWindow {
                 );
             }
         }
+        Ok(())
     }
 
     #[inline]
@@ -1877,7 +2540,34 @@ impl UnownedWindow {
 
     #[inline]
     pub fn pre_present_notify(&self) {
-        // TODO timer
+        self.update_sync_counter();
+    }
+
+    /// Tells the window manager that the frame for the last configured `_NET_WM_SYNC_REQUEST`
+    /// is being presented.
+    pub(super) fn update_sync_counter(&self) {
+        let counter = match self.sync_counter {
+            Some(counter) => counter,
+            None => return,
+        };
+
+        let value = {
+            let mut shared_state = self.shared_state_lock();
+            match shared_state.sync_request {
+                Some((value, true)) => {
+                    shared_state.sync_request = None;
+                    value
+                }
+                _ => return,
+            }
+        };
+
+        if let Ok(c) = self.xconn.xcb_connection().sync_set_counter(counter, value) {
+            c.ignore_error();
+        }
+        self.xconn
+            .flush_requests()
+            .expect("Failed to update the sync counter");
     }
 
     #[cfg(feature = "rwh_04")]
@@ -1933,18 +2623,77 @@ impl UnownedWindow {
 
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
-        None
+        self.shared_state_lock().theme
     }
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        let to_external = |err: X11Error| ExternalError::Os(os_error!(OsError::XError(err.into())));
+        let size = self.inner_size();
+        let image = self
+            .xconn
+            .xcb_connection()
+            .get_image(
+                xproto::ImageFormat::Z_PIXMAP,
+                self.xwindow,
+                0,
+                0,
+                size.width as u16,
+                size.height as u16,
+                !0,
+            )
+            .map_err(|err| to_external(err.into()))?
+            .reply()
+            .map_err(|err| to_external(err.into()))?;
+
+        let setup = self.xconn.xcb_connection().setup();
+        let format = setup
+            .pixmap_formats
+            .iter()
+            .find(|format| format.depth == image.depth);
+        let visual = setup
+            .roots
+            .iter()
+            .flat_map(|root| &root.allowed_depths)
+            .flat_map(|depth| &depth.visuals)
+            .find(|visual| visual.visual_id == self.visual);
+        let (format, visual) = match (format, visual) {
+            (Some(format), Some(visual))
+                if matches!(format.bits_per_pixel, 24 | 32)
+                    && visual.class == xproto::VisualClass::TRUE_COLOR =>
+            {
+                (format, visual)
+            }
+            _ => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+
+        let layout = PixelLayout {
+            bits_per_pixel: format.bits_per_pixel,
+            scanline_pad: format.scanline_pad,
+            lsb_first: setup.image_byte_order == xproto::ImageOrder::LSB_FIRST,
+            red_mask: visual.red_mask,
+            green_mask: visual.green_mask,
+            blue_mask: visual.blue_mask,
+            // Without an alpha channel in the visual, the window is opaque.
+            alpha_mask: if image.depth == 32 {
+                !(visual.red_mask | visual.green_mask | visual.blue_mask)
+            } else {
+                0
+            },
+        };
+        let rgba = image_to_rgba(&image.data, size, &layout)
+            .ok_or_else(|| ExternalError::NotSupported(NotSupportedError::new()))?;
+        Ok(ImageBuffer::from_rgba(rgba, size))
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.shared_state_lock().has_focus
     }
 
     pub fn title(&self) -> String {
-        String::new()
+        self.shared_state_lock().title.clone()
     }
 }
 
@@ -1969,3 +2718,221 @@ fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
         Size::Logical(size) => size.to_physical::<i32>(scale_factor).into(),
     }
 }
+
+/// The layout of the pixels of a `Z_PIXMAP` image, with 24 or 32 bits per pixel.
+#[derive(Debug, Clone, Copy)]
+struct PixelLayout {
+    bits_per_pixel: u8,
+    /// The multiple of bits the rows are padded to.
+    scanline_pad: u8,
+    lsb_first: bool,
+    red_mask: u32,
+    green_mask: u32,
+    blue_mask: u32,
+    /// The mask of the alpha channel, which is zero for opaque images.
+    alpha_mask: u32,
+}
+
+/// Converts the data of a `Z_PIXMAP` image of `size` to RGBA, or returns `None` if it's
+/// incomplete.
+fn image_to_rgba(data: &[u8], size: PhysicalSize<u32>, layout: &PixelLayout) -> Option<Vec<u8>> {
+    let (width, height) = (size.width as usize, size.height as usize);
+    let bits_per_pixel = layout.bits_per_pixel as usize;
+    let pad = layout.scanline_pad as usize;
+    let stride = (width * bits_per_pixel + pad - 1) / pad * pad / 8;
+    let channel = |pixel: u32, mask: u32| -> u8 {
+        if mask == 0 {
+            return 0xff;
+        }
+        let shift = mask.trailing_zeros();
+        let max = u64::from(mask >> shift);
+        (u64::from((pixel & mask) >> shift) * 0xff / max) as u8
+    };
+
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in data.chunks(stride).take(height) {
+        for bytes in row.chunks_exact(bits_per_pixel / 8).take(width) {
+            let pixel = if layout.lsb_first {
+                bytes
+                    .iter()
+                    .rev()
+                    .fold(0, |pixel, &byte| pixel << 8 | u32::from(byte))
+            } else {
+                bytes
+                    .iter()
+                    .fold(0, |pixel, &byte| pixel << 8 | u32::from(byte))
+            };
+            let alpha = channel(pixel, layout.alpha_mask);
+            // The colors of ARGB visuals are premultiplied by the alpha.
+            let unpremultiply = |color: u8| match alpha {
+                0 => 0,
+                alpha => (u16::from(color) * 0xff / u16::from(alpha)).min(0xff) as u8,
+            };
+            rgba.extend_from_slice(&[
+                unpremultiply(channel(pixel, layout.red_mask)),
+                unpremultiply(channel(pixel, layout.green_mask)),
+                unpremultiply(channel(pixel, layout.blue_mask)),
+                alpha,
+            ]);
+        }
+    }
+    (rgba.len() == width * height * 4).then_some(rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ICONIC_STATE;
+    use super::*;
+    use crate::dpi::{LogicalPosition, LogicalSize};
+
+    const HIDDEN: xproto::Atom = 1;
+    const NORMAL_STATE: u32 = 1;
+
+    const RGB: PixelLayout = PixelLayout {
+        bits_per_pixel: 24,
+        scanline_pad: 32,
+        lsb_first: true,
+        red_mask: 0xff0000,
+        green_mask: 0x00ff00,
+        blue_mask: 0x0000ff,
+        alpha_mask: 0,
+    };
+
+    #[test]
+    fn image_rows_are_read_past_their_padding() {
+        // Two rows of three pixels, each padded from 9 to 12 bytes.
+        let data = [
+            0x30, 0x20, 0x10, 0x60, 0x50, 0x40, 0x90, 0x80, 0x70, 0, 0, 0, //
+            0xff, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0xff, 0, 0, 0,
+        ];
+
+        let rgba = image_to_rgba(&data, PhysicalSize::new(3, 2), &RGB).unwrap();
+        assert_eq!(
+            rgba,
+            [
+                0x10, 0x20, 0x30, 0xff, 0x40, 0x50, 0x60, 0xff, 0x70, 0x80, 0x90, 0xff, //
+                0x00, 0x00, 0xff, 0xff, 0x00, 0xff, 0x00, 0xff, 0xff, 0x00, 0x00, 0xff,
+            ]
+        );
+
+        // The last row is missing.
+        assert_eq!(
+            image_to_rgba(&data[..12], PhysicalSize::new(3, 2), &RGB),
+            None
+        );
+    }
+
+    #[test]
+    fn image_byte_order_is_respected() {
+        let msb_first = PixelLayout {
+            lsb_first: false,
+            ..RGB
+        };
+
+        let data = [0x10, 0x20, 0x30, 0];
+        let rgba = image_to_rgba(&data, PhysicalSize::new(1, 1), &msb_first).unwrap();
+        assert_eq!(rgba, [0x10, 0x20, 0x30, 0xff]);
+    }
+
+    #[test]
+    fn argb_images_are_unpremultiplied() {
+        let argb = PixelLayout {
+            bits_per_pixel: 32,
+            alpha_mask: 0xff000000,
+            ..RGB
+        };
+
+        // Half-transparent white, transparent, and opaque red.
+        let data = [
+            0x80, 0x80, 0x80, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff,
+        ];
+        let rgba = image_to_rgba(&data, PhysicalSize::new(3, 1), &argb).unwrap();
+        assert_eq!(
+            rgba,
+            [0xff, 0xff, 0xff, 0x80, 0, 0, 0, 0, 0xff, 0x00, 0x00, 0xff]
+        );
+    }
+
+    #[test]
+    fn minimize_then_restore_is_reported_once_each() {
+        let mut reported = false;
+
+        // The window manager iconifies the window, and then hides it in a second property change.
+        let minimized = is_minimized_state(&[ICONIC_STATE, 0], &[], HIDDEN);
+        assert_eq!(minimized_changed(&mut reported, minimized), Some(true));
+        let minimized = is_minimized_state(&[ICONIC_STATE, 0], &[HIDDEN], HIDDEN);
+        assert_eq!(minimized_changed(&mut reported, minimized), None);
+
+        let minimized = is_minimized_state(&[NORMAL_STATE, 0], &[], HIDDEN);
+        assert_eq!(minimized_changed(&mut reported, minimized), Some(false));
+        assert_eq!(minimized_changed(&mut reported, minimized), None);
+    }
+
+    #[test]
+    fn hidden_windows_are_minimized_without_wm_state() {
+        assert!(is_minimized_state(&[], &[2, HIDDEN], HIDDEN));
+        assert!(!is_minimized_state(&[], &[2], HIDDEN));
+        assert!(!is_minimized_state(&[NORMAL_STATE, 0], &[], HIDDEN));
+    }
+
+    #[test]
+    fn maximize_request_is_reported_until_confirmed() {
+        let mut pending = Some(true);
+        confirm_maximized(&mut pending, false);
+        assert_eq!(pending, Some(true));
+        confirm_maximized(&mut pending, true);
+        assert_eq!(pending, None);
+    }
+
+    #[test]
+    fn unset_theme_only_defaults_the_hint() {
+        assert_eq!(gtk_theme_variant(None), "dark");
+        assert_eq!(gtk_theme_variant(Some(Theme::Dark)), "dark");
+        assert_eq!(gtk_theme_variant(Some(Theme::Light)), "light");
+    }
+
+    #[test]
+    fn size_hints_follow_scale_factor_changes() {
+        let mut shared_state =
+            SharedState::new(X11MonitorHandle::dummy(), &WindowAttributes::default())
+                .into_inner()
+                .unwrap();
+        shared_state.min_inner_size = Some(LogicalSize::new(640, 480).into());
+        shared_state.max_inner_size = Some(PhysicalSize::new(1920, 1080).into());
+
+        assert_eq!(
+            shared_state.min_max_size_hints(1.0),
+            (Some((640, 480)), Some((1920, 1080)))
+        );
+
+        // The window moved to a monitor with twice the density.
+        assert_eq!(
+            shared_state.min_max_size_hints(2.0),
+            (Some((1280, 960)), Some((1920, 1080)))
+        );
+    }
+
+    #[test]
+    fn ime_position_follows_scale_factor_changes() {
+        let mut shared_state =
+            SharedState::new(X11MonitorHandle::dummy(), &WindowAttributes::default())
+                .into_inner()
+                .unwrap();
+        assert_eq!(shared_state.ime_position(1.0), None);
+
+        shared_state.ime_cursor_area = Some((
+            LogicalPosition::new(100, 50).into(),
+            LogicalSize::new(20, 10).into(),
+        ));
+        assert_eq!(shared_state.ime_position(1.0), Some((100, 50)));
+
+        // The window moved to a monitor with twice the density, without a new cursor area.
+        assert_eq!(shared_state.ime_position(2.0), Some((200, 100)));
+
+        shared_state.ime_cursor_area = Some((
+            PhysicalPosition::new(100, 50).into(),
+            PhysicalSize::new(20, 10).into(),
+        ));
+        assert_eq!(shared_state.ime_position(2.0), Some((100, 50)));
+    }
+}
@@ -10,11 +10,12 @@ use std::{
 
 use crate::window::CursorIcon;
 
-use super::{atoms::Atoms, ffi, monitor::MonitorHandle};
+use super::{atoms::Atoms, ffi, monitor::MonitorHandle, util::PointerGrabs};
 use x11rb::{
-    connection::Connection,
+    connection::{Connection, RequestConnection},
     protocol::{
         randr::ConnectionExt as _,
+        sync::{self, ConnectionExt as _},
         xproto::{self, ConnectionExt},
     },
     resource_manager,
@@ -58,9 +59,15 @@ pub struct XConnection {
     /// RandR version.
     randr_version: (u32, u32),
 
+    /// Whether the XSync extension is available, for the `_NET_WM_SYNC_REQUEST` protocol.
+    sync_supported: bool,
+
     /// Atom for the XSettings screen.
     xsettings_screen: Option<xproto::Atom>,
 
+    /// The pointer grabs of the application, which share the single grab of the client.
+    pub pointer_grabs: Mutex<PointerGrabs>,
+
     pub latest_error: Mutex<Option<XError>>,
     pub cursor_cache: Mutex<HashMap<Option<CursorIcon>, ffi::Cursor>>,
 }
@@ -119,6 +126,18 @@ impl XConnection {
             .reply()
             .expect("failed to query XRandR version");
 
+        // Initialize the XSync extension, which has to be done before using it.
+        let sync_supported = xcb
+            .extension_information(sync::X11_EXTENSION_NAME)
+            .ok()
+            .flatten()
+            .is_some()
+            && xcb
+                .sync_initialize(3, 1)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .is_some();
+
         let xsettings_screen = Self::new_xsettings_screen(&xcb, default_screen);
         if xsettings_screen.is_none() {
             log::warn!("error setting XSETTINGS; Xft options won't reload automatically")
@@ -143,7 +162,9 @@ impl XConnection {
             monitor_handles: Mutex::new(None),
             database: RwLock::new(database),
             cursor_cache: Default::default(),
+            pointer_grabs: Default::default(),
             randr_version: (randr_version.major_version, randr_version.minor_version),
+            sync_supported,
             xsettings_screen,
         })
     }
@@ -195,6 +216,12 @@ impl XConnection {
         self.randr_version
     }
 
+    /// Whether the XSync extension is available.
+    #[inline]
+    pub fn sync_supported(&self) -> bool {
+        self.sync_supported
+    }
+
     /// Get the underlying XCB connection.
     #[inline]
     pub fn xcb_connection(&self) -> &XCBConnection {
@@ -103,6 +103,7 @@ enum EventWrapper {
     ScaleFactorChanged {
         window: Id<WinitWindow>,
         suggested_size: PhysicalSize<u32>,
+        old_scale_factor: f64,
         scale_factor: f64,
     },
 }
@@ -110,9 +111,7 @@ enum EventWrapper {
 #[derive(Default)]
 struct Handler {
     stop_app_on_launch: AtomicBool,
-    stop_app_before_wait: AtomicBool,
-    stop_app_after_wait: AtomicBool,
-    stop_app_on_redraw: AtomicBool,
+    spinning: AtomicBool,
     launched: AtomicBool,
     running: AtomicBool,
     in_callback: AtomicBool,
@@ -122,7 +121,6 @@ struct Handler {
     callback: Mutex<Option<Box<dyn EventHandler>>>,
     pending_events: Mutex<VecDeque<EventWrapper>>,
     pending_redraw: Mutex<Vec<WindowId>>,
-    wait_timeout: Mutex<Option<Instant>>,
     waker: Mutex<EventLoopWaker>,
 }
 
@@ -199,10 +197,7 @@ impl Handler {
         // looks like there have been recuring re-entrancy issues with callback handling that might
         // make that awkward)
         self.running.store(false, Ordering::Relaxed);
-        self.set_stop_app_on_redraw_requested(false);
-        self.set_stop_app_before_wait(false);
-        self.set_stop_app_after_wait(false);
-        self.set_wait_timeout(None);
+        self.set_spinning(false);
     }
 
     pub fn exit(&self) {
@@ -229,52 +224,16 @@ impl Handler {
         self.stop_app_on_launch.load(Ordering::Relaxed)
     }
 
-    pub fn set_stop_app_before_wait(&self, stop_before_wait: bool) {
+    pub fn set_spinning(&self, spinning: bool) {
         // Relaxed ordering because we don't actually have multiple threads involved, we just want
         // interior mutability
-        self.stop_app_before_wait
-            .store(stop_before_wait, Ordering::Relaxed);
+        self.spinning.store(spinning, Ordering::Relaxed);
     }
 
-    pub fn should_stop_app_before_wait(&self) -> bool {
+    pub fn is_spinning(&self) -> bool {
         // Relaxed ordering because we don't actually have multiple threads involved, we just want
         // interior mutability
-        self.stop_app_before_wait.load(Ordering::Relaxed)
-    }
-
-    pub fn set_stop_app_after_wait(&self, stop_after_wait: bool) {
-        // Relaxed ordering because we don't actually have multiple threads involved, we just want
-        // interior mutability
-        self.stop_app_after_wait
-            .store(stop_after_wait, Ordering::Relaxed);
-    }
-
-    pub fn set_wait_timeout(&self, new_timeout: Option<Instant>) {
-        let mut timeout = self.wait_timeout.lock().unwrap();
-        *timeout = new_timeout;
-    }
-
-    pub fn wait_timeout(&self) -> Option<Instant> {
-        *self.wait_timeout.lock().unwrap()
-    }
-
-    pub fn should_stop_app_after_wait(&self) -> bool {
-        // Relaxed ordering because we don't actually have multiple threads involved, we just want
-        // interior mutability
-        self.stop_app_after_wait.load(Ordering::Relaxed)
-    }
-
-    pub fn set_stop_app_on_redraw_requested(&self, stop_on_redraw: bool) {
-        // Relaxed ordering because we don't actually have multiple threads involved, we just want
-        // interior mutability
-        self.stop_app_on_redraw
-            .store(stop_on_redraw, Ordering::Relaxed);
-    }
-
-    pub fn should_stop_app_on_redraw_requested(&self) -> bool {
-        // Relaxed ordering because we don't actually have multiple threads involved, we just want
-        // interior mutability
-        self.stop_app_on_redraw.load(Ordering::Relaxed)
+        self.spinning.load(Ordering::Relaxed)
     }
 
     fn set_control_flow(&self, new_control_flow: ControlFlow) {
@@ -329,6 +288,7 @@ impl Handler {
         &self,
         window: &WinitWindow,
         suggested_size: PhysicalSize<u32>,
+        old_scale_factor: f64,
         scale_factor: f64,
     ) {
         if let Some(ref mut callback) = *self.callback.lock().unwrap() {
@@ -336,6 +296,7 @@ impl Handler {
             let scale_factor_changed_event = Event::WindowEvent {
                 window_id: WindowId(window.id()),
                 event: WindowEvent::ScaleFactorChanged {
+                    old_scale_factor,
                     scale_factor,
                     inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
                 },
@@ -396,26 +357,19 @@ impl AppState {
     }
 
     // If `pump_events` is called to progress the event loop then we bootstrap the event
-    // loop via `[NSApp run]` but will use `CFRunLoopRunInMode` for subsequent calls to
+    // loop via `[NSApp run]` but will dispatch the `NSEvent`s ourselves in subsequent calls to
     // `pump_events`
     pub fn request_stop_on_launch() {
         HANDLER.request_stop_app_on_launch();
     }
 
-    pub fn set_stop_app_before_wait(stop_before_wait: bool) {
-        HANDLER.set_stop_app_before_wait(stop_before_wait);
-    }
-
-    pub fn set_stop_app_after_wait(stop_after_wait: bool) {
-        HANDLER.set_stop_app_after_wait(stop_after_wait);
-    }
-
-    pub fn set_wait_timeout(timeout: Option<Instant>) {
-        HANDLER.set_wait_timeout(timeout);
-    }
-
-    pub fn set_stop_app_on_redraw_requested(stop_on_redraw: bool) {
-        HANDLER.set_stop_app_on_redraw_requested(stop_on_redraw);
+    /// Set while `pump_events` waits for the next `NSEvent` without running the `NSApp`.
+    ///
+    /// The run loop observers don't dispatch `NewEvents` and `AboutToWait` in the meantime, since
+    /// `pump_events` does so itself, but post a dummy event on wake up so that the wait also ends
+    /// for wake ups that don't come with an `NSEvent`, like user events or the control flow timer.
+    pub fn set_spinning(spinning: bool) {
+        HANDLER.set_spinning(spinning);
     }
 
     pub fn set_control_flow(control_flow: ControlFlow) {
@@ -506,6 +460,11 @@ impl AppState {
             .upgrade()
             .expect("The panic info must exist here. This failure indicates a developer error.");
 
+        if HANDLER.is_spinning() {
+            NSApp().postEvent_atStart(&NSEvent::dummy(), true);
+            return;
+        }
+
         // Return when in callback due to https://github.com/rust-windowing/winit/issues/1779
         if panic_info.is_panicking()
             || HANDLER.get_in_callback()
@@ -515,10 +474,6 @@ impl AppState {
             return;
         }
 
-        if HANDLER.should_stop_app_after_wait() {
-            Self::stop();
-        }
-
         let start = HANDLER.get_start_time().unwrap();
         let cause = match HANDLER.control_flow() {
             ControlFlow::Poll => StartCause::Poll,
@@ -561,17 +516,13 @@ impl AppState {
         // Redraw request might come out of order from the OS.
         // -> Don't go back into the callback when our callstack originates from there
         if !HANDLER.in_callback.swap(true, Ordering::AcqRel) {
+            // This redraw satisfies any queued request, so don't emit another one this iteration.
+            HANDLER.redraw().retain(|&id| id != window_id);
             HANDLER.handle_nonuser_event(Event::WindowEvent {
                 window_id,
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { dirty_rects: None },
             });
             HANDLER.set_in_callback(false);
-
-            // `pump_events` will request to stop immediately _after_ dispatching RedrawRequested events
-            // as a way to ensure that `pump_events` can't block an external loop indefinitely
-            if HANDLER.should_stop_app_on_redraw_requested() {
-                AppState::stop();
-            }
         }
     }
 
@@ -585,6 +536,7 @@ impl AppState {
     pub fn queue_static_scale_factor_changed_event(
         window: Id<WinitWindow>,
         suggested_size: PhysicalSize<u32>,
+        old_scale_factor: f64,
         scale_factor: f64,
     ) {
         HANDLER
@@ -592,6 +544,7 @@ impl AppState {
             .push_back(EventWrapper::ScaleFactorChanged {
                 window,
                 suggested_size,
+                old_scale_factor,
                 scale_factor,
             });
     }
@@ -614,7 +567,8 @@ impl AppState {
         // Return when in callback due to https://github.com/rust-windowing/winit/issues/1779
         // XXX: how does it make sense that `get_in_callback()` can ever return `true` here if we're
         // about to return to the `CFRunLoop` to poll for new events?
-        if panic_info.is_panicking()
+        if HANDLER.is_spinning()
+            || panic_info.is_panicking()
             || HANDLER.get_in_callback()
             || !HANDLER.have_callback()
             || !HANDLER.is_running()
@@ -632,11 +586,13 @@ impl AppState {
                 EventWrapper::ScaleFactorChanged {
                     window,
                     suggested_size,
+                    old_scale_factor,
                     scale_factor,
                 } => {
                     HANDLER.handle_scale_factor_changed_event(
                         &window,
                         suggested_size,
+                        old_scale_factor,
                         scale_factor,
                     );
                 }
@@ -646,7 +602,7 @@ impl AppState {
         for window_id in HANDLER.should_redraw() {
             HANDLER.handle_nonuser_event(Event::WindowEvent {
                 window_id,
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { dirty_rects: None },
             });
         }
 
@@ -657,31 +613,16 @@ impl AppState {
             Self::stop();
         }
 
-        if HANDLER.should_stop_app_before_wait() {
-            Self::stop();
-        }
         HANDLER.update_start_time();
-        let wait_timeout = HANDLER.wait_timeout(); // configured by pump_events
         let app_timeout = match HANDLER.control_flow() {
             ControlFlow::Wait => None,
             ControlFlow::Poll => Some(Instant::now()),
             ControlFlow::WaitUntil(instant) => Some(instant),
         };
-        HANDLER
-            .waker()
-            .start_at(min_timeout(wait_timeout, app_timeout));
+        HANDLER.waker().start_at(app_timeout);
     }
 }
 
-/// Returns the minimum `Option<Instant>`, taking into account that `None`
-/// equates to an infinite timeout, not a zero timeout (so can't just use
-/// `Option::min`)
-fn min_timeout(a: Option<Instant>, b: Option<Instant>) -> Option<Instant> {
-    a.map_or(b, |a_timeout| {
-        b.map_or(Some(a_timeout), |b_timeout| Some(a_timeout.min(b_timeout)))
-    })
-}
-
 /// A hack to make activation of multiple windows work when creating them before
 /// `applicationDidFinishLaunching:` / `Event::Event::NewEvents(StartCause::Init)`.
 ///
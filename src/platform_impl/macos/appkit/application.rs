@@ -1,10 +1,12 @@
-use icrate::Foundation::{MainThreadMarker, NSArray, NSInteger, NSObject, NSUInteger};
+use icrate::Foundation::{
+    MainThreadMarker, NSArray, NSDate, NSInteger, NSObject, NSString, NSUInteger,
+};
 use objc2::rc::Id;
 use objc2::runtime::AnyObject;
 use objc2::{extern_class, extern_methods, msg_send_id, mutability, ClassType};
 use objc2::{Encode, Encoding};
 
-use super::{NSAppearance, NSEvent, NSMenu, NSResponder, NSWindow};
+use super::{NSAppearance, NSDockTile, NSEvent, NSEventMask, NSMenu, NSResponder, NSWindow};
 
 extern_class!(
     #[derive(Debug, PartialEq, Eq, Hash)]
@@ -39,6 +41,21 @@ extern_methods!(
         #[method(postEvent:atStart:)]
         pub fn postEvent_atStart(&self, event: &NSEvent, front_of_queue: bool);
 
+        #[method_id(nextEventMatchingMask:untilDate:inMode:dequeue:)]
+        pub unsafe fn nextEventMatchingMask_untilDate_inMode_dequeue(
+            &self,
+            mask: NSEventMask,
+            expiration: Option<&NSDate>,
+            mode: &NSString,
+            dequeue: bool,
+        ) -> Option<Id<NSEvent>>;
+
+        #[method(sendEvent:)]
+        pub fn sendEvent(&self, event: &NSEvent);
+
+        #[method(updateWindows)]
+        pub fn updateWindows(&self);
+
         #[method(presentationOptions)]
         pub fn presentationOptions(&self) -> NSApplicationPresentationOptions;
 
@@ -65,6 +82,9 @@ extern_methods!(
         #[method(hideOtherApplications:)]
         pub fn hideOtherApplications(&self, sender: Option<&AnyObject>);
 
+        #[method_id(dockTile)]
+        pub fn dockTile(&self) -> Id<NSDockTile>;
+
         #[method(stop:)]
         pub fn stop(&self, sender: Option<&AnyObject>);
 
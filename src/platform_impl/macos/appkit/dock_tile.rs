@@ -0,0 +1,19 @@
+use icrate::Foundation::{NSObject, NSString};
+use objc2::{extern_class, extern_methods, mutability, ClassType};
+
+extern_class!(
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    pub(crate) struct NSDockTile;
+
+    unsafe impl ClassType for NSDockTile {
+        type Super = NSObject;
+        type Mutability = mutability::InteriorMutable;
+    }
+);
+
+extern_methods!(
+    unsafe impl NSDockTile {
+        #[method(setBadgeLabel:)]
+        pub fn setBadgeLabel(&self, label: Option<&NSString>);
+    }
+);
@@ -246,6 +246,17 @@ unsafe impl Encode for NSEventPhase {
     const ENCODING: Encoding = NSUInteger::ENCODING;
 }
 
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct NSEventMask: u64 {
+        const NSAnyEventMask = u64::MAX;
+    }
+}
+
+unsafe impl Encode for NSEventMask {
+    const ENCODING: Encoding = u64::ENCODING;
+}
+
 #[allow(dead_code)]
 #[repr(i16)] // short
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -17,6 +17,7 @@ mod button;
 mod color;
 mod control;
 mod cursor;
+mod dock_tile;
 mod event;
 mod image;
 mod menu;
@@ -40,9 +41,10 @@ pub(crate) use self::button::NSButton;
 pub(crate) use self::color::NSColor;
 pub(crate) use self::control::NSControl;
 pub(crate) use self::cursor::NSCursor;
+pub(crate) use self::dock_tile::NSDockTile;
 #[allow(unused_imports)]
 pub(crate) use self::event::{
-    NSEvent, NSEventModifierFlags, NSEventPhase, NSEventSubtype, NSEventType,
+    NSEvent, NSEventMask, NSEventModifierFlags, NSEventPhase, NSEventSubtype, NSEventType,
 };
 pub(crate) use self::image::NSImage;
 pub(crate) use self::menu::NSMenu;
@@ -57,10 +59,12 @@ pub(crate) use self::text_input_context::NSTextInputContext;
 pub(crate) use self::version::NSAppKitVersion;
 pub(crate) use self::view::{NSTrackingRectTag, NSView};
 pub(crate) use self::window::{
-    NSBackingStoreType, NSWindow, NSWindowButton, NSWindowLevel, NSWindowOcclusionState,
-    NSWindowOrderingMode, NSWindowSharingType, NSWindowStyleMask, NSWindowTabbingMode,
-    NSWindowTitleVisibility,
+    NSBackingStoreType, NSWindow, NSWindowButton, NSWindowCollectionBehavior, NSWindowLevel,
+    NSWindowOcclusionState, NSWindowOrderingMode, NSWindowSharingType, NSWindowStyleMask,
+    NSWindowTabbingMode, NSWindowTitleVisibility,
 };
 
 #[link(name = "AppKit", kind = "framework")]
-extern "C" {}
+extern "C" {
+    pub fn NSBeep();
+}
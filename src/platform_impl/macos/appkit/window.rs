@@ -88,6 +88,9 @@ extern_methods!(
         #[method(setMovable:)]
         pub(crate) fn setMovable(&self, movable: bool);
 
+        #[method(sharingType)]
+        pub(crate) fn sharingType(&self) -> NSWindowSharingType;
+
         #[method(setSharingType:)]
         pub(crate) fn setSharingType(&self, sharingType: NSWindowSharingType);
 
@@ -115,6 +118,12 @@ extern_methods!(
         #[method(setStyleMask:)]
         pub(crate) fn setStyleMask(&self, mask: NSWindowStyleMask);
 
+        #[method(collectionBehavior)]
+        pub(crate) fn collectionBehavior(&self) -> NSWindowCollectionBehavior;
+
+        #[method(setCollectionBehavior:)]
+        pub(crate) fn setCollectionBehavior(&self, behavior: NSWindowCollectionBehavior);
+
         #[method(registerForDraggedTypes:)]
         pub(crate) fn registerForDraggedTypes(&self, types: &NSArray<NSPasteboardType>);
 
@@ -124,6 +133,13 @@ extern_methods!(
         #[method(orderFront:)]
         pub(crate) fn orderFront(&self, sender: Option<&AnyObject>);
 
+        #[method(orderWindow:relativeTo:)]
+        pub(crate) fn orderWindow_relativeTo(
+            &self,
+            place: NSWindowOrderingMode,
+            other_window_number: NSInteger,
+        );
+
         #[method(miniaturize:)]
         pub(crate) fn miniaturize(&self, sender: Option<&AnyObject>);
 
@@ -157,6 +173,9 @@ extern_methods!(
         #[method_id(title)]
         pub(crate) fn title_(&self) -> Id<NSString>;
 
+        #[method(setSubtitle:)]
+        pub(crate) fn setSubtitle(&self, subtitle: &NSString);
+
         #[method(setReleasedWhenClosed:)]
         pub(crate) fn setReleasedWhenClosed(&self, val: bool);
 
@@ -175,6 +194,9 @@ extern_methods!(
         #[method(setLevel:)]
         pub(crate) fn setLevel(&self, level: NSWindowLevel);
 
+        #[method(level)]
+        pub(crate) fn level(&self) -> NSWindowLevel;
+
         #[method(setAllowsAutomaticWindowTabbing:)]
         pub(crate) fn setAllowsAutomaticWindowTabbing(val: bool);
 
@@ -387,6 +409,17 @@ unsafe impl Encode for NSWindowStyleMask {
     const ENCODING: Encoding = NSUInteger::ENCODING;
 }
 
+bitflags! {
+    #[derive(Debug, Clone, Copy)]
+    pub struct NSWindowCollectionBehavior: NSUInteger {
+        const NSWindowCollectionBehaviorCanJoinAllSpaces = 1 << 0;
+    }
+}
+
+unsafe impl Encode for NSWindowCollectionBehavior {
+    const ENCODING: Encoding = NSUInteger::ENCODING;
+}
+
 #[allow(dead_code)]
 #[repr(usize)] // NSUInteger
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
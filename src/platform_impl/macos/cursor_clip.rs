@@ -0,0 +1,130 @@
+//! The cursor clip of `EventLoopWindowTarget::clip_cursor`.
+//!
+//! There's no API to confine the cursor to a rectangle of the desktop, so a timer checks where
+//! the cursor is, and warps it back into the rectangle whenever it left it.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::ptr;
+
+use core_foundation::base::CFRelease;
+use core_foundation::date::CFAbsoluteTimeGetCurrent;
+use core_foundation::runloop::{
+    kCFRunLoopCommonModes, CFRunLoopAddTimer, CFRunLoopGetMain, CFRunLoopTimerCreate,
+    CFRunLoopTimerInvalidate, CFRunLoopTimerRef,
+};
+use core_graphics::display::{CGDisplay, CGPoint, CGRect, CGSize};
+use core_graphics::event::CGEvent;
+use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+use super::monitor;
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalRect};
+use crate::error::ExternalError;
+
+/// How often the cursor position is checked, in seconds.
+const CHECK_INTERVAL: f64 = 1.0 / 120.0;
+
+thread_local! {
+    static CURSOR_CLIP: RefCell<Option<CursorClip>> = RefCell::new(None);
+}
+
+struct CursorClip {
+    timer: CFRunLoopTimerRef,
+    /// The rectangle in points, with the origin at the top-left of the main display.
+    rect: CGRect,
+}
+
+impl Drop for CursorClip {
+    fn drop(&mut self) {
+        unsafe {
+            CFRunLoopTimerInvalidate(self.timer);
+            CFRelease(self.timer as _);
+        }
+    }
+}
+
+pub(crate) fn clip_cursor(rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+    let rect = match rect {
+        Some(rect) => to_points(rect),
+        None => {
+            CURSOR_CLIP.with(|clip| clip.borrow_mut().take());
+            return Ok(());
+        }
+    };
+
+    CURSOR_CLIP.with(|clip| {
+        let mut clip = clip.borrow_mut();
+        match clip.as_mut() {
+            Some(clip) => clip.rect = rect,
+            None => {
+                let timer = unsafe {
+                    let timer = CFRunLoopTimerCreate(
+                        ptr::null_mut(),
+                        CFAbsoluteTimeGetCurrent(),
+                        CHECK_INTERVAL,
+                        0,
+                        0,
+                        check_cursor,
+                        ptr::null_mut(),
+                    );
+                    CFRunLoopAddTimer(CFRunLoopGetMain(), timer, kCFRunLoopCommonModes);
+                    timer
+                };
+                *clip = Some(CursorClip { timer, rect });
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Converts a rectangle of the desktop to points, with the scale factor of the monitor its
+/// top-left corner is on, like `MonitorHandle::position`.
+fn to_points(rect: PhysicalRect) -> CGRect {
+    let (x, y) = (rect.position.x, rect.position.y);
+    let scale_factor = monitor::available_monitors()
+        .into_iter()
+        .find(|monitor| {
+            let position = monitor.position();
+            let size = monitor.size();
+            (position.x..position.x + size.width as i32).contains(&x)
+                && (position.y..position.y + size.height as i32).contains(&y)
+        })
+        .unwrap_or_else(monitor::primary_monitor)
+        .scale_factor();
+
+    let position: LogicalPosition<f64> = rect.position.to_logical(scale_factor);
+    let size: LogicalSize<f64> = rect.size.to_logical(scale_factor);
+    CGRect::new(
+        &CGPoint::new(position.x, position.y),
+        &CGSize::new(size.width, size.height),
+    )
+}
+
+extern "C" fn check_cursor(_timer: CFRunLoopTimerRef, _info: *mut c_void) {
+    let rect = match CURSOR_CLIP.with(|clip| clip.borrow().as_ref().map(|clip| clip.rect)) {
+        Some(rect) => rect,
+        None => return,
+    };
+    let location = match CGEventSource::new(CGEventSourceStateID::CombinedSessionState)
+        .and_then(CGEvent::new)
+    {
+        Ok(event) => event.location(),
+        Err(()) => return,
+    };
+
+    // The bottom and right edges are outside the rectangle.
+    let clamp = |value: f64, min: f64, length: f64| value.clamp(min, min + (length - 1.0).max(0.0));
+    let clamped = CGPoint::new(
+        clamp(location.x, rect.origin.x, rect.size.width),
+        clamp(location.y, rect.origin.y, rect.size.height),
+    );
+    if (clamped.x, clamped.y) == (location.x, location.y) {
+        return;
+    }
+
+    let warped = CGDisplay::warp_mouse_cursor_position(clamped)
+        .and_then(|()| CGDisplay::associate_mouse_and_mouse_cursor_position(true));
+    if let Err(err) = warped {
+        log::warn!("Failed to warp the cursor back into its clip: CGError {err}");
+    }
+}
@@ -21,6 +21,7 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEventExtra {
     pub text_with_all_modifiers: Option<SmolStr>,
     pub key_without_modifiers: Key,
@@ -233,6 +234,7 @@ pub fn code_to_key(key: PhysicalKey, scancode: u16) -> Key {
         KeyCode::NumLock => NamedKey::NumLock,
         KeyCode::AudioVolumeUp => NamedKey::AudioVolumeUp,
         KeyCode::AudioVolumeDown => NamedKey::AudioVolumeDown,
+        KeyCode::AudioVolumeMute => NamedKey::AudioVolumeMute,
 
         // Other numpad keys all generate text on macOS (if I understand correctly)
         KeyCode::NumpadEnter => NamedKey::Enter,
@@ -444,8 +446,9 @@ impl PhysicalKeyExtScancode for PhysicalKey {
             KeyCode::NumpadMultiply => Some(0x43),
             KeyCode::NumpadAdd => Some(0x45),
             KeyCode::NumLock => Some(0x47),
-            KeyCode::AudioVolumeUp => Some(0x49),
-            KeyCode::AudioVolumeDown => Some(0x4a),
+            KeyCode::AudioVolumeUp => Some(0x48),
+            KeyCode::AudioVolumeDown => Some(0x49),
+            KeyCode::AudioVolumeMute => Some(0x4a),
             KeyCode::NumpadDivide => Some(0x4b),
             KeyCode::NumpadEnter => Some(0x4c),
             KeyCode::NumpadSubtract => Some(0x4e),
@@ -568,13 +571,10 @@ impl PhysicalKeyExtScancode for PhysicalKey {
             0x45 => KeyCode::NumpadAdd,
             //0x46 => unknown,
             0x47 => KeyCode::NumLock,
-            //0x48 => KeyCode::NumpadClear,
-
-            // TODO: (Artur) for me, kVK_VolumeUp is 0x48
-            // macOS 10.11
             // /System/Library/Frameworks/Carbon.framework/Versions/A/Frameworks/HIToolbox.framework/Versions/A/Headers/Events.h
-            0x49 => KeyCode::AudioVolumeUp,
-            0x4a => KeyCode::AudioVolumeDown,
+            0x48 => KeyCode::AudioVolumeUp,
+            0x49 => KeyCode::AudioVolumeDown,
+            0x4a => KeyCode::AudioVolumeMute,
             0x4b => KeyCode::NumpadDivide,
             0x4c => KeyCode::NumpadEnter,
             //0x4d => unknown,
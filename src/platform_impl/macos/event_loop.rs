@@ -9,25 +9,30 @@ use std::{
     ptr,
     rc::{Rc, Weak},
     sync::mpsc,
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use core_foundation::base::{CFIndex, CFRelease};
 use core_foundation::runloop::{
-    kCFRunLoopCommonModes, CFRunLoopAddSource, CFRunLoopGetMain, CFRunLoopSourceContext,
-    CFRunLoopSourceCreate, CFRunLoopSourceRef, CFRunLoopSourceSignal, CFRunLoopWakeUp,
+    kCFRunLoopCommonModes, kCFRunLoopDefaultMode, CFRunLoopAddSource, CFRunLoopGetMain,
+    CFRunLoopSourceContext, CFRunLoopSourceCreate, CFRunLoopSourceRef, CFRunLoopSourceSignal,
+    CFRunLoopWakeUp,
 };
-use icrate::Foundation::MainThreadMarker;
+use icrate::Foundation::{MainThreadMarker, NSDate, NSString};
 use objc2::rc::{autoreleasepool, Id};
 use objc2::runtime::NSObjectProtocol;
 use objc2::{msg_send_id, ClassType};
 
-use super::appkit::{NSApp, NSApplication, NSApplicationActivationPolicy, NSEvent, NSWindow};
+use super::appkit::{
+    NSApp, NSApplication, NSApplicationActivationPolicy, NSEvent, NSEventMask, NSWindow,
+};
 use crate::{
-    error::EventLoopError,
-    event::Event,
+    dpi::PhysicalRect,
+    error::{EventLoopError, ExternalError},
+    event::{DeviceInfo, Event},
     event_loop::{
-        ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget,
+        Backend, ControlFlow, DeviceEvents, EventLoopClosed,
+        EventLoopWindowTarget as RootWindowTarget, PowerState,
     },
     platform::{macos::ActivationPolicy, pump_events::PumpStatus},
     platform_impl::platform::{
@@ -36,7 +41,9 @@ use crate::{
         app_state::{AppState, Callback},
         monitor::{self, MonitorHandle},
         observer::setup_control_flow_observers,
+        window::WindowId,
     },
+    window::WindowId as RootWindowId,
 };
 
 #[derive(Default)]
@@ -70,10 +77,15 @@ impl PanicInfo {
 #[derive(Debug)]
 pub struct EventLoopWindowTarget<T: 'static> {
     mtm: MainThreadMarker,
-    p: PhantomData<T>,
+    // Event sender, used for EventLoopProxy.
+    sender: mpsc::Sender<T>,
 }
 
 impl<T: 'static> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy::new(self.sender.clone())
+    }
+
     #[inline]
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         monitor::available_monitors()
@@ -88,6 +100,26 @@ impl<T: 'static> EventLoopWindowTarget<T> {
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    #[inline]
+    pub fn device_info(&self, _device_id: super::DeviceId) -> Option<DeviceInfo> {
+        None
+    }
+
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        Backend::MacOS
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> Option<PowerState> {
+        super::power::power_state()
+    }
+
+    #[inline]
+    pub fn clip_cursor(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        super::cursor_clip::clip_cursor(rect)
+    }
+
     #[cfg(feature = "rwh_05")]
     #[inline]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
@@ -112,6 +144,10 @@ impl<T: 'static> EventLoopWindowTarget<T> {
         AppState::control_flow()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        AppState::queue_redraw(RootWindowId(window_id));
+    }
+
     pub(crate) fn exit(&self) {
         AppState::exit()
     }
@@ -141,6 +177,13 @@ impl<T> EventLoopWindowTarget<T> {
     pub(crate) fn allows_automatic_window_tabbing(&self) -> bool {
         NSWindow::allowsAutomaticWindowTabbing()
     }
+
+    pub(crate) fn set_dock_badge(&self, label: Option<&str>) {
+        let label = label.map(NSString::from_str);
+        NSApplication::shared(self.mtm)
+            .dockTile()
+            .setBadgeLabel(label.as_deref());
+    }
 }
 
 pub struct EventLoop<T: 'static> {
@@ -153,8 +196,7 @@ pub struct EventLoop<T: 'static> {
     /// it around here as well.
     _delegate: Id<ApplicationDelegate>,
 
-    // Event sender and receiver, used for EventLoopProxy.
-    sender: mpsc::Sender<T>,
+    // Event receiver, used for EventLoopProxy.
     receiver: Rc<mpsc::Receiver<T>>,
 
     window_target: Rc<RootWindowTarget<T>>,
@@ -190,8 +232,10 @@ impl<T> EventLoop<T> {
     pub(crate) fn new(
         attributes: &PlatformSpecificEventLoopAttributes,
     ) -> Result<Self, EventLoopError> {
-        let mtm = MainThreadMarker::new()
-            .expect("on macOS, `EventLoop` must be created on the main thread!");
+        let mtm = match MainThreadMarker::new() {
+            Some(mtm) => mtm,
+            None => return Err(EventLoopError::NotMainThread),
+        };
 
         let app: Id<NSApplication> =
             unsafe { msg_send_id![WinitApplication::class(), sharedApplication] };
@@ -223,13 +267,10 @@ impl<T> EventLoop<T> {
         Ok(EventLoop {
             app,
             _delegate: delegate,
-            sender,
             receiver: Rc::new(receiver),
             window_target: Rc::new(RootWindowTarget {
-                p: EventLoopWindowTarget {
-                    mtm,
-                    p: PhantomData,
-                },
+                p: EventLoopWindowTarget { mtm, sender },
+                handler_state: Default::default(),
                 _marker: PhantomData,
             }),
             panic_info,
@@ -297,12 +338,6 @@ impl<T> EventLoop<T> {
             // catch panics to make sure we can't unwind without clearing the set callback
             // (which would leave the global `AppState` in an undefined, unsafe state)
             let catch_result = catch_unwind(AssertUnwindSafe(|| {
-                // clear / normalize pump_events state
-                AppState::set_wait_timeout(None);
-                AppState::set_stop_app_before_wait(false);
-                AppState::set_stop_app_after_wait(false);
-                AppState::set_stop_app_on_redraw_requested(false);
-
                 if AppState::is_launched() {
                     debug_assert!(!AppState::is_running());
                     AppState::start_running(); // Set is_running = true + dispatch `NewEvents(Init)` + `Resumed`
@@ -336,6 +371,46 @@ impl<T> EventLoop<T> {
         Ok(())
     }
 
+    /// Waits up to `timeout` for the next `NSEvent` in the default run loop mode, then dispatches
+    /// it along with all the others that are queued, followed by the redraws and `AboutToWait`.
+    fn spin(&self, app: &NSApplication, timeout: Option<Duration>) {
+        let until = unsafe {
+            match timeout {
+                Some(Duration::ZERO) => NSDate::distantPast(),
+                Some(duration) => NSDate::dateWithTimeIntervalSinceNow(duration.as_secs_f64()),
+                None => NSDate::distantFuture(),
+            }
+        };
+        // `kCFRunLoopDefaultMode` is toll-free bridged to `NSDefaultRunLoopMode`
+        let mode = unsafe { &*(kCFRunLoopDefaultMode as *const NSString) };
+        let next_event = |until: &NSDate| unsafe {
+            app.nextEventMatchingMask_untilDate_inMode_dequeue(
+                NSEventMask::NSAnyEventMask,
+                Some(until),
+                mode,
+                true,
+            )
+        };
+
+        AppState::set_spinning(true);
+        let event = next_event(&until);
+        AppState::set_spinning(false);
+
+        AppState::wakeup(Rc::downgrade(&self.panic_info));
+        if let Some(event) = event {
+            app.sendEvent(&event);
+        }
+        let distant_past = unsafe { NSDate::distantPast() };
+        while !self.panic_info.is_panicking() {
+            match next_event(&distant_past) {
+                Some(event) => app.sendEvent(&event),
+                None => break,
+            }
+        }
+        app.updateWindows();
+        AppState::cleared(Rc::downgrade(&self.panic_info));
+    }
+
     pub fn pump_events<F>(&mut self, timeout: Option<Duration>, callback: F) -> PumpStatus
     where
         F: FnMut(Event<T>, &RootWindowTarget<T>),
@@ -398,29 +473,10 @@ impl<T> EventLoop<T> {
                     // we just starting to re-run the same `EventLoop` again.
                     AppState::start_running(); // Set is_running = true + dispatch `NewEvents(Init)` + `Resumed`
                 } else {
-                    // Only run the NSApp for as long as the given `Duration` allows so we
-                    // don't block the external loop.
-                    match timeout {
-                        Some(Duration::ZERO) => {
-                            AppState::set_wait_timeout(None);
-                            AppState::set_stop_app_before_wait(true);
-                        }
-                        Some(duration) => {
-                            AppState::set_stop_app_before_wait(false);
-                            let timeout = Instant::now() + duration;
-                            AppState::set_wait_timeout(Some(timeout));
-                            AppState::set_stop_app_after_wait(true);
-                        }
-                        None => {
-                            AppState::set_wait_timeout(None);
-                            AppState::set_stop_app_before_wait(false);
-                            AppState::set_stop_app_after_wait(true);
-                        }
-                    }
-                    AppState::set_stop_app_on_redraw_requested(true);
-                    unsafe {
-                        app.run();
-                    }
+                    // Instead of running the `NSApp`, which can only be stopped once the run loop
+                    // is about to wait, we dispatch the pending `NSEvent`s ourselves, like
+                    // `[NSApp run]` does, so the given `Duration` bounds the wait.
+                    self.spin(&app, timeout);
                 }
 
                 // While the app is running it's possible that we catch a panic
@@ -455,7 +511,7 @@ impl<T> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.sender.clone())
+        self.window_target.p.create_proxy()
     }
 }
 
@@ -550,4 +606,12 @@ impl<T> EventLoopProxy<T> {
         }
         Ok(())
     }
+
+    pub fn wake_up(&self) {
+        unsafe {
+            CFRunLoopSourceSignal(self.source);
+            let rl = CFRunLoopGetMain();
+            CFRunLoopWakeUp(rl);
+        }
+    }
 }
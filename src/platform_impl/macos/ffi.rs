@@ -5,7 +5,8 @@
 use std::ffi::c_void;
 
 use core_foundation::{
-    array::CFArrayRef, dictionary::CFDictionaryRef, string::CFStringRef, uuid::CFUUIDRef,
+    array::CFArrayRef, base::CFTypeRef, dictionary::CFDictionaryRef, string::CFStringRef,
+    uuid::CFUUIDRef,
 };
 use core_graphics::{
     base::CGError,
@@ -210,3 +211,14 @@ extern "C" {
         unicodeString: *mut UniChar,
     ) -> OSStatus;
 }
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    pub fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+
+    pub fn IOPSCopyPowerSourcesList(blob: CFTypeRef) -> CFArrayRef;
+
+    pub fn IOPSGetPowerSourceDescription(blob: CFTypeRef, ps: CFTypeRef) -> CFDictionaryRef;
+
+    pub fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFStringRef;
+}
@@ -5,12 +5,14 @@ mod app;
 mod app_delegate;
 mod app_state;
 mod appkit;
+mod cursor_clip;
 mod event;
 mod event_loop;
 mod ffi;
 mod menu;
 mod monitor;
 mod observer;
+mod power;
 mod view;
 mod window;
 mod window_delegate;
@@ -38,6 +40,10 @@ impl DeviceId {
     pub const unsafe fn dummy() -> Self {
         DeviceId
     }
+
+    pub fn persistent_id(&self) -> Option<u64> {
+        None
+    }
 }
 
 // Constant device ID; to be removed when if backend is updated to report real device IDs.
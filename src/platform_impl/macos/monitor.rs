@@ -14,7 +14,7 @@ use objc2::rc::Id;
 
 use super::appkit::NSScreen;
 use super::ffi;
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::dpi::{PhysicalPosition, PhysicalRect, PhysicalSize};
 
 #[derive(Clone)]
 pub struct VideoMode {
@@ -209,6 +209,35 @@ impl MonitorHandle {
         )
     }
 
+    /// Returns the part of the monitor which isn't covered by the menu bar and the Dock.
+    pub(crate) fn work_area(&self) -> PhysicalRect {
+        let screen = match self.ns_screen() {
+            Some(screen) => screen,
+            None => return PhysicalRect::new(self.position(), self.size()),
+        };
+        // The frames of the screens have their origin at the bottom-left, unlike the display
+        // bounds.
+        let frame = screen.frame();
+        let visible = screen.visibleFrame();
+        let left = visible.origin.x - frame.origin.x;
+        let top = (frame.origin.y + frame.size.height) - (visible.origin.y + visible.size.height);
+        let bounds = unsafe { CGDisplayBounds(self.native_identifier()) };
+        let scale_factor = screen.backingScaleFactor() as f64;
+        PhysicalRect::new(
+            PhysicalPosition::from_logical::<_, f64>(
+                (
+                    bounds.origin.x as f64 + left as f64,
+                    bounds.origin.y as f64 + top as f64,
+                ),
+                scale_factor,
+            ),
+            PhysicalSize::from_logical::<_, f64>(
+                (visible.size.width as f64, visible.size.height as f64),
+                scale_factor,
+            ),
+        )
+    }
+
     pub fn scale_factor(&self) -> f64 {
         match self.ns_screen() {
             Some(screen) => screen.backingScaleFactor() as f64,
@@ -0,0 +1,76 @@
+//! Reading of the power state from the IOKit power sources.
+
+use core_foundation::array::CFArray;
+use core_foundation::base::{CFType, TCFType};
+use core_foundation::dictionary::CFDictionary;
+use core_foundation::number::CFNumber;
+use core_foundation::string::CFString;
+
+use super::ffi;
+use crate::event_loop::PowerState;
+
+// The keys and values of the power source descriptions, from `IOPSKeys.h`.
+const TYPE_KEY: &str = "Type";
+const INTERNAL_BATTERY_TYPE: &str = "InternalBattery";
+const CURRENT_CAPACITY_KEY: &str = "Current Capacity";
+const MAX_CAPACITY_KEY: &str = "Max Capacity";
+const BATTERY_POWER_VALUE: &str = "Battery Power";
+
+pub(crate) fn power_state() -> Option<PowerState> {
+    let info = unsafe { ffi::IOPSCopyPowerSourcesInfo() };
+    if info.is_null() {
+        return None;
+    }
+    let info = unsafe { CFType::wrap_under_create_rule(info) };
+    let sources = unsafe { ffi::IOPSCopyPowerSourcesList(info.as_CFTypeRef()) };
+    if sources.is_null() {
+        return None;
+    }
+    let sources: CFArray<CFType> = unsafe { CFArray::wrap_under_create_rule(sources) };
+
+    let mut batteries = 0;
+    let (mut current, mut max) = (0, 0);
+    for source in sources.iter() {
+        let description = unsafe {
+            ffi::IOPSGetPowerSourceDescription(info.as_CFTypeRef(), source.as_CFTypeRef())
+        };
+        if description.is_null() {
+            continue;
+        }
+        let description: CFDictionary<CFString, CFType> =
+            unsafe { CFDictionary::wrap_under_get_rule(description) };
+        let value = |key| description.find(&CFString::from_static_string(key));
+
+        let is_battery = value(TYPE_KEY)
+            .and_then(|value| value.downcast::<CFString>())
+            .map_or(false, |value| value == INTERNAL_BATTERY_TYPE);
+        if !is_battery {
+            continue;
+        }
+        batteries += 1;
+
+        let capacity = |key| {
+            value(key)
+                .and_then(|value| value.downcast::<CFNumber>())
+                .and_then(|value| value.to_i64())
+        };
+        if let (Some(battery_current), Some(battery_max)) =
+            (capacity(CURRENT_CAPACITY_KEY), capacity(MAX_CAPACITY_KEY))
+        {
+            current += battery_current;
+            max += battery_max;
+        }
+    }
+    if batteries == 0 {
+        return None;
+    }
+
+    let providing = unsafe { ffi::IOPSGetProvidingPowerSourceType(info.as_CFTypeRef()) };
+    let on_battery = !providing.is_null()
+        && unsafe { CFString::wrap_under_get_rule(providing) } == BATTERY_POWER_VALUE;
+
+    Some(PowerState {
+        on_battery,
+        battery_percent: (max > 0).then(|| (100 * current) as f32 / max as f32),
+    })
+}
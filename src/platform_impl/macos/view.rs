@@ -2,6 +2,7 @@
 use std::boxed::Box;
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
+use std::mem;
 use std::ptr::NonNull;
 
 use icrate::Foundation::{
@@ -21,7 +22,7 @@ use super::{
     event::{code_to_key, code_to_location},
 };
 use crate::{
-    dpi::{LogicalPosition, LogicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalRect, PhysicalSize},
     event::{
         DeviceEvent, ElementState, Event, Ime, Modifiers, MouseButton, MouseScrollDelta,
         TouchPhase, WindowEvent,
@@ -36,19 +37,32 @@ use crate::{
         window::WinitWindow,
         DEVICE_ID,
     },
-    window::WindowId,
+    platform_impl::CursorRegions,
+    window::{CursorIcon, WindowId},
 };
 
 #[derive(Debug)]
 struct CursorState {
     visible: bool,
+    /// The cursor selected by the application.
+    icon: CursorIcon,
+    regions: CursorRegions,
+    /// The cursor shown over the view, which is the one of the hovered region if any.
     cursor: Id<NSCursor>,
 }
 
+impl CursorState {
+    fn update_cursor(&mut self) {
+        self.cursor = NSCursor::from_icon(self.regions.cursor(self.icon));
+    }
+}
+
 impl Default for CursorState {
     fn default() -> Self {
         Self {
             visible: true,
+            icon: CursorIcon::Default,
+            regions: Default::default(),
             cursor: Default::default(),
         }
     }
@@ -140,6 +154,9 @@ pub struct ViewState {
 
     marked_text: RefCell<Id<NSMutableAttributedString>>,
     accepts_first_mouse: bool,
+
+    /// The rectangles set with `set_shape`, outside of which the view ignores the cursor.
+    shape: RefCell<Option<Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>>>,
 }
 
 declare_class!(
@@ -266,6 +283,19 @@ declare_class!(
             None
         }
 
+        #[method_id(hitTest:)]
+        fn hit_test(&self, point: NSPoint) -> Option<Id<NSView>> {
+            trace_scope!("hitTest:");
+            // The point is in the coordinates of the frame view of the window, which are the ones
+            // of the window since it covers all of it.
+            let view_point = self.convertPoint_fromView(point, None);
+            if self.is_in_shape(view_point) {
+                unsafe { msg_send_id![super(self), hitTest: point] }
+            } else {
+                None
+            }
+        }
+
         #[method(resetCursorRects)]
         fn reset_cursor_rects(&self) {
             trace_scope!("resetCursorRects");
@@ -667,6 +697,13 @@ declare_class!(
         fn mouse_exited(&self, _event: &NSEvent) {
             trace_scope!("mouseExited:");
 
+            let mut cursor_state = self.state.cursor_state.borrow_mut();
+            if cursor_state.regions.cursor_left() {
+                cursor_state.update_cursor();
+                drop(cursor_state);
+                self.window().invalidateCursorRectsForView(self);
+            }
+
             self.queue_event(WindowEvent::CursorLeft {
                 device_id: DEVICE_ID,
             });
@@ -760,13 +797,33 @@ declare_class!(
                 _ => return,
             };
 
-            self.queue_event(WindowEvent::TouchpadRotate {
+            self.queue_event(WindowEvent::RotationGesture {
                 device_id: DEVICE_ID,
-                delta: event.rotation(),
+                delta_radians: event.rotation().to_radians(),
                 phase,
             });
         }
 
+        #[method(swipeWithEvent:)]
+        fn swipe_with_event(&self, event: &NSEvent) {
+            trace_scope!("swipeWithEvent:");
+
+            // The deltas are positive when swiping left and up, and the swipe has ended by the
+            // time it's reported, so the whole gesture is reported at once.
+            let delta = PhysicalPosition::new(-event.deltaX(), -event.deltaY());
+            for (delta, phase) in [
+                (PhysicalPosition::new(0.0, 0.0), TouchPhase::Started),
+                (delta, TouchPhase::Moved),
+                (PhysicalPosition::new(0.0, 0.0), TouchPhase::Ended),
+            ] {
+                self.queue_event(WindowEvent::PanGesture {
+                    device_id: DEVICE_ID,
+                    delta,
+                    phase,
+                });
+            }
+        }
+
         #[method(pressureChangeWithEvent:)]
         fn pressure_change_with_event(&self, event: &NSEvent) {
             trace_scope!("pressureChangeWithEvent:");
@@ -853,9 +910,29 @@ impl WinitView {
             .unwrap_or_default()
     }
 
-    pub(super) fn set_cursor_icon(&self, icon: Id<NSCursor>) {
+    /// Set the cursor selected by the application.
+    ///
+    /// Returns whether the shown cursor changed.
+    pub(super) fn set_cursor_icon(&self, icon: CursorIcon) -> bool {
         let mut cursor_state = self.state.cursor_state.borrow_mut();
-        cursor_state.cursor = icon;
+        let previous = mem::replace(&mut cursor_state.icon, icon);
+        let changed = cursor_state.regions.cursor_set(previous, icon);
+        if changed {
+            cursor_state.update_cursor();
+        }
+        changed
+    }
+
+    /// Set the cursors shown over the regions of the view.
+    ///
+    /// Returns whether the shown cursor changed.
+    pub(super) fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) -> bool {
+        let mut cursor_state = self.state.cursor_state.borrow_mut();
+        let changed = cursor_state.regions.set_regions(regions);
+        if changed {
+            cursor_state.update_cursor();
+        }
+        changed
     }
 
     /// Set whether the cursor should be visible or not.
@@ -889,6 +966,43 @@ impl WinitView {
         }
     }
 
+    /// Discard any pending dead key composition, which AppKit stores as marked text.
+    pub(super) fn reset_dead_keys(&self) {
+        *self.state.marked_text.borrow_mut() = NSMutableAttributedString::new();
+
+        let input_context = self.inputContext().expect("input context");
+        input_context.discardMarkedText();
+
+        if self.state.ime_state.get() == ImeState::Preedit {
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            self.state.ime_state.set(ImeState::Ground);
+        }
+    }
+
+    pub(super) fn set_shape(&self, shape: Option<Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>>) {
+        *self.state.shape.borrow_mut() = shape;
+    }
+
+    /// Whether a point in the coordinates of the view is within the shape of the window.
+    fn is_in_shape(&self, view_point: NSPoint) -> bool {
+        let shape = self.state.shape.borrow();
+        let shape = match shape.as_ref() {
+            Some(shape) => shape,
+            None => return true,
+        };
+
+        let view_rect = self.frame();
+        let x = view_point.x as f64;
+        let y = view_rect.size.height as f64 - view_point.y as f64;
+        let position: PhysicalPosition<f64> =
+            LogicalPosition::new(x, y).to_physical(self.scale_factor());
+        shape.iter().any(|(origin, size)| {
+            let (left, top) = (origin.x as f64, origin.y as f64);
+            (left..left + size.width as f64).contains(&position.x)
+                && (top..top + size.height as f64).contains(&position.y)
+        })
+    }
+
     pub(super) fn set_ime_cursor_area(
         &self,
         position: LogicalPosition<f64>,
@@ -1056,12 +1170,20 @@ impl WinitView {
         let x = view_point.x as f64;
         let y = view_rect.size.height as f64 - view_point.y as f64;
         let logical_position = LogicalPosition::new(x, y);
+        let position = logical_position.to_physical(self.scale_factor());
+
+        let mut cursor_state = self.state.cursor_state.borrow_mut();
+        if cursor_state.regions.cursor_moved(position) {
+            cursor_state.update_cursor();
+            drop(cursor_state);
+            self.window().invalidateCursorRectsForView(self);
+        }
 
         self.update_modifiers(event, false);
 
         self.queue_event(WindowEvent::CursorMoved {
             device_id: DEVICE_ID,
-            position: logical_position.to_physical(self.scale_factor()),
+            position,
         });
     }
 }
@@ -9,7 +9,8 @@ use std::sync::{Mutex, MutexGuard};
 
 use crate::{
     dpi::{
-        LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size, Size::Logical,
+        LogicalPosition, LogicalSize, PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size,
+        Size::Logical,
     },
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     event::WindowEvent,
@@ -26,12 +27,18 @@ use crate::{
         window_delegate::WinitWindowDelegate,
         Fullscreen, OsError,
     },
+    platform_impl::{AutoGrab, SizeConstraints},
     window::{
-        CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-        WindowAttributes, WindowButtons, WindowId as RootWindowId, WindowLevel,
+        CursorGrabMode, CursorIcon, DropEffect, ImageBuffer, ImeHints, ImePurpose, Insets,
+        ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons,
+        WindowId as RootWindowId, WindowLevel, WindowShape,
     },
 };
-use core_graphics::display::{CGDisplay, CGPoint};
+use core_graphics::display::{CGDisplay, CGPoint, CGRect, CGSize};
+use core_graphics::window::{
+    create_image, kCGWindowImageBestResolution, kCGWindowImageBoundsIgnoreFraming,
+    kCGWindowListOptionIncludingWindow, CGWindowID,
+};
 use icrate::Foundation::{
     CGFloat, MainThreadBound, MainThreadMarker, NSArray, NSCopying, NSInteger, NSObject, NSPoint,
     NSRect, NSSize, NSString,
@@ -42,9 +49,10 @@ use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType};
 
 use super::appkit::{
     NSApp, NSAppKitVersion, NSAppearance, NSApplicationPresentationOptions, NSBackingStoreType,
-    NSColor, NSCursor, NSFilenamesPboardType, NSRequestUserAttentionType, NSResponder, NSScreen,
-    NSView, NSWindow, NSWindowButton, NSWindowLevel, NSWindowSharingType, NSWindowStyleMask,
-    NSWindowTabbingMode, NSWindowTitleVisibility,
+    NSBeep, NSColor, NSEvent, NSEventType, NSFilenamesPboardType, NSRequestUserAttentionType,
+    NSResponder, NSScreen, NSView, NSWindow, NSWindowButton, NSWindowCollectionBehavior,
+    NSWindowLevel, NSWindowSharingType, NSWindowStyleMask, NSWindowTabbingMode,
+    NSWindowTitleVisibility,
 };
 use super::ffi::CGSMainConnectionID;
 use super::ffi::CGSSetWindowBackgroundBlurRadius;
@@ -64,10 +72,16 @@ impl Drop for Window {
 
 impl Window {
     pub(crate) fn new<T: 'static>(
-        _window_target: &EventLoopWindowTarget<T>,
+        window_target: &EventLoopWindowTarget<T>,
         attributes: WindowAttributes,
         pl_attribs: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Self, RootOsError> {
+        if window_target.exiting() {
+            return Err(os_error!(OsError::CreationError(
+                "the event loop is exiting"
+            )));
+        }
+
         let mtm = MainThreadMarker::new()
             .expect("windows can only be created on the main thread on macOS");
         let (window, _delegate) = autoreleasepool(|_| WinitWindow::new(attributes, pl_attribs))?;
@@ -205,7 +219,40 @@ declare_class!(
         #[method(canBecomeKeyWindow)]
         fn can_become_key_window(&self) -> bool {
             trace_scope!("canBecomeKeyWindow");
-            true
+            !self.lock_shared_state("canBecomeKeyWindow").disabled
+        }
+
+        #[method(sendEvent:)]
+        fn send_event(&self, event: &NSEvent) {
+            trace_scope!("sendEvent:");
+            if self.lock_shared_state("sendEvent").disabled {
+                match event.type_() {
+                    NSEventType::NSLeftMouseDown
+                    | NSEventType::NSRightMouseDown
+                    | NSEventType::NSOtherMouseDown => {
+                        // Play the same sound as clicking the parent of a modal window.
+                        unsafe { NSBeep() };
+                        return;
+                    }
+                    NSEventType::NSLeftMouseUp
+                    | NSEventType::NSRightMouseUp
+                    | NSEventType::NSOtherMouseUp
+                    | NSEventType::NSMouseMoved
+                    | NSEventType::NSLeftMouseDragged
+                    | NSEventType::NSRightMouseDragged
+                    | NSEventType::NSOtherMouseDragged
+                    | NSEventType::NSScrollWheel
+                    | NSEventType::NSKeyDown
+                    | NSEventType::NSKeyUp
+                    | NSEventType::NSFlagsChanged
+                    | NSEventType::NSEventTypeMagnify
+                    | NSEventType::NSEventTypeRotate
+                    | NSEventType::NSEventTypePressure => return,
+                    _ => (),
+                }
+            }
+
+            unsafe { msg_send![super(self), sendEvent: event] }
         }
     }
 );
@@ -239,6 +286,15 @@ pub struct SharedState {
     pub(crate) resize_increments: NSSize,
     /// The state of the `Option` as `Alt`.
     pub(crate) option_as_alt: OptionAsAlt,
+    /// Whether the window ignores input, see `set_enabled`.
+    pub(crate) disabled: bool,
+    /// The effect reported to the dragging source while files are hovered.
+    pub(crate) drop_effect: DropEffect,
+    /// Whether the cursor is locked with `set_cursor_grab`.
+    pub(crate) cursor_locked: bool,
+    pub(crate) auto_grab: AutoGrab,
+    /// The IME cursor area as requested, converted again when the scale factor changes.
+    pub(crate) ime_cursor_area: Option<(Position, Size)>,
 
     decorations: bool,
 }
@@ -311,13 +367,13 @@ impl WinitWindow {
                     let scale_factor = NSScreen::main()
                         .map(|screen| screen.backingScaleFactor() as f64)
                         .unwrap_or(1.0);
-                    let (width, height) = match attrs.inner_size {
-                        Some(size) => {
-                            let logical = size.to_logical(scale_factor);
-                            (logical.width, logical.height)
-                        }
-                        None => (800.0, 600.0),
-                    };
+                    let size = attrs
+                        .inner_size
+                        .unwrap_or_else(|| LogicalSize::new(800.0, 600.0).into());
+                    let size: PhysicalSize<u32> =
+                        SizeConstraints::from_attributes(&attrs, None, scale_factor)
+                            .clamp(size.to_physical(scale_factor));
+                    let LogicalSize { width, height } = size.to_logical(scale_factor);
                     let (left, bottom) = match attrs.position {
                         Some(position) => {
                             let logical = util::window_position(position.to_logical(scale_factor));
@@ -610,6 +666,11 @@ impl WinitWindow {
         Some(self.isVisible())
     }
 
+    pub fn set_enabled(&self, enabled: bool) {
+        // Key events are dropped in `sendEvent:` while the window stays the key window.
+        self.lock_shared_state("set_enabled").disabled = !enabled;
+    }
+
     pub fn request_redraw(&self) {
         AppState::queue_redraw(RootWindowId(self.id()));
     }
@@ -628,13 +689,14 @@ impl WinitWindow {
     }
 
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
-        let content_rect = self.contentRectForFrameRect(self.frame());
-        let position = LogicalPosition::new(
-            content_rect.origin.x as f64,
-            util::bottom_left_to_top_left(content_rect),
-        );
-        let scale_factor = self.scale_factor();
-        Ok(position.to_physical(scale_factor))
+        // Derive the content origin from the frame, so that rounding to physical pixels can't
+        // make it disagree with `outer_position` and `frame_insets`.
+        let outer = self.outer_position()?;
+        let insets = self.frame_insets();
+        Ok(PhysicalPosition::new(
+            outer.x + insets.left as i32,
+            outer.y + insets.top as i32,
+        ))
     }
 
     pub fn set_outer_position(&self, position: Position) {
@@ -659,6 +721,44 @@ impl WinitWindow {
         logical.to_physical(scale_factor)
     }
 
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, visible: bool) -> Result<(), NotSupportedError> {
+        let mut behavior = self.collectionBehavior();
+        behavior.set(
+            NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces,
+            visible,
+        );
+        self.setCollectionBehavior(behavior);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        Some(
+            self.collectionBehavior()
+                .contains(NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces),
+        )
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        let frame = self.frame();
+        let content = self.contentRectForFrameRect(frame);
+        let scale_factor = self.scale_factor();
+        // The rectangles use a bottom-left origin.
+        let to_physical = |inset: CGFloat| (inset as f64 * scale_factor).round().max(0.0) as u32;
+        Insets {
+            top: to_physical(
+                (frame.origin.y + frame.size.height) - (content.origin.y + content.size.height),
+            ),
+            bottom: to_physical(content.origin.y - frame.origin.y),
+            left: to_physical(content.origin.x - frame.origin.x),
+            right: to_physical(
+                (frame.origin.x + frame.size.width) - (content.origin.x + content.size.width),
+            ),
+        }
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let scale_factor = self.scale_factor();
@@ -830,12 +930,67 @@ impl WinitWindow {
 
     pub fn set_cursor_icon(&self, icon: CursorIcon) {
         let view = self.view();
-        view.set_cursor_icon(NSCursor::from_icon(icon));
-        self.invalidateCursorRectsForView(&view);
+        if view.set_cursor_icon(icon) {
+            self.invalidateCursorRectsForView(&view);
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        let view = self.view();
+        if view.set_cursor_regions(regions) {
+            self.invalidateCursorRectsForView(&view);
+        }
     }
 
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.lock_shared_state("set_cursor_grab")
+            .auto_grab
+            .grab_changed();
+        self.grab_cursor(mode)
+    }
+
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, _rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_auto_grab(&self, auto_grab: bool) {
+        self.lock_shared_state("set_auto_grab")
+            .auto_grab
+            .set_enabled(auto_grab);
+    }
+
+    /// Releases or re-applies the cursor lock with [`AutoGrab`] when the window became or
+    /// resigned key.
+    pub(crate) fn update_auto_grab(&self, focused: bool) {
+        let mode = {
+            let mut shared_state = self.lock_shared_state("update_auto_grab");
+            if focused {
+                shared_state.auto_grab.focus_gained()
+            } else {
+                let mode = if shared_state.cursor_locked {
+                    CursorGrabMode::Locked
+                } else {
+                    CursorGrabMode::None
+                };
+                shared_state
+                    .auto_grab
+                    .focus_lost(mode)
+                    .then_some(CursorGrabMode::None)
+            }
+        };
+
+        if let Some(mode) = mode {
+            if let Err(err) = self.grab_cursor(mode) {
+                warn!("Failed to update the cursor grab after a focus change: {err}");
+            }
+        }
+    }
+
+    fn grab_cursor(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let associate_mouse_cursor = match mode {
             CursorGrabMode::Locked => false,
             CursorGrabMode::None => true,
@@ -846,7 +1001,9 @@ impl WinitWindow {
 
         // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
         CGDisplay::associate_mouse_and_mouse_cursor_position(associate_mouse_cursor)
-            .map_err(|status| ExternalError::Os(os_error!(OsError::CGError(status))))
+            .map_err(|status| ExternalError::Os(os_error!(OsError::CGError(status))))?;
+        self.lock_shared_state("grab_cursor").cursor_locked = !associate_mouse_cursor;
+        Ok(())
     }
 
     #[inline]
@@ -902,6 +1059,12 @@ impl WinitWindow {
         Ok(())
     }
 
+    pub fn set_shape(&self, shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        self.view()
+            .set_shape(shape.as_ref().map(WindowShape::rectangles));
+        Ok(())
+    }
+
     pub(crate) fn is_zoomed(&self) -> bool {
         // because `isZoomed` doesn't work if the window's borderless,
         // we make it resizable temporalily.
@@ -1291,8 +1454,15 @@ impl WinitWindow {
         // https://developer.apple.com/library/content/documentation/Cocoa/Conceptual/WinPanel/Tasks/SettingWindowTitle.html
     }
 
+    #[inline]
+    pub fn set_drop_feedback(&self, effect: DropEffect) {
+        self.lock_shared_state("set_drop_feedback").drop_effect = effect;
+    }
+
     #[inline]
     pub fn set_ime_cursor_area(&self, spot: Position, size: Size) {
+        self.lock_shared_state("set_ime_cursor_area")
+            .ime_cursor_area = Some((spot, size));
         let scale_factor = self.scale_factor();
         let logical_spot = spot.to_logical(scale_factor);
         let size = size.to_logical(scale_factor);
@@ -1308,7 +1478,24 @@ impl WinitWindow {
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn set_above(&self, sibling: &Window) -> Result<(), ExternalError> {
+        let sibling = sibling.maybe_wait_on_main(|sibling| sibling.windowNumber());
+        self.orderWindow_relativeTo(NSWindowOrderingMode::NSWindowAbove, sibling);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_below(&self, sibling: &Window) -> Result<(), ExternalError> {
+        let sibling = sibling.maybe_wait_on_main(|sibling| sibling.windowNumber());
+        self.orderWindow_relativeTo(NSWindowOrderingMode::NSWindowBelow, sibling);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
         let is_minimized = self.isMiniaturized();
         let is_visible = self.isVisible();
 
@@ -1316,6 +1503,7 @@ impl WinitWindow {
             NSApp().activateIgnoringOtherApps(true);
             self.makeKeyAndOrderFront(None);
         }
+        Ok(())
     }
 
     #[inline]
@@ -1346,6 +1534,11 @@ impl WinitWindow {
         monitor::available_monitors()
     }
 
+    #[inline]
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        monitor.work_area()
+    }
+
     #[inline]
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         let monitor = monitor::primary_monitor();
@@ -1429,12 +1622,67 @@ impl WinitWindow {
         })
     }
 
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        // The system would only capture the desktop behind the window.
+        if self.sharingType() == NSWindowSharingType::NSWindowSharingNone {
+            return Ok(ImageBuffer::from_rgba(Vec::new(), PhysicalSize::new(0, 0)));
+        }
+
+        // Quartz uses a top-left origin, on the main display.
+        let content = self.contentRectForFrameRect(self.frame());
+        let bounds = CGRect::new(
+            &CGPoint::new(
+                content.origin.x as f64,
+                util::bottom_left_to_top_left(content),
+            ),
+            &CGSize::new(content.size.width as f64, content.size.height as f64),
+        );
+        let image = create_image(
+            bounds,
+            kCGWindowListOptionIncludingWindow,
+            self.windowNumber() as CGWindowID,
+            kCGWindowImageBoundsIgnoreFraming | kCGWindowImageBestResolution,
+        )
+        .ok_or_else(|| {
+            ExternalError::Os(os_error!(OsError::CreationError(
+                "Couldn't capture the window",
+            )))
+        })?;
+        // The window images are BGRA, with premultiplied alpha.
+        if image.bits_per_pixel() != 32 {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        let (width, height) = (image.width(), image.height());
+        let data = image.data();
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for row in data.bytes().chunks(image.bytes_per_row()).take(height) {
+            for pixel in row.chunks_exact(4).take(width) {
+                let [b, g, r, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+                let unpremultiply = |color: u8| match a {
+                    0 => 0,
+                    a => (u16::from(color) * 0xff / u16::from(a)).min(0xff) as u8,
+                };
+                rgba.extend_from_slice(&[unpremultiply(r), unpremultiply(g), unpremultiply(b), a]);
+            }
+        }
+        Ok(ImageBuffer::from_rgba(
+            rgba,
+            PhysicalSize::new(width as u32, height as u32),
+        ))
+    }
+
     pub fn title(&self) -> String {
         self.title_().to_string()
     }
 
     pub fn reset_dead_keys(&self) {
-        // (Artur) I couldn't find a way to implement this.
+        self.view().reset_dead_keys();
+    }
+
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        true
     }
 }
 
@@ -1578,6 +1826,14 @@ impl WindowExtMacOS for WinitWindow {
         self.setDocumentEdited(edited)
     }
 
+    fn set_subtitle(&self, subtitle: &str) {
+        // Only available since macOS 11.
+        let has_subtitle: bool = unsafe { msg_send![self, respondsToSelector: sel!(setSubtitle:)] };
+        if has_subtitle {
+            self.setSubtitle(&NSString::from_str(subtitle))
+        }
+    }
+
     fn set_option_as_alt(&self, option_as_alt: OptionAsAlt) {
         let mut shared_state_lock = self.lock_shared_state("set_option_as_alt");
         shared_state_lock.option_as_alt = option_as_alt;
@@ -1587,6 +1843,14 @@ impl WindowExtMacOS for WinitWindow {
         let shared_state_lock = self.lock_shared_state("option_as_alt");
         shared_state_lock.option_as_alt
     }
+
+    fn set_window_level_raw(&self, level: i64) {
+        self.setLevel(NSWindowLevel(level as NSInteger));
+    }
+
+    fn window_level_raw(&self) -> i64 {
+        self.level().0 as i64
+    }
 }
 
 pub(super) fn get_ns_theme() -> Theme {
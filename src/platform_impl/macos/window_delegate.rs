@@ -2,7 +2,7 @@
 use std::cell::Cell;
 use std::ptr::{self, NonNull};
 
-use icrate::Foundation::{NSArray, NSObject, NSSize, NSString};
+use icrate::Foundation::{NSArray, NSObject, NSPoint, NSSize, NSString, NSUInteger};
 use objc2::declare::{Ivar, IvarDrop};
 use objc2::rc::{autoreleasepool, Id};
 use objc2::runtime::AnyObject;
@@ -20,7 +20,7 @@ use super::{
 use crate::{
     dpi::{LogicalPosition, LogicalSize},
     event::{Event, WindowEvent},
-    window::WindowId,
+    window::{DropEffect, WindowId},
 };
 
 #[derive(Debug)]
@@ -129,6 +129,20 @@ declare_class!(
             self.emit_move_event();
         }
 
+        /// Invoked when the window has been minimized into the Dock
+        #[method(windowDidMiniaturize:)]
+        fn window_did_miniaturize(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowDidMiniaturize:");
+            self.queue_event(WindowEvent::Minimized(true));
+        }
+
+        /// Invoked when the window has been restored from the Dock
+        #[method(windowDidDeminiaturize:)]
+        fn window_did_deminiaturize(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowDidDeminiaturize:");
+            self.queue_event(WindowEvent::Minimized(false));
+        }
+
         #[method(windowWillStartLiveResize:)]
         fn window_will_start_live_resize(&self, _: Option<&AnyObject>) {
             trace_scope!("windowWillStartLiveResize:");
@@ -164,6 +178,7 @@ declare_class!(
             trace_scope!("windowDidBecomeKey:");
             // TODO: center the cursor if the window had mouse grab when it
             // lost focus
+            self.window.update_auto_grab(true);
             self.queue_event(WindowEvent::Focused(true));
         }
 
@@ -178,13 +193,14 @@ declare_class!(
             // easily fall out of synchrony with reality.  This requires us to emit
             // a synthetic ModifiersChanged event when we lose focus.
             self.window.view().reset_modifiers();
+            self.window.update_auto_grab(false);
 
             self.queue_event(WindowEvent::Focused(false));
         }
 
         /// Invoked when the dragged image enters destination bounds or frame
         #[method(draggingEntered:)]
-        fn dragging_entered(&self, sender: &NSObject) -> bool {
+        fn dragging_entered(&self, sender: &NSObject) -> NSUInteger {
             trace_scope!("draggingEntered:");
 
             use std::path::PathBuf;
@@ -198,7 +214,16 @@ declare_class!(
                 self.queue_event(WindowEvent::HoveredFile(path));
             });
 
-            true
+            self.queue_dragging_location(sender);
+            self.drag_operation()
+        }
+
+        /// Invoked periodically as the image is held within the destination area
+        #[method(draggingUpdated:)]
+        fn dragging_updated(&self, sender: &NSObject) -> NSUInteger {
+            trace_scope!("draggingUpdated:");
+            self.queue_dragging_location(sender);
+            self.drag_operation()
         }
 
         /// Invoked when the image is released
@@ -451,15 +476,25 @@ impl WinitWindowDelegate {
 
     fn queue_static_scale_factor_changed_event(&self) {
         let scale_factor = self.window.scale_factor();
-        if scale_factor == self.state.previous_scale_factor.get() {
+        let old_scale_factor = self.state.previous_scale_factor.replace(scale_factor);
+        if scale_factor == old_scale_factor {
             return;
         };
 
-        self.state.previous_scale_factor.set(scale_factor);
+        // A physical cursor area maps to another point of the view at the new scale.
+        let ime_cursor_area = self
+            .window
+            .lock_shared_state("queue_static_scale_factor_changed_event")
+            .ime_cursor_area;
+        if let Some((spot, size)) = ime_cursor_area {
+            self.window.set_ime_cursor_area(spot, size);
+        }
+
         let suggested_size = self.view_size();
         AppState::queue_static_scale_factor_changed_event(
             self.window.clone(),
             suggested_size.to_physical(scale_factor),
+            old_scale_factor,
             scale_factor,
         );
     }
@@ -476,6 +511,27 @@ impl WinitWindowDelegate {
         }
     }
 
+    fn queue_dragging_location(&self, sender: &NSObject) {
+        let window_point: NSPoint = unsafe { msg_send![sender, draggingLocation] };
+        let view = self.window.contentView();
+        let view_point = view.convertPoint_fromView(window_point, None);
+        let view_rect = view.frame();
+        let x = view_point.x as f64;
+        let y = view_rect.size.height as f64 - view_point.y as f64;
+        let position = LogicalPosition::new(x, y).to_physical(self.window.scale_factor());
+        self.queue_event(WindowEvent::HoveredFileMoved { position });
+    }
+
+    /// The `NSDragOperation` matching the effect set with `set_drop_feedback`.
+    fn drag_operation(&self) -> NSUInteger {
+        match self.window.lock_shared_state("drag_operation").drop_effect {
+            DropEffect::None => 0,  // NSDragOperationNone
+            DropEffect::Copy => 1,  // NSDragOperationCopy
+            DropEffect::Link => 2,  // NSDragOperationLink
+            DropEffect::Move => 16, // NSDragOperationMove
+        }
+    }
+
     fn view_size(&self) -> LogicalSize<f64> {
         let size = self.window.contentView().frame().size;
         LogicalSize::new(size.width as f64, size.height as f64)
@@ -25,6 +25,27 @@ mod platform;
 
 pub use self::platform::*;
 
+#[cfg(any(x11_platform, macos_platform))]
+mod cursor_grab;
+#[cfg(any(x11_platform, macos_platform))]
+pub(crate) use self::cursor_grab::AutoGrab;
+#[cfg(not(any(ios_platform, android_platform, orbital_platform)))]
+mod cursor_regions;
+#[cfg(not(any(ios_platform, android_platform, orbital_platform)))]
+pub(crate) use self::cursor_regions::CursorRegions;
+#[cfg(any(wayland_platform, windows_platform))]
+mod ime_cursor_area;
+#[cfg(any(wayland_platform, windows_platform))]
+pub(crate) use self::ime_cursor_area::ImeCursorArea;
+#[cfg(any(x11_platform, wayland_platform, windows_platform, macos_platform))]
+mod size_constraints;
+#[cfg(any(x11_platform, wayland_platform, windows_platform, macos_platform))]
+pub(crate) use self::size_constraints::SizeConstraints;
+#[cfg(not(any(macos_platform, orbital_platform)))]
+mod touch;
+#[cfg(not(any(macos_platform, orbital_platform)))]
+pub(crate) use self::touch::TouchIds;
+
 /// Helper for converting between platform-specific and generic VideoMode/MonitorHandle
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum Fullscreen {
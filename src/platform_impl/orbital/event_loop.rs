@@ -14,7 +14,8 @@ use orbclient::{
 use smol_str::SmolStr;
 
 use crate::{
-    error::EventLoopError,
+    dpi::PhysicalRect,
+    error::{EventLoopError, ExternalError, NotSupportedError},
     event::{self, Ime, Modifiers, StartCause},
     event_loop::{self, ControlFlow, DeviceEvents},
     keyboard::{
@@ -308,7 +309,6 @@ impl EventState {
 pub struct EventLoop<T: 'static> {
     windows: Vec<(Arc<RedoxSocket>, EventState)>,
     window_target: event_loop::EventLoopWindowTarget<T>,
-    user_events_sender: mpsc::Sender<T>,
     user_events_receiver: mpsc::Receiver<T>,
 }
 
@@ -348,11 +348,11 @@ impl<T: 'static> EventLoop<T> {
                     destroys: Arc::new(Mutex::new(VecDeque::new())),
                     event_socket,
                     wake_socket,
-                    p: PhantomData,
+                    user_events_sender,
                 },
+                handler_state: Default::default(),
                 _marker: PhantomData,
             },
-            user_events_sender,
             user_events_receiver,
         })
     }
@@ -673,7 +673,7 @@ impl<T: 'static> EventLoop<T> {
                 event_handler(
                     event::Event::WindowEvent {
                         window_id: RootWindowId(window_id),
-                        event: event::WindowEvent::RedrawRequested,
+                        event: event::WindowEvent::RedrawRequested { dirty_rects: None },
                     },
                     &self.window_target,
                 );
@@ -759,10 +759,7 @@ impl<T: 'static> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy {
-            user_events_sender: self.user_events_sender.clone(),
-            wake_socket: self.window_target.p.wake_socket.clone(),
-        }
+        self.window_target.p.create_proxy()
     }
 }
 
@@ -781,6 +778,10 @@ impl<T> EventLoopProxy<T> {
 
         Ok(())
     }
+
+    pub fn wake_up(&self) {
+        self.wake_socket.wake().unwrap();
+    }
 }
 
 impl<T> Clone for EventLoopProxy<T> {
@@ -802,10 +803,17 @@ pub struct EventLoopWindowTarget<T: 'static> {
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) event_socket: Arc<RedoxSocket>,
     pub(super) wake_socket: Arc<TimeSocket>,
-    p: PhantomData<T>,
+    user_events_sender: mpsc::Sender<T>,
 }
 
 impl<T: 'static> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy {
+            user_events_sender: self.user_events_sender.clone(),
+            wake_socket: self.wake_socket.clone(),
+        }
+    }
+
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         Some(MonitorHandle)
     }
@@ -819,6 +827,26 @@ impl<T: 'static> EventLoopWindowTarget<T> {
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    #[inline]
+    pub fn device_info(&self, _device_id: DeviceId) -> Option<event::DeviceInfo> {
+        None
+    }
+
+    #[inline]
+    pub fn backend(&self) -> event_loop::Backend {
+        event_loop::Backend::Orbital
+    }
+
+    #[inline]
+    pub fn power_state(&self) -> Option<event_loop::PowerState> {
+        None
+    }
+
+    #[inline]
+    pub fn clip_cursor(&self, _rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[cfg(feature = "rwh_05")]
     #[inline]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
@@ -843,6 +871,15 @@ impl<T: 'static> EventLoopWindowTarget<T> {
         self.control_flow.get()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        let mut redraws = self.redraws.lock().unwrap();
+        if !redraws.contains(&window_id) {
+            redraws.push_back(window_id);
+
+            self.wake_socket.wake().unwrap();
+        }
+    }
+
     pub(crate) fn exit(&self) {
         self.exit.set(true);
     }
@@ -59,7 +59,14 @@ impl RedoxSocket {
 
     fn fpath<'a>(&self, buf: &'a mut [u8]) -> syscall::Result<&'a str> {
         let count = syscall::fpath(self.fd, buf)?;
-        str::from_utf8(&buf[..count]).map_err(|_err| syscall::Error::new(syscall::EINVAL))
+        match str::from_utf8(&buf[..count]) {
+            Ok(path) => Ok(path),
+            // The path was cut by the end of the buffer in the middle of a character.
+            Err(err) if err.error_len().is_none() => {
+                Ok(str::from_utf8(&buf[..err.valid_up_to()]).unwrap())
+            }
+            Err(_) => Err(syscall::Error::new(syscall::EINVAL)),
+        }
     }
 }
 
@@ -130,6 +137,10 @@ impl DeviceId {
     pub const fn dummy() -> Self {
         DeviceId
     }
+
+    pub fn persistent_id(&self) -> Option<u64> {
+        None
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -265,6 +276,7 @@ impl VideoMode {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEventExtra {
     pub key_without_modifiers: Key,
     pub text_with_all_modifiers: Option<SmolStr>,
@@ -4,11 +4,11 @@ use std::{
 };
 
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size},
     error,
     platform_impl::Fullscreen,
     window,
-    window::ImePurpose,
+    window::{ImageBuffer, ImeHints, ImePurpose},
 };
 
 use super::{
@@ -32,6 +32,7 @@ pub struct Window {
     redraws: Arc<Mutex<VecDeque<WindowId>>>,
     destroys: Arc<Mutex<VecDeque<WindowId>>>,
     wake_socket: Arc<TimeSocket>,
+    title: Mutex<String>,
 }
 
 impl Window {
@@ -40,6 +41,12 @@ impl Window {
         attrs: window::WindowAttributes,
         _: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Self, error::OsError> {
+        if el.exiting() {
+            return Err(os_error!(OsError::new(syscall::Error::new(
+                syscall::ESHUTDOWN
+            ))));
+        }
+
         let scale = MonitorHandle.scale_factor();
 
         let (x, y) = if let Some(pos) = attrs.position {
@@ -129,6 +136,7 @@ impl Window {
             redraws: el.redraws.clone(),
             destroys: el.destroys.clone(),
             wake_socket: el.wake_socket.clone(),
+            title: Mutex::new(attrs.title),
         })
     }
 
@@ -176,6 +184,11 @@ impl Window {
         v
     }
 
+    #[inline]
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        PhysicalRect::new(monitor.position(), monitor.size())
+    }
+
     #[inline]
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
         Some(MonitorHandle)
@@ -205,6 +218,11 @@ impl Window {
         // TODO?
     }
 
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        true
+    }
+
     #[inline]
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, error::NotSupportedError> {
         let mut buf: [u8; 4096] = [0; 4096];
@@ -257,6 +275,25 @@ impl Window {
         self.inner_size()
     }
 
+    #[inline]
+    pub fn set_visible_on_all_workspaces(
+        &self,
+        _visible: bool,
+    ) -> Result<(), error::NotSupportedError> {
+        Err(error::NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> window::Insets {
+        //TODO: adjust for window decorations
+        window::Insets::default()
+    }
+
     #[inline]
     pub fn set_min_inner_size(&self, _: Option<Size>) {}
 
@@ -265,13 +302,8 @@ impl Window {
 
     #[inline]
     pub fn title(&self) -> String {
-        let mut buf: [u8; 4096] = [0; 4096];
-        let path = self
-            .window_socket
-            .fpath(&mut buf)
-            .expect("failed to read properties");
-        let properties = WindowProperties::new(path);
-        properties.title.to_string()
+        // The window path is limited in length, so the full title is kept on our side.
+        self.title.lock().unwrap().clone()
     }
 
     #[inline]
@@ -279,6 +311,7 @@ impl Window {
         self.window_socket
             .write(format!("T,{title}").as_bytes())
             .expect("failed to set title");
+        *self.title.lock().unwrap() = title.to_owned();
     }
 
     #[inline]
@@ -299,6 +332,9 @@ impl Window {
         Some(!self.get_flag(ORBITAL_FLAG_HIDDEN).unwrap_or(false))
     }
 
+    #[inline]
+    pub fn set_enabled(&self, _enabled: bool) {}
+
     #[inline]
     pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
         None
@@ -372,6 +408,9 @@ impl Window {
     #[inline]
     pub fn set_window_icon(&self, _window_icon: Option<crate::icon::Icon>) {}
 
+    #[inline]
+    pub fn set_drop_feedback(&self, _effect: crate::window::DropEffect) {}
+
     #[inline]
     pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
@@ -382,7 +421,26 @@ impl Window {
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
     #[inline]
-    pub fn focus_window(&self) {}
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
+    #[inline]
+    pub fn set_above(&self, _sibling: &Window) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    #[inline]
+    pub fn set_below(&self, _sibling: &Window) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), error::NotSupportedError> {
+        Err(error::NotSupportedError::new())
+    }
 
     #[inline]
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
@@ -390,6 +448,9 @@ impl Window {
     #[inline]
     pub fn set_cursor_icon(&self, _: window::CursorIcon) {}
 
+    #[inline]
+    pub fn set_cursor_regions(&self, _: Vec<(PhysicalRect, window::CursorIcon)>) {}
+
     #[inline]
     pub fn set_cursor_position(&self, _: Position) -> Result<(), error::ExternalError> {
         Err(error::ExternalError::NotSupported(
@@ -416,6 +477,19 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn confine_cursor_to_rect(
+        &self,
+        _rect: Option<PhysicalRect>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
+    #[inline]
+    pub fn set_auto_grab(&self, _auto_grab: bool) {}
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         let _ = self
@@ -462,6 +536,15 @@ impl Window {
         ))
     }
 
+    pub fn set_shape(
+        &self,
+        _shape: Option<window::WindowShape>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
+
     #[cfg(feature = "rwh_04")]
     #[inline]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
@@ -526,6 +609,13 @@ impl Window {
     pub fn set_theme(&self, _theme: Option<window::Theme>) {}
 
     pub fn set_content_protected(&self, _protected: bool) {}
+
+    #[inline]
+    pub fn capture(&self) -> Result<ImageBuffer, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(
+            error::NotSupportedError::new(),
+        ))
+    }
 }
 
 impl Drop for Window {
@@ -0,0 +1,139 @@
+//! Clamping of the sizes requested with
+//! [`Window::request_inner_size`](crate::window::Window::request_inner_size), for the backends
+//! which apply the constraints themselves instead of leaving them to the windowing system, and
+//! of the initial inner size of the windows.
+
+use crate::dpi::Size;
+use crate::window::WindowAttributes;
+
+/// The constraints on the inner size of a window, in the unit of the sizes they're applied to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SizeConstraints {
+    pub min: Option<(u32, u32)>,
+    pub max: Option<(u32, u32)>,
+    pub increments: Option<(u32, u32)>,
+    /// The size the increments are counted from, which defaults to `min`.
+    pub base: Option<(u32, u32)>,
+}
+
+impl SizeConstraints {
+    /// The constraints of a window created with `attributes`, in physical pixels at the given
+    /// scale factor.
+    pub fn from_attributes(
+        attributes: &WindowAttributes,
+        base: Option<Size>,
+        scale_factor: f64,
+    ) -> Self {
+        let to_physical = |size: Size| size.to_physical::<u32>(scale_factor).into();
+        Self {
+            min: attributes.min_inner_size.map(to_physical),
+            max: attributes.max_inner_size.map(to_physical),
+            increments: attributes.resize_increments.map(to_physical),
+            base: base.map(to_physical),
+        }
+    }
+
+    /// Clamps `size` between `min` and `max` first, and then rounds it down to a whole number of
+    /// `increments` from `base`, without going below `min`.
+    pub fn clamp<S: Into<(u32, u32)> + From<(u32, u32)>>(&self, size: S) -> S {
+        let (width, height) = size.into();
+        let (min_width, min_height) = self.min.unwrap_or((0, 0));
+        let (max_width, max_height) = self.max.unwrap_or((u32::MAX, u32::MAX));
+        let mut width = width.min(max_width).max(min_width);
+        let mut height = height.min(max_height).max(min_height);
+
+        if let Some((width_increment, height_increment)) = self.increments {
+            let (base_width, base_height) = self.base.or(self.min).unwrap_or((0, 0));
+            width = round_down(width, base_width, width_increment).max(min_width);
+            height = round_down(height, base_height, height_increment).max(min_height);
+        }
+
+        S::from((width, height))
+    }
+}
+
+fn round_down(size: u32, base: u32, increment: u32) -> u32 {
+    if increment == 0 || size <= base {
+        size
+    } else {
+        size - (size - base) % increment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpi::{LogicalSize, PhysicalSize};
+
+    #[test]
+    fn min_and_max_clamp_the_size() {
+        let constraints = SizeConstraints {
+            min: Some((640, 480)),
+            max: Some((1280, 720)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            constraints.clamp(PhysicalSize::new(100, 100)),
+            PhysicalSize::new(640, 480)
+        );
+        assert_eq!(
+            constraints.clamp(PhysicalSize::new(2000, 600)),
+            PhysicalSize::new(1280, 600)
+        );
+    }
+
+    #[test]
+    fn increments_apply_after_min_and_max() {
+        let constraints = SizeConstraints {
+            min: Some((100, 100)),
+            max: Some((195, 1000)),
+            increments: Some((10, 30)),
+            ..Default::default()
+        };
+
+        // The max is applied first, and the increments then round down from the min.
+        assert_eq!(
+            constraints.clamp(PhysicalSize::new(500, 145)),
+            PhysicalSize::new(190, 130)
+        );
+        // The min isn't a multiple of the increments but is still allowed.
+        assert_eq!(
+            constraints.clamp(PhysicalSize::new(50, 50)),
+            PhysicalSize::new(100, 100)
+        );
+
+        let constraints = SizeConstraints {
+            base: Some((5, 0)),
+            ..constraints
+        };
+        assert_eq!(
+            constraints.clamp(PhysicalSize::new(500, 145)),
+            PhysicalSize::new(195, 120)
+        );
+    }
+
+    #[test]
+    fn attributes_are_converted_at_the_scale_factor() {
+        let attributes = WindowAttributes {
+            min_inner_size: Some(LogicalSize::new(1.5, 3.5).into()),
+            resize_increments: Some(PhysicalSize::new(10, 20).into()),
+            ..Default::default()
+        };
+        let constraints = SizeConstraints::from_attributes(&attributes, None, 2.0);
+
+        assert_eq!(
+            constraints,
+            SizeConstraints {
+                min: Some((3, 7)),
+                increments: Some((10, 20)),
+                ..Default::default()
+            }
+        );
+        // The initial size is snapped to the increments, counted from the minimum size.
+        assert_eq!(
+            constraints.clamp(PhysicalSize::new(805, 617)),
+            PhysicalSize::new(803, 607)
+        );
+    }
+}
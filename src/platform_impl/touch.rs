@@ -0,0 +1,82 @@
+//! Tracking of the identifiers reported in [`Touch::id`](crate::event::Touch::id).
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::event::TouchPhase;
+
+static NEXT_TOUCH_ID: AtomicU64 = AtomicU64::new(0);
+
+fn next_touch_id() -> u64 {
+    NEXT_TOUCH_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Maps the identifiers used by the platform, which are usually recycled as soon as a finger is
+/// lifted, to identifiers that are never handed out twice.
+#[derive(Debug)]
+pub(crate) struct TouchIds<K> {
+    active: HashMap<K, u64>,
+}
+
+impl<K> Default for TouchIds<K> {
+    fn default() -> Self {
+        Self {
+            active: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash> TouchIds<K> {
+    /// Returns the identifier to report for the platform touch `key` in the given `phase`.
+    ///
+    /// [`TouchPhase::Started`] always allocates a new identifier, which is released again once
+    /// the touch is [`TouchPhase::Ended`] or [`TouchPhase::Cancelled`].
+    pub fn get(&mut self, key: K, phase: TouchPhase) -> u64 {
+        match phase {
+            TouchPhase::Started => {
+                let id = next_touch_id();
+                self.active.insert(key, id);
+                id
+            }
+            // We may have missed the start if the touch began before the window was created.
+            TouchPhase::Moved => *self.active.entry(key).or_insert_with(next_touch_id),
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.active.remove(&key).unwrap_or_else(next_touch_id)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_unique_across_overlapping_touches() {
+        let mut ids = TouchIds::default();
+
+        let first = ids.get(0, TouchPhase::Started);
+        let second = ids.get(1, TouchPhase::Started);
+        assert_ne!(first, second);
+        assert_eq!(ids.get(0, TouchPhase::Moved), first);
+        assert_eq!(ids.get(1, TouchPhase::Moved), second);
+
+        // The platform reuses the key of the first finger while the second one is still down.
+        assert_eq!(ids.get(0, TouchPhase::Ended), first);
+        let third = ids.get(0, TouchPhase::Started);
+        assert_ne!(third, first);
+        assert_ne!(third, second);
+
+        assert_eq!(ids.get(1, TouchPhase::Cancelled), second);
+        assert_eq!(ids.get(0, TouchPhase::Ended), third);
+    }
+
+    #[test]
+    fn ids_are_unique_across_trackers() {
+        let mut a = TouchIds::default();
+        let mut b = TouchIds::default();
+
+        assert_ne!(a.get(0, TouchPhase::Started), b.get(0, TouchPhase::Started));
+    }
+}
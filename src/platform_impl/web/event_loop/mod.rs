@@ -1,5 +1,5 @@
 use std::marker::PhantomData;
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::mpsc::{self, Receiver};
 
 use crate::error::EventLoopError;
 use crate::event::Event;
@@ -17,7 +17,6 @@ pub use window_target::EventLoopWindowTarget;
 
 pub struct EventLoop<T: 'static> {
     elw: RootEventLoopWindowTarget<T>,
-    user_event_sender: Sender<T>,
     user_event_receiver: Receiver<T>,
 }
 
@@ -28,12 +27,12 @@ impl<T> EventLoop<T> {
     pub(crate) fn new(_: &PlatformSpecificEventLoopAttributes) -> Result<Self, EventLoopError> {
         let (user_event_sender, user_event_receiver) = mpsc::channel();
         let elw = RootEventLoopWindowTarget {
-            p: EventLoopWindowTarget::new(),
+            p: EventLoopWindowTarget::new(user_event_sender),
+            handler_state: Default::default(),
             _marker: PhantomData,
         };
         Ok(EventLoop {
             elw,
-            user_event_sender,
             user_event_receiver,
         })
     }
@@ -44,21 +43,27 @@ impl<T> EventLoop<T> {
     {
         let target = RootEventLoopWindowTarget {
             p: self.elw.p.clone(),
+            handler_state: self.elw.handler_state.clone(),
             _marker: PhantomData,
         };
 
-        // SAFETY: Don't use `move` to make sure we leak the `event_handler` and `target`.
-        let handler: Box<dyn FnMut(Event<()>)> = Box::new(|event| {
+        // SAFETY: Only borrow the `event_handler` and `target` to make sure we leak them. The
+        // receiver is moved in, so that it is dropped with the handler once the loop exits, and
+        // `EventLoopProxy::send_event` starts failing.
+        let event_handler = &mut event_handler;
+        let target = &target;
+        let user_event_receiver = self.user_event_receiver;
+        let handler: Box<dyn FnMut(Event<()>)> = Box::new(move |event| {
             let event = match event.map_nonuser_event() {
                 Ok(event) => event,
                 Err(Event::UserEvent(())) => Event::UserEvent(
-                    self.user_event_receiver
+                    user_event_receiver
                         .try_recv()
                         .expect("handler woken up without user event"),
                 ),
                 Err(_) => unreachable!(),
             };
-            event_handler(event, &target)
+            event_handler(event, target)
         });
         // SAFETY: The `transmute` is necessary because `run()` requires `'static`. This is safe
         // because this function will never return and all resources not cleaned up by the point we
@@ -81,6 +86,7 @@ impl<T> EventLoop<T> {
     {
         let target = RootEventLoopWindowTarget {
             p: self.elw.p.clone(),
+            handler_state: self.elw.handler_state.clone(),
             _marker: PhantomData,
         };
 
@@ -102,7 +108,7 @@ impl<T> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.elw.p.waker(), self.user_event_sender.clone())
+        self.elw.p.create_proxy()
     }
 
     pub fn window_target(&self) -> &RootEventLoopWindowTarget<T> {
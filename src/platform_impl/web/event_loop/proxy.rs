@@ -7,12 +7,21 @@ use crate::platform_impl::platform::r#async::Waker;
 
 pub struct EventLoopProxy<T: 'static> {
     runner: Waker<Weak<Execution>>,
+    wake_up: Waker<Weak<Execution>>,
     sender: Sender<T>,
 }
 
 impl<T: 'static> EventLoopProxy<T> {
-    pub fn new(runner: Waker<Weak<Execution>>, sender: Sender<T>) -> Self {
-        Self { runner, sender }
+    pub fn new(
+        runner: Waker<Weak<Execution>>,
+        wake_up: Waker<Weak<Execution>>,
+        sender: Sender<T>,
+    ) -> Self {
+        Self {
+            runner,
+            wake_up,
+            sender,
+        }
     }
 
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
@@ -22,12 +31,17 @@ impl<T: 'static> EventLoopProxy<T> {
         self.runner.wake();
         Ok(())
     }
+
+    pub fn wake_up(&self) {
+        self.wake_up.wake();
+    }
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         Self {
             runner: self.runner.clone(),
+            wake_up: self.wake_up.clone(),
             sender: self.sender.clone(),
         }
     }
@@ -37,6 +37,7 @@ type OnEventHandle<T> = RefCell<Option<EventListenerHandle<dyn FnMut(T)>>>;
 
 pub struct Execution {
     proxy_spawner: WakerSpawner<Weak<Self>>,
+    wake_up_spawner: WakerSpawner<Weak<Self>>,
     control_flow: Cell<ControlFlow>,
     poll_strategy: Cell<PollStrategy>,
     exit: Cell<bool>,
@@ -156,8 +157,18 @@ impl Shared {
             })
             .expect("`EventLoop` has to be created in the main thread");
 
+            // Wake ups without a user event only need to run an iteration, and any number of
+            // them are handled by a single one.
+            let wake_up_spawner = WakerSpawner::new(weak.clone(), |runner, _| {
+                if let Some(runner) = runner.upgrade() {
+                    Shared(runner).send_events(iter::empty::<Event<()>>())
+                }
+            })
+            .expect("`EventLoop` has to be created in the main thread");
+
             Execution {
                 proxy_spawner,
+                wake_up_spawner,
                 control_flow: Cell::new(ControlFlow::default()),
                 poll_strategy: Cell::new(PollStrategy::default()),
                 exit: Cell::new(false),
@@ -593,7 +604,7 @@ impl Shared {
         for window_id in redraw_events {
             self.handle_event(Event::WindowEvent {
                 window_id,
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::RedrawRequested { dirty_rects: None },
             });
         }
 
@@ -622,7 +633,14 @@ impl Shared {
         if self.is_closed() {
             self.exit();
         }
-        match *self.0.runner.borrow_mut() {
+        // Events are only handled with the runner free, see `send_events`, unless an event handler
+        // panicked: WebAssembly doesn't unwind, so its borrow is never released. Panicking again
+        // when the page is unloaded would only hide the panic of the application.
+        let mut runner_enum = match self.0.runner.try_borrow_mut() {
+            Ok(runner_enum) => runner_enum,
+            Err(_) => return,
+        };
+        match *runner_enum {
             RunnerEnum::Running(ref mut runner) => {
                 runner.handle_single_event(self, event);
             }
@@ -632,6 +650,7 @@ impl Shared {
             // If the Runner has been destroyed, there is nothing to do.
             RunnerEnum::Destroyed => return,
         }
+        drop(runner_enum);
 
         let is_closed = self.exiting();
 
@@ -711,7 +730,8 @@ impl Shared {
             }
         };
 
-        if let RunnerEnum::Running(ref mut runner) = *self.0.runner.borrow_mut() {
+        // The runner is only borrowed here if an event handler panicked, see `handle_event`.
+        if let Ok(RunnerEnum::Running(runner)) = self.0.runner.try_borrow_mut().as_deref_mut() {
             runner.state = new_state;
         }
     }
@@ -830,6 +850,10 @@ impl Shared {
     pub(crate) fn waker(&self) -> Waker<Weak<Execution>> {
         self.0.proxy_spawner.waker()
     }
+
+    pub(crate) fn wake_up_waker(&self) -> Waker<Weak<Execution>> {
+        self.0.wake_up_spawner.waker()
+    }
 }
 
 pub(crate) enum EventWrapper {
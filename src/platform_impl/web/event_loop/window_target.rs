@@ -1,26 +1,30 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{vec_deque::IntoIter as VecDequeIter, VecDeque};
 use std::iter;
-use std::marker::PhantomData;
-use std::rc::{Rc, Weak};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
 
 use web_sys::Element;
 
-use super::runner::{EventWrapper, Execution};
+use super::runner::EventWrapper;
 use super::{
     super::{monitor::MonitorHandle, KeyEventExtra},
     backend,
     device::DeviceId,
     runner,
-    window::WindowId,
+    window::{CursorState, WindowId},
+    EventLoopProxy,
 };
+use crate::dpi::PhysicalRect;
+use crate::error::{ExternalError, NotSupportedError};
 use crate::event::{
-    DeviceId as RootDeviceId, ElementState, Event, KeyEvent, Touch, TouchPhase, WindowEvent,
+    DeviceId as RootDeviceId, DeviceInfo, ElementState, Event, KeyEvent, Touch, TouchPhase,
+    WindowEvent,
 };
-use crate::event_loop::{ControlFlow, DeviceEvents};
+use crate::event_loop::{Backend, ControlFlow, DeviceEvents, PowerState};
 use crate::keyboard::ModifiersState;
 use crate::platform::web::PollStrategy;
-use crate::platform_impl::platform::r#async::Waker;
+use crate::platform_impl::TouchIds;
 use crate::window::{Theme, WindowId as RootWindowId};
 
 #[derive(Default)]
@@ -45,7 +49,7 @@ impl Clone for ModifiersShared {
 pub struct EventLoopWindowTarget<T: 'static> {
     pub(crate) runner: runner::Shared,
     modifiers: ModifiersShared,
-    _marker: PhantomData<T>,
+    user_event_sender: Sender<T>,
 }
 
 impl<T> Clone for EventLoopWindowTarget<T> {
@@ -53,20 +57,28 @@ impl<T> Clone for EventLoopWindowTarget<T> {
         Self {
             runner: self.runner.clone(),
             modifiers: self.modifiers.clone(),
-            _marker: PhantomData,
+            user_event_sender: self.user_event_sender.clone(),
         }
     }
 }
 
 impl<T> EventLoopWindowTarget<T> {
-    pub fn new() -> Self {
+    pub fn new(user_event_sender: Sender<T>) -> Self {
         Self {
             runner: runner::Shared::new(),
             modifiers: ModifiersShared::default(),
-            _marker: PhantomData,
+            user_event_sender,
         }
     }
 
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy::new(
+            self.runner.waker(),
+            self.runner.wake_up_waker(),
+            self.user_event_sender.clone(),
+        )
+    }
+
     pub fn run(&self, event_handler: Box<runner::EventHandler>, event_loop_recreation: bool) {
         self.runner.event_loop_recreation(event_loop_recreation);
         self.runner.set_listener(event_handler);
@@ -79,14 +91,14 @@ impl<T> EventLoopWindowTarget<T> {
     pub fn register(
         &self,
         canvas: &Rc<RefCell<backend::Canvas>>,
+        cursor: &Rc<RefCell<CursorState>>,
         id: WindowId,
-        prevent_default: bool,
     ) {
         let canvas_clone = canvas.clone();
         let mut canvas = canvas.borrow_mut();
         canvas.set_attribute("data-raw-handle", &id.0.to_string());
 
-        canvas.on_touch_start(prevent_default);
+        canvas.on_touch_start();
 
         let runner = self.runner.clone();
         let has_focus = canvas.has_focus.clone();
@@ -176,7 +188,6 @@ impl<T> EventLoopWindowTarget<T> {
                     .chain(modifiers_changed),
                 );
             },
-            prevent_default,
         );
 
         let runner = self.runner.clone();
@@ -213,7 +224,6 @@ impl<T> EventLoopWindowTarget<T> {
                     .chain(modifiers_changed),
                 )
             },
-            prevent_default,
         );
 
         let has_focus = canvas.has_focus.clone();
@@ -221,8 +231,16 @@ impl<T> EventLoopWindowTarget<T> {
             let runner = self.runner.clone();
             let has_focus = has_focus.clone();
             let modifiers = self.modifiers.clone();
+            let cursor = cursor.clone();
+            let raw = canvas.raw().clone();
 
             move |active_modifiers, pointer_id| {
+                let mut cursor = cursor.borrow_mut();
+                if cursor.regions.cursor_left() {
+                    cursor.apply(&raw);
+                }
+                drop(cursor);
+
                 let focus = (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
@@ -271,6 +289,9 @@ impl<T> EventLoopWindowTarget<T> {
             }
         });
 
+        // Browsers are free to reuse the ids of pointers that went away.
+        let touch_ids = Rc::new(RefCell::new(TouchIds::default()));
+
         canvas.on_cursor_move(
             {
                 let runner = self.runner.clone();
@@ -291,6 +312,8 @@ impl<T> EventLoopWindowTarget<T> {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let cursor = cursor.clone();
+                let raw = canvas.raw().clone();
 
                 move |active_modifiers, pointer_id, events| {
                     let modifiers =
@@ -303,6 +326,12 @@ impl<T> EventLoopWindowTarget<T> {
                         });
 
                     runner.send_events(modifiers.into_iter().chain(events.flat_map(|position| {
+                        let mut cursor = cursor.borrow_mut();
+                        if cursor.regions.cursor_moved(position) {
+                            cursor.apply(&raw);
+                        }
+                        drop(cursor);
+
                         let device_id = RootDeviceId(DeviceId(pointer_id));
 
                         iter::once(Event::WindowEvent {
@@ -319,6 +348,7 @@ impl<T> EventLoopWindowTarget<T> {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let touch_ids = touch_ids.clone();
 
                 move |active_modifiers, device_id, events| {
                     let modifiers =
@@ -330,15 +360,17 @@ impl<T> EventLoopWindowTarget<T> {
                             }
                         });
 
+                    let touch_id = touch_ids.borrow_mut().get(device_id, TouchPhase::Moved);
                     runner.send_events(modifiers.into_iter().chain(events.map(
-                        |(location, force)| Event::WindowEvent {
+                        move |(location, force)| Event::WindowEvent {
                             window_id: RootWindowId(id),
                             event: WindowEvent::Touch(Touch {
-                                id: device_id as u64,
+                                id: touch_id,
                                 device_id: RootDeviceId(DeviceId(device_id)),
                                 phase: TouchPhase::Moved,
                                 force: Some(force),
                                 location,
+                                palm: false,
                             }),
                         },
                     )));
@@ -393,7 +425,6 @@ impl<T> EventLoopWindowTarget<T> {
                     ]));
                 }
             },
-            prevent_default,
         );
 
         canvas.on_mouse_press(
@@ -451,6 +482,7 @@ impl<T> EventLoopWindowTarget<T> {
             {
                 let runner = self.runner.clone();
                 let modifiers = self.modifiers.clone();
+                let touch_ids = touch_ids.clone();
 
                 move |active_modifiers, device_id, location, force| {
                     let modifiers = (modifiers.get() != active_modifiers).then(|| {
@@ -465,17 +497,17 @@ impl<T> EventLoopWindowTarget<T> {
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
                             event: WindowEvent::Touch(Touch {
-                                id: device_id as u64,
+                                id: touch_ids.borrow_mut().get(device_id, TouchPhase::Started),
                                 device_id: RootDeviceId(DeviceId(device_id)),
                                 phase: TouchPhase::Started,
                                 force: Some(force),
                                 location,
+                                palm: false,
                             }),
                         },
                     )))
                 }
             },
-            prevent_default,
         );
 
         canvas.on_mouse_release(
@@ -537,6 +569,7 @@ impl<T> EventLoopWindowTarget<T> {
                 let runner_touch = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let touch_ids = touch_ids.clone();
 
                 move |active_modifiers, device_id, location, force| {
                     let modifiers =
@@ -552,11 +585,12 @@ impl<T> EventLoopWindowTarget<T> {
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
                             event: WindowEvent::Touch(Touch {
-                                id: device_id as u64,
+                                id: touch_ids.borrow_mut().get(device_id, TouchPhase::Ended),
                                 device_id: RootDeviceId(DeviceId(device_id)),
                                 phase: TouchPhase::Ended,
                                 force: Some(force),
                                 location,
+                                palm: false,
                             }),
                         },
                     )));
@@ -566,41 +600,39 @@ impl<T> EventLoopWindowTarget<T> {
 
         let runner = self.runner.clone();
         let modifiers = self.modifiers.clone();
-        canvas.on_mouse_wheel(
-            move |pointer_id, delta, active_modifiers| {
-                let modifiers_changed = (has_focus.get() && modifiers.get() != active_modifiers)
-                    .then(|| {
-                        modifiers.set(active_modifiers);
-                        Event::WindowEvent {
-                            window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
-                        }
-                    });
-
-                runner.send_events(modifiers_changed.into_iter().chain(iter::once(
+        canvas.on_mouse_wheel(move |pointer_id, delta, active_modifiers| {
+            let modifiers_changed =
+                (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
+                    modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::MouseWheel {
-                            device_id: RootDeviceId(DeviceId(pointer_id)),
-                            delta,
-                            phase: TouchPhase::Moved,
-                        },
+                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                    }
+                });
+
+            runner.send_events(modifiers_changed.into_iter().chain(iter::once(
+                Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::MouseWheel {
+                        device_id: RootDeviceId(DeviceId(pointer_id)),
+                        delta,
+                        phase: TouchPhase::Moved,
                     },
-                )));
-            },
-            prevent_default,
-        );
+                },
+            )));
+        });
 
         let runner = self.runner.clone();
         canvas.on_touch_cancel(move |device_id, location, force| {
             runner.send_event(Event::WindowEvent {
                 window_id: RootWindowId(id),
                 event: WindowEvent::Touch(Touch {
-                    id: device_id as u64,
+                    id: touch_ids.borrow_mut().get(device_id, TouchPhase::Cancelled),
                     device_id: RootDeviceId(DeviceId(device_id)),
                     phase: TouchPhase::Cancelled,
                     force: Some(force),
                     location,
+                    palm: false,
                 }),
             });
         });
@@ -640,6 +672,7 @@ impl<T> EventLoopWindowTarget<T> {
                     canvas.set_current_size(new_size);
                     if canvas.old_size() != new_size {
                         canvas.set_old_size(new_size);
+                        canvas.set_buffer_size(new_size);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
                             event: WindowEvent::Resized(new_size),
@@ -650,7 +683,14 @@ impl<T> EventLoopWindowTarget<T> {
             },
         );
 
+        // Animation frames keep coming while the canvas is scrolled out of view, so its redraws
+        // are held back until it intersects the viewport again.
+        let scrolled_out = Rc::new(Cell::new(false));
+        let redraw_deferred = Rc::new(Cell::new(false));
+
         let runner = self.runner.clone();
+        let intersection_scrolled_out = scrolled_out.clone();
+        let intersection_redraw_deferred = redraw_deferred.clone();
         canvas.on_intersection(move |is_intersecting| {
             // only fire if visible while skipping the first event if it's intersecting
             if backend::is_visible(runner.document())
@@ -663,14 +703,40 @@ impl<T> EventLoopWindowTarget<T> {
             }
 
             canvas_clone.borrow_mut().is_intersecting = Some(is_intersecting);
+            intersection_scrolled_out.set(!is_intersecting);
+            if is_intersecting && intersection_redraw_deferred.replace(false) {
+                runner.request_redraw(RootWindowId(id));
+            }
         });
 
         let runner = self.runner.clone();
-        canvas.on_animation_frame(move || runner.request_redraw(RootWindowId(id)));
+        canvas.on_animation_frame(move || {
+            if scrolled_out.get() {
+                redraw_deferred.set(true);
+            } else {
+                runner.request_redraw(RootWindowId(id));
+            }
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_fullscreen_change(move |is_fullscreen| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::FullscreenChanged(is_fullscreen),
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_fullscreen_error(move || {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::FullscreenFailed,
+            });
+        });
 
         canvas.on_touch_end();
 
-        canvas.on_context_menu(prevent_default);
+        canvas.on_context_menu();
     }
 
     pub fn available_monitors(&self) -> VecDequeIter<MonitorHandle> {
@@ -701,6 +767,22 @@ impl<T> EventLoopWindowTarget<T> {
         self.runner.listen_device_events(allowed)
     }
 
+    pub fn device_info(&self, _device_id: DeviceId) -> Option<DeviceInfo> {
+        None
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend::Web
+    }
+
+    pub fn power_state(&self) -> Option<PowerState> {
+        None
+    }
+
+    pub fn clip_cursor(&self, _rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
         self.runner.set_control_flow(control_flow)
     }
@@ -709,6 +791,10 @@ impl<T> EventLoopWindowTarget<T> {
         self.runner.control_flow()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        self.runner.request_redraw(RootWindowId(window_id))
+    }
+
     pub(crate) fn exit(&self) {
         self.runner.exit()
     }
@@ -724,8 +810,4 @@ impl<T> EventLoopWindowTarget<T> {
     pub(crate) fn poll_strategy(&self) -> PollStrategy {
         self.runner.poll_strategy()
     }
-
-    pub(crate) fn waker(&self) -> Waker<Weak<Execution>> {
-        self.runner.waker()
-    }
 }
@@ -3,6 +3,7 @@ use smol_str::SmolStr;
 use crate::keyboard::{Key, KeyCode, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub(crate) struct KeyEventExtra;
 
 impl Key {
@@ -321,6 +322,8 @@ impl Key {
 }
 
 impl PhysicalKey {
+    /// Maps the `code` of a `KeyboardEvent`, including the legacy values still reported by older
+    /// browsers.
     pub fn from_key_code_attribute_value(kcav: &str) -> Self {
         PhysicalKey::Code(match kcav {
             "Backquote" => KeyCode::Backquote,
@@ -381,8 +384,9 @@ impl PhysicalKey {
             "ControlLeft" => KeyCode::ControlLeft,
             "ControlRight" => KeyCode::ControlRight,
             "Enter" => KeyCode::Enter,
-            "MetaLeft" => KeyCode::SuperLeft,
-            "MetaRight" => KeyCode::SuperRight,
+            // Firefox reported `OSLeft` and `OSRight` until version 118.
+            "MetaLeft" | "OSLeft" => KeyCode::SuperLeft,
+            "MetaRight" | "OSRight" => KeyCode::SuperRight,
             "ShiftLeft" => KeyCode::ShiftLeft,
             "ShiftRight" => KeyCode::ShiftRight,
             "Space" => KeyCode::Space,
@@ -455,15 +459,17 @@ impl PhysicalKey {
             "LaunchApp2" => KeyCode::LaunchApp2,
             "LaunchMail" => KeyCode::LaunchMail,
             "MediaPlayPause" => KeyCode::MediaPlayPause,
-            "MediaSelect" => KeyCode::MediaSelect,
+            // Chrome reports the media select key as `LaunchMediaPlayer`.
+            "MediaSelect" | "LaunchMediaPlayer" => KeyCode::MediaSelect,
             "MediaStop" => KeyCode::MediaStop,
             "MediaTrackNext" => KeyCode::MediaTrackNext,
             "MediaTrackPrevious" => KeyCode::MediaTrackPrevious,
             "Power" => KeyCode::Power,
             "Sleep" => KeyCode::Sleep,
-            "AudioVolumeDown" => KeyCode::AudioVolumeDown,
-            "AudioVolumeMute" => KeyCode::AudioVolumeMute,
-            "AudioVolumeUp" => KeyCode::AudioVolumeUp,
+            // Firefox reported the `Volume` names until version 49, and still does on Linux.
+            "AudioVolumeDown" | "VolumeDown" => KeyCode::AudioVolumeDown,
+            "AudioVolumeMute" | "VolumeMute" => KeyCode::AudioVolumeMute,
+            "AudioVolumeUp" | "VolumeUp" => KeyCode::AudioVolumeUp,
             "WakeUp" => KeyCode::WakeUp,
             "Hyper" => KeyCode::Hyper,
             "Turbo" => KeyCode::Turbo,
@@ -520,3 +526,152 @@ impl PhysicalKey {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_codes_map_to_key_codes() {
+        let letters = [
+            KeyCode::KeyA,
+            KeyCode::KeyB,
+            KeyCode::KeyC,
+            KeyCode::KeyD,
+            KeyCode::KeyE,
+            KeyCode::KeyF,
+            KeyCode::KeyG,
+            KeyCode::KeyH,
+            KeyCode::KeyI,
+            KeyCode::KeyJ,
+            KeyCode::KeyK,
+            KeyCode::KeyL,
+            KeyCode::KeyM,
+            KeyCode::KeyN,
+            KeyCode::KeyO,
+            KeyCode::KeyP,
+            KeyCode::KeyQ,
+            KeyCode::KeyR,
+            KeyCode::KeyS,
+            KeyCode::KeyT,
+            KeyCode::KeyU,
+            KeyCode::KeyV,
+            KeyCode::KeyW,
+            KeyCode::KeyX,
+            KeyCode::KeyY,
+            KeyCode::KeyZ,
+        ];
+        let digits = [
+            KeyCode::Digit0,
+            KeyCode::Digit1,
+            KeyCode::Digit2,
+            KeyCode::Digit3,
+            KeyCode::Digit4,
+            KeyCode::Digit5,
+            KeyCode::Digit6,
+            KeyCode::Digit7,
+            KeyCode::Digit8,
+            KeyCode::Digit9,
+        ];
+        let numpad_digits = [
+            KeyCode::Numpad0,
+            KeyCode::Numpad1,
+            KeyCode::Numpad2,
+            KeyCode::Numpad3,
+            KeyCode::Numpad4,
+            KeyCode::Numpad5,
+            KeyCode::Numpad6,
+            KeyCode::Numpad7,
+            KeyCode::Numpad8,
+            KeyCode::Numpad9,
+        ];
+        let function_keys = [
+            KeyCode::F1,
+            KeyCode::F2,
+            KeyCode::F3,
+            KeyCode::F4,
+            KeyCode::F5,
+            KeyCode::F6,
+            KeyCode::F7,
+            KeyCode::F8,
+            KeyCode::F9,
+            KeyCode::F10,
+            KeyCode::F11,
+            KeyCode::F12,
+            KeyCode::F13,
+            KeyCode::F14,
+            KeyCode::F15,
+            KeyCode::F16,
+            KeyCode::F17,
+            KeyCode::F18,
+            KeyCode::F19,
+            KeyCode::F20,
+            KeyCode::F21,
+            KeyCode::F22,
+            KeyCode::F23,
+            KeyCode::F24,
+        ];
+
+        let mut expected = Vec::new();
+        for (letter, key_code) in ('A'..='Z').zip(letters) {
+            expected.push((format!("Key{letter}"), key_code));
+        }
+        for (digit, (key_code, numpad_key_code)) in
+            digits.into_iter().zip(numpad_digits).enumerate()
+        {
+            expected.push((format!("Digit{digit}"), key_code));
+            expected.push((format!("Numpad{digit}"), numpad_key_code));
+        }
+        for (number, key_code) in (1..).zip(function_keys) {
+            expected.push((format!("F{number}"), key_code));
+        }
+        let named = [
+            ("NumpadAdd", KeyCode::NumpadAdd),
+            ("NumpadDecimal", KeyCode::NumpadDecimal),
+            ("NumpadDivide", KeyCode::NumpadDivide),
+            ("NumpadEnter", KeyCode::NumpadEnter),
+            ("NumpadEqual", KeyCode::NumpadEqual),
+            ("NumpadMultiply", KeyCode::NumpadMultiply),
+            ("NumpadSubtract", KeyCode::NumpadSubtract),
+            ("NumLock", KeyCode::NumLock),
+            ("ArrowDown", KeyCode::ArrowDown),
+            ("ArrowLeft", KeyCode::ArrowLeft),
+            ("ArrowRight", KeyCode::ArrowRight),
+            ("ArrowUp", KeyCode::ArrowUp),
+            ("Delete", KeyCode::Delete),
+            ("End", KeyCode::End),
+            ("Home", KeyCode::Home),
+            ("Insert", KeyCode::Insert),
+            ("PageDown", KeyCode::PageDown),
+            ("PageUp", KeyCode::PageUp),
+            ("Backspace", KeyCode::Backspace),
+            ("Enter", KeyCode::Enter),
+            ("Escape", KeyCode::Escape),
+            ("Space", KeyCode::Space),
+            ("Tab", KeyCode::Tab),
+            ("MetaLeft", KeyCode::SuperLeft),
+            ("MetaRight", KeyCode::SuperRight),
+            ("OSLeft", KeyCode::SuperLeft),
+            ("OSRight", KeyCode::SuperRight),
+            ("VolumeMute", KeyCode::AudioVolumeMute),
+            ("LaunchMediaPlayer", KeyCode::MediaSelect),
+        ];
+        expected.extend(
+            named
+                .iter()
+                .map(|&(code, key_code)| (code.to_owned(), key_code)),
+        );
+
+        for (code, key_code) in expected {
+            assert_eq!(
+                PhysicalKey::from_key_code_attribute_value(&code),
+                PhysicalKey::Code(key_code),
+                "{code}"
+            );
+        }
+        assert_eq!(
+            PhysicalKey::from_key_code_attribute_value(""),
+            PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+        );
+    }
+}
@@ -35,11 +35,11 @@ impl AnimationFrameHandler {
         })
     }
 
+    /// Requests the next animation frame, unless one is already pending, so that all the requests
+    /// made before it result in a single callback.
     pub fn request(&self) {
-        if let Some(handle) = self.handle.take() {
-            self.window
-                .cancel_animation_frame(handle)
-                .expect("Failed to cancel animation frame");
+        if self.handle.get().is_some() {
+            return;
         }
 
         let handle = self
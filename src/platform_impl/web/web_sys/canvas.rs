@@ -1,4 +1,4 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::rc::{Rc, Weak};
 use std::sync::{Arc, Mutex};
 
@@ -12,14 +12,15 @@ use web_sys::{
 use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
 use crate::error::OsError as RootOE;
 use crate::event::{Force, InnerSizeWriter, MouseButton, MouseScrollDelta};
-use crate::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
+use crate::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, PhysicalKey};
+use crate::platform::web::TouchAction;
 use crate::platform_impl::{OsError, PlatformSpecificWindowBuilderAttributes};
 use crate::window::{WindowAttributes, WindowId as RootWindowId};
 
 use super::super::WindowId;
 use super::animation_frame::AnimationFrameHandler;
 use super::event_handle::EventListenerHandle;
-use super::fullscreen::FullscreenHandler;
+use super::fullscreen::{self, FullscreenHandler};
 use super::intersection_handle::IntersectionObserverHandle;
 use super::media_query_handle::MediaQueryListHandle;
 use super::pointer::PointerHandler;
@@ -31,6 +32,11 @@ pub struct Canvas {
     id: WindowId,
     pub has_focus: Rc<Cell<bool>>,
     pub is_intersecting: Option<bool>,
+    /// Whether winit created the canvas element, in which case it's removed from the page on drop.
+    created: bool,
+    auto_resize: Cell<bool>,
+    /// The scale factor reported by the last `ScaleFactorChanged` event, or the one of creation.
+    scale_factor: Cell<f64>,
     on_touch_start: Option<EventListenerHandle<dyn FnMut(Event)>>,
     on_focus: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
     on_blur: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
@@ -44,6 +50,8 @@ pub struct Canvas {
     animation_frame_handler: AnimationFrameHandler,
     on_touch_end: Option<EventListenerHandle<dyn FnMut(Event)>>,
     on_context_menu: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_fullscreen_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_fullscreen_error: Option<EventListenerHandle<dyn FnMut(Event)>>,
 }
 
 pub struct Common {
@@ -55,6 +63,13 @@ pub struct Common {
     old_size: Rc<Cell<PhysicalSize<u32>>>,
     current_size: Rc<Cell<PhysicalSize<u32>>>,
     fullscreen_handler: Rc<FullscreenHandler>,
+    /// Whether the default action of the browser should be prevented for handled events.
+    pub prevent_default: Rc<Cell<bool>>,
+    /// Keys whose default action is never prevented.
+    pub passthrough_keys: Rc<RefCell<Vec<KeyCode>>>,
+    pub context_menu_enabled: Rc<Cell<bool>>,
+    /// Whether pressed pointers are captured by the canvas until they are released.
+    pub pointer_capture: bool,
 }
 
 impl Canvas {
@@ -65,6 +80,7 @@ impl Canvas {
         attr: &WindowAttributes,
         platform_attr: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Self, RootOE> {
+        let created = platform_attr.canvas.0.is_none();
         let canvas = match platform_attr.canvas.0 {
             Some(canvas) => canvas,
             None => document
@@ -92,6 +108,10 @@ impl Canvas {
                 .map_err(|_| os_error!(OsError("Failed to set a tabindex".to_owned())))?;
         }
 
+        if let Some(touch_action) = css_touch_action(platform_attr.touch_action) {
+            super::set_canvas_style_property(&canvas, "touch-action", touch_action);
+        }
+
         #[allow(clippy::disallowed_methods)]
         let style = window
             .get_computed_style(&canvas)
@@ -107,6 +127,10 @@ impl Canvas {
             old_size: Rc::default(),
             current_size: Rc::default(),
             fullscreen_handler: Rc::new(FullscreenHandler::new(document.clone(), canvas.clone())),
+            prevent_default: Rc::new(Cell::new(platform_attr.prevent_default)),
+            passthrough_keys: Rc::default(),
+            context_menu_enabled: Rc::new(Cell::new(!platform_attr.prevent_default)),
+            pointer_capture: platform_attr.pointer_capture,
         };
 
         if let Some(size) = attr.inner_size {
@@ -142,6 +166,9 @@ impl Canvas {
             id,
             has_focus: Rc::new(Cell::new(false)),
             is_intersecting: None,
+            created,
+            auto_resize: Cell::new(platform_attr.auto_resize),
+            scale_factor: Cell::new(super::scale_factor(&window)),
             on_touch_start: None,
             on_blur: None,
             on_focus: None,
@@ -155,6 +182,8 @@ impl Canvas {
             animation_frame_handler: AnimationFrameHandler::new(window),
             on_touch_end: None,
             on_context_menu: None,
+            on_fullscreen_change: None,
+            on_fullscreen_error: None,
         })
     }
 
@@ -213,6 +242,25 @@ impl Canvas {
         self.common.current_size.set(size)
     }
 
+    /// Sets the `width` and `height` attributes of the canvas to `size` when requested with
+    /// `WindowBuilderExtWebSys::with_auto_resize`.
+    pub fn set_buffer_size(&self, size: PhysicalSize<u32>) {
+        if self.auto_resize.get() {
+            self.common.raw.set_width(size.width);
+            self.common.raw.set_height(size.height);
+        }
+    }
+
+    pub fn set_auto_resize(&self, auto_resize: bool) {
+        self.auto_resize.set(auto_resize);
+
+        // Catch up with the size that was last reported, if it was observed already.
+        let size = self.old_size();
+        if size.width != 0 && size.height != 0 {
+            self.set_buffer_size(size);
+        }
+    }
+
     #[inline]
     pub fn window(&self) -> &web_sys::Window {
         &self.common.window
@@ -228,14 +276,31 @@ impl Canvas {
         &self.common.raw
     }
 
+    pub fn prevent_default(&self) -> bool {
+        self.common.prevent_default.get()
+    }
+
+    pub fn set_prevent_default(&self, prevent_default: bool) {
+        self.common.prevent_default.set(prevent_default)
+    }
+
+    pub fn set_passthrough_keys(&self, keys: Vec<KeyCode>) {
+        *self.common.passthrough_keys.borrow_mut() = keys;
+    }
+
+    pub fn set_context_menu_enabled(&self, enabled: bool) {
+        self.common.context_menu_enabled.set(enabled)
+    }
+
     #[inline]
     pub fn style(&self) -> &CssStyleDeclaration {
         &self.common.style
     }
 
-    pub fn on_touch_start(&mut self, prevent_default: bool) {
+    pub fn on_touch_start(&mut self) {
+        let prevent_default = self.common.prevent_default.clone();
         self.on_touch_start = Some(self.common.add_event("touchstart", move |event: Event| {
-            if prevent_default {
+            if prevent_default.get() {
                 event.prevent_default();
             }
         }));
@@ -259,19 +324,21 @@ impl Canvas {
         }));
     }
 
-    pub fn on_keyboard_release<F>(&mut self, mut handler: F, prevent_default: bool)
+    pub fn on_keyboard_release<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
     {
+        let prevent_default = self.common.key_prevent_default();
         self.on_keyboard_release =
             Some(self.common.add_event("keyup", move |event: KeyboardEvent| {
-                if prevent_default {
+                let physical_key = event::key_code(&event);
+                if prevent_default(physical_key) {
                     event.prevent_default();
                 }
                 let key = event::key(&event);
                 let modifiers = event::keyboard_modifiers(&event);
                 handler(
-                    event::key_code(&event),
+                    physical_key,
                     key,
                     event::key_text(&event),
                     event::key_location(&event),
@@ -281,20 +348,22 @@ impl Canvas {
             }));
     }
 
-    pub fn on_keyboard_press<F>(&mut self, mut handler: F, prevent_default: bool)
+    pub fn on_keyboard_press<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
     {
+        let prevent_default = self.common.key_prevent_default();
         self.on_keyboard_press = Some(self.common.add_transient_event(
             "keydown",
             move |event: KeyboardEvent| {
-                if prevent_default {
+                let physical_key = event::key_code(&event);
+                if prevent_default(physical_key) {
                     event.prevent_default();
                 }
                 let key = event::key(&event);
                 let modifiers = event::keyboard_modifiers(&event);
                 handler(
-                    event::key_code(&event),
+                    physical_key,
                     key,
                     event::key_text(&event),
                     event::key_location(&event),
@@ -342,7 +411,6 @@ impl Canvas {
         modifier_handler: MOD,
         mouse_handler: M,
         touch_handler: T,
-        prevent_default: bool,
     ) where
         MOD: 'static + FnMut(ModifiersState),
         M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
@@ -353,7 +421,6 @@ impl Canvas {
             modifier_handler,
             mouse_handler,
             touch_handler,
-            prevent_default,
         )
     }
 
@@ -363,7 +430,6 @@ impl Canvas {
         mouse_handler: M,
         touch_handler: T,
         button_handler: B,
-        prevent_default: bool,
     ) where
         MOD: 'static + FnMut(ModifiersState),
         M: 'static + FnMut(ModifiersState, i32, &mut dyn Iterator<Item = PhysicalPosition<f64>>),
@@ -377,7 +443,6 @@ impl Canvas {
             mouse_handler,
             touch_handler,
             button_handler,
-            prevent_default,
         )
     }
 
@@ -388,13 +453,14 @@ impl Canvas {
         self.pointer_handler.on_touch_cancel(&self.common, handler)
     }
 
-    pub fn on_mouse_wheel<F>(&mut self, mut handler: F, prevent_default: bool)
+    pub fn on_mouse_wheel<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(i32, MouseScrollDelta, ModifiersState),
     {
         let window = self.common.window.clone();
+        let prevent_default = self.common.prevent_default.clone();
         self.on_mouse_wheel = Some(self.common.add_event("wheel", move |event: WheelEvent| {
-            if prevent_default {
+            if prevent_default.get() {
                 event.prevent_default();
             }
 
@@ -449,17 +515,50 @@ impl Canvas {
         self.on_touch_end = Some(self.common.add_transient_event("touchend", |_| {}));
     }
 
-    pub(crate) fn on_context_menu(&mut self, prevent_default: bool) {
+    pub(crate) fn on_context_menu(&mut self) {
+        let enabled = self.common.context_menu_enabled.clone();
         self.on_context_menu = Some(self.common.add_event(
             "contextmenu",
             move |event: PointerEvent| {
-                if prevent_default {
+                if !enabled.get() {
                     event.prevent_default();
                 }
             },
         ));
     }
 
+    pub(crate) fn on_fullscreen_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        let fullscreen_handler = Rc::downgrade(&self.common.fullscreen_handler);
+        self.on_fullscreen_change = Some(self.common.add_event(
+            fullscreen::change_event_name(self.raw()),
+            move |_: Event| {
+                if let Some(fullscreen_handler) = fullscreen_handler.upgrade() {
+                    handler(fullscreen_handler.is_fullscreen())
+                }
+            },
+        ));
+    }
+
+    pub(crate) fn on_fullscreen_error<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        let fullscreen_handler = Rc::downgrade(&self.common.fullscreen_handler);
+        self.on_fullscreen_error = Some(self.common.add_event(
+            fullscreen::error_event_name(self.raw()),
+            move |_: Event| {
+                if let Some(fullscreen_handler) = fullscreen_handler.upgrade() {
+                    if fullscreen_handler.handle_error() {
+                        handler()
+                    }
+                }
+            },
+        ));
+    }
+
     pub fn request_fullscreen(&self) {
         self.common.fullscreen_handler.request_fullscreen()
     }
@@ -485,11 +584,13 @@ impl Canvas {
     ) {
         // First, we send the `ScaleFactorChanged` event:
         self.set_current_size(current_size);
+        let old_scale_factor = self.scale_factor.replace(scale);
         let new_size = {
             let new_size = Arc::new(Mutex::new(current_size));
             event_handler(crate::event::Event::WindowEvent {
                 window_id: RootWindowId(self.id),
                 event: crate::event::WindowEvent::ScaleFactorChanged {
+                    old_scale_factor,
                     scale_factor: scale,
                     inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_size)),
                 },
@@ -513,6 +614,7 @@ impl Canvas {
         } else if self.old_size() != new_size {
             // Then we at least send a resized event.
             self.set_old_size(new_size);
+            self.set_buffer_size(new_size);
             runner.send_event(crate::event::Event::WindowEvent {
                 window_id: RootWindowId(self.id),
                 event: crate::event::WindowEvent::Resized(new_size),
@@ -539,10 +641,41 @@ impl Canvas {
         self.on_touch_end = None;
         self.common.fullscreen_handler.cancel();
         self.on_context_menu = None;
+        self.on_fullscreen_change = None;
+        self.on_fullscreen_error = None;
+    }
+}
+
+impl Drop for Canvas {
+    fn drop(&mut self) {
+        // A canvas provided with `WindowBuilderExtWebSys::with_canvas` belongs to the application.
+        if self.created {
+            self.common.raw.remove();
+        }
+    }
+}
+
+fn css_touch_action(touch_action: TouchAction) -> Option<&'static str> {
+    match touch_action {
+        TouchAction::Auto => None,
+        TouchAction::None => Some("none"),
+        TouchAction::PanX => Some("pan-x"),
+        TouchAction::PanY => Some("pan-y"),
+        TouchAction::Manipulation => Some("manipulation"),
     }
 }
 
 impl Common {
+    /// Returns a function telling whether the default action of a key event should be prevented.
+    fn key_prevent_default(&self) -> impl Fn(PhysicalKey) -> bool {
+        let prevent_default = self.prevent_default.clone();
+        let passthrough_keys = self.passthrough_keys.clone();
+        move |key| {
+            prevent_default.get()
+                && !matches!(key, PhysicalKey::Code(code) if passthrough_keys.borrow().contains(&code))
+        }
+    }
+
     pub fn add_event<E, F>(
         &self,
         event_name: &'static str,
@@ -18,25 +18,26 @@ pub struct FullscreenHandler {
     document: Document,
     canvas: HtmlCanvasElement,
     fullscreen_requested: Rc<Cell<bool>>,
+    /// Whether the pending request was made during a transient activation.
+    activated: Rc<Cell<bool>>,
     _fullscreen_change: EventListenerHandle<dyn FnMut()>,
 }
 
 impl FullscreenHandler {
     pub fn new(document: Document, canvas: HtmlCanvasElement) -> Self {
         let fullscreen_requested = Rc::new(Cell::new(false));
+        let activated = Rc::new(Cell::new(false));
         let fullscreen_change = EventListenerHandle::new(
             canvas.clone(),
-            if has_fullscreen_api_support(&canvas) {
-                "fullscreenchange"
-            } else {
-                "webkitfullscreenchange"
-            },
+            change_event_name(&canvas),
             Closure::new({
                 let fullscreen_requested = fullscreen_requested.clone();
+                let activated = activated.clone();
                 move || {
                     // It doesn't matter if the canvas entered or exitted fullscreen mode,
                     // we don't want to request it again later.
                     fullscreen_requested.set(false);
+                    activated.set(false);
                 }
             }),
         );
@@ -45,6 +46,7 @@ impl FullscreenHandler {
             document,
             canvas,
             fullscreen_requested,
+            activated,
             _fullscreen_change: fullscreen_change,
         }
     }
@@ -77,6 +79,8 @@ impl FullscreenHandler {
 
     pub fn request_fullscreen(&self) {
         if !self.is_fullscreen() {
+            // The request consumes the activation, so check it first.
+            self.activated.set(self.has_transient_activation());
             self.internal_request_fullscreen();
             self.fullscreen_requested.set(true);
         }
@@ -84,7 +88,53 @@ impl FullscreenHandler {
 
     pub fn transient_activation(&self) {
         if self.fullscreen_requested.get() {
-            self.internal_request_fullscreen()
+            self.internal_request_fullscreen();
+            self.activated.set(true);
+        }
+    }
+
+    /// Whether the document is in a transient activation, i.e. handling a user gesture.
+    fn has_transient_activation(&self) -> bool {
+        #[wasm_bindgen]
+        extern "C" {
+            type WindowNavigator;
+
+            #[wasm_bindgen(method, getter)]
+            fn navigator(this: &WindowNavigator) -> NavigatorUserActivation;
+
+            type NavigatorUserActivation;
+
+            #[wasm_bindgen(method, getter, js_name = userActivation)]
+            fn user_activation(this: &NavigatorUserActivation) -> Option<UserActivation>;
+
+            type UserActivation;
+
+            #[wasm_bindgen(method, getter, js_name = isActive)]
+            fn is_active(this: &UserActivation) -> bool;
+        }
+
+        let window: WindowNavigator = match self.document.default_view() {
+            Some(window) => window.unchecked_into(),
+            None => return false,
+        };
+        // Browsers without the User Activation API queue the request until the next activation.
+        window
+            .navigator()
+            .user_activation()
+            .map_or(false, |activation| activation.is_active())
+    }
+
+    /// Handles a `fullscreenerror` event, returning whether the request was denied for good.
+    ///
+    /// Requests made outside of a transient activation always fail, but stay queued until the
+    /// next one.
+    pub fn handle_error(&self) -> bool {
+        if self.fullscreen_requested.get() && self.activated.get() {
+            self.fullscreen_requested.set(false);
+            self.activated.set(false);
+            true
+        } else {
+            false
         }
     }
 
@@ -132,10 +182,28 @@ impl FullscreenHandler {
         }
 
         self.fullscreen_requested.set(false);
+        self.activated.set(false);
     }
 
     pub fn cancel(&self) {
         self.fullscreen_requested.set(false);
+        self.activated.set(false);
+    }
+}
+
+pub fn change_event_name(canvas: &HtmlCanvasElement) -> &'static str {
+    if has_fullscreen_api_support(canvas) {
+        "fullscreenchange"
+    } else {
+        "webkitfullscreenchange"
+    }
+}
+
+pub fn error_event_name(canvas: &HtmlCanvasElement) -> &'static str {
+    if has_fullscreen_api_support(canvas) {
+        "fullscreenerror"
+    } else {
+        "webkitfullscreenerror"
     }
 }
 
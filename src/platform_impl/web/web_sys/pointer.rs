@@ -80,9 +80,15 @@ impl PointerHandler {
         T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
     {
         let window = canvas_common.window.clone();
+        let canvas = canvas_common.raw.clone();
+        let pointer_capture = canvas_common.pointer_capture;
         self.on_pointer_release = Some(canvas_common.add_transient_event(
             "pointerup",
             move |event: PointerEvent| {
+                if pointer_capture && canvas.has_pointer_capture(event.pointer_id()) {
+                    let _e = canvas.release_pointer_capture(event.pointer_id());
+                }
+
                 let modifiers = event::mouse_modifiers(&event);
 
                 match event.pointer_type().as_str() {
@@ -110,7 +116,6 @@ impl PointerHandler {
         mut modifier_handler: MOD,
         mut mouse_handler: M,
         mut touch_handler: T,
-        prevent_default: bool,
     ) where
         MOD: 'static + FnMut(ModifiersState),
         M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
@@ -118,16 +123,26 @@ impl PointerHandler {
     {
         let window = canvas_common.window.clone();
         let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
+        let pointer_capture = canvas_common.pointer_capture;
         self.on_pointer_press = Some(canvas_common.add_transient_event(
             "pointerdown",
             move |event: PointerEvent| {
-                if prevent_default {
+                if prevent_default.get() {
                     // prevent text selection
                     event.prevent_default();
                     // but still focus element
                     let _ = canvas.focus();
                 }
 
+                if pointer_capture {
+                    // Keep receiving events for this pointer while it is outside of the canvas.
+                    // Error is swallowed here since the error would occur every time the mouse is
+                    // clicked when the cursor is grabbed, and there is probably not a situation
+                    // where this could fail, that we care if it fails.
+                    let _e = canvas.set_pointer_capture(event.pointer_id());
+                }
+
                 let modifiers = event::mouse_modifiers(&event);
 
                 match event.pointer_type().as_str() {
@@ -146,11 +161,6 @@ impl PointerHandler {
                             event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                             event::mouse_button(&event).expect("no mouse button pressed"),
                         );
-
-                        // Error is swallowed here since the error would occur every time the mouse is
-                        // clicked when the cursor is grabbed, and there is probably not a situation where
-                        // this could fail, that we care if it fails.
-                        let _e = canvas.set_pointer_capture(event.pointer_id());
                     }
                     _ => modifier_handler(modifiers),
                 }
@@ -165,7 +175,6 @@ impl PointerHandler {
         mut mouse_handler: M,
         mut touch_handler: T,
         mut button_handler: B,
-        prevent_default: bool,
     ) where
         MOD: 'static + FnMut(ModifiersState),
         M: 'static + FnMut(ModifiersState, i32, &mut dyn Iterator<Item = PhysicalPosition<f64>>),
@@ -175,6 +184,7 @@ impl PointerHandler {
     {
         let window = canvas_common.window.clone();
         let canvas = canvas_common.raw.clone();
+        let prevent_default = canvas_common.prevent_default.clone();
         self.on_cursor_move = Some(canvas_common.add_event(
             "pointermove",
             move |event: PointerEvent| {
@@ -197,7 +207,7 @@ impl PointerHandler {
                         "expect pointer type of a chorded button event to be a mouse"
                     );
 
-                    if prevent_default {
+                    if prevent_default.get() {
                         // prevent text selection
                         event.prevent_default();
                         // but still focus element
@@ -1,19 +1,24 @@
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOE};
 use crate::icon::Icon;
+use crate::keyboard::KeyCode;
+use crate::platform::web::TouchAction;
+use crate::platform_impl::CursorRegions;
 use crate::window::{
-    CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
+    CursorGrabMode, CursorIcon, ImageBuffer, ImeHints, ImePurpose, Insets, ResizeDirection, Theme,
+    UserAttentionType, WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
+    WindowShape,
 };
 use crate::SendSyncWrapper;
 
 use web_sys::HtmlCanvasElement;
 
 use super::r#async::Dispatcher;
-use super::{backend, monitor::MonitorHandle, EventLoopWindowTarget, Fullscreen};
+use super::{backend, monitor::MonitorHandle, EventLoopWindowTarget, Fullscreen, OsError};
 
 use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::mem;
 use std::rc::Rc;
 
 pub struct Window {
@@ -24,7 +29,8 @@ pub struct Inner {
     id: WindowId,
     pub window: web_sys::Window,
     canvas: Rc<RefCell<backend::Canvas>>,
-    previous_pointer: RefCell<&'static str>,
+    cursor: Rc<RefCell<CursorState>>,
+    title: RefCell<String>,
     destroy_fn: Option<Box<dyn FnOnce()>>,
 }
 
@@ -34,17 +40,20 @@ impl Window {
         attr: WindowAttributes,
         platform_attr: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Self, RootOE> {
-        let id = target.generate_id();
+        if target.exiting() {
+            return Err(os_error!(OsError("the event loop is exiting".to_owned())));
+        }
 
-        let prevent_default = platform_attr.prevent_default;
+        let id = target.generate_id();
 
         let window = target.runner.window();
         let document = target.runner.document();
         let canvas =
             backend::Canvas::create(id, window.clone(), document.clone(), &attr, platform_attr)?;
         let canvas = Rc::new(RefCell::new(canvas));
+        let cursor = Rc::new(RefCell::new(CursorState::default()));
 
-        target.register(&canvas, id, prevent_default);
+        target.register(&canvas, &cursor, id);
 
         let runner = target.runner.clone();
         let destroy_fn = Box::new(move || runner.notify_destroy_window(RootWI(id)));
@@ -53,7 +62,8 @@ impl Window {
             id,
             window: window.clone(),
             canvas,
-            previous_pointer: RefCell::new("auto"),
+            cursor,
+            title: RefCell::default(),
             destroy_fn: Some(destroy_fn),
         };
 
@@ -82,11 +92,43 @@ impl Window {
             .value()
             .map(|inner| inner.canvas.borrow().raw().clone())
     }
+
+    pub(crate) fn prevent_default(&self) -> bool {
+        self.inner
+            .queue(|inner| inner.canvas.borrow().prevent_default())
+    }
+
+    pub(crate) fn set_prevent_default(&self, prevent_default: bool) {
+        self.inner
+            .dispatch(move |inner| inner.canvas.borrow().set_prevent_default(prevent_default))
+    }
+
+    pub(crate) fn set_passthrough_keys(&self, keys: Vec<KeyCode>) {
+        self.inner
+            .dispatch(move |inner| inner.canvas.borrow().set_passthrough_keys(keys))
+    }
+
+    pub(crate) fn set_context_menu_enabled(&self, enabled: bool) {
+        self.inner
+            .dispatch(move |inner| inner.canvas.borrow().set_context_menu_enabled(enabled))
+    }
+
+    pub(crate) fn scale_canvas_to_display(&self, enabled: bool) {
+        self.inner
+            .dispatch(move |inner| inner.canvas.borrow().set_auto_resize(enabled))
+    }
+
+    pub(crate) fn set_cursor_css(&self, cursor: &str) {
+        let cursor = cursor.to_owned();
+        self.inner
+            .dispatch(move |inner| inner.set_cursor_css(cursor))
+    }
 }
 
 impl Inner {
     pub fn set_title(&self, title: &str) {
-        self.canvas.borrow().set_attribute("alt", title)
+        self.canvas.borrow().set_attribute("alt", title);
+        *self.title.borrow_mut() = title.to_owned();
     }
 
     pub fn set_transparent(&self, _transparent: bool) {}
@@ -102,6 +144,10 @@ impl Inner {
         None
     }
 
+    pub fn set_enabled(&self, _enabled: bool) {
+        // Intentionally a no-op
+    }
+
     pub fn request_redraw(&self) {
         self.canvas.borrow().request_animation_frame();
     }
@@ -139,6 +185,21 @@ impl Inner {
         self.inner_size()
     }
 
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, _visible: bool) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        Insets::default()
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let size = size.to_logical(self.scale_factor());
@@ -195,8 +256,31 @@ impl Inner {
 
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
-        *self.previous_pointer.borrow_mut() = cursor.name();
-        backend::set_canvas_style_property(self.canvas.borrow().raw(), "cursor", cursor.name());
+        let mut state = self.cursor.borrow_mut();
+        let previous = mem::replace(&mut state.icon, cursor);
+        let had_css = state.css.take().is_some();
+        if state.regions.cursor_set(previous, cursor) || had_css {
+            state.apply(self.canvas.borrow().raw());
+        }
+    }
+
+    /// Selects a CSS cursor instead of the icon, until the next call to `set_cursor_icon`.
+    pub(crate) fn set_cursor_css(&self, cursor: String) {
+        let mut state = self.cursor.borrow_mut();
+        let icon = state.icon;
+        let region_left = state.regions.cursor_set(icon, icon);
+        if region_left || state.css.as_deref() != Some(cursor.as_str()) {
+            state.css = Some(cursor);
+            state.apply(self.canvas.borrow().raw());
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        let mut state = self.cursor.borrow_mut();
+        if state.regions.set_regions(regions) {
+            state.apply(self.canvas.borrow().raw());
+        }
     }
 
     #[inline]
@@ -220,17 +304,20 @@ impl Inner {
             .map_err(ExternalError::Os)
     }
 
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, _rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    /// Browsers exit the pointer lock whenever the page loses focus.
+    #[inline]
+    pub fn set_auto_grab(&self, _auto_grab: bool) {}
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
-        if !visible {
-            backend::set_canvas_style_property(self.canvas.borrow().raw(), "cursor", "none");
-        } else {
-            backend::set_canvas_style_property(
-                self.canvas.borrow().raw(),
-                "cursor",
-                &self.previous_pointer.borrow(),
-            );
-        }
+        let mut state = self.cursor.borrow_mut();
+        state.visible = visible;
+        state.apply(self.canvas.borrow().raw());
     }
 
     #[inline]
@@ -251,6 +338,11 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn set_shape(&self, _shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_minimized(&self, _minimized: bool) {
         // Intentionally a no-op, as canvases cannot be 'minimized'
@@ -312,6 +404,9 @@ impl Inner {
         // Currently an intentional no-op
     }
 
+    #[inline]
+    pub fn set_drop_feedback(&self, _effect: crate::window::DropEffect) {}
+
     #[inline]
     pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {
         // Currently a no-op as it does not seem there is good support for this on web
@@ -328,8 +423,24 @@ impl Inner {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn set_ime_hints(&self, _hints: ImeHints) {
+        // Currently not implemented
+    }
+
+    #[inline]
+    pub fn set_above(&self, _sibling: &Window) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_below(&self, _sibling: &Window) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
         let _ = self.canvas.borrow().raw().focus();
+        Ok(())
     }
 
     #[inline]
@@ -347,6 +458,11 @@ impl Inner {
         VecDeque::new()
     }
 
+    #[inline]
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        PhysicalRect::new(monitor.position(), monitor.size())
+    }
+
     #[inline]
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         None
@@ -412,18 +528,28 @@ impl Inner {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    #[inline]
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.canvas.borrow().has_focus.get()
     }
 
     pub fn title(&self) -> String {
-        String::new()
+        self.title.borrow().clone()
     }
 
     pub fn reset_dead_keys(&self) {
         // Not supported
     }
+
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        true
+    }
 }
 
 impl Drop for Inner {
@@ -433,6 +559,61 @@ impl Drop for Inner {
         }
     }
 }
+
+/// The cursor shown over a canvas, shared with its pointer event handlers.
+pub(crate) struct CursorState {
+    visible: bool,
+    /// The cursor selected by the application.
+    icon: CursorIcon,
+    /// The CSS cursor selected with `WindowExtWebSys::set_cursor_css`, shown instead of `icon`.
+    css: Option<String>,
+    pub regions: CursorRegions,
+}
+
+impl Default for CursorState {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            icon: CursorIcon::Default,
+            css: None,
+            regions: CursorRegions::default(),
+        }
+    }
+}
+
+impl CursorState {
+    /// Shows the cursor of the region under the pointer, or the selected one.
+    pub fn apply(&self, canvas: &HtmlCanvasElement) {
+        backend::set_canvas_style_property(canvas, "cursor", self.css_value());
+    }
+
+    /// Returns the value of the CSS `cursor` property of the canvas.
+    ///
+    /// A hidden cursor stays `none` whatever the pointer does, until it's made visible again.
+    fn css_value(&self) -> &str {
+        if !self.visible {
+            "none"
+        } else if let Some(hovered) = self.regions.hovered() {
+            css_cursor(hovered)
+        } else if let Some(css) = &self.css {
+            css
+        } else {
+            css_cursor(self.icon)
+        }
+    }
+}
+
+/// Returns the value of the CSS `cursor` property for `cursor`.
+///
+/// Browsers ignore unknown values, which would keep showing the previous cursor, so the
+/// variants that aren't part of CSS fall back to the closest cursor that is.
+fn css_cursor(cursor: CursorIcon) -> &'static str {
+    match cursor {
+        CursorIcon::DndAsk => "copy",
+        CursorIcon::AllResize => "move",
+        cursor => cursor.name(),
+    }
+}
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(pub(crate) u32);
 
@@ -460,6 +641,9 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub(crate) prevent_default: bool,
     pub(crate) focusable: bool,
     pub(crate) append: bool,
+    pub(crate) auto_resize: bool,
+    pub(crate) pointer_capture: bool,
+    pub(crate) touch_action: TouchAction,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -469,6 +653,37 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
             prevent_default: true,
             focusable: true,
             append: false,
+            auto_resize: false,
+            pointer_capture: true,
+            touch_action: TouchAction::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_cursor_persists_across_enter_and_leave() {
+        let mut state = CursorState::default();
+        let region = PhysicalRect::new((0, 0).into(), (10, 10).into());
+        state
+            .regions
+            .set_regions(vec![(region, CursorIcon::Pointer)]);
+
+        state.visible = false;
+        for position in [(5.0, 5.0), (50.0, 50.0), (5.0, 5.0)] {
+            state.regions.cursor_moved(position.into());
+            assert_eq!(state.css_value(), "none");
+            state.regions.cursor_left();
+            assert_eq!(state.css_value(), "none");
         }
+
+        state.visible = true;
+        state.regions.cursor_moved((5.0, 5.0).into());
+        assert_eq!(state.css_value(), "pointer");
+        state.regions.cursor_left();
+        assert_eq!(state.css_value(), "default");
     }
 }
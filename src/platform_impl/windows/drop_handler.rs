@@ -3,16 +3,20 @@ use std::{
     os::windows::ffi::OsStringExt,
     path::PathBuf,
     ptr,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use windows_sys::{
     core::{IUnknown, GUID, HRESULT},
     Win32::{
-        Foundation::{DV_E_FORMATETC, HWND, POINTL, S_OK},
+        Foundation::{DV_E_FORMATETC, HWND, POINT, POINTL, S_OK},
+        Graphics::Gdi::ScreenToClient,
         System::{
             Com::{IDataObject, DVASPECT_CONTENT, FORMATETC, TYMED_HGLOBAL},
-            Ole::{CF_HDROP, DROPEFFECT_COPY, DROPEFFECT_NONE},
+            Ole::{CF_HDROP, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE, DROPEFFECT_NONE},
         },
         UI::Shell::{DragFinish, DragQueryFileW, HDROP},
     },
@@ -20,16 +24,22 @@ use windows_sys::{
 
 use crate::platform_impl::platform::{
     definitions::{IDataObjectVtbl, IDropTarget, IDropTargetVtbl, IUnknownVtbl},
+    window_state::WindowState,
     WindowId,
 };
 
-use crate::{event::Event, window::WindowId as RootWindowId};
+use crate::{
+    dpi::PhysicalPosition,
+    event::{Event, WindowEvent},
+    window::{DropEffect, WindowId as RootWindowId},
+};
 
 #[repr(C)]
 pub struct FileDropHandlerData {
     pub interface: IDropTarget,
     refcount: AtomicUsize,
     window: HWND,
+    window_state: Arc<Mutex<WindowState>>,
     send_event: Box<dyn Fn(Event<()>)>,
     cursor_effect: u32,
     hovered_is_valid: bool, /* If the currently hovered item is not valid there must not be any `HoveredFileCancelled` emitted */
@@ -41,13 +51,18 @@ pub struct FileDropHandler {
 
 #[allow(non_snake_case)]
 impl FileDropHandler {
-    pub fn new(window: HWND, send_event: Box<dyn Fn(Event<()>)>) -> FileDropHandler {
+    pub fn new(
+        window: HWND,
+        window_state: Arc<Mutex<WindowState>>,
+        send_event: Box<dyn Fn(Event<()>)>,
+    ) -> FileDropHandler {
         let data = Box::new(FileDropHandlerData {
             interface: IDropTarget {
                 lpVtbl: &DROP_TARGET_VTBL as *const IDropTargetVtbl,
             },
             refcount: AtomicUsize::new(1),
             window,
+            window_state,
             send_event,
             cursor_effect: DROPEFFECT_NONE,
             hovered_is_valid: false,
@@ -88,7 +103,7 @@ impl FileDropHandler {
         this: *mut IDropTarget,
         pDataObj: *const IDataObject,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         use crate::event::WindowEvent::HoveredFile;
@@ -102,13 +117,8 @@ impl FileDropHandler {
             })
         };
         drop_handler.hovered_is_valid = hdrop.is_some();
-        drop_handler.cursor_effect = if drop_handler.hovered_is_valid {
-            DROPEFFECT_COPY
-        } else {
-            DROPEFFECT_NONE
-        };
         unsafe {
-            *pdwEffect = drop_handler.cursor_effect;
+            *pdwEffect = drop_handler.hover_moved(pt);
         }
 
         S_OK
@@ -117,12 +127,12 @@ impl FileDropHandler {
     pub unsafe extern "system" fn DragOver(
         this: *mut IDropTarget,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         let drop_handler = unsafe { Self::from_interface(this) };
         unsafe {
-            *pdwEffect = drop_handler.cursor_effect;
+            *pdwEffect = drop_handler.hover_moved(pt);
         }
 
         S_OK
@@ -225,6 +235,39 @@ impl FileDropHandlerData {
     fn send_event(&self, event: Event<()>) {
         (self.send_event)(event);
     }
+
+    /// Notify about the new cursor position and return the effect set by the user.
+    unsafe fn hover_moved(&mut self, pt: *const POINTL) -> u32 {
+        if !self.hovered_is_valid {
+            self.cursor_effect = DROPEFFECT_NONE;
+            return self.cursor_effect;
+        }
+
+        let mut location = unsafe {
+            POINT {
+                x: (*pt).x,
+                y: (*pt).y,
+            }
+        };
+        if unsafe { ScreenToClient(self.window, &mut location) } != 0 {
+            self.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(self.window)),
+                event: WindowEvent::HoveredFileMoved {
+                    position: PhysicalPosition::new(location.x as f64, location.y as f64),
+                },
+            });
+        }
+
+        // The lock must not be held while sending the event, since the user is expected to
+        // update the effect from the event handler.
+        self.cursor_effect = match self.window_state.lock().unwrap().drop_effect {
+            DropEffect::None => DROPEFFECT_NONE,
+            DropEffect::Copy => DROPEFFECT_COPY,
+            DropEffect::Move => DROPEFFECT_MOVE,
+            DropEffect::Link => DROPEFFECT_LINK,
+        };
+        self.cursor_effect
+    }
 }
 
 impl Drop for FileDropHandler {
@@ -9,6 +9,7 @@ use std::{
     marker::PhantomData,
     mem, panic, ptr,
     rc::Rc,
+    slice,
     sync::{
         atomic::{AtomicU32, Ordering},
         mpsc::{self, Receiver, Sender},
@@ -21,13 +22,15 @@ use once_cell::sync::Lazy;
 
 use windows_sys::Win32::{
     Devices::HumanInterfaceDevice::MOUSE_MOVE_RELATIVE,
-    Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+    Foundation::{HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
     Graphics::Gdi::{
-        GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, RedrawWindow, ScreenToClient,
-        ValidateRect, MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT, SC_SCREENSAVE,
+        CreateRectRgn, DeleteObject, GetMonitorInfoW, GetRegionData, GetUpdateRgn, MonitorFromRect,
+        MonitorFromWindow, RedrawWindow, ScreenToClient, ValidateRect, HRGN, MONITORINFO,
+        MONITOR_DEFAULTTONULL, NULLREGION, RDW_INTERNALPAINT, RGNDATA, RGN_ERROR, SC_SCREENSAVE,
     },
     System::{
         Ole::RevokeDragDrop,
+        Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS},
         Threading::{GetCurrentThreadId, INFINITE},
     },
     UI::{
@@ -37,10 +40,12 @@ use windows_sys::Win32::{
             KeyboardAndMouse::{
                 ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
             },
-            Pointer::{POINTER_FLAG_DOWN, POINTER_FLAG_UP, POINTER_FLAG_UPDATE},
+            Pointer::{
+                POINTER_FLAG_CANCELED, POINTER_FLAG_DOWN, POINTER_FLAG_UP, POINTER_FLAG_UPDATE,
+            },
             Touch::{
                 CloseTouchInputHandle, GetTouchInputInfo, TOUCHEVENTF_DOWN, TOUCHEVENTF_MOVE,
-                TOUCHEVENTF_UP, TOUCHINPUT,
+                TOUCHEVENTF_PALM, TOUCHEVENTF_UP, TOUCHINPUT,
             },
             RAWINPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
         },
@@ -49,16 +54,17 @@ use windows_sys::Win32::{
             GetCursorPos, GetMenu, GetMessageW, KillTimer, LoadCursorW, PeekMessageW, PostMessageW,
             RegisterClassExW, RegisterWindowMessageA, SetCursor, SetTimer, SetWindowPos,
             TranslateMessage, CREATESTRUCTW, GIDC_ARRIVAL, GIDC_REMOVAL, GWL_STYLE, GWL_USERDATA,
-            HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG, NCCALCSIZE_PARAMS, PM_REMOVE, PT_PEN,
-            PT_TOUCH, RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED,
-            SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS,
-            WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
-            WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
-            WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUT_DEVICE_CHANGE,
-            WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN,
-            WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE,
-            WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN,
-            WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR,
+            HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG, NCCALCSIZE_PARAMS,
+            PBT_APMPOWERSTATUSCHANGE, PM_REMOVE, PT_PEN, PT_TOUCH, RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL,
+            SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SIZE_MINIMIZED, SWP_NOACTIVATE, SWP_NOMOVE,
+            SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WM_CAPTURECHANGED, WM_CLOSE,
+            WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE,
+            WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_SETCONTEXT,
+            WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUT_DEVICE_CHANGE, WM_KEYDOWN, WM_KEYUP,
+            WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR,
+            WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE,
+            WM_NCDESTROY, WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP,
+            WM_POINTERUPDATE, WM_POWERBROADCAST, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR,
             WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP,
             WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING, WM_XBUTTONDOWN, WM_XBUTTONUP,
             WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT,
@@ -68,13 +74,16 @@ use windows_sys::Win32::{
 };
 
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize},
-    error::EventLoopError,
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize},
+    error::{EventLoopError, ExternalError},
     event::{
-        DeviceEvent, Event, Force, Ime, InnerSizeWriter, RawKeyEvent, Touch, TouchPhase,
-        WindowEvent,
+        DeviceEvent, DeviceInfo, Event, Force, Ime, InnerSizeWriter, RawKeyEvent, Touch,
+        TouchPhase, WindowEvent,
+    },
+    event_loop::{
+        Backend, ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW,
+        PowerState,
     },
-    event_loop::{ControlFlow, DeviceEvents, EventLoopClosed, EventLoopWindowTarget as RootELW},
     keyboard::ModifiersState,
     platform::pump_events::PumpStatus,
     platform_impl::platform::{
@@ -88,7 +97,7 @@ use crate::{
         raw_input, util,
         window::InitData,
         window_state::{CursorFlags, ImeState, WindowFlags, WindowState},
-        wrap_device_id, Fullscreen, WindowId, DEVICE_ID,
+        wrap_device_id, DeviceId, Fullscreen, WindowId, DEVICE_ID,
     },
     window::WindowId as RootWindowId,
 };
@@ -136,7 +145,6 @@ pub(crate) enum ProcResult {
 }
 
 pub struct EventLoop<T: 'static> {
-    thread_msg_sender: Sender<T>,
     window_target: RootELW<T>,
     msg_hook: Option<Box<dyn FnMut(*const c_void) -> bool + 'static>>,
 }
@@ -144,6 +152,7 @@ pub struct EventLoop<T: 'static> {
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) any_thread: bool,
     pub(crate) dpi_aware: bool,
+    pub(crate) app_id: Option<String>,
     pub(crate) msg_hook: Option<Box<dyn FnMut(*const c_void) -> bool + 'static>>,
 }
 
@@ -152,6 +161,7 @@ impl Default for PlatformSpecificEventLoopAttributes {
         Self {
             any_thread: false,
             dpi_aware: true,
+            app_id: None,
             msg_hook: None,
         }
     }
@@ -160,6 +170,7 @@ impl Default for PlatformSpecificEventLoopAttributes {
 pub struct EventLoopWindowTarget<T: 'static> {
     thread_id: u32,
     thread_msg_target: HWND,
+    thread_msg_sender: Sender<T>,
     pub(crate) runner_shared: EventLoopRunnerShared<T>,
 }
 
@@ -170,18 +181,18 @@ impl<T: 'static> EventLoop<T> {
         let thread_id = unsafe { GetCurrentThreadId() };
 
         if !attributes.any_thread && thread_id != main_thread_id() {
-            panic!(
-                "Initializing the event loop outside of the main thread is a significant \
-                 cross-platform compatibility hazard. If you absolutely need to create an \
-                 EventLoop on a different thread, you can use the \
-                 `EventLoopBuilderExtWindows::any_thread` function."
-            );
+            return Err(EventLoopError::NotMainThread);
         }
 
         if attributes.dpi_aware {
             become_dpi_aware();
         }
 
+        if let Some(app_id) = &attributes.app_id {
+            util::set_app_user_model_id(app_id)
+                .map_err(|err| EventLoopError::Os(os_error!(err)))?;
+        }
+
         let thread_msg_target = create_event_target_window::<T>();
 
         let runner_shared = Rc::new(EventLoopRunner::new(thread_msg_target));
@@ -194,13 +205,14 @@ impl<T: 'static> EventLoop<T> {
         );
 
         Ok(EventLoop {
-            thread_msg_sender,
             window_target: RootELW {
                 p: EventLoopWindowTarget {
                     thread_id,
                     thread_msg_target,
+                    thread_msg_sender,
                     runner_shared,
                 },
+                handler_state: Default::default(),
                 _marker: PhantomData,
             },
             msg_hook: attributes.msg_hook.take(),
@@ -466,10 +478,7 @@ impl<T: 'static> EventLoop<T> {
     }
 
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy {
-            target_window: self.window_target.p.thread_msg_target,
-            event_send: self.thread_msg_sender.clone(),
-        }
+        self.window_target.p.create_proxy()
     }
 
     fn exit_code(&self) -> Option<i32> {
@@ -478,6 +487,13 @@ impl<T: 'static> EventLoop<T> {
 }
 
 impl<T> EventLoopWindowTarget<T> {
+    pub fn create_proxy(&self) -> EventLoopProxy<T> {
+        EventLoopProxy {
+            target_window: self.thread_msg_target,
+            event_send: self.thread_msg_sender.clone(),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn create_thread_executor(&self) -> EventLoopThreadExecutor {
         EventLoopThreadExecutor {
@@ -496,6 +512,31 @@ impl<T> EventLoopWindowTarget<T> {
         Some(monitor)
     }
 
+    pub fn device_info(&self, device_id: DeviceId) -> Option<DeviceInfo> {
+        if device_id.0 == 0 {
+            return None;
+        }
+        raw_input::get_device_info(device_id.0 as HANDLE)
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend::Windows
+    }
+
+    pub fn power_state(&self) -> Option<PowerState> {
+        power_state()
+    }
+
+    pub fn clip_cursor(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        let rect = rect.map(|rect| RECT {
+            left: rect.position.x,
+            top: rect.position.y,
+            right: rect.position.x + rect.size.width as i32,
+            bottom: rect.position.y + rect.size.height as i32,
+        });
+        util::set_desktop_cursor_clip(rect).map_err(|err| ExternalError::Os(os_error!(err)))
+    }
+
     #[cfg(feature = "rwh_05")]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
         rwh_05::RawDisplayHandle::Windows(rwh_05::WindowsDisplayHandle::empty())
@@ -522,6 +563,12 @@ impl<T> EventLoopWindowTarget<T> {
         self.runner_shared.control_flow()
     }
 
+    pub(crate) fn request_redraw(&self, window_id: WindowId) {
+        // An internal paint has no update region, so it's reported like an explicit
+        // `request_redraw`.
+        unsafe { RedrawWindow(window_id.0, ptr::null(), 0, RDW_INTERNALPAINT) };
+    }
+
     pub(crate) fn exit(&self) {
         self.runner_shared.set_exit_code(0)
     }
@@ -580,6 +627,24 @@ fn main_thread_id() -> u32 {
     unsafe { MAIN_THREAD_ID }
 }
 
+/// Returns the state of the system battery, if there's one.
+fn power_state() -> Option<PowerState> {
+    let mut status: SYSTEM_POWER_STATUS = unsafe { mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == false.into() {
+        return None;
+    }
+
+    // The battery flag is 128 without a system battery, and 255 if it's unknown.
+    if status.BatteryFlag & 128 != 0 {
+        return None;
+    }
+    Some(PowerState {
+        on_battery: status.ACLineStatus == 0,
+        battery_percent: (status.BatteryLifePercent <= 100)
+            .then_some(status.BatteryLifePercent as f32),
+    })
+}
+
 /// Returns the minimum `Option<Duration>`, taking into account that `None`
 /// equates to an infinite timeout, not a zero timeout (so can't just use
 /// `Option::min`)
@@ -704,6 +769,10 @@ impl<T: 'static> EventLoopProxy<T> {
             })
             .map_err(|e| EventLoopClosed(e.0))
     }
+
+    pub fn wake_up(&self) {
+        unsafe { PostMessageW(self.target_window, USER_EVENT_MSG_ID.get(), 1, 0) };
+    }
 }
 
 /// A lazily-initialized window message ID.
@@ -759,7 +828,7 @@ impl LazyMessageId {
 }
 
 // Message sent by the `EventLoopProxy` when we want to wake up the thread.
-// WPARAM and LPARAM are unused.
+// WPARAM is non-zero when no user event was queued along with the message, LPARAM is unused.
 static USER_EVENT_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::WakeupMsg\0");
 // Message sent when we want to execute a closure in the thread.
 // WPARAM contains a Box<Box<dyn FnMut()>> that must be retrieved with `Box::from_raw`,
@@ -873,6 +942,57 @@ unsafe fn release_mouse(mut window_state: MutexGuard<'_, WindowState>) {
     }
 }
 
+/// Returns the rectangles making up the update region of the window, or `None` if it's empty.
+unsafe fn update_rects(window: HWND) -> Option<Vec<PhysicalRect>> {
+    let region = unsafe { CreateRectRgn(0, 0, 0, 0) };
+    if region == 0 {
+        return None;
+    }
+
+    let rects = match unsafe { GetUpdateRgn(window, region, false.into()) } {
+        RGN_ERROR | NULLREGION => None,
+        _ => unsafe { region_rects(region) },
+    };
+
+    unsafe { DeleteObject(region) };
+    rects
+}
+
+/// Returns the rectangles making up `region`.
+unsafe fn region_rects(region: HRGN) -> Option<Vec<PhysicalRect>> {
+    let size = unsafe { GetRegionData(region, 0, ptr::null_mut()) };
+    if size == 0 {
+        return None;
+    }
+
+    // `RGNDATA` has to be aligned to 4 bytes.
+    let mut buffer = vec![0u32; (size as usize + 3) / 4];
+    let data = buffer.as_mut_ptr() as *mut RGNDATA;
+    if unsafe { GetRegionData(region, size, data) } == 0 {
+        return None;
+    }
+
+    let rects = unsafe {
+        slice::from_raw_parts(
+            (*data).Buffer.as_ptr() as *const RECT,
+            (*data).rdh.nCount as usize,
+        )
+    };
+    let rects = rects
+        .iter()
+        .map(|rect| {
+            PhysicalRect::new(
+                PhysicalPosition::new(rect.left, rect.top),
+                PhysicalSize::new(
+                    (rect.right - rect.left) as u32,
+                    (rect.bottom - rect.top) as u32,
+                ),
+            )
+        })
+        .collect();
+    Some(rects)
+}
+
 fn normalize_pointer_pressure(pressure: u32) -> Option<Force> {
     match pressure {
         1..=1024 => Some(Force::Normalized(pressure as f64 / 1024.0)),
@@ -1134,16 +1254,25 @@ unsafe fn public_window_callback_inner<T: 'static>(
         }
 
         WM_PAINT => {
-            userdata.window_state_lock().redraw_requested =
-                userdata.event_loop_runner.should_buffer();
+            // An explicit `request_redraw` means that everything is dirty, regardless of what
+            // Windows invalidated.
+            let requested = mem::replace(
+                &mut userdata.window_state_lock().redraw_requested,
+                userdata.event_loop_runner.should_buffer(),
+            );
 
             // We'll buffer only in response to `UpdateWindow`, if win32 decides to redraw the
             // window outside the normal flow of the event loop. This way mark event as handled
             // and request a normal redraw with `RedrawWindow`.
             if !userdata.event_loop_runner.should_buffer() {
+                let dirty_rects = if requested {
+                    None
+                } else {
+                    unsafe { update_rects(window) }
+                };
                 userdata.send_event(Event::WindowEvent {
                     window_id: RootWindowId(WindowId(window)),
-                    event: WindowEvent::RedrawRequested,
+                    event: WindowEvent::RedrawRequested { dirty_rects },
                 });
             }
 
@@ -1248,6 +1377,16 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 });
             }
 
+            // Keep a confined cursor within the new client area.
+            {
+                let mut w = userdata.window_state_lock();
+                if w.mouse.cursor_flags().contains(CursorFlags::GRABBED)
+                    || w.mouse.cursor_confinement().is_some()
+                {
+                    w.mouse.set_cursor_flags(window, |_| ()).ok();
+                }
+            }
+
             // This is necessary for us to still get sent WM_SIZE.
             result = ProcResult::DefWindowProc(wparam);
         }
@@ -1263,7 +1402,8 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 event: Resized(physical_size),
             };
 
-            {
+            let minimized = wparam == SIZE_MINIMIZED as usize;
+            let minimized_changed = {
                 let mut w = userdata.window_state_lock();
                 // See WindowFlags::MARKER_RETAIN_STATE_ON_SIZE docs for info on why this `if` check exists.
                 if !w
@@ -1273,8 +1413,15 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     let maximized = wparam == SIZE_MAXIMIZED as usize;
                     w.set_window_flags_in_place(|f| f.set(WindowFlags::MAXIMIZED, maximized));
                 }
-            }
+                std::mem::replace(&mut w.minimized, minimized) != minimized
+            };
             userdata.send_event(event);
+            if minimized_changed {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::Minimized(minimized),
+                });
+            }
             result = ProcResult::Value(0);
         }
 
@@ -1283,7 +1430,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
         }
 
         WM_IME_STARTCOMPOSITION => {
-            let ime_allowed = userdata.window_state_lock().ime_allowed;
+            let ime_allowed = userdata.window_state_lock().ime_cursor_area.allowed();
             if ime_allowed {
                 userdata.window_state_lock().ime_state = ImeState::Enabled;
 
@@ -1299,7 +1446,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
         WM_IME_COMPOSITION => {
             let ime_allowed_and_composing = {
                 let w = userdata.window_state_lock();
-                w.ime_allowed && w.ime_state != ImeState::Disabled
+                w.ime_cursor_area.allowed() && w.ime_state != ImeState::Disabled
             };
             // Windows Hangul IME sends WM_IME_COMPOSITION after WM_IME_ENDCOMPOSITION, so
             // check whether composing.
@@ -1353,7 +1500,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
         WM_IME_ENDCOMPOSITION => {
             let ime_allowed_or_composing = {
                 let w = userdata.window_state_lock();
-                w.ime_allowed || w.ime_state != ImeState::Disabled
+                w.ime_cursor_area.allowed() || w.ime_state != ImeState::Disabled
             };
             if ime_allowed_or_composing {
                 if userdata.window_state_lock().ime_state == ImeState::Preedit {
@@ -1399,7 +1546,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 let mut w = userdata.window_state_lock();
                 w.set_window_flags_in_place(|f| f.set(WindowFlags::MINIMIZED, true));
             }
-            // Send `WindowEvent::Minimized` here if we decide to implement one
+            // `WindowEvent::Minimized` is sent from `WM_SIZE`, which also covers `ShowWindow`
 
             if wparam == SC_SCREENSAVE as usize {
                 let window_state = userdata.window_state_lock();
@@ -1495,6 +1642,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 w.mouse
                     .set_cursor_flags(window, |f| f.set(CursorFlags::IN_WINDOW, false))
                     .ok();
+                w.mouse.cursor_regions.cursor_left();
             }
 
             userdata.send_event(Event::WindowEvent {
@@ -1768,21 +1916,27 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     let x = location.x as f64 + (input.x % 100) as f64 / 100f64;
                     let y = location.y as f64 + (input.y % 100) as f64 / 100f64;
                     let location = PhysicalPosition::new(x, y);
+                    let phase = if util::has_flag(input.dwFlags, TOUCHEVENTF_DOWN) {
+                        TouchPhase::Started
+                    } else if util::has_flag(input.dwFlags, TOUCHEVENTF_UP) {
+                        TouchPhase::Ended
+                    } else if util::has_flag(input.dwFlags, TOUCHEVENTF_MOVE) {
+                        TouchPhase::Moved
+                    } else {
+                        continue;
+                    };
+                    let id = userdata
+                        .window_state_lock()
+                        .touch_ids
+                        .get(input.dwID, phase);
                     userdata.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
                         event: WindowEvent::Touch(Touch {
-                            phase: if util::has_flag(input.dwFlags, TOUCHEVENTF_DOWN) {
-                                TouchPhase::Started
-                            } else if util::has_flag(input.dwFlags, TOUCHEVENTF_UP) {
-                                TouchPhase::Ended
-                            } else if util::has_flag(input.dwFlags, TOUCHEVENTF_MOVE) {
-                                TouchPhase::Moved
-                            } else {
-                                continue;
-                            },
+                            phase,
                             location,
                             force: None, // WM_TOUCH doesn't support pressure information
-                            id: input.dwID as u64,
+                            id,
+                            palm: util::has_flag(input.dwFlags, TOUCHEVENTF_PALM),
                             device_id: DEVICE_ID,
                         }),
                     });
@@ -1915,22 +2069,33 @@ unsafe fn public_window_callback_inner<T: 'static>(
                     let x = location.x as f64 + x.fract();
                     let y = location.y as f64 + y.fract();
                     let location = PhysicalPosition::new(x, y);
+                    // The system cancels contacts it rejected, e.g. palms, instead of lifting them.
+                    let canceled = util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_CANCELED);
+                    let phase = if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_DOWN) {
+                        TouchPhase::Started
+                    } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_UP) {
+                        if canceled {
+                            TouchPhase::Cancelled
+                        } else {
+                            TouchPhase::Ended
+                        }
+                    } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_UPDATE) {
+                        TouchPhase::Moved
+                    } else {
+                        continue;
+                    };
+                    let id = userdata
+                        .window_state_lock()
+                        .touch_ids
+                        .get(pointer_info.pointerId, phase);
                     userdata.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
                         event: WindowEvent::Touch(Touch {
-                            phase: if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_DOWN) {
-                                TouchPhase::Started
-                            } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_UP) {
-                                TouchPhase::Ended
-                            } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_UPDATE)
-                            {
-                                TouchPhase::Moved
-                            } else {
-                                continue;
-                            },
+                            phase,
                             location,
                             force,
-                            id: pointer_info.pointerId as u64,
+                            id,
+                            palm: canceled,
                             device_id: DEVICE_ID,
                         }),
                     });
@@ -1972,13 +2137,21 @@ unsafe fn public_window_callback_inner<T: 'static>(
 
         WM_SETCURSOR => {
             let set_cursor_to = {
-                let window_state = userdata.window_state_lock();
+                let mut window_state = userdata.window_state_lock();
                 // The return value for the preceding `WM_NCHITTEST` message is conveniently
                 // provided through the low-order word of lParam. We use that here since
                 // `WM_MOUSEMOVE` seems to come after `WM_SETCURSOR` for a given cursor movement.
                 let in_client_area = super::loword(lparam as u32) as u32 == HTCLIENT;
                 if in_client_area {
-                    Some(window_state.mouse.cursor)
+                    let mut location = unsafe { mem::zeroed() };
+                    if unsafe { GetCursorPos(&mut location) } != false.into()
+                        && unsafe { ScreenToClient(window, &mut location) } != false.into()
+                    {
+                        let location = PhysicalPosition::new(location.x as f64, location.y as f64);
+                        window_state.mouse.cursor_regions.cursor_moved(location);
+                    }
+                    let mouse = &window_state.mouse;
+                    Some(mouse.cursor_regions.cursor(mouse.cursor))
                 } else {
                     None
                 }
@@ -2101,6 +2274,7 @@ unsafe fn public_window_callback_inner<T: 'static>(
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: ScaleFactorChanged {
+                    old_scale_factor,
                     scale_factor: new_scale_factor,
                     inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
                 },
@@ -2238,9 +2412,24 @@ unsafe fn public_window_callback_inner<T: 'static>(
                 )
             };
 
+            // Place the candidate window again, the cursor area being converted at the new scale.
+            let ime_cursor_area = userdata.window_state_lock().ime_cursor_area.applied();
+            if let Some((spot, size)) = ime_cursor_area {
+                unsafe {
+                    ImeContext::current(window).set_ime_cursor_area(spot, size, new_scale_factor)
+                };
+            }
+
             result = ProcResult::Value(0);
         }
 
+        WM_POWERBROADCAST => {
+            if wparam == PBT_APMPOWERSTATUSCHANGE as WPARAM {
+                userdata.send_event(Event::PowerStateChanged(power_state()));
+            }
+            result = ProcResult::DefWindowProc(wparam);
+        }
+
         WM_SETTINGCHANGE => {
             use crate::event::WindowEvent::ThemeChanged;
 
@@ -2331,7 +2520,9 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
 
         WM_INPUT_DEVICE_CHANGE => {
             let event = match wparam as u32 {
-                GIDC_ARRIVAL => DeviceEvent::Added,
+                GIDC_ARRIVAL => DeviceEvent::Added {
+                    info: raw_input::get_device_info(lparam as HANDLE),
+                },
                 GIDC_REMOVAL => DeviceEvent::Removed,
                 _ => unreachable!(),
             };
@@ -2353,8 +2544,11 @@ unsafe extern "system" fn thread_event_target_callback<T: 'static>(
         }
 
         _ if msg == USER_EVENT_MSG_ID.get() => {
-            if let Ok(event) = userdata.user_event_receiver.recv() {
-                userdata.send_event(Event::UserEvent(event));
+            // A non-zero WPARAM is a plain wake up from `EventLoopProxy::wake_up`.
+            if wparam == 0 {
+                if let Ok(event) = userdata.user_event_receiver.recv() {
+                    userdata.send_event(Event::UserEvent(event));
+                }
             }
             0
         }
@@ -63,7 +63,8 @@ pub(crate) enum RunnerState {
 
 enum BufferedEvent<T: 'static> {
     Event(Event<T>),
-    ScaleFactorChanged(WindowId, f64, PhysicalSize<u32>),
+    /// The window, its old and new scale factors, and the suggested inner size.
+    ScaleFactorChanged(WindowId, f64, f64, PhysicalSize<u32>),
 }
 
 impl<T> EventLoopRunner<T> {
@@ -217,7 +218,7 @@ impl<T> EventLoopRunner<T> {
 
     pub(crate) fn send_event(&self, event: Event<T>) {
         if let Event::WindowEvent {
-            event: WindowEvent::RedrawRequested,
+            event: WindowEvent::RedrawRequested { .. },
             ..
         } = event
         {
@@ -381,12 +382,14 @@ impl<T> BufferedEvent<T> {
             Event::WindowEvent {
                 event:
                     WindowEvent::ScaleFactorChanged {
+                        old_scale_factor,
                         scale_factor,
                         inner_size_writer,
                     },
                 window_id,
             } => BufferedEvent::ScaleFactorChanged(
                 window_id,
+                old_scale_factor,
                 scale_factor,
                 *inner_size_writer
                     .new_inner_size
@@ -402,11 +405,12 @@ impl<T> BufferedEvent<T> {
     pub fn dispatch_event(self, dispatch: impl FnOnce(Event<T>)) {
         match self {
             Self::Event(event) => dispatch(event),
-            Self::ScaleFactorChanged(window_id, scale_factor, new_inner_size) => {
+            Self::ScaleFactorChanged(window_id, old_scale_factor, scale_factor, new_inner_size) => {
                 let user_new_innner_size = Arc::new(Mutex::new(new_inner_size));
                 dispatch(Event::WindowEvent {
                     window_id,
                     event: WindowEvent::ScaleFactorChanged {
+                        old_scale_factor,
                         scale_factor,
                         inner_size_writer: InnerSizeWriter::new(Arc::downgrade(
                             &user_new_innner_size,
@@ -1005,3 +1005,109 @@ fn vkey_to_non_char_key(
         _ => Key::Unidentified(native_code),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The media, volume and browser keys, with their virtual-key and their logical key.
+    const MEDIA_AND_BROWSER_KEYS: &[(KeyCode, VIRTUAL_KEY, NamedKey)] = &[
+        (KeyCode::BrowserBack, VK_BROWSER_BACK, NamedKey::BrowserBack),
+        (
+            KeyCode::BrowserFavorites,
+            VK_BROWSER_FAVORITES,
+            NamedKey::BrowserFavorites,
+        ),
+        (
+            KeyCode::BrowserForward,
+            VK_BROWSER_FORWARD,
+            NamedKey::BrowserForward,
+        ),
+        (KeyCode::BrowserHome, VK_BROWSER_HOME, NamedKey::BrowserHome),
+        (
+            KeyCode::BrowserRefresh,
+            VK_BROWSER_REFRESH,
+            NamedKey::BrowserRefresh,
+        ),
+        (
+            KeyCode::BrowserSearch,
+            VK_BROWSER_SEARCH,
+            NamedKey::BrowserSearch,
+        ),
+        (KeyCode::BrowserStop, VK_BROWSER_STOP, NamedKey::BrowserStop),
+        (
+            KeyCode::LaunchApp1,
+            VK_LAUNCH_APP1,
+            NamedKey::LaunchApplication1,
+        ),
+        (
+            KeyCode::LaunchApp2,
+            VK_LAUNCH_APP2,
+            NamedKey::LaunchApplication2,
+        ),
+        (KeyCode::LaunchMail, VK_LAUNCH_MAIL, NamedKey::LaunchMail),
+        (
+            KeyCode::MediaPlayPause,
+            VK_MEDIA_PLAY_PAUSE,
+            NamedKey::MediaPlayPause,
+        ),
+        (
+            KeyCode::MediaSelect,
+            VK_LAUNCH_MEDIA_SELECT,
+            NamedKey::LaunchMediaPlayer,
+        ),
+        (KeyCode::MediaStop, VK_MEDIA_STOP, NamedKey::MediaStop),
+        (
+            KeyCode::MediaTrackNext,
+            VK_MEDIA_NEXT_TRACK,
+            NamedKey::MediaTrackNext,
+        ),
+        (
+            KeyCode::MediaTrackPrevious,
+            VK_MEDIA_PREV_TRACK,
+            NamedKey::MediaTrackPrevious,
+        ),
+        (
+            KeyCode::AudioVolumeDown,
+            VK_VOLUME_DOWN,
+            NamedKey::AudioVolumeDown,
+        ),
+        (
+            KeyCode::AudioVolumeMute,
+            VK_VOLUME_MUTE,
+            NamedKey::AudioVolumeMute,
+        ),
+        (
+            KeyCode::AudioVolumeUp,
+            VK_VOLUME_UP,
+            NamedKey::AudioVolumeUp,
+        ),
+    ];
+
+    /// The US English keyboard layout.
+    const HKL_EN_US: u64 = 0x0409_0409;
+
+    #[test]
+    fn media_and_browser_keycodes_to_vkey() {
+        for &(keycode, vkey, _) in MEDIA_AND_BROWSER_KEYS {
+            assert_eq!(keycode_to_vkey(keycode, HKL_EN_US), vkey, "{keycode:?}");
+        }
+    }
+
+    #[test]
+    fn media_and_browser_vkeys_to_named_key() {
+        for &(_, vkey, named_key) in MEDIA_AND_BROWSER_KEYS {
+            let key = vkey_to_non_char_key(vkey, NativeKey::Windows(vkey), HKL_EN_US, false);
+            assert_eq!(key, Key::Named(named_key), "{vkey:#x}");
+        }
+    }
+
+    #[test]
+    fn media_and_browser_keycodes_scancode_roundtrip() {
+        for &(keycode, _, _) in MEDIA_AND_BROWSER_KEYS {
+            let physical_key = PhysicalKey::Code(keycode);
+            let scancode = physical_key.to_scancode().unwrap();
+            assert_eq!(PhysicalKey::from_scancode(scancode), physical_key);
+        }
+    }
+}
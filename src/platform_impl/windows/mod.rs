@@ -62,6 +62,14 @@ impl DeviceId {
 }
 
 impl DeviceId {
+    pub fn persistent_id(&self) -> Option<u64> {
+        if self.0 != 0 {
+            Some(self.0 as u64)
+        } else {
+            None
+        }
+    }
+
     pub fn persistent_identifier(&self) -> Option<String> {
         if self.0 != 0 {
             raw_input::get_raw_input_device_name(self.0 as HANDLE)
@@ -81,7 +89,9 @@ fn wrap_device_id(id: u32) -> RootDeviceId {
 pub type OsError = std::io::Error;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEventExtra {
+    #[cfg_attr(feature = "serde", serde(rename = "text_with_all_modifiers"))]
     pub text_with_all_modifers: Option<SmolStr>,
     pub key_without_modifiers: Key,
 }
@@ -16,7 +16,7 @@ use windows_sys::Win32::{
 
 use super::util::decode_wide;
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize},
     monitor::VideoMode as RootVideoMode,
     platform_impl::platform::{
         dpi::{dpi_to_scale_factor, get_monitor_dpi},
@@ -220,6 +220,22 @@ impl MonitorHandle {
             .unwrap_or(PhysicalPosition { x: 0, y: 0 })
     }
 
+    /// Returns the part of the monitor which isn't covered by the taskbar and the app bars.
+    pub(crate) fn work_area(&self) -> PhysicalRect {
+        get_monitor_info(self.0)
+            .map(|info| {
+                let rc_work = info.monitorInfo.rcWork;
+                PhysicalRect::new(
+                    PhysicalPosition::new(rc_work.left, rc_work.top),
+                    PhysicalSize::new(
+                        (rc_work.right - rc_work.left) as u32,
+                        (rc_work.bottom - rc_work.top) as u32,
+                    ),
+                )
+            })
+            .unwrap_or_else(|_| PhysicalRect::new(self.position(), self.size()))
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         dpi_to_scale_factor(get_monitor_dpi(self.0).unwrap_or(96))
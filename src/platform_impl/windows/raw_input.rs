@@ -5,7 +5,13 @@ use std::{
 
 use windows_sys::Win32::{
     Devices::HumanInterfaceDevice::{
-        HID_USAGE_GENERIC_KEYBOARD, HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
+        HidP_GetCaps, HidP_GetValueCaps, HidP_Input, HIDP_CAPS, HIDP_STATUS_SUCCESS,
+        HIDP_VALUE_CAPS, HID_USAGE_DIGITIZER_PEN, HID_USAGE_DIGITIZER_TIP_PRESSURE,
+        HID_USAGE_DIGITIZER_TOUCH_PAD, HID_USAGE_DIGITIZER_X_TILT, HID_USAGE_DIGITIZER_Y_TILT,
+        HID_USAGE_GENERIC_KEYBOARD, HID_USAGE_GENERIC_MOUSE, HID_USAGE_GENERIC_RX,
+        HID_USAGE_GENERIC_RY, HID_USAGE_GENERIC_RZ, HID_USAGE_GENERIC_SLIDER,
+        HID_USAGE_GENERIC_WHEEL, HID_USAGE_GENERIC_X, HID_USAGE_GENERIC_Y, HID_USAGE_GENERIC_Z,
+        HID_USAGE_PAGE_DIGITIZER, HID_USAGE_PAGE_GENERIC,
     },
     Foundation::{HANDLE, HWND},
     UI::{
@@ -14,9 +20,9 @@ use windows_sys::Win32::{
             KeyboardAndMouse::{MapVirtualKeyW, MAPVK_VK_TO_VSC_EX, VK_NUMLOCK, VK_SHIFT},
             RegisterRawInputDevices, HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTDEVICELIST,
             RAWINPUTHEADER, RAWKEYBOARD, RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDEV_REMOVE,
-            RIDI_DEVICEINFO, RIDI_DEVICENAME, RID_DEVICE_INFO, RID_DEVICE_INFO_HID,
-            RID_DEVICE_INFO_KEYBOARD, RID_DEVICE_INFO_MOUSE, RID_INPUT, RIM_TYPEHID,
-            RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
+            RIDI_DEVICEINFO, RIDI_DEVICENAME, RIDI_PREPARSEDDATA, RID_DEVICE_INFO,
+            RID_DEVICE_INFO_HID, RID_DEVICE_INFO_KEYBOARD, RID_DEVICE_INFO_MOUSE, RID_INPUT,
+            RIM_TYPEHID, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
         },
         WindowsAndMessaging::{
             RI_KEY_E0, RI_KEY_E1, RI_MOUSE_BUTTON_1_DOWN, RI_MOUSE_BUTTON_1_UP,
@@ -28,7 +34,7 @@ use windows_sys::Win32::{
 };
 
 use crate::{
-    event::ElementState,
+    event::{AxisId, AxisInfo, AxisKind, DeviceInfo, DeviceKind, ElementState},
     event_loop::DeviceEvents,
     keyboard::{KeyCode, PhysicalKey},
     platform::scancode::PhysicalKeyExtScancode,
@@ -140,6 +146,143 @@ pub fn get_raw_input_device_name(handle: HANDLE) -> Option<String> {
     util::decode_wide(&name).into_string().ok()
 }
 
+pub fn get_device_info(handle: HANDLE) -> Option<DeviceInfo> {
+    let name = get_raw_input_device_name(handle)?;
+
+    // The device path looks like `\\?\HID#VID_046D&PID_C52B&...`.
+    let path_id = |prefix: &str| {
+        let path = name.to_uppercase();
+        let start = path.find(prefix)? + prefix.len();
+        u16::from_str_radix(path.get(start..start + 4)?, 16).ok()
+    };
+    let (mut vendor_id, mut product_id) = (path_id("VID_"), path_id("PID_"));
+
+    let mut axes = Vec::new();
+    let kind = match get_raw_input_device_info(handle)? {
+        RawDeviceInfo::Mouse(_) => {
+            // The same axes as `DeviceEvent::Motion`.
+            axes = ["X", "Y"]
+                .iter()
+                .enumerate()
+                .map(|(id, name)| AxisInfo {
+                    id: id as AxisId,
+                    name: Some(name.to_string()),
+                    kind: AxisKind::Relative,
+                    range: None,
+                })
+                .collect();
+            DeviceKind::Mouse
+        }
+        RawDeviceInfo::Keyboard(_) => DeviceKind::Keyboard,
+        RawDeviceInfo::Hid(hid) => {
+            axes = get_hid_axes(handle);
+            vendor_id = Some(hid.dwVendorId as u16);
+            product_id = Some(hid.dwProductId as u16);
+            match (hid.usUsagePage, hid.usUsage) {
+                (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_PEN) => DeviceKind::Pen,
+                (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_TOUCH_PAD) => DeviceKind::Touchpad,
+                _ => DeviceKind::Other,
+            }
+        }
+    };
+
+    Some(DeviceInfo {
+        name,
+        kind,
+        vendor_id,
+        product_id,
+        axes,
+    })
+}
+
+/// Returns the values of the input reports of a HID device, in the order they are described in.
+fn get_hid_axes(handle: HANDLE) -> Vec<AxisInfo> {
+    let mut size = 0;
+    let status =
+        unsafe { GetRawInputDeviceInfoW(handle, RIDI_PREPARSEDDATA, ptr::null_mut(), &mut size) };
+    if status != 0 || size == 0 {
+        return Vec::new();
+    }
+
+    // The preparsed data is opaque, but should be aligned like any other allocation.
+    let mut data: Vec<u64> = vec![0; (size as usize + 7) / 8];
+    let status = unsafe {
+        GetRawInputDeviceInfoW(
+            handle,
+            RIDI_PREPARSEDDATA,
+            data.as_mut_ptr() as _,
+            &mut size,
+        )
+    };
+    if status == u32::MAX || status == 0 {
+        return Vec::new();
+    }
+    let preparsed_data = data.as_ptr() as isize;
+
+    let mut caps: HIDP_CAPS = unsafe { mem::zeroed() };
+    if unsafe { HidP_GetCaps(preparsed_data, &mut caps) } != HIDP_STATUS_SUCCESS {
+        return Vec::new();
+    }
+
+    let mut len = caps.NumberInputValueCaps;
+    let mut value_caps: Vec<HIDP_VALUE_CAPS> = Vec::with_capacity(len as usize);
+    let status = unsafe {
+        HidP_GetValueCaps(
+            HidP_Input,
+            value_caps.as_mut_ptr(),
+            &mut len,
+            preparsed_data,
+        )
+    };
+    if status != HIDP_STATUS_SUCCESS {
+        return Vec::new();
+    }
+    unsafe { value_caps.set_len(len as usize) };
+
+    value_caps
+        .iter()
+        .enumerate()
+        .map(|(id, caps)| {
+            let usage = unsafe {
+                if caps.IsRange != 0 {
+                    caps.Anonymous.Range.UsageMin
+                } else {
+                    caps.Anonymous.NotRange.Usage
+                }
+            };
+            AxisInfo {
+                id: id as AxisId,
+                name: hid_usage_name(caps.UsagePage, usage).map(str::to_owned),
+                kind: if caps.IsAbsolute != 0 {
+                    AxisKind::Absolute
+                } else {
+                    AxisKind::Relative
+                },
+                range: (caps.LogicalMin < caps.LogicalMax)
+                    .then_some(caps.LogicalMin as f64..=caps.LogicalMax as f64),
+            }
+        })
+        .collect()
+}
+
+fn hid_usage_name(usage_page: u16, usage: u16) -> Option<&'static str> {
+    let name = match (usage_page, usage) {
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_X) => "X",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_Y) => "Y",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_Z) => "Z",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_RX) => "Rx",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_RY) => "Ry",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_RZ) => "Rz",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_SLIDER) => "Slider",
+        (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_WHEEL) => "Wheel",
+        (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_TIP_PRESSURE) => "Tip Pressure",
+        (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_X_TILT) => "X Tilt",
+        (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_Y_TILT) => "Y Tilt",
+        _ => return None,
+    };
+    Some(name)
+}
+
 pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     let device_size = size_of::<RAWINPUTDEVICE>() as u32;
 
@@ -6,7 +6,10 @@ use std::{
     ops::BitAnd,
     os::windows::prelude::{OsStrExt, OsStringExt},
     ptr,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
 };
 
 use once_cell::sync::Lazy;
@@ -25,6 +28,7 @@ use windows_sys::{
                 KeyboardAndMouse::GetActiveWindow,
                 Pointer::{POINTER_INFO, POINTER_PEN_INFO, POINTER_TOUCH_INFO},
             },
+            Shell::SetCurrentProcessExplicitAppUserModelID,
             WindowsAndMessaging::{
                 ClipCursor, GetClientRect, GetClipCursor, GetSystemMetrics, GetWindowPlacement,
                 GetWindowRect, IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS,
@@ -65,6 +69,28 @@ pub(crate) fn win_to_err(result: BOOL) -> Result<(), io::Error> {
     }
 }
 
+/// Sets the AppUserModelID of the process, which must happen before any window is created.
+pub(crate) fn set_app_user_model_id(app_id: &str) -> Result<(), io::Error> {
+    // The ID is limited to 128 characters and can't contain spaces.
+    if app_id.is_empty()
+        || app_id.encode_utf16().count() > 128
+        || app_id.contains(|c: char| c == ' ' || c == '\0')
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid AppUserModelID: {app_id:?}"),
+        ));
+    }
+
+    let app_id = encode_wide(app_id);
+    let result = unsafe { SetCurrentProcessExplicitAppUserModelID(app_id.as_ptr()) };
+    if result < 0 {
+        Err(io::Error::from_raw_os_error(result))
+    } else {
+        Ok(())
+    }
+}
+
 pub enum WindowArea {
     Outer,
     Inner,
@@ -118,6 +144,20 @@ pub fn get_cursor_clip() -> Result<RECT, io::Error> {
     }
 }
 
+/// The clip of `EventLoopWindowTarget::clip_cursor`, which applies while no window with a cursor
+/// grab is focused.
+static DESKTOP_CURSOR_CLIP: Mutex<Option<RECT>> = Mutex::new(None);
+
+pub fn desktop_cursor_clip() -> Option<RECT> {
+    *DESKTOP_CURSOR_CLIP.lock().unwrap()
+}
+
+/// Sets the clip of `EventLoopWindowTarget::clip_cursor`, and applies it.
+pub fn set_desktop_cursor_clip(rect: Option<RECT>) -> Result<(), io::Error> {
+    *DESKTOP_CURSOR_CLIP.lock().unwrap() = rect;
+    set_cursor_clip(rect)
+}
+
 /// Sets the cursor's clip rect.
 ///
 /// Note that calling this will automatically dispatch a `WM_MOUSEMOVE` event.
@@ -16,11 +16,15 @@ use windows_sys::Win32::{
     Graphics::{
         Dwm::{DwmEnableBlurBehindWindow, DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND},
         Gdi::{
-            ChangeDisplaySettingsExW, ClientToScreen, CreateRectRgn, DeleteObject, InvalidateRgn,
-            RedrawWindow, CDS_FULLSCREEN, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE,
-            DISP_CHANGE_BADPARAM, DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL, RDW_INTERNALPAINT,
+            BitBlt, ChangeDisplaySettingsExW, ClientToScreen, CombineRgn, CreateCompatibleBitmap,
+            CreateCompatibleDC, CreateRectRgn, DeleteDC, DeleteObject, GetDC, GetDIBits,
+            InvalidateRgn, RedrawWindow, ReleaseDC, SelectObject, SetWindowRgn, BITMAPINFO,
+            BITMAPINFOHEADER, BI_RGB, CDS_FULLSCREEN, DIB_RGB_COLORS, DISP_CHANGE_BADFLAGS,
+            DISP_CHANGE_BADMODE, DISP_CHANGE_BADPARAM, DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL,
+            RDW_INTERNALPAINT, RGN_OR, SRCCOPY,
         },
     },
+    Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS, PW_CLIENTONLY},
     System::{
         Com::{
             CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
@@ -38,26 +42,28 @@ use windows_sys::Win32::{
         },
         WindowsAndMessaging::{
             CreateWindowExW, EnableMenuItem, FlashWindowEx, GetClientRect, GetCursorPos,
-            GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowPlacement,
+            GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindow, GetWindowPlacement,
             GetWindowTextLengthW, GetWindowTextW, IsWindowVisible, LoadCursorW, PeekMessageW,
             PostMessageW, RegisterClassExW, SetCursor, SetCursorPos, SetForegroundWindow,
             SetMenuDefaultItem, SetWindowDisplayAffinity, SetWindowPlacement, SetWindowPos,
             SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, FLASHWINFO,
-            FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY, GWLP_HINSTANCE, HTBOTTOM,
-            HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP, HTTOPLEFT, HTTOPRIGHT,
-            MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY, PM_NOREMOVE,
-            SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER,
-            SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN,
-            TPM_RETURNCMD, WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND,
-            WNDCLASSEXW,
+            FLASHW_ALL, FLASHW_CAPTION, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY, GWLP_HINSTANCE,
+            GWL_EXSTYLE, GW_HWNDPREV, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT,
+            HTRIGHT, HTTOP, HTTOPLEFT, HTTOPRIGHT, HWND_TOP, MENU_ITEM_STATE, MFS_DISABLED,
+            MFS_ENABLED, MF_BYCOMMAND, NID_READY, PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE,
+            SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE,
+            SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN, TPM_RETURNCMD,
+            WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND, WNDCLASSEXW,
+            WS_EX_TOPMOST,
         },
     },
 };
 
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     icon::Icon,
+    platform::windows::{FlashConfig, FlashTarget},
     platform_impl::platform::{
         dark_mode::try_theme,
         definitions::{
@@ -74,9 +80,11 @@ use crate::{
         window_state::{CursorFlags, SavedWindow, WindowFlags, WindowState},
         Fullscreen, PlatformSpecificWindowBuilderAttributes, WindowId,
     },
+    platform_impl::SizeConstraints,
     window::{
-        CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-        WindowAttributes, WindowButtons, WindowLevel,
+        CursorGrabMode, CursorIcon, DropEffect, ImageBuffer, ImeHints, ImePurpose, Insets,
+        ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+        WindowShape,
     },
 };
 
@@ -98,6 +106,13 @@ impl Window {
         w_attr: WindowAttributes,
         pl_attr: PlatformSpecificWindowBuilderAttributes,
     ) -> Result<Window, RootOsError> {
+        if event_loop.exiting() {
+            return Err(os_error!(io::Error::new(
+                io::ErrorKind::Other,
+                "the event loop is exiting"
+            )));
+        }
+
         // We dispatch an `init` function because of code style.
         // First person to remove the need for cloning here gets a cookie!
         //
@@ -236,6 +251,30 @@ impl Window {
             .unwrap()
     }
 
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, _visible: bool) -> Result<(), NotSupportedError> {
+        // `IVirtualDesktopManager` can only move windows between desktops, pinning them is only
+        // possible with undocumented interfaces.
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        None
+    }
+
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        let outer = util::WindowArea::Outer.get_rect(self.hwnd()).unwrap();
+        let inner = util::WindowArea::Inner.get_rect(self.hwnd()).unwrap();
+        Insets {
+            top: (inner.top - outer.top).max(0) as u32,
+            bottom: (outer.bottom - inner.bottom).max(0) as u32,
+            left: (inner.left - outer.left).max(0) as u32,
+            right: (outer.right - inner.right).max(0) as u32,
+        }
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let scale_factor = self.scale_factor();
@@ -395,13 +434,30 @@ impl Window {
 
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
-        self.window_state_lock().mouse.cursor = cursor;
+        let mut window_state = self.window_state_lock();
+        let previous = mem::replace(&mut window_state.mouse.cursor, cursor);
+        let changed = window_state
+            .mouse
+            .cursor_regions
+            .cursor_set(previous, cursor);
+        drop(window_state);
+        if !changed {
+            return;
+        }
         self.thread_executor.execute_in_thread(move || unsafe {
             let cursor = LoadCursorW(0, util::to_windows_cursor(cursor));
             SetCursor(cursor);
         });
     }
 
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
+        self.window_state_lock()
+            .mouse
+            .cursor_regions
+            .set_regions(regions);
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let confine = match mode {
@@ -429,6 +485,29 @@ impl Window {
         rx.recv().unwrap()
     }
 
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        let (tx, rx) = channel();
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let result = window_state
+                .lock()
+                .unwrap()
+                .mouse
+                .set_cursor_confinement(window, rect)
+                .map_err(|e| ExternalError::Os(os_error!(e)));
+            let _ = tx.send(result);
+        });
+        rx.recv().unwrap()
+    }
+
+    /// The cursor is only ever clipped while the window is focused, see `refresh_os_cursor`.
+    #[inline]
+    pub fn set_auto_grab(&self, _auto_grab: bool) {}
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         let window = self.window;
@@ -625,6 +704,49 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn set_shape(&self, shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        let window = self.window;
+        let rectangles = shape.map(|shape| shape.rectangles());
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let region = match rectangles {
+                Some(rectangles) => {
+                    // The region is relative to the top-left corner of the window, non-client
+                    // area included, while the shape is relative to the client area.
+                    let (offset_x, offset_y) = match (
+                        util::WindowArea::Outer.get_rect(window),
+                        util::WindowArea::Inner.get_rect(window),
+                    ) {
+                        (Ok(outer), Ok(inner)) => (inner.left - outer.left, inner.top - outer.top),
+                        _ => (0, 0),
+                    };
+
+                    let region = CreateRectRgn(0, 0, 0, 0);
+                    for (position, size) in rectangles {
+                        let left = position.x + offset_x;
+                        let top = position.y + offset_y;
+                        let rectangle = CreateRectRgn(
+                            left,
+                            top,
+                            left + size.width as i32,
+                            top + size.height as i32,
+                        );
+                        CombineRgn(region, region, rectangle, RGN_OR);
+                        DeleteObject(rectangle);
+                    }
+                    region
+                }
+                // No region restores the default shape.
+                None => 0,
+            };
+
+            // The system owns the region after this call, so it must not be deleted.
+            SetWindowRgn(window, region, true.into());
+        });
+
+        Ok(())
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.hwnd())
@@ -873,6 +995,11 @@ impl Window {
         Some(monitor::current_monitor(self.hwnd()))
     }
 
+    #[inline]
+    pub fn work_area(&self, monitor: &MonitorHandle) -> PhysicalRect {
+        monitor.work_area()
+    }
+
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         if let Some(ref window_icon) = window_icon {
@@ -886,7 +1013,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_enable(&self, enabled: bool) {
+    pub fn set_drop_feedback(&self, effect: DropEffect) {
+        self.window_state_lock().drop_effect = effect;
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
         unsafe { EnableWindow(self.hwnd(), enabled.into()) };
     }
 
@@ -907,8 +1039,16 @@ impl Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            let scale_factor = state.lock().unwrap().scale_factor;
-            ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            let (cursor_area, scale_factor) = {
+                let mut state = state.lock().unwrap();
+                (
+                    state.ime_cursor_area.set_area(spot, size),
+                    state.scale_factor,
+                )
+            };
+            if let Some((spot, size)) = cursor_area {
+                ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            }
         });
     }
 
@@ -917,19 +1057,35 @@ impl Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            state.lock().unwrap().ime_allowed = allowed;
+            let (cursor_area, scale_factor) = {
+                let mut state = state.lock().unwrap();
+                (
+                    state.ime_cursor_area.set_allowed(allowed),
+                    state.scale_factor,
+                )
+            };
             ImeContext::set_ime_allowed(window, allowed);
+
+            // The cursor area can't be set without an associated input context, so apply the
+            // last one now to place the candidate window right on the first composition.
+            if let Some((spot, size)) = cursor_area {
+                ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            }
         })
     }
 
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_hints(&self, _hints: ImeHints) {}
+
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let window = self.window;
         let active_window_handle = unsafe { GetActiveWindow() };
-        if window == active_window_handle {
+        // Stopping a flash must still work once the window got focused.
+        if request_type.is_some() && window == active_window_handle {
             return;
         }
 
@@ -952,6 +1108,35 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn flash(&self, config: FlashConfig) {
+        let window = self.window;
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let flags = match config.target {
+                FlashTarget::Caption => FLASHW_CAPTION,
+                FlashTarget::TaskbarButton => FLASHW_TRAY,
+                FlashTarget::All => FLASHW_ALL,
+            };
+            let (flags, count) = match config.count {
+                Some(count) => (flags, count),
+                None => (flags | FLASHW_TIMERNOFG, 0),
+            };
+            let timeout = config
+                .rate
+                .map(|rate| u32::try_from(rate.as_millis()).unwrap_or(u32::MAX))
+                .unwrap_or(0);
+
+            let flash_info = FLASHWINFO {
+                cbSize: mem::size_of::<FLASHWINFO>() as u32,
+                hwnd: window,
+                dwFlags: flags,
+                uCount: count,
+                dwTimeout: timeout,
+            };
+            FlashWindowEx(&flash_info);
+        });
+    }
+
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
         try_theme(self.window, theme);
@@ -995,7 +1180,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
         let window_flags = self.window_state_lock().window_flags();
 
         let is_visible = window_flags.contains(WindowFlags::VISIBLE);
@@ -1005,6 +1190,38 @@ impl Window {
         if is_visible && !is_minimized && !is_foreground {
             unsafe { force_window_active(self.window) };
         }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_above(&self, sibling: &Window) -> Result<(), ExternalError> {
+        // `SetWindowPos` places the window below the one it's given, which is the window right
+        // above `sibling` here. Windows that are always on top are stacked separately, so the
+        // window right above a regular `sibling` may be unrelated to it.
+        let is_topmost =
+            |hwnd| unsafe { super::get_window_long(hwnd, GWL_EXSTYLE) } as u32 & WS_EX_TOPMOST != 0;
+        let above = unsafe { GetWindow(sibling.hwnd(), GW_HWNDPREV) };
+        if above == self.hwnd() {
+            return Ok(());
+        }
+        if above == 0 || is_topmost(above) != is_topmost(sibling.hwnd()) {
+            self.insert_after(HWND_TOP)
+        } else {
+            self.insert_after(above)
+        }
+    }
+
+    #[inline]
+    pub fn set_below(&self, sibling: &Window) -> Result<(), ExternalError> {
+        self.insert_after(sibling.hwnd())
+    }
+
+    fn insert_after(&self, insert_after: HWND) -> Result<(), ExternalError> {
+        let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE;
+        if unsafe { SetWindowPos(self.hwnd(), insert_after, 0, 0, 0, 0, flags) } == false.into() {
+            return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+        }
+        Ok(())
     }
 
     #[inline]
@@ -1021,6 +1238,71 @@ impl Window {
         };
     }
 
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        /// Captures the contents composited by DWM, like DirectX swap chains, since Windows 8.1.
+        const PW_RENDERFULLCONTENT: PRINT_WINDOW_FLAGS = 2;
+
+        let hwnd = self.hwnd();
+        let size = self.inner_size();
+        let (width, height) = (size.width as i32, size.height as i32);
+        if width == 0 || height == 0 {
+            return Ok(ImageBuffer::from_rgba(Vec::new(), size));
+        }
+
+        let mut bgra = vec![0u8; size.width as usize * size.height as usize * 4];
+        let result = unsafe {
+            let window_dc = GetDC(hwnd);
+            if window_dc == 0 {
+                return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+            }
+            let memory_dc = CreateCompatibleDC(window_dc);
+            let bitmap = CreateCompatibleBitmap(window_dc, width, height);
+            let previous = SelectObject(memory_dc, bitmap);
+
+            let mut copied = PrintWindow(hwnd, memory_dc, PW_CLIENTONLY | PW_RENDERFULLCONTENT)
+                != false.into()
+                || BitBlt(memory_dc, 0, 0, width, height, window_dc, 0, 0, SRCCOPY) != false.into();
+            SelectObject(memory_dc, previous);
+
+            if copied {
+                let mut info: BITMAPINFO = mem::zeroed();
+                info.bmiHeader = BITMAPINFOHEADER {
+                    biSize: mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    // A negative height makes the rows go from the top.
+                    biHeight: -height,
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB,
+                    ..mem::zeroed()
+                };
+                copied = GetDIBits(
+                    memory_dc,
+                    bitmap,
+                    0,
+                    size.height,
+                    bgra.as_mut_ptr().cast(),
+                    &mut info,
+                    DIB_RGB_COLORS,
+                ) == height;
+            }
+            let error = io::Error::last_os_error();
+
+            DeleteObject(bitmap);
+            DeleteDC(memory_dc);
+            ReleaseDC(hwnd, window_dc);
+            copied.then_some(()).ok_or(error)
+        };
+        result.map_err(|err| ExternalError::Os(os_error!(err)))?;
+
+        // GDI doesn't keep the alpha channel, the pixels are opaque.
+        for pixel in bgra.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+            pixel[3] = 0xff;
+        }
+        Ok(ImageBuffer::from_rgba(bgra, size))
+    }
+
     #[inline]
     pub fn reset_dead_keys(&self) {
         // `ToUnicode` consumes the dead-key by default, so we are constructing a fake (but valid)
@@ -1040,6 +1322,11 @@ impl Window {
             );
         }
     }
+
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        true
+    }
 }
 
 impl Drop for Window {
@@ -1124,6 +1411,7 @@ impl<'a, T: 'static> InitData<'a, T> {
             let file_drop_runner = self.event_loop.runner_shared.clone();
             let file_drop_handler = FileDropHandler::new(
                 win.window,
+                win.window_state.clone(),
                 Box::new(move |event| {
                     if let Ok(e) = event.map_nonuser_event() {
                         file_drop_runner.send_event(e)
@@ -1210,17 +1498,15 @@ impl<'a, T: 'static> InitData<'a, T> {
 
         win.set_enabled_buttons(attributes.enabled_buttons);
 
+        let scale_factor = win.scale_factor();
         let size = attributes
             .inner_size
             .unwrap_or_else(|| PhysicalSize::new(800, 600).into());
-        let max_size = attributes
-            .max_inner_size
-            .unwrap_or_else(|| PhysicalSize::new(f64::MAX, f64::MAX).into());
-        let min_size = attributes
-            .min_inner_size
-            .unwrap_or_else(|| PhysicalSize::new(0, 0).into());
-        let clamped_size = Size::clamp(size, min_size, max_size, win.scale_factor());
-        win.request_inner_size(clamped_size);
+        // Windows doesn't support resize increments, but the initial size is still snapped to
+        // them, like on the other platforms.
+        let clamped_size = SizeConstraints::from_attributes(&attributes, None, scale_factor)
+            .clamp(size.to_physical::<u32>(scale_factor));
+        win.request_inner_size(clamped_size.into());
 
         // let margins = MARGINS {
         //     cxLeftWidth: 1,
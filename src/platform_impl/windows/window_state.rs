@@ -1,9 +1,12 @@
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Size},
     icon::Icon,
     keyboard::ModifiersState,
-    platform_impl::platform::{event_loop, util, Fullscreen},
-    window::{CursorIcon, Theme, WindowAttributes},
+    platform_impl::{
+        platform::{event_loop, util, Fullscreen},
+        CursorRegions, ImeCursorArea, TouchIds,
+    },
+    window::{CursorIcon, DropEffect, Theme, WindowAttributes},
 };
 use std::io;
 use std::sync::MutexGuard;
@@ -46,7 +49,9 @@ pub(crate) struct WindowState {
     pub window_flags: WindowFlags,
 
     pub ime_state: ImeState,
-    pub ime_allowed: bool,
+    /// Whether the IME is allowed, and the last IME cursor area, which is also applied when the
+    /// IME gets allowed or the scale factor changes.
+    pub ime_cursor_area: ImeCursorArea,
 
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
@@ -58,6 +63,15 @@ pub(crate) struct WindowState {
     pub dragging: bool,
 
     pub skip_taskbar: bool,
+
+    // Used by the `IDropTarget` to report the effect of a drop.
+    pub drop_effect: DropEffect,
+
+    // The minimized state last reported with `WindowEvent::Minimized`.
+    pub minimized: bool,
+
+    // Maps the `WM_TOUCH` and `WM_POINTER*` ids to the ones we report.
+    pub touch_ids: TouchIds<u32>,
 }
 
 #[derive(Clone)]
@@ -68,8 +82,11 @@ pub struct SavedWindow {
 #[derive(Clone)]
 pub struct MouseProperties {
     pub cursor: CursorIcon,
+    pub cursor_regions: CursorRegions,
     pub capture_count: u32,
     cursor_flags: CursorFlags,
+    /// The rectangle of the client area set with `Window::confine_cursor_to_rect`.
+    cursor_confinement: Option<PhysicalRect>,
     pub last_position: Option<PhysicalPosition<f64>>,
 }
 
@@ -144,8 +161,10 @@ impl WindowState {
         WindowState {
             mouse: MouseProperties {
                 cursor: CursorIcon::default(),
+                cursor_regions: CursorRegions::default(),
                 capture_count: 0,
                 cursor_flags: CursorFlags::empty(),
+                cursor_confinement: None,
                 last_position: None,
             },
 
@@ -165,7 +184,7 @@ impl WindowState {
             window_flags: WindowFlags::empty(),
 
             ime_state: ImeState::Disabled,
-            ime_allowed: false,
+            ime_cursor_area: ImeCursorArea::default(),
 
             is_active: false,
             is_focused: false,
@@ -174,6 +193,12 @@ impl WindowState {
             dragging: false,
 
             skip_taskbar: false,
+
+            drop_effect: DropEffect::default(),
+
+            minimized: false,
+
+            touch_ids: TouchIds::default(),
         }
     }
 
@@ -230,7 +255,10 @@ impl MouseProperties {
     {
         let old_flags = self.cursor_flags;
         f(&mut self.cursor_flags);
-        match self.cursor_flags.refresh_os_cursor(window) {
+        match self
+            .cursor_flags
+            .refresh_os_cursor(window, self.cursor_confinement)
+        {
             Ok(()) => (),
             Err(e) => {
                 self.cursor_flags = old_flags;
@@ -240,6 +268,25 @@ impl MouseProperties {
 
         Ok(())
     }
+
+    pub fn cursor_confinement(&self) -> Option<PhysicalRect> {
+        self.cursor_confinement
+    }
+
+    pub fn set_cursor_confinement(
+        &mut self,
+        window: HWND,
+        rect: Option<PhysicalRect>,
+    ) -> Result<(), io::Error> {
+        let old_rect = std::mem::replace(&mut self.cursor_confinement, rect);
+        match self.cursor_flags.refresh_os_cursor(window, rect) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.cursor_confinement = old_rect;
+                Err(e)
+            }
+        }
+    }
 }
 
 impl WindowFlags {
@@ -493,13 +540,30 @@ impl WindowFlags {
 }
 
 impl CursorFlags {
-    fn refresh_os_cursor(self, window: HWND) -> Result<(), io::Error> {
+    fn refresh_os_cursor(
+        self,
+        window: HWND,
+        confinement: Option<PhysicalRect>,
+    ) -> Result<(), io::Error> {
         let client_rect = util::WindowArea::Inner.get_rect(window)?;
 
         if util::is_focused(window) {
-            let cursor_clip = match self.contains(CursorFlags::GRABBED) {
-                true => Some(client_rect),
-                false => None,
+            let cursor_clip = match (confinement, self.contains(CursorFlags::GRABBED)) {
+                // The confinement is relative to the client area, and replaces it as the clip.
+                (Some(rect), _) => {
+                    let left = client_rect.left + rect.position.x;
+                    let top = client_rect.top + rect.position.y;
+                    Some(RECT {
+                        left: left.clamp(client_rect.left, client_rect.right),
+                        top: top.clamp(client_rect.top, client_rect.bottom),
+                        right: (left + rect.size.width as i32)
+                            .clamp(client_rect.left, client_rect.right),
+                        bottom: (top + rect.size.height as i32)
+                            .clamp(client_rect.top, client_rect.bottom),
+                    })
+                }
+                (None, true) => Some(client_rect),
+                (None, false) => util::desktop_cursor_clip(),
             };
 
             let rect_to_tuple = |rect: RECT| (rect.left, rect.top, rect.right, rect.bottom);
@@ -0,0 +1,125 @@
+//! Watching of the state returned by
+//! [`EventLoopWindowTarget::power_state`](crate::event_loop::EventLoopWindowTarget::power_state).
+//!
+//! Few systems notify the applications of power changes, and those which do all do it
+//! differently, while the state changes rarely. It's thus polled from the end of the iterations
+//! of the event loop, while the application listens to it, to emit [`Event::PowerStateChanged`].
+//! The backends which get notified emit the event themselves, and it's filtered the same way, so
+//! that the application only sees actual changes.
+//!
+//! [`Event::PowerStateChanged`]: crate::event::Event::PowerStateChanged
+
+#[cfg(not(wasm_platform))]
+use std::time::{Duration, Instant};
+#[cfg(wasm_platform)]
+use web_time::{Duration, Instant};
+
+use crate::event_loop::PowerState;
+
+/// How often the power state is checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+pub(crate) struct Power {
+    /// The state last returned to the application.
+    last: Option<PowerState>,
+    /// When to check the state next, if it's listened to.
+    next_check: Option<Instant>,
+}
+
+impl Power {
+    /// Starts or stops polling the power state, starting from `state`.
+    pub(crate) fn listen(&mut self, listen: bool, state: Option<PowerState>, now: Instant) {
+        self.last = state;
+        self.next_check = listen.then(|| now + POLL_INTERVAL);
+    }
+
+    /// Called when the application queried the power state, which it's now up to date with.
+    pub(crate) fn queried(&mut self, state: Option<PowerState>) {
+        if self.next_check.is_some() {
+            self.last = state;
+        }
+    }
+
+    /// Returns when the power state has to be checked next, if it's listened to.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.next_check
+    }
+
+    pub(crate) fn is_due(&self, now: Instant) -> bool {
+        self.next_check
+            .map_or(false, |next_check| next_check <= now)
+    }
+
+    /// Records the state checked at `now`, by polling or because the backend reported it, and
+    /// returns it if the application has to be notified of it.
+    pub(crate) fn checked(
+        &mut self,
+        state: Option<PowerState>,
+        now: Instant,
+    ) -> Option<Option<PowerState>> {
+        self.next_check?;
+        self.next_check = Some(now + POLL_INTERVAL);
+        if state == self.last {
+            return None;
+        }
+        self.last = state;
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ON_BATTERY: PowerState = PowerState {
+        on_battery: true,
+        battery_percent: Some(80.0),
+    };
+
+    #[test]
+    fn only_changes_are_reported_while_listened_to() {
+        let start = Instant::now();
+        let mut power = Power::default();
+        assert!(!power.is_due(start + POLL_INTERVAL));
+
+        power.listen(true, None, start);
+        assert!(!power.is_due(start));
+        let now = start + POLL_INTERVAL;
+        assert!(power.is_due(now));
+        assert_eq!(power.checked(Some(ON_BATTERY), now), Some(Some(ON_BATTERY)));
+        assert!(!power.is_due(now));
+
+        let now = now + POLL_INTERVAL;
+        assert_eq!(power.checked(Some(ON_BATTERY), now), None);
+        assert_eq!(power.checked(None, now), Some(None));
+        assert_eq!(power.checked(Some(ON_BATTERY), now), Some(Some(ON_BATTERY)));
+
+        power.listen(false, None, now);
+        assert_eq!(power.deadline(), None);
+        assert!(!power.is_due(now + POLL_INTERVAL));
+        assert_eq!(power.checked(Some(ON_BATTERY), now), None);
+    }
+
+    #[test]
+    fn reported_changes_postpone_the_next_check() {
+        let start = Instant::now();
+        let mut power = Power::default();
+        power.listen(true, None, start);
+
+        let now = start + POLL_INTERVAL / 2;
+        assert_eq!(power.checked(Some(ON_BATTERY), now), Some(Some(ON_BATTERY)));
+        assert_eq!(power.deadline(), Some(now + POLL_INTERVAL));
+    }
+
+    #[test]
+    fn queries_only_update_the_listened_state() {
+        let now = Instant::now();
+        let mut power = Power::default();
+        power.queried(Some(ON_BATTERY));
+        power.listen(true, None, now);
+        assert_eq!(power.last, None);
+        power.queried(Some(ON_BATTERY));
+        assert_eq!(power.checked(Some(ON_BATTERY), now), None);
+    }
+}
@@ -0,0 +1,565 @@
+//! Scheduling of [`WindowEvent::RedrawRequested`] according to the hints set with
+//! [`Window::set_redraw_hint`](crate::window::Window::set_redraw_hint), and of the
+//! [`Event::Timer`]s created with
+//! [`EventLoopWindowTarget::create_timer`](crate::event_loop::EventLoopWindowTarget::create_timer),
+//! and throttling of the redraw requests with
+//! [`EventLoopWindowTarget::set_wait_throttle`](crate::event_loop::EventLoopWindowTarget::set_wait_throttle).
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(wasm_platform))]
+use std::time::{Duration, Instant};
+#[cfg(wasm_platform)]
+use web_time::{Duration, Instant};
+
+use crate::cursor_clip::CursorClip;
+use crate::cursor_presence::CursorPresence;
+use crate::event::{Event, WindowEvent};
+use crate::event_loop::{ControlFlow, EventLoopWindowTarget, TimerId};
+use crate::held_input::HeldInput;
+use crate::power::Power;
+use crate::window::{RedrawHint, WindowId};
+
+/// The state of the wrapper of the event handler, which the event loop keeps across the calls of
+/// `pump_events` and `run_on_demand`.
+#[derive(Default)]
+pub(crate) struct HandlerState {
+    /// Shared with the windows, which set their hints from any thread.
+    pub(crate) scheduler: Arc<Mutex<Scheduler>>,
+    pub(crate) cursor_presence: RefCell<CursorPresence>,
+    pub(crate) held_input: RefCell<HeldInput>,
+    /// The windows hidden since the last event, shared with the windows, which are hidden from
+    /// any thread.
+    pub(crate) hidden_windows: Arc<Mutex<Vec<WindowId>>>,
+    pub(crate) power: RefCell<Power>,
+    pub(crate) cursor_clip: RefCell<CursorClip>,
+    /// The control flow requested by the application, while the event loop waits for the
+    /// deadline we replaced it with instead.
+    overridden: Cell<Option<(ControlFlow, Instant)>>,
+}
+
+/// Wraps the application's event handler to dispatch the timers due, to report the changes of
+/// the power state, see [`crate::power`], and to request the redraws due at the end of every
+/// iteration of the event loop.
+///
+/// This is the entry point of every event loop, so the handler is also wrapped to balance the
+/// cursor enter and leave events, see [`crate::cursor_presence`], to release the keys and
+/// buttons held on focus loss, see [`crate::held_input`], and to release the cursor clip while
+/// the application is unfocused, see [`crate::cursor_clip`].
+pub(crate) fn wrap<T, F>(
+    state: Rc<HandlerState>,
+    event_handler: F,
+) -> impl FnMut(Event<T>, &EventLoopWindowTarget<T>)
+where
+    F: FnMut(Event<T>, &EventLoopWindowTarget<T>),
+{
+    let event_handler = crate::cursor_presence::wrap(state.clone(), event_handler);
+    let event_handler = crate::held_input::wrap(state.clone(), event_handler);
+    let mut event_handler = crate::cursor_clip::wrap(state.clone(), event_handler);
+
+    move |event, elwt| {
+        // The application gets the control flow it requested back while it handles the events,
+        // unless it changed it since, so that it never mistakes our deadline for its own.
+        if let Some(requested) = restored(state.overridden.take(), elwt.control_flow()) {
+            elwt.set_control_flow(requested);
+        }
+
+        // The backends which get notified of power changes report them whenever they come.
+        let event = match event {
+            Event::PowerStateChanged(power_state) => {
+                let checked = state
+                    .power
+                    .borrow_mut()
+                    .checked(power_state, Instant::now());
+                match checked {
+                    Some(power_state) => Event::PowerStateChanged(power_state),
+                    None => return,
+                }
+            }
+            event => event,
+        };
+
+        if let Event::NewEvents(_) = event {
+            state.scheduler.lock().unwrap().in_iteration = true;
+        }
+
+        let about_to_wait = matches!(event, Event::AboutToWait);
+        if about_to_wait && !elwt.exiting() {
+            // The lock is released while dispatching, so that the handler can create and cancel
+            // timers; a timer cancelled by the handler of a previous one isn't fired anymore.
+            let now = Instant::now();
+            let due = state.scheduler.lock().unwrap().due_timers(now);
+            for id in due {
+                if elwt.exiting() {
+                    break;
+                }
+                if state.scheduler.lock().unwrap().fire_timer(id, now) {
+                    event_handler(Event::Timer(id), elwt);
+                }
+            }
+
+            if !elwt.exiting() && state.power.borrow().is_due(now) {
+                let checked = state.power.borrow_mut().checked(elwt.p.power_state(), now);
+                if let Some(power_state) = checked {
+                    event_handler(Event::PowerStateChanged(power_state), elwt);
+                }
+            }
+        }
+
+        if let Event::WindowEvent {
+            window_id,
+            event: WindowEvent::RedrawRequested { .. },
+        } = event
+        {
+            state
+                .scheduler
+                .lock()
+                .unwrap()
+                .redrawn(window_id, Instant::now());
+        }
+
+        event_handler(event, elwt);
+
+        if !about_to_wait || elwt.exiting() {
+            return;
+        }
+
+        let control_flow = elwt.control_flow();
+        let (due, deadline) = {
+            let mut scheduler = state.scheduler.lock().unwrap();
+            scheduler.in_iteration = false;
+            scheduler.due(Instant::now(), control_flow == ControlFlow::Poll)
+        };
+        let deadline = match (deadline, state.power.borrow().deadline()) {
+            (Some(deadline), Some(power)) => Some(deadline.min(power)),
+            (deadline, power) => deadline.or(power),
+        };
+        for window_id in due {
+            elwt.p.request_redraw(window_id.0);
+        }
+
+        let scheduled = schedule(control_flow, deadline);
+        if let ControlFlow::WaitUntil(ours) = scheduled {
+            if scheduled != control_flow {
+                state.overridden.set(Some((control_flow, ours)));
+                elwt.set_control_flow(scheduled);
+            }
+        }
+    }
+}
+
+/// Returns the control flow the application requested, if the event loop still waits for the
+/// deadline we replaced it with.
+fn restored(
+    overridden: Option<(ControlFlow, Instant)>,
+    current: ControlFlow,
+) -> Option<ControlFlow> {
+    let (requested, ours) = overridden?;
+    (current == ControlFlow::WaitUntil(ours)).then_some(requested)
+}
+
+/// Wakes the event loop up by `deadline` at the latest.
+fn schedule(control_flow: ControlFlow, deadline: Option<Instant>) -> ControlFlow {
+    match (control_flow, deadline) {
+        (ControlFlow::Wait, Some(deadline)) => ControlFlow::WaitUntil(deadline),
+        (ControlFlow::WaitUntil(instant), Some(deadline)) if deadline < instant => {
+            ControlFlow::WaitUntil(deadline)
+        }
+        (control_flow, _) => control_flow,
+    }
+}
+
+struct Window {
+    id: WindowId,
+    hint: RedrawHint,
+    last_redraw: Option<Instant>,
+}
+
+struct Timer {
+    id: TimerId,
+    interval: Duration,
+    repeat: bool,
+    next: Instant,
+}
+
+/// A window whose redraw requests are throttled.
+struct ThrottledRedraw {
+    id: WindowId,
+    last_redraw: Instant,
+    pending: bool,
+}
+
+pub(crate) struct Scheduler {
+    windows: Vec<Window>,
+    timers: Vec<Timer>,
+    wait_throttle: Option<Duration>,
+    redraws: Vec<ThrottledRedraw>,
+    /// Whether the event loop is between `NewEvents` and `AboutToWait`, which is when the held
+    /// back redraw requests are sure to be made.
+    in_iteration: bool,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    const fn new() -> Self {
+        Self {
+            windows: Vec::new(),
+            timers: Vec::new(),
+            wait_throttle: None,
+            redraws: Vec::new(),
+            in_iteration: false,
+        }
+    }
+
+    pub(crate) fn set_wait_throttle(&mut self, throttle: Option<Duration>) {
+        self.wait_throttle = throttle;
+    }
+
+    /// Returns whether the redraw request of the window is held back by the wait throttle, in
+    /// which case it's made at the end of the iteration the throttle allows it in.
+    pub(crate) fn throttle_redraw(&mut self, id: WindowId, now: Instant) -> bool {
+        let throttle = match self.wait_throttle {
+            Some(throttle) if self.in_iteration => throttle,
+            _ => return false,
+        };
+
+        match self.redraws.iter_mut().find(|redraw| redraw.id == id) {
+            Some(redraw) if now < redraw.last_redraw + throttle => {
+                redraw.pending = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn create_timer(
+        &mut self,
+        id: TimerId,
+        interval: Duration,
+        repeat: bool,
+        now: Instant,
+    ) {
+        self.timers.push(Timer {
+            id,
+            interval,
+            repeat,
+            next: now + interval,
+        });
+    }
+
+    pub(crate) fn cancel_timer(&mut self, id: TimerId) {
+        self.timers.retain(|timer| timer.id != id);
+    }
+
+    /// Returns the timers due at `now`, by deadline and then by creation.
+    fn due_timers(&self, now: Instant) -> Vec<TimerId> {
+        let mut due: Vec<_> = self
+            .timers
+            .iter()
+            .filter(|timer| timer.next <= now)
+            .map(|timer| (timer.next, timer.id))
+            .collect();
+        due.sort();
+        due.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Removes or reschedules a timer returned by [`Self::due_timers`], and returns whether it
+    /// fires, which is not the case if it got cancelled in the meantime.
+    fn fire_timer(&mut self, id: TimerId, now: Instant) -> bool {
+        let index = match self.timers.iter().position(|timer| timer.id == id) {
+            Some(index) if self.timers[index].next <= now => index,
+            _ => return false,
+        };
+
+        let timer = &mut self.timers[index];
+        if timer.repeat {
+            // Ticks missed while the event loop was busy are skipped, instead of firing in a burst.
+            timer.next += timer.interval;
+            if timer.next <= now {
+                timer.next = now + timer.interval;
+            }
+        } else {
+            self.timers.swap_remove(index);
+        }
+        true
+    }
+
+    pub(crate) fn set_hint(&mut self, id: WindowId, hint: RedrawHint) {
+        let index = self.windows.iter().position(|window| window.id == id);
+        match (index, hint) {
+            (Some(index), RedrawHint::OnDemand) => {
+                self.windows.swap_remove(index);
+            }
+            (Some(index), hint) => self.windows[index].hint = hint,
+            (None, RedrawHint::OnDemand) => (),
+            (None, hint) => self.windows.push(Window {
+                id,
+                hint,
+                last_redraw: None,
+            }),
+        }
+    }
+
+    fn redrawn(&mut self, id: WindowId, now: Instant) {
+        if let Some(window) = self.windows.iter_mut().find(|window| window.id == id) {
+            window.last_redraw = Some(now);
+        }
+
+        match self.redraws.iter_mut().find(|redraw| redraw.id == id) {
+            Some(redraw) => redraw.last_redraw = now,
+            None if self.wait_throttle.is_some() => self.redraws.push(ThrottledRedraw {
+                id,
+                last_redraw: now,
+                pending: false,
+            }),
+            None => (),
+        }
+    }
+
+    pub(crate) fn remove(&mut self, id: WindowId) {
+        self.set_hint(id, RedrawHint::OnDemand);
+        self.redraws.retain(|redraw| redraw.id != id);
+    }
+
+    /// Returns the windows to redraw now, and when the next throttled redraw or timer is due.
+    ///
+    /// The redraw requests held back by the wait throttle are all made with `poll` or without a
+    /// throttle anymore.
+    fn due(&mut self, now: Instant, poll: bool) -> (Vec<WindowId>, Option<Instant>) {
+        let mut due = Vec::new();
+        let mut deadline: Option<Instant> = None;
+
+        for redraw in self.redraws.iter_mut().filter(|redraw| redraw.pending) {
+            let next = match self.wait_throttle {
+                Some(throttle) if !poll => redraw.last_redraw + throttle,
+                _ => now,
+            };
+
+            if next <= now {
+                redraw.pending = false;
+                due.push(redraw.id);
+            } else {
+                deadline = Some(deadline.map_or(next, |deadline| deadline.min(next)));
+            }
+        }
+        if self.wait_throttle.is_none() {
+            self.redraws.clear();
+        }
+
+        for window in &self.windows {
+            let next = match (window.hint, window.last_redraw) {
+                (RedrawHint::OnDemand, _) => continue,
+                (RedrawHint::Continuous, _) | (RedrawHint::Throttled(_), None) => now,
+                (RedrawHint::Throttled(interval), Some(last_redraw)) => last_redraw + interval,
+            };
+
+            if next <= now {
+                if !due.contains(&window.id) {
+                    due.push(window.id);
+                }
+            } else {
+                deadline = Some(deadline.map_or(next, |deadline| deadline.min(next)));
+            }
+        }
+
+        for timer in &self.timers {
+            deadline = Some(deadline.map_or(timer.next, |deadline| deadline.min(timer.next)));
+        }
+
+        (due, deadline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn window_id(id: u64) -> WindowId {
+        WindowId::from(id)
+    }
+
+    #[test]
+    fn on_demand_windows_are_not_scheduled() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_hint(window_id(1), RedrawHint::Continuous);
+        scheduler.set_hint(window_id(1), RedrawHint::OnDemand);
+
+        assert!(scheduler.windows.is_empty());
+        assert_eq!(scheduler.due(Instant::now(), false), (Vec::new(), None));
+    }
+
+    #[test]
+    fn continuous_windows_are_always_due() {
+        let mut scheduler = Scheduler::new();
+        let now = Instant::now();
+        scheduler.set_hint(window_id(1), RedrawHint::Continuous);
+        scheduler.redrawn(window_id(1), now);
+
+        assert_eq!(scheduler.due(now, false), (vec![window_id(1)], None));
+    }
+
+    #[test]
+    fn throttled_windows_wait_for_their_interval() {
+        let mut scheduler = Scheduler::new();
+        let interval = Duration::from_millis(16);
+        let now = Instant::now();
+        scheduler.set_hint(window_id(1), RedrawHint::Throttled(interval));
+        scheduler.set_hint(window_id(2), RedrawHint::Throttled(interval * 2));
+
+        assert_eq!(
+            scheduler.due(now, false),
+            (vec![window_id(1), window_id(2)], None)
+        );
+
+        scheduler.redrawn(window_id(1), now);
+        scheduler.redrawn(window_id(2), now);
+        assert_eq!(
+            scheduler.due(now, false),
+            (Vec::new(), Some(now + interval))
+        );
+        assert_eq!(
+            scheduler.due(now + interval, false),
+            (vec![window_id(1)], Some(now + interval * 2))
+        );
+    }
+
+    #[test]
+    fn wait_throttle_holds_redraw_requests_back() {
+        let mut scheduler = Scheduler::new();
+        let throttle = Duration::from_millis(16);
+        let now = Instant::now();
+        scheduler.wait_throttle = Some(throttle);
+        scheduler.in_iteration = true;
+
+        // The first redraw isn't throttled, the request made while handling it is.
+        assert!(!scheduler.throttle_redraw(window_id(1), now));
+        scheduler.redrawn(window_id(1), now);
+        assert!(scheduler.throttle_redraw(window_id(1), now));
+        assert_eq!(
+            scheduler.due(now, false),
+            (Vec::new(), Some(now + throttle))
+        );
+        assert_eq!(
+            scheduler.due(now + throttle, false),
+            (vec![window_id(1)], None)
+        );
+
+        // Requests made outside of an iteration, from another thread, aren't held back.
+        scheduler.redrawn(window_id(1), now + throttle);
+        scheduler.in_iteration = false;
+        assert!(!scheduler.throttle_redraw(window_id(1), now + throttle));
+
+        // `Poll` ignores the throttle.
+        scheduler.in_iteration = true;
+        assert!(scheduler.throttle_redraw(window_id(1), now + throttle));
+        assert_eq!(
+            scheduler.due(now + throttle, true),
+            (vec![window_id(1)], None)
+        );
+    }
+
+    #[test]
+    fn timers_fire_by_deadline() {
+        let mut scheduler = Scheduler::new();
+        let now = Instant::now();
+        let (one_shot, repeating) = (TimerId::next(), TimerId::next());
+        scheduler.create_timer(one_shot, Duration::from_millis(20), false, now);
+        scheduler.create_timer(repeating, Duration::from_millis(10), true, now);
+
+        assert_eq!(scheduler.due_timers(now), []);
+        assert_eq!(
+            scheduler.due(now, false).1,
+            Some(now + Duration::from_millis(10))
+        );
+
+        let later = now + Duration::from_millis(20);
+        assert_eq!(scheduler.due_timers(later), [repeating, one_shot]);
+        assert!(scheduler.fire_timer(repeating, later));
+        assert!(scheduler.fire_timer(one_shot, later));
+
+        // The one-shot timer is gone, and the repeating one is due again in one interval.
+        assert_eq!(scheduler.due_timers(later), []);
+        assert_eq!(
+            scheduler.due(later, false).1,
+            Some(later + Duration::from_millis(10))
+        );
+    }
+
+    #[test]
+    fn repeating_timers_skip_missed_ticks() {
+        let mut scheduler = Scheduler::new();
+        let interval = Duration::from_millis(10);
+        let now = Instant::now();
+        let id = TimerId::next();
+        scheduler.create_timer(id, interval, true, now);
+
+        let later = now + interval * 5 + Duration::from_millis(1);
+        assert!(scheduler.fire_timer(id, later));
+        assert!(!scheduler.fire_timer(id, later));
+        assert_eq!(scheduler.due(later, false).1, Some(later + interval));
+    }
+
+    #[test]
+    fn cancelled_timers_do_not_fire() {
+        let mut scheduler = Scheduler::new();
+        let now = Instant::now();
+        let id = TimerId::next();
+        scheduler.create_timer(id, Duration::ZERO, true, now);
+
+        assert_eq!(scheduler.due_timers(now), [id]);
+        scheduler.cancel_timer(id);
+        assert!(!scheduler.fire_timer(id, now));
+        assert_eq!(scheduler.due(now, false).1, None);
+    }
+
+    #[test]
+    fn deadline_only_shortens_the_wait() {
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        assert_eq!(
+            schedule(ControlFlow::Wait, Some(now)),
+            ControlFlow::WaitUntil(now)
+        );
+        assert_eq!(
+            schedule(ControlFlow::WaitUntil(later), Some(now)),
+            ControlFlow::WaitUntil(now)
+        );
+        assert_eq!(
+            schedule(ControlFlow::WaitUntil(now), Some(later)),
+            ControlFlow::WaitUntil(now)
+        );
+        assert_eq!(schedule(ControlFlow::Poll, Some(now)), ControlFlow::Poll);
+        assert_eq!(schedule(ControlFlow::Wait, None), ControlFlow::Wait);
+    }
+
+    #[test]
+    fn requested_control_flow_is_restored_unless_changed() {
+        let ours = Instant::now();
+        let overridden = Some((ControlFlow::Wait, ours));
+
+        assert_eq!(
+            restored(overridden, ControlFlow::WaitUntil(ours)),
+            Some(ControlFlow::Wait)
+        );
+        // The application set another control flow since.
+        assert_eq!(restored(overridden, ControlFlow::Poll), None);
+        assert_eq!(
+            restored(
+                overridden,
+                ControlFlow::WaitUntil(ours + Duration::from_secs(1))
+            ),
+            None
+        );
+        assert_eq!(restored(None, ControlFlow::WaitUntil(ours)), None);
+    }
+}
@@ -1,12 +1,20 @@
 //! The [`Window`] struct and associated types.
 use std::fmt;
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(wasm_platform))]
+use std::time::{Duration, Instant};
+#[cfg(wasm_platform)]
+use web_time::{Duration, Instant};
 
 use crate::{
-    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+    dpi::{PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size},
     error::{ExternalError, NotSupportedError, OsError},
     event_loop::EventLoopWindowTarget,
     monitor::{MonitorHandle, VideoMode},
-    platform_impl, SendSyncWrapper,
+    platform_impl,
+    scheduler::Scheduler,
+    SendSyncWrapper,
 };
 
 pub use crate::icon::{BadIcon, Icon};
@@ -53,6 +61,10 @@ pub use cursor_icon::{CursorIcon, ParseError as CursorIconParseError};
 /// ```
 pub struct Window {
     pub(crate) window: platform_impl::Window,
+    /// The scheduler of the event loop the window was built with.
+    scheduler: Arc<Mutex<Scheduler>>,
+    /// The windows hidden since the last event of that event loop.
+    hidden_windows: Arc<Mutex<Vec<WindowId>>>,
 }
 
 impl fmt::Debug for Window {
@@ -71,7 +83,9 @@ impl Drop for Window {
             if let Some(Fullscreen::Exclusive(_)) = w.fullscreen().map(|f| f.into()) {
                 w.set_fullscreen(None);
             }
-        })
+        });
+
+        self.scheduler.lock().unwrap().remove(self.id());
     }
 }
 
@@ -81,7 +95,12 @@ impl Drop for Window {
 ///
 /// Whenever you receive an event specific to a window, this event contains a `WindowId` which you
 /// can then compare to the ids of your windows.
+///
+/// With the `serde` feature, a `WindowId` is serialized as its `u64` value, which is only
+/// meaningful within the running event loop.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "u64", into = "u64"))]
 pub struct WindowId(pub(crate) platform_impl::WindowId);
 
 impl WindowId {
@@ -135,6 +154,7 @@ impl fmt::Debug for WindowBuilder {
 #[derive(Debug, Clone)]
 pub struct WindowAttributes {
     pub inner_size: Option<Size>,
+    pub outer_size: Option<Size>,
     pub min_inner_size: Option<Size>,
     pub max_inner_size: Option<Size>,
     pub position: Option<Position>,
@@ -147,6 +167,7 @@ pub struct WindowAttributes {
     pub blur: bool,
     pub decorations: bool,
     pub window_icon: Option<Icon>,
+    pub generated_icon: bool,
     pub preferred_theme: Option<Theme>,
     pub resize_increments: Option<Size>,
     pub content_protected: bool,
@@ -162,6 +183,7 @@ impl Default for WindowAttributes {
     fn default() -> WindowAttributes {
         WindowAttributes {
             inner_size: None,
+            outer_size: None,
             min_inner_size: None,
             max_inner_size: None,
             position: None,
@@ -176,6 +198,7 @@ impl Default for WindowAttributes {
             decorations: true,
             window_level: Default::default(),
             window_icon: None,
+            generated_icon: false,
             preferred_theme: None,
             resize_increments: None,
             content_protected: false,
@@ -224,6 +247,30 @@ impl WindowBuilder {
         self
     }
 
+    /// Requests the whole window, including its title bar and borders, to be of specific
+    /// dimensions.
+    ///
+    /// The inner size is computed from the [frame insets](Window::frame_insets) once the window
+    /// is created, and requested like with [`Window::request_inner_size`]. This takes precedence
+    /// over [`WindowBuilder::with_inner_size`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The frame extents are only known once the window manager has reparented the
+    ///   window, which happens after it's mapped. The window is thus created with this as its
+    ///   inner size, and resized to fit the frame in when it first becomes visible, so the first
+    ///   [`WindowEvent::Resized`] may not match the requested size yet.
+    /// - **Wayland:** Server-side decorations aren't accounted for by the frame insets, so only
+    ///   client-side decorations are fit in.
+    /// - **iOS / Android / Web / Orbital:** Windows have no frame, so this is the inner size.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    #[inline]
+    pub fn with_outer_size<S: Into<Size>>(mut self, size: S) -> Self {
+        self.window.outer_size = Some(size.into());
+        self
+    }
+
     /// Sets the minimum dimensions a window can have.
     ///
     /// If this is not set, the window will have no minimum dimensions (aside
@@ -335,7 +382,17 @@ impl WindowBuilder {
     ///
     /// The default is to show the window.
     ///
+    /// A hidden window can be fully configured before it's shown for the first time, which
+    /// avoids the window being visible with its default geometry for a few frames:
+    ///
+    /// 1. Build the window with `with_visible(false)`.
+    /// 2. Set its size, position, icon and other attributes.
+    /// 3. Call [`Window::set_visible`] with `true` and render the first frame in response to
+    ///    the following [`WindowEvent::RedrawRequested`].
+    ///
     /// See [`Window::set_visible`] for details.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     #[inline]
     pub fn with_visible(mut self, visible: bool) -> Self {
         self.window.visible = visible;
@@ -358,6 +415,10 @@ impl WindowBuilder {
 
     /// Sets whether the background of the window should be blurred by the system.
     ///
+    /// Combined with [`with_transparent(true)`](Self::with_transparent), the blur is set up
+    /// before the window is first shown, so there's no flash of un-blurred transparency. When
+    /// the compositor doesn't support blurring, the window is just transparent.
+    ///
     /// The default is `false`.
     ///
     /// See [`Window::set_blur`] for details.
@@ -401,6 +462,9 @@ impl WindowBuilder {
     ///
     /// The default is `None`.
     ///
+    /// The icon is validated when it's created with [`Icon::from_rgba`], which returns a
+    /// [`BadIcon`] for pixels not matching its dimensions or dimensions over [`Icon::MAX_SIZE`].
+    ///
     /// See [`Window::set_window_icon`] for details.
     #[inline]
     pub fn with_window_icon(mut self, window_icon: Option<Icon>) -> Self {
@@ -408,6 +472,26 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets whether to generate a window icon when none is given with
+    /// [`WindowBuilder::with_window_icon`].
+    ///
+    /// The generated icon is the first letter or digit of the title, in white on a background
+    /// color picked from the title, which is nicer than the generic placeholder of the system for
+    /// prototypes and internal tools. An icon given with [`WindowBuilder::with_window_icon`]
+    /// always takes precedence, and the icon isn't generated again when the title changes.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only has an effect where [`Window::set_window_icon`] is supported, that's to say on
+    /// **Windows** and **X11**.
+    #[inline]
+    pub fn with_generated_icon(mut self, generated_icon: bool) -> Self {
+        self.window.generated_icon = generated_icon;
+        self
+    }
+
     /// Sets a specific theme for the window.
     ///
     /// If `None` is provided, the window will use the system theme.
@@ -431,7 +515,20 @@ impl WindowBuilder {
     ///
     /// The default is `None`.
     ///
+    /// The initial inner size, from [`WindowBuilder::with_inner_size`] or the default one, is
+    /// snapped to the increments at creation, so the first frame is already on the grid. It's
+    /// rounded down to a whole number of increments from the base size, which is the minimum
+    /// inner size unless one is set on X11, so the window never ends up larger than requested
+    /// nor than the maximum inner size. It's never rounded below the minimum inner size, though,
+    /// which wins when both disagree.
+    ///
     /// See [`Window::set_resize_increments`] for details.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / Windows:** The initial size is snapped even though the increments aren't
+    ///   applied to later resizes.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
     #[inline]
     pub fn with_resize_increments<S: Into<Size>>(mut self, resize_increments: S) -> Self {
         self.window.resize_increments = Some(resize_increments.into());
@@ -500,19 +597,54 @@ impl WindowBuilder {
     ///
     /// Possible causes of error include denied permission, incompatible system, and lack of memory.
     ///
+    /// The state requested through the builder is reflected by the window's getters as soon as
+    /// this returns, without waiting for an event; this covers [`Window::theme`],
+    /// [`Window::is_resizable`], [`Window::is_decorated`], [`Window::is_maximized`] and
+    /// [`Window::fullscreen`], except where noted on the individual getter.
+    ///
     /// ## Platform-specific
     ///
     /// - **Web:** The window is created but not inserted into the web page automatically. Please
     ///   see the web platform module for more information.
     #[inline]
     pub fn build<T: 'static>(
-        self,
+        mut self,
         window_target: &EventLoopWindowTarget<T>,
     ) -> Result<Window, OsError> {
+        if self.window.generated_icon && self.window.window_icon.is_none() {
+            self.window.window_icon = Icon::from_title(&self.window.title).ok();
+        }
+
+        // The frame isn't known before the window is created, so the window starts without room
+        // for it, and is shrunk to fit it in right after.
+        let outer_size = self.window.outer_size;
+        if outer_size.is_some() {
+            self.window.inner_size = outer_size;
+        }
+
         let window =
             platform_impl::Window::new(&window_target.p, self.window, self.platform_specific)?;
         window.maybe_queue_on_main(|w| w.request_redraw());
-        Ok(Window { window })
+        let window = Window {
+            window,
+            scheduler: window_target.handler_state.scheduler.clone(),
+            hidden_windows: window_target.handler_state.hidden_windows.clone(),
+        };
+
+        if let Some(outer_size) = outer_size {
+            // The outer size is what the inner size would be without the frame.
+            let outer_size = outer_size.to_physical(window.scale_factor());
+            let inner_size = DecorationsResize::PreserveOuter.inner_size(
+                outer_size,
+                Insets::default(),
+                window.frame_insets(),
+            );
+            if inner_size != window.inner_size() {
+                let _ = window.request_inner_size(inner_size);
+            }
+        }
+
+        Ok(window)
     }
 }
 
@@ -543,6 +675,39 @@ impl Window {
         self.window.maybe_wait_on_main(|w| WindowId(w.id()))
     }
 
+    /// Returns `Ok` if the raw window and display handles of the window are valid, so that a
+    /// graphics surface can be created for it.
+    ///
+    /// The handles are the ones returned by [`HasWindowHandle`] and [`HasDisplayHandle`]. Checking
+    /// this before creating a surface, instead of assuming the handles are valid as soon as the
+    /// window is created, is needed on platforms where the window handle follows the application
+    /// lifecycle. A graphics context shared between several windows only depends on the display
+    /// handle, and can outlive the surfaces.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** The handle is only valid between [`Event::Resumed`] and [`Event::Suspended`],
+    ///   and [`HandleError::Unavailable`] is returned outside of them. Surfaces must be dropped on
+    ///   `Suspended` and recreated on the next `Resumed`.
+    /// - **Headless:** Always returns [`HandleError::NotSupported`].
+    /// - **Windows / macOS / iOS / X11 / Wayland / Web / Orbital:** The handles are valid from
+    ///   the creation of the window until it's dropped.
+    ///
+    /// [`HasWindowHandle`]: rwh_06::HasWindowHandle
+    /// [`HasDisplayHandle`]: rwh_06::HasDisplayHandle
+    /// [`HandleError::Unavailable`]: rwh_06::HandleError::Unavailable
+    /// [`HandleError::NotSupported`]: rwh_06::HandleError::NotSupported
+    /// [`Event::Resumed`]: crate::event::Event::Resumed
+    /// [`Event::Suspended`]: crate::event::Event::Suspended
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_window_handle_ready(&self) -> Result<(), rwh_06::HandleError> {
+        self.window.maybe_wait_on_main(|w| {
+            w.raw_display_handle_rwh_06()?;
+            w.raw_window_handle_rwh_06().map(|_| ())
+        })
+    }
+
     /// Returns the scale factor that can be used to map logical pixels to physical pixels, and
     /// vice versa.
     ///
@@ -592,12 +757,56 @@ impl Window {
     /// - **iOS:** Can only be called on the main thread.
     /// - **Wayland:** The events are aligned with the frame callbacks when [`Window::pre_present_notify`]
     ///                is used.
-    /// - **Web:** [`WindowEvent::RedrawRequested`] will be aligned with the `requestAnimationFrame`.
+    /// - **Web:** Redraws are driven by `requestAnimationFrame`: this only marks the window as
+    ///   needing a redraw in the next animation frame, so all the requests made before it are
+    ///   coalesced into a single [`WindowEvent::RedrawRequested`].
+    ///
+    /// The request may be held back until the end of a later iteration by
+    /// [`EventLoopWindowTarget::set_wait_throttle`].
     ///
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`EventLoopWindowTarget::set_wait_throttle`]: crate::event_loop::EventLoopWindowTarget::set_wait_throttle
     #[inline]
     pub fn request_redraw(&self) {
-        self.window.maybe_queue_on_main(|w| w.request_redraw())
+        let throttled = self
+            .scheduler
+            .lock()
+            .unwrap()
+            .throttle_redraw(self.id(), Instant::now());
+        if !throttled {
+            self.window.maybe_queue_on_main(|w| w.request_redraw())
+        }
+    }
+
+    /// Sets how winit schedules [`WindowEvent::RedrawRequested`] for this window.
+    ///
+    /// With [`RedrawHint::Continuous`] a redraw is requested at the end of every iteration of the
+    /// event loop, like in a game loop, and with [`RedrawHint::Throttled`] at most once per
+    /// interval, waking the event loop up with [`ControlFlow::WaitUntil`] when the next one is
+    /// due. The default, [`RedrawHint::OnDemand`], only redraws when [`Window::request_redraw`]
+    /// is called or the windowing system asks for it.
+    ///
+    /// The scheduled redraws go through [`Window::request_redraw`], so they're still aligned with
+    /// the windowing system drawing loop, and the control flow set by the application is only
+    /// ever shortened to meet a throttled deadline.
+    ///
+    /// This doesn't control the swap interval of the GPU, i.e. whether presenting waits for
+    /// vertical sync; that's up to the graphics API the application draws with.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Combine with [`Window::pre_present_notify`] to also throttle the redraws to
+    ///   the compositor's frame callbacks.
+    /// - **Web:** The redraws are aligned with `requestAnimationFrame`.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    #[inline]
+    pub fn set_redraw_hint(&self, hint: RedrawHint) {
+        self.scheduler.lock().unwrap().set_hint(self.id(), hint);
+        if hint != RedrawHint::OnDemand {
+            self.request_redraw();
+        }
     }
 
     /// Notify the windowing system before presenting to the window.
@@ -629,9 +838,16 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// **Wayland:** - schedules a frame callback to throttle [`WindowEvent::RedrawRequested`].
+    /// - **Wayland:** schedules a frame callback to throttle [`WindowEvent::RedrawRequested`].
+    /// - **X11:** acknowledges the last `_NET_WM_SYNC_REQUEST` of the window manager, which then
+    ///   shows the resized window only once its new frame is presented. This removes the flicker
+    ///   and stretching while resizing on compositing window managers like KWin and Mutter, as
+    ///   long as the application draws promptly on [`WindowEvent::Resized`] and
+    ///   [`WindowEvent::RedrawRequested`]. The request is otherwise acknowledged after
+    ///   [`WindowEvent::RedrawRequested`] returns.
     ///
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     #[inline]
     pub fn pre_present_notify(&self) {
         self.window.maybe_queue_on_main(|w| w.pre_present_notify());
@@ -639,28 +855,62 @@ impl Window {
 
     /// Reset the dead key state of the keyboard.
     ///
-    /// This is useful when a dead key is bound to trigger an action. Then
-    /// this function can be called to reset the dead key state so that
-    /// follow-up text input won't be affected by the dead key.
+    /// This clears any in-progress dead key or compose sequence for this window, so that
+    /// follow-up text input won't be affected by it. Call this when a text field loses focus,
+    /// its contents are reset, or a dead key is bound to trigger an action. Without it, typing
+    /// `^` and then switching fields could insert `â` into the newly focused field.
+    ///
+    /// This is a no-op when there's no pending composition.
     ///
     /// ## Platform-specific
-    /// - **Web, macOS:** Does nothing
-    // ---------------------------
-    // Developers' Note: If this cannot be implemented on every desktop platform
-    // at least, then this function should be provided through a platform specific
-    // extension trait
+    ///
+    /// - **Windows:** Clears the dead key buffer of the active keyboard layout.
+    /// - **X11 / Wayland:** Resets the XKB compose state before the next key press is processed.
+    /// - **macOS:** Discards the marked text of the window's input context.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
     pub fn reset_dead_keys(&self) {
         self.window.maybe_queue_on_main(|w| w.reset_dead_keys())
     }
+
+    /// Returns whether the native surface of the window currently exists.
+    ///
+    /// Graphics surfaces, such as an `EGLSurface`, must only be created from the window's raw
+    /// window handle while this returns `true`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** The surface is created before [`Event::Resumed`] and destroyed after
+    ///   [`Event::Suspended`], which correspond to the `surfaceCreated` and `surfaceDestroyed`
+    ///   callbacks of Android's `SurfaceHolder`. Any surface created from the window has to be
+    ///   destroyed in response to `Suspended`. Creating an `EGLSurface` outside of this range,
+    ///   or keeping one alive past it, is undefined behavior and usually crashes the application.
+    /// - **iOS / macOS / Web / Windows / X11 / Wayland / Orbital:** Always `true`, the surface
+    ///   lives as long as the window.
+    ///
+    /// [`Event::Resumed`]: crate::event::Event::Resumed
+    /// [`Event::Suspended`]: crate::event::Event::Suspended
+    #[inline]
+    pub fn surface_valid(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| w.surface_valid())
+    }
 }
 
 /// Position and size functions.
 impl Window {
-    /// Returns the position of the top-left hand corner of the window's client area relative to the
-    /// top-left hand corner of the desktop.
+    /// Returns the position of the top-left hand corner of the window's client area on the
+    /// desktop.
     ///
     /// The same conditions that apply to [`Window::outer_position`] apply to this method.
     ///
+    /// Where both positions are supported, the client area is offset from the outer position by
+    /// the [`frame_insets`](Window::frame_insets):
+    ///
+    /// ```text
+    /// inner_position = outer_position + (frame_insets.left, frame_insets.top)
+    /// ```
+    ///
+    /// This is what to use for placing a popup at a point relative to the window's content.
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread. Returns the top left coordinates of the
@@ -675,15 +925,13 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.inner_position())
     }
 
-    /// Returns the position of the top-left hand corner of the window relative to the
-    /// top-left hand corner of the desktop.
+    /// Returns the position of the top-left hand corner of the window on the desktop.
     ///
-    /// Note that the top-left hand corner of the desktop is not necessarily the same as
-    /// the screen. If the user uses a desktop with multiple monitors, the top-left hand corner
-    /// of the desktop is the top-left hand corner of the monitor at the top-left of the desktop.
+    /// The desktop coordinates are the same as for [`MonitorHandle::position`], with the origin
+    /// at the top-left hand corner of the primary monitor. They're negative on a monitor to the
+    /// left of or above the primary one, or when the window is partially off-screen.
     ///
-    /// The coordinates can be negative if the top-left hand corner of the window is outside
-    /// of the visible screen region.
+    /// The window's offset on the monitor it's on is `outer_position - monitor.position()`.
     ///
     /// ## Platform-specific
     ///
@@ -730,6 +978,52 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_outer_position(position))
     }
 
+    /// Like [`Window::set_outer_position`], but snaps the window flush to the edges of the work
+    /// area they're within `snap_distance` physical pixels of, like interactive moves often do.
+    ///
+    /// The work area is the part of the monitor the position falls on which isn't covered by the
+    /// taskbar, docks or panels. Each axis snaps to the nearest of its two edges, and positions
+    /// outside of every monitor aren't snapped. A `snap_distance` of `0` is the same as
+    /// [`Window::set_outer_position`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The work area is the part of the monitor inside `_NET_WORKAREA`, which the
+    ///   window manager only reports for the whole X screen. Without it, the whole monitor is
+    ///   used.
+    /// - **iOS / Web / Orbital:** The whole monitor is used as the work area.
+    /// - **Android / Wayland:** Unsupported.
+    #[inline]
+    pub fn set_outer_position_snapped<P: Into<Position>>(&self, position: P, snap_distance: u32) {
+        let position = position.into();
+        self.window.maybe_queue_on_main(move |w| {
+            if snap_distance == 0 {
+                return w.set_outer_position(position);
+            }
+
+            let physical = position.to_physical::<i32>(w.scale_factor());
+            let work_area = w
+                .available_monitors()
+                .into_iter()
+                .find(|monitor| {
+                    let (x, y) = (
+                        physical.x - monitor.position().x,
+                        physical.y - monitor.position().y,
+                    );
+                    let (width, height): (u32, u32) = monitor.size().into();
+                    (0..width as i32).contains(&x) && (0..height as i32).contains(&y)
+                })
+                .map(|monitor| w.work_area(&monitor));
+            let position = match work_area {
+                Some(work_area) => {
+                    snap_to_edges(physical, w.outer_size(), work_area, snap_distance).into()
+                }
+                None => position,
+            };
+            w.set_outer_position(position)
+        })
+    }
+
     /// Returns the physical size of the window's client area.
     ///
     /// The client area is the content of the window, excluding the title bar and borders.
@@ -759,6 +1053,12 @@ impl Window {
     /// When `None` is returned, it means that the request went to the display system,
     /// and the actual size will be delivered later with the [`WindowEvent::Resized`].
     ///
+    /// The requested size is clamped between the [minimum](Window::set_min_inner_size) and
+    /// [maximum](Window::set_max_inner_size) inner sizes first, and then rounded down to a
+    /// whole number of [resize increments](Window::set_resize_increments), without going below
+    /// the minimum. Either way, the returned or reported size is the one after clamping, so
+    /// comparing it to the requested one tells whether the request was clamped.
+    ///
     /// See [`Window::inner_size`] for more information about the values.
     ///
     /// The request could automatically un-maximize the window if it's maximized.
@@ -779,6 +1079,9 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **Web:** Sets the size of the canvas element. Doesn't account for CSS [`transform`].
+    /// - **X11:** The constraints are applied before the request is sent, since not every window
+    ///   manager applies all of them. The window manager can still pick another size.
+    /// - **Wayland:** Returns the clamped size. Resize increments are unsupported.
     ///
     /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
@@ -806,6 +1109,29 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.outer_size())
     }
 
+    /// Returns the thickness of the title bar and borders on each side of the window.
+    ///
+    /// This is the difference between [`Window::outer_size`] and [`Window::inner_size`], but
+    /// queried at once, so it can't be torn by a resize happening in between two calls.
+    ///
+    /// All insets are zero for windows without decorations and for fullscreen windows. The
+    /// insets can change when the decorations do, e.g. after [`WindowEvent::ThemeChanged`] on
+    /// platforms where the decoration thickness depends on the theme.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Derived from `_NET_FRAME_EXTENTS`, or a heuristic if the window manager doesn't
+    ///   support it. The window's own X border counts as part of the frame.
+    /// - **Wayland:** Returns the thickness of the client-side decorations, server-side
+    ///   decorations are not accounted for.
+    /// - **iOS / Android / Web / Orbital:** Always zero.
+    ///
+    /// [`WindowEvent::ThemeChanged`]: crate::event::WindowEvent::ThemeChanged
+    #[inline]
+    pub fn frame_insets(&self) -> Insets {
+        self.window.maybe_wait_on_main(|w| w.frame_insets())
+    }
+
     /// Sets a minimum dimension size for the window.
     ///
     /// ```no_run
@@ -821,9 +1147,23 @@ impl Window {
     /// window.set_min_inner_size(Some(PhysicalSize::new(400, 200)));
     /// ```
     ///
+    /// The size is kept in the unit it was given in. A logical size is converted to physical
+    /// pixels again whenever the scale factor changes, so it stays the same logical size when the
+    /// window moves to a monitor with a different density, while a physical size stays the same
+    /// number of pixels.
+    ///
     /// ## Platform-specific
     ///
+    /// - **Wayland / macOS:** The size is enforced in logical units, so a physical size is
+    ///   converted with the scale factor at the time it's set.
+    /// - **Wayland:** The size is sent to the compositor with `xdg_toplevel.set_min_size`, but
+    ///   compositors don't have to enforce it while the user resizes the window, so winit clamps
+    ///   the sizes the compositor configures instead, and reports the clamped size with
+    ///   [`WindowEvent::Resized`]. Elsewhere the windowing system enforces it. The size the
+    ///   compositor sets for a maximized, fullscreen or tiled window isn't clamped.
     /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     #[inline]
     pub fn set_min_inner_size<S: Into<Size>>(&self, min_size: Option<S>) {
         let min_size = min_size.map(|s| s.into());
@@ -846,9 +1186,23 @@ impl Window {
     /// window.set_max_inner_size(Some(PhysicalSize::new(400, 200)));
     /// ```
     ///
+    /// The size is kept in the unit it was given in. A logical size is converted to physical
+    /// pixels again whenever the scale factor changes, so it stays the same logical size when the
+    /// window moves to a monitor with a different density, while a physical size stays the same
+    /// number of pixels.
+    ///
     /// ## Platform-specific
     ///
+    /// - **Wayland / macOS:** The size is enforced in logical units, so a physical size is
+    ///   converted with the scale factor at the time it's set.
+    /// - **Wayland:** The size is sent to the compositor with `xdg_toplevel.set_max_size`, but
+    ///   compositors don't have to enforce it while the user resizes the window, so winit clamps
+    ///   the sizes the compositor configures instead, and reports the clamped size with
+    ///   [`WindowEvent::Resized`]. Elsewhere the windowing system enforces it. The size the
+    ///   compositor sets for a maximized, fullscreen or tiled window isn't clamped.
     /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     #[inline]
     pub fn set_max_inner_size<S: Into<Size>>(&self, max_size: Option<S>) {
         let max_size = max_size.map(|s| s.into());
@@ -873,6 +1227,8 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11:** Increments are counted from the base size, which can be set with
+    ///   `WindowBuilderExtX11::with_base_size` and `WindowExtX11::set_base_size`.
     /// - **macOS:** Increments are converted to logical size and then macOS rounds them to whole numbers.
     /// - **Wayland / Windows:** Not implemented.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
@@ -888,8 +1244,14 @@ impl Window {
 impl Window {
     /// Modifies the title of the window.
     ///
+    /// The system may only display part of a long title, e.g. in the title bar or the taskbar,
+    /// but winit never cuts a title in the middle of a character, and [`Window::title`] returns
+    /// the full title.
+    ///
     /// ## Platform-specific
     ///
+    /// - **Wayland:** Titles longer than 1024 bytes are truncated to the last character boundary
+    ///   before they're sent to the compositor.
     /// - **iOS / Android:** Unsupported.
     #[inline]
     pub fn set_title(&self, title: &str) {
@@ -921,8 +1283,13 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / iOS / X11 / Web / Windows:** Unsupported.
-    /// - **Wayland:** Only works with org_kde_kwin_blur_manager protocol.
+    /// - **Android / iOS / Web / Windows:** Unsupported.
+    /// - **X11:** Sets `_KDE_NET_WM_BLUR_BEHIND_REGION`, which is supported by KWin. Requires the
+    ///   window to be created with [`WindowBuilder::with_transparent`].
+    /// - **Wayland:** Only works with org_kde_kwin_blur_manager protocol, which is supported by
+    ///   KWin but not by GNOME.
+    ///
+    /// Other compositors ignore the request, leaving the window unblurred.
     #[inline]
     pub fn set_blur(&self, blur: bool) {
         self.window.maybe_queue_on_main(move |w| w.set_blur(blur))
@@ -934,27 +1301,72 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / Wayland / Web:** Unsupported.
+    /// - **Android / Web:** Unsupported.
     /// - **iOS:** Can only be called on the main thread.
+    /// - **X11:** The position and size set while the window is hidden are also written to the
+    ///   size hints, so the window manager maps the window with them.
+    /// - **Wayland:** A window can't be hidden once it was shown. For a window created with
+    ///   [`WindowBuilder::with_visible`] set to `false`, [`WindowEvent::RedrawRequested`] isn't
+    ///   delivered until the window is shown, since the first buffer commit maps the window.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     #[inline]
     pub fn set_visible(&self, visible: bool) {
+        if !visible {
+            self.hidden_windows.lock().unwrap().push(self.id());
+        }
         self.window
             .maybe_queue_on_main(move |w| w.set_visible(visible))
     }
 
     /// Gets the window's current visibility state.
     ///
+    /// This reports whether the window is shown by the system, which follows
+    /// [`Window::set_visible`] but also depends on the platform's handling of minimized windows,
+    /// see below.
+    ///
     /// `None` means it couldn't be determined, so it is not recommended to use this to drive your rendering backend.
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Not implemented.
+    /// - **X11:** Reports whether the window is mapped, which is `false` while minimized. After
+    ///   [`Window::set_visible`] with `true`, this may stay `false` until the window manager
+    ///   maps the window.
+    /// - **Windows:** Uses `IsWindowVisible`, which is `true` while minimized.
+    /// - **macOS:** Uses `isVisible`, which is `false` while minimized.
     /// - **Wayland / iOS / Android / Web:** Unsupported.
     #[inline]
     pub fn is_visible(&self) -> Option<bool> {
         self.window.maybe_wait_on_main(|w| w.is_visible())
     }
 
+    /// Enables or disables mouse and keyboard input to the window.
+    ///
+    /// A disabled window stays visible but doesn't receive any input, which is how the parent of
+    /// a modal dialog is expected to behave: disable the parent while the dialog is shown and
+    /// enable it again before the dialog is closed. Keyboard focus won't be given to a disabled
+    /// window, though this is distinct from a window that can't be focused, since a disabled
+    /// window also ignores all pointer input.
+    ///
+    /// Clicking a disabled window plays the system's alert sound where the platform does so for
+    /// modal windows, and is otherwise ignored.
+    ///
+    /// The default is `true`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `EnableWindow`, see
+    ///   <https://learn.microsoft.com/en-us/windows/win32/winmsg/window-features#disabled-windows>.
+    /// - **macOS:** Input events are dropped and mouse clicks result in `NSBeep`.
+    /// - **X11:** Input events are no longer selected and the window advertises that it doesn't
+    ///   accept the keyboard focus through `WM_HINTS`.
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_enabled(enabled))
+    }
+
     /// Sets whether the window is resizable or not.
     ///
     /// Note that making the window unresizable doesn't exempt you from handling [`WindowEvent::Resized`], as that
@@ -979,7 +1391,6 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Not implemented.
     /// - **iOS / Android / Web:** Unsupported.
     #[inline]
     pub fn is_resizable(&self) -> bool {
@@ -1021,7 +1432,9 @@ impl Window {
 
     /// Gets the window's current minimized state.
     ///
-    /// `None` will be returned, if the minimized state couldn't be determined.
+    /// `None` is only returned when the platform has no way to report the minimized state. The
+    /// state is updated once the system has applied a change requested with
+    /// [`Window::set_minimized`], which is signaled by [`WindowEvent::Minimized`].
     ///
     /// ## Note
     ///
@@ -1029,8 +1442,11 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland**: always `None`.
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **Wayland**: always `None`, the protocol doesn't tell clients whether they are minimized.
+    /// - **Web:** always `Some(false)`, a canvas can't be minimized.
+    /// - **iOS / Android / Orbital:** Unsupported, always `None`.
+    ///
+    /// [`WindowEvent::Minimized`]: crate::event::WindowEvent::Minimized
     #[inline]
     pub fn is_minimized(&self) -> Option<bool> {
         self.window.maybe_wait_on_main(|w| w.is_minimized())
@@ -1051,6 +1467,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11:** The window manager applies a maximize request asynchronously, so the requested
+    ///   state is returned until it shows up in `_NET_WM_STATE`, and the one set by the window
+    ///   manager afterwards.
     /// - **iOS / Android / Web:** Unsupported.
     #[inline]
     pub fn is_maximized(&self) -> bool {
@@ -1077,9 +1496,14 @@ impl Window {
     /// - **Wayland:** Does not support exclusive fullscreen mode and will no-op a request.
     /// - **Windows:** Screen saver is disabled in fullscreen mode.
     /// - **Android / Orbital:** Unsupported.
-    /// - **Web:** Does nothing without a [transient activation], but queues the request
-    ///   for the next activation.
-    ///
+    /// - **Web:** Uses `Element.requestFullscreen()`, which browsers only allow during a
+    ///   [transient activation], i.e. while handling a key press, a mouse button press or a touch.
+    ///   Without one the request is queued and retried on the next activation. Success is reported
+    ///   with [`WindowEvent::FullscreenChanged`], and a request that was denied even with user
+    ///   activation with [`WindowEvent::FullscreenFailed`]. Leaving fullscreen is always allowed.
+    ///
+    /// [`WindowEvent::FullscreenChanged`]: crate::event::WindowEvent::FullscreenChanged
+    /// [`WindowEvent::FullscreenFailed`]: crate::event::WindowEvent::FullscreenFailed
     /// [transient activation]: https://developer.mozilla.org/en-US/docs/Glossary/Transient_activation
     #[inline]
     pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
@@ -1107,8 +1531,13 @@ impl Window {
     /// By default this is enabled. Note that fullscreen windows and windows on
     /// mobile and web platforms naturally do not have decorations.
     ///
+    /// The size of the window changes the way the platform does it natively, see
+    /// [`Window::set_decorations_preserving`] to choose which size is kept.
+    ///
     /// ## Platform-specific
     ///
+    /// - **X11 / Wayland:** The inner size is kept, and the outer size changes with the frame.
+    /// - **Windows / macOS:** The outer size is kept, and the inner size changes with the frame.
     /// - **iOS / Android / Web:** No effect.
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
@@ -1116,6 +1545,41 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_decorations(decorations))
     }
 
+    /// Turns window decorations on or off like [`Window::set_decorations`], and then resizes the
+    /// window to keep either its inner or its outer size as it was, see [`DecorationsResize`].
+    ///
+    /// The size is computed from the [frame insets](Window::frame_insets) and requested with
+    /// [`Window::request_inner_size`], so it's subject to the same constraints. This is best
+    /// effort: the insets are read right after the decorations change, and the size isn't
+    /// corrected if the platform only applies the new frame later.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** With [`DecorationsResize::PreserveOuter`], the window manager may not have
+    ///   drawn the new frame yet when decorations are turned on, in which case the window isn't
+    ///   resized.
+    /// - **Wayland:** Server-side decorations aren't accounted for by the frame insets, so only
+    ///   client-side decorations are preserved against.
+    /// - **iOS / Android / Web:** No effect.
+    pub fn set_decorations_preserving(&self, decorations: bool, resize: DecorationsResize) {
+        if decorations == self.is_decorated() {
+            return;
+        }
+
+        let inner_size = self.inner_size();
+        let insets = self.frame_insets();
+        self.set_decorations(decorations);
+        let new_insets = if decorations {
+            self.frame_insets()
+        } else {
+            Insets::default()
+        };
+        let new_inner_size = resize.inner_size(inner_size, insets, new_insets);
+        if new_inner_size != self.inner_size() {
+            let _ = self.request_inner_size(new_inner_size);
+        }
+    }
+
     /// Gets the window's current decorations state.
     ///
     /// Returns `true` when windows are decorated (server-side or by Winit).
@@ -1139,6 +1603,40 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_window_level(level))
     }
 
+    /// Sets whether the window is shown on every workspace, also known as virtual desktop or
+    /// Space.
+    ///
+    /// Combined with [`WindowLevel::AlwaysOnTop`], this is the usual way to build a floating
+    /// widget or HUD that follows the user across workspaces.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Sets `NSWindowCollectionBehaviorCanJoinAllSpaces`.
+    /// - **X11:** Sets `_NET_WM_STATE_STICKY`.
+    /// - **Windows:** Unsupported, pinning a window to every virtual desktop isn't part of the
+    ///   public virtual desktop API.
+    /// - **Wayland:** Unsupported, there's no standard protocol for it.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_visible_on_all_workspaces(&self, visible: bool) -> Result<(), NotSupportedError> {
+        self.window
+            .maybe_wait_on_main(|w| w.set_visible_on_all_workspaces(visible))
+    }
+
+    /// Gets whether the window is shown on every workspace.
+    ///
+    /// See [`Window::set_visible_on_all_workspaces`] for details.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Wayland / iOS / Android / Web / Orbital:** Unsupported, always returns
+    ///   `None`.
+    #[inline]
+    pub fn is_visible_on_all_workspaces(&self) -> Option<bool> {
+        self.window
+            .maybe_wait_on_main(|w| w.is_visible_on_all_workspaces())
+    }
+
     /// Sets the window icon.
     ///
     /// On Windows and X11, this is typically the small icon in the top-left
@@ -1159,6 +1657,31 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_window_icon(window_icon))
     }
 
+    /// Sets the feedback the system shows while files are dragged over the window.
+    ///
+    /// This is meant to be called synchronously while handling [`WindowEvent::HoveredFile`] and
+    /// [`WindowEvent::HoveredFileMoved`], so the application can accept or refuse the drop
+    /// depending on the region currently under the cursor. The effect stays active until it is
+    /// changed again, the default is [`DropEffect::Copy`].
+    ///
+    /// Setting [`DropEffect::None`] rejects the drop.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Returned from `IDropTarget::DragEnter` and `IDropTarget::DragOver`.
+    /// - **macOS:** Returned from `draggingEntered:` and `draggingUpdated:`. Since events are
+    ///   queued, the effect applies starting with the next dragging update.
+    /// - **X11:** Sent as the action of the `XdndStatus` reply.
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::HoveredFile`]: crate::event::WindowEvent::HoveredFile
+    /// [`WindowEvent::HoveredFileMoved`]: crate::event::WindowEvent::HoveredFileMoved
+    #[inline]
+    pub fn set_drop_feedback(&self, effect: DropEffect) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_drop_feedback(effect))
+    }
+
     /// Set the IME cursor editing area, where the `position` is the top left corner of that area
     /// and `size` is the size of this area starting from the position. An example of such area
     /// could be a input field in the UI or line in the editor.
@@ -1172,6 +1695,15 @@ impl Window {
     ///
     /// (Apple's official term is "candidate window", see their [chinese] and [japanese] guides).
     ///
+    /// The area is remembered while the IME isn't allowed, so set it before calling
+    /// [`Window::set_ime_allowed`] to have the candidate box in the right place from the first
+    /// composition on.
+    ///
+    /// The area is kept in the unit it was given in, like the [minimum inner size]. A logical
+    /// area is converted to physical pixels again whenever the scale factor changes and sent to
+    /// the IME, so the candidate box stays next to the text when the window moves to a monitor
+    /// with a different density, without calling this method again.
+    ///
     /// ## Example
     ///
     /// ```no_run
@@ -1192,6 +1724,7 @@ impl Window {
     /// - **X11:** - area is not supported, only position.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     ///
+    /// [minimum inner size]: Window::set_min_inner_size
     /// [chinese]: https://support.apple.com/guide/chinese-input-method/use-the-candidate-window-cim12992/104/mac/12.0
     /// [japanese]: https://support.apple.com/guide/japanese-input-method/use-the-candidate-window-jpim10262/6.3/mac/12.0
     #[inline]
@@ -1214,6 +1747,11 @@ impl Window {
     ///
     /// IME is **not** allowed by default.
     ///
+    /// Allowing IME applies the last area set with [`Window::set_ime_cursor_area`], so the
+    /// candidate box is placed correctly when the first composition starts. If no area was ever
+    /// set, the candidate box is placed where the system sees fit, usually near the window's
+    /// top-left corner.
+    ///
     /// ## Platform-specific
     ///
     /// - **macOS:** IME must be enabled to receive text-input where dead-key sequences are combined.
@@ -1239,6 +1777,57 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_ime_purpose(purpose))
     }
 
+    /// Sets the hints on how the IME should assist the input, see [`ImeHints`].
+    ///
+    /// The hints apply on top of the [`ImePurpose`] set with [`Window::set_ime_purpose`], and
+    /// are kept while the IME is disabled with [`Window::set_ime_allowed`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Sent as the content hint of the text input, along with the hints of the
+    ///   purpose. [`ImeHints::AUTO_CORRECT`] also requests completions, unless
+    ///   [`ImeHints::NO_SUGGESTIONS`] is set.
+    /// - **iOS / Android:** Unsupported, as winit doesn't show the virtual keyboard there yet.
+    /// - **Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_ime_hints(&self, hints: ImeHints) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_ime_hints(hints))
+    }
+
+    /// Moves the window right above `sibling` in the stacking order, without focusing it.
+    ///
+    /// `sibling` must be a window created by the same event loop. Windows with a different
+    /// [`WindowLevel`] are stacked separately, so this only reorders windows of the same level.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Asks the window manager to restack the window, which it may ignore.
+    /// - **Wayland:** The stacking order is controlled by the compositor only, so this always
+    ///   returns [`ExternalError::NotSupported`].
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_above(&self, sibling: &Window) -> Result<(), ExternalError> {
+        self.window
+            .maybe_wait_on_main(|w| w.set_above(&sibling.window))
+    }
+
+    /// Moves the window right below `sibling` in the stacking order.
+    ///
+    /// See [`Window::set_above`] for the requirements on `sibling`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Asks the window manager to restack the window, which it may ignore.
+    /// - **Wayland:** The stacking order is controlled by the compositor only, so this always
+    ///   returns [`ExternalError::NotSupported`].
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_below(&self, sibling: &Window) -> Result<(), ExternalError> {
+        self.window
+            .maybe_wait_on_main(|w| w.set_below(&sibling.window))
+    }
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1246,12 +1835,23 @@ impl Window {
     /// you are certain that's what the user wants. Focus stealing can cause an extremely disruptive
     /// user experience.
     ///
+    /// Getting `Ok` only means that the request was made: the window may still not get focus.
+    /// Use [`Window::has_focus`] or wait for [`WindowEvent::Focused`] to know whether it did.
+    ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Wayland / Orbital:** Unsupported.
+    /// - **X11:** The window manager may reject the request as part of its focus stealing
+    ///   prevention, in which case the window usually gets marked as urgent. Use
+    ///   [`Window::request_user_attention`] instead to get the attention of the user without
+    ///   stealing focus.
+    /// - **Wayland:** Windows can't focus themselves, so this always returns an error. Use
+    ///   [`Window::request_user_attention`] instead.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
     #[inline]
-    pub fn focus_window(&self) {
-        self.window.maybe_queue_on_main(|w| w.focus_window())
+    pub fn focus_window(&self) -> Result<(), NotSupportedError> {
+        self.window.maybe_wait_on_main(|w| w.focus_window())
     }
 
     /// Gets whether the window has keyboard focus.
@@ -1291,7 +1891,9 @@ impl Window {
     /// - **Wayland:** Sets the theme for the client side decorations. Using `None` will use dbus
     ///   to get the system preference.
     /// - **X11:** Sets `_GTK_THEME_VARIANT` hint to `dark` or `light` and if `None` is used, it will default to  [`Theme::Dark`].
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **Web:** Unsupported, a canvas can't override the color scheme of the browser. Apply the
+    ///   theme to the page itself instead, e.g. with the CSS `color-scheme` property.
+    /// - **iOS / Android / Orbital:** Unsupported.
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
         self.window.maybe_queue_on_main(move |w| w.set_theme(theme))
@@ -1302,7 +1904,14 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **macOS:** This is an app-wide setting.
-    /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported.
+    /// - **Wayland:** Returns the theme requested with [`WindowBuilder::with_theme`] or
+    ///   [`Window::set_theme`].
+    /// - **X11:** Returns the theme last requested with [`WindowBuilder::with_theme`] or
+    ///   [`Window::set_theme`], which is `None` if it was unset.
+    /// - **Android:** Returns the theme matching the night mode of the device configuration.
+    /// - **Web:** Returns the theme matching the `prefers-color-scheme` media query of the
+    ///   browser, or `None` if it can't be evaluated.
+    /// - **iOS / Orbital:** Unsupported.
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
         self.window.maybe_wait_on_main(|w| w.theme())
@@ -1322,11 +1931,41 @@ impl Window {
             .maybe_queue_on_main(move |w| w.set_content_protected(protected))
     }
 
+    /// Captures the current contents of the window, e.g. for bug reports or thumbnails.
+    ///
+    /// The image covers the inner area of the window, without the decorations, and its size
+    /// is the inner size of the window at the time of the capture.
+    ///
+    /// The contents are read back by the system compositor or the display server, so content
+    /// rendered by the GPU, like with OpenGL or Vulkan, may not be captured, or be captured as
+    /// the frame before the last one. Windows protected with
+    /// [`Window::set_content_protected`] are captured as black pixels or as an empty image.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `PrintWindow`, falling back to copying the window from the screen
+    ///   with `BitBlt`, which captures whatever overlaps the window. Protected windows are
+    ///   black.
+    /// - **macOS:** Uses `CGWindowListCreateImage`. Starting with macOS 10.15, the system may
+    ///   ask the user for the Screen Recording permission on the first capture. Protected
+    ///   windows return an empty image.
+    /// - **X11:** Uses `GetImage`, which fails if the window isn't mapped. Parts of the window
+    ///   covered by other windows may not be captured without a compositing manager.
+    /// - **Wayland:** Unsupported, the compositor doesn't let clients capture their windows.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn capture(&self) -> Result<ImageBuffer, ExternalError> {
+        self.window.maybe_wait_on_main(|w| w.capture())
+    }
+
     /// Gets the current title of the window.
     ///
+    /// This is the full title set with [`Window::set_title`] or
+    /// [`WindowBuilder::with_title`], even if the system only displays part of it.
+    ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / x11 / Wayland / Web:** Unsupported. Always returns an empty string.
+    /// - **iOS / Android:** Unsupported. Always returns an empty string.
     #[inline]
     pub fn title(&self) -> String {
         self.window.maybe_wait_on_main(|w| w.title())
@@ -1335,15 +1974,66 @@ impl Window {
 
 /// Cursor functions.
 impl Window {
+    /// Modifies the cursor of the window.
+    ///
+    /// Accepts anything convertible into a [`Cursor`], so it can be called with a [`CursorIcon`]
+    /// directly or with a stored [`Cursor`].
+    ///
+    /// This overrides the regions set with [`Window::set_cursor_regions`] until they are set
+    /// again.
+    ///
+    /// Winit remembers the cursor last set on each window, and only asks the system to change it
+    /// when the new cursor is different, as compared with [`PartialEq`]. Setting the cursor on
+    /// every frame, as immediate mode UIs do with the cursor of the hovered widget, is cheap and
+    /// doesn't need to be avoided.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Sets the CSS `cursor` property of the canvas to [`CursorIcon::name`].
+    ///   [`CursorIcon::DndAsk`] and [`CursorIcon::AllResize`] have no CSS equivalent and show
+    ///   `copy` and `move` respectively.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_cursor(&self, cursor: impl Into<Cursor>) {
+        match cursor.into() {
+            Cursor::Icon(icon) => self
+                .window
+                .maybe_queue_on_main(move |w| w.set_cursor_icon(icon)),
+        }
+    }
+
     /// Modifies the cursor icon of the window.
     ///
+    /// This is the same as calling [`Window::set_cursor`] with a [`CursorIcon`].
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS / Android / Orbital:** Unsupported.
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
+        self.set_cursor(cursor)
+    }
+
+    /// Shows a different cursor over each region of the window, instead of the one set with
+    /// [`Window::set_cursor`].
+    ///
+    /// The regions are in physical pixels relative to the top-left corner of the window's client
+    /// area, and the first region containing the cursor wins. Outside of all regions the cursor
+    /// set with [`Window::set_cursor`] is shown. This is meant for client-side decorations, to
+    /// show the resize cursors over the window's edges without tracking the cursor position
+    /// yourself.
+    ///
+    /// The regions are hit-tested by winit on every cursor move, so keep their number small.
+    /// They take effect on the next cursor move, and setting a cursor with [`Window::set_cursor`]
+    /// overrides them until this is called again. Pass an empty `Vec` to remove them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_cursor_regions(&self, regions: Vec<(PhysicalRect, CursorIcon)>) {
         self.window
-            .maybe_queue_on_main(move |w| w.set_cursor_icon(cursor))
+            .maybe_queue_on_main(move |w| w.set_cursor_regions(regions))
     }
 
     /// Changes the position of the cursor in window coordinates.
@@ -1364,7 +2054,17 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **Wayland**: Cursor must be in [`CursorGrabMode::Locked`].
+    /// - **X11:** The warp is queued and sent together with the other warps at the end of the
+    ///   event loop iteration, so calling this repeatedly in one iteration only moves the cursor
+    ///   to the last position. Failures are logged instead of returned. The first
+    ///   [`WindowEvent::CursorMoved`] that lands exactly on the warped position is assumed to be
+    ///   caused by the warp and isn't reported; motion that was already queued before the warp
+    ///   still is. [`DeviceEvent::MouseMotion`] isn't affected by warps at all, and is what
+    ///   camera controls should use.
     /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    ///
+    /// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+    /// [`DeviceEvent::MouseMotion`]: crate::event::DeviceEvent::MouseMotion
     #[inline]
     pub fn set_cursor_position<P: Into<Position>>(&self, position: P) -> Result<(), ExternalError> {
         let position = position.into();
@@ -1387,11 +2087,107 @@ impl Window {
     ///             .or_else(|_e| window.set_cursor_grab(CursorGrabMode::Locked))
     ///             .unwrap();
     /// ```
+    ///
+    /// See [`Window::set_auto_grab`] to release the grab while the window is unfocused.
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         self.window.maybe_wait_on_main(|w| w.set_cursor_grab(mode))
     }
 
+    /// Confines the cursor to a rectangle of the window, or releases the confinement with `None`.
+    ///
+    /// The rectangle is relative to the top-left corner of the client area, and is clipped to
+    /// it. Unlike [`EventLoopWindowTarget::clip_cursor`], whose rectangle is in desktop
+    /// coordinates, the confinement follows the window when it's moved or resized, so the
+    /// rectangle only has to be set again when the application wants a different one.
+    ///
+    /// It's applied while the window is focused, without a [`CursorGrabMode`], and replaces the
+    /// whole window as the confinement of [`CursorGrabMode::Confined`]. [`CursorGrabMode::Locked`]
+    /// takes precedence over it, and the rectangle applies again once the lock is released.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The compositor confines the pointer once it enters the rectangle, which
+    ///   may not happen right away.
+    /// - **macOS / iOS / Android / Web / Orbital:** Always returns an
+    ///   [`ExternalError::NotSupported`].
+    ///
+    /// [`EventLoopWindowTarget::clip_cursor`]: crate::event_loop::EventLoopWindowTarget::clip_cursor
+    #[inline]
+    pub fn confine_cursor_to_rect(&self, rect: Option<PhysicalRect>) -> Result<(), ExternalError> {
+        self.window
+            .maybe_wait_on_main(move |w| w.confine_cursor_to_rect(rect))
+    }
+
+    /// Sets whether the cursor grab should be released when the window loses focus, and
+    /// re-applied when it regains it.
+    ///
+    /// This keeps the cursor from staying grabbed by an unfocused window after the user switched
+    /// to another application, e.g. with Alt-Tab, without having to call
+    /// [`Window::set_cursor_grab`] on every [`WindowEvent::Focused`]. Both
+    /// [`CursorGrabMode::Confined`] and [`CursorGrabMode::Locked`] are restored to the mode that
+    /// was active when the focus was lost. Calling [`Window::set_cursor_grab`] while the window is
+    /// unfocused replaces the mode to restore, so a grab released with
+    /// [`CursorGrabMode::None`] stays released.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / macOS:** The grab is released before [`WindowEvent::Focused`] is delivered.
+    /// - **Windows / Wayland / Web:** The grab is always released while the window is unfocused,
+    ///   so this does nothing. On Web, the browser doesn't re-apply the pointer lock either.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
+    #[inline]
+    pub fn set_auto_grab(&self, auto_grab: bool) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_auto_grab(auto_grab))
+    }
+
+    /// Sets the grab and the visibility of the cursor together.
+    ///
+    /// This is [`Window::set_cursor_grab`] followed by [`Window::set_cursor_visible`], run in one
+    /// go on the thread owning the window, so that no event is processed in between. The grab is
+    /// always applied first: on X11 the cursor is hidden by changing the cursor of the window,
+    /// which only holds while the pointer is over the window, and the grab keeps it there, so
+    /// hiding before grabbing can briefly show the cursor over another window. If the grab fails,
+    /// the visibility isn't changed and the error is returned, so the cursor is never left hidden
+    /// but free, or grabbed but visible because of a partial update.
+    ///
+    /// [`Window::set_cursor_grab`] and [`Window::set_cursor_visible`] remain available for finer
+    /// control.
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`CursorGrabMode`] for the grab modes each platform supports.
+    /// [`PointerMode::Grabbed`] with `locked: true` fails on X11 and Windows, where applications
+    /// usually fall back to `locked: false` and read the relative motion from
+    /// [`DeviceEvent::MouseMotion`].
+    ///
+    /// [`DeviceEvent::MouseMotion`]: crate::event::DeviceEvent::MouseMotion
+    #[inline]
+    pub fn set_pointer_mode(&self, mode: PointerMode) -> Result<(), ExternalError> {
+        let (grab, visible) = match mode {
+            PointerMode::Visible => (CursorGrabMode::None, true),
+            PointerMode::Hidden => (CursorGrabMode::None, false),
+            PointerMode::Grabbed { locked, hidden } => {
+                let grab = if locked {
+                    CursorGrabMode::Locked
+                } else {
+                    CursorGrabMode::Confined
+                };
+                (grab, !hidden)
+            }
+        };
+        self.window.maybe_wait_on_main(move |w| {
+            w.set_cursor_grab(grab)?;
+            w.set_cursor_visible(visible);
+            Ok(())
+        })
+    }
+
     /// Modifies the cursor's visibility.
     ///
     /// If `false`, this will hide the cursor. If `true`, this will show the cursor.
@@ -1403,6 +2199,9 @@ impl Window {
     /// - **Wayland:** The cursor is only hidden within the confines of the window.
     /// - **macOS:** The cursor is hidden as long as the window has input focus, even if the cursor is
     ///   outside of the window.
+    /// - **Web:** The cursor is hidden over the canvas with the CSS `cursor: none`, which is kept
+    ///   while the pointer leaves and re-enters it, like on the other platforms. Combine it with
+    ///   [`CursorGrabMode::Locked`], which uses the Pointer Lock API, for a first person camera.
     /// - **iOS / Android:** Unsupported.
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
@@ -1456,6 +2255,30 @@ impl Window {
             .maybe_queue_on_main(move |w| w.show_window_menu(position))
     }
 
+    /// Sets the shape of the window, or restores its rectangular shape with `None`.
+    ///
+    /// Outside of the shape the window isn't drawn, and cursor events go to whatever is behind
+    /// it. Decorations are clipped along with the rest of the window, so this is mostly useful
+    /// for undecorated windows.
+    ///
+    /// The shape isn't updated when the window is resized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `SetWindowRgn`.
+    /// - **X11:** Uses the Shape extension to set both the bounding and input shapes. While
+    ///   [`Window::set_cursor_hittest`] is disabled, the input shape stays empty.
+    /// - **Wayland:** Only the input region can be changed, so the window still draws outside the
+    ///   shape; make it transparent to hide that part. The region is applied with the next
+    ///   surface commit.
+    /// - **macOS:** Only the cursor events are clipped, with `hitTest:`, so the window still draws
+    ///   outside the shape; make it transparent to hide that part.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_shape(&self, shape: Option<WindowShape>) -> Result<(), ExternalError> {
+        self.window.maybe_wait_on_main(|w| w.set_shape(shape))
+    }
+
     /// Modifies whether the window catches cursor events.
     ///
     /// If `true`, the window will catch the cursor events. If `false`, events are passed through
@@ -1475,7 +2298,22 @@ impl Window {
 impl Window {
     /// Returns the monitor on which the window currently resides.
     ///
+    /// This is the monitor used by [`Fullscreen::Borderless(None)`].
+    ///
     /// Returns `None` if current monitor can't be detected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The monitor the outer frame of the window overlaps the most, computed from the
+    ///   window's current geometry, or the first monitor if the window doesn't overlap any of
+    ///   them. On a tie the previous monitor is kept, so while dragging a window across the edge
+    ///   between two monitors the result flips once the window's center crosses the edge, which
+    ///   is also when [`WindowEvent::ScaleFactorChanged`] is emitted if their scale factors
+    ///   differ.
+    /// - **Windows:** The monitor the window overlaps the most, as picked by the system.
+    ///
+    /// [`Fullscreen::Borderless(None)`]: Fullscreen::Borderless
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
     #[inline]
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
         self.window
@@ -1571,6 +2409,52 @@ unsafe impl rwh_04::HasRawWindowHandle for Window {
     }
 }
 
+/// The cursor of a window, see [`Window::set_cursor`].
+///
+/// This lets code store the cursor it wants to show as a single value. `Cursor` implements
+/// [`PartialEq`], which [`Window::set_cursor`] uses to skip the call to the system when the
+/// cursor didn't change, so the cursor can be computed and set on every frame.
+///
+/// More kinds of cursors may be added in the future, which is why this enum is
+/// `#[non_exhaustive]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Cursor {
+    /// One of the cursors provided by the system.
+    Icon(CursorIcon),
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor::Icon(CursorIcon::default())
+    }
+}
+
+impl From<CursorIcon> for Cursor {
+    fn from(icon: CursorIcon) -> Self {
+        Cursor::Icon(icon)
+    }
+}
+
+/// How winit schedules redraws of a window.
+///
+/// Use this enum with [`Window::set_redraw_hint`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RedrawHint {
+    /// Redraw at the end of every iteration of the event loop.
+    Continuous,
+
+    /// Only redraw when [`Window::request_redraw`] is called or the windowing system asks for it.
+    #[default]
+    OnDemand,
+
+    /// Redraw at most once per the given interval, e.g. `Duration::from_secs(1) / 30` to target
+    /// 30 frames per second.
+    Throttled(Duration),
+}
+
 /// The behavior of cursor grabbing.
 ///
 /// Use this enum with [`Window::set_cursor_grab`] to grab the cursor.
@@ -1582,6 +2466,9 @@ pub enum CursorGrabMode {
 
     /// The cursor is confined to the window area.
     ///
+    /// The confinement tracks the client area of the window, and is updated when the window is
+    /// moved or resized.
+    ///
     /// There's no guarantee that the cursor will be hidden. You should hide it by yourself if you
     /// want to do so.
     ///
@@ -1603,8 +2490,26 @@ pub enum CursorGrabMode {
     Locked,
 }
 
+/// The grab and visibility of the cursor, set together with [`Window::set_pointer_mode`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PointerMode {
+    /// The cursor is visible and free to leave the window.
+    Visible,
+
+    /// The cursor is hidden, but free to leave the window.
+    Hidden,
+
+    /// The cursor is grabbed, with [`CursorGrabMode::Locked`] if `locked` is `true` and
+    /// [`CursorGrabMode::Confined`] otherwise, and hidden if `hidden` is `true`.
+    ///
+    /// A first person camera typically uses `Grabbed { locked: true, hidden: true }`.
+    Grabbed { locked: bool, hidden: bool },
+}
+
 /// Defines the orientation that a window resize will be performed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ResizeDirection {
     East,
     North,
@@ -1632,12 +2537,174 @@ impl From<ResizeDirection> for CursorIcon {
     }
 }
 
+/// The shape of a window, see [`Window::set_shape`].
+///
+/// Coordinates are in physical pixels, relative to the top-left corner of the window's client
+/// area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowShape {
+    /// The union of the given rectangles.
+    Rectangles(Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>),
+
+    /// The pixels of a `width * height` alpha bitmap, stored row by row, whose alpha is greater
+    /// than `threshold`.
+    ///
+    /// Missing trailing bytes are treated as fully transparent.
+    AlphaMask {
+        width: u32,
+        height: u32,
+        alpha: Vec<u8>,
+        threshold: u8,
+    },
+}
+
+impl WindowShape {
+    /// Flatten the shape into a list of rectangles, which is what the platforms consume.
+    pub(crate) fn rectangles(&self) -> Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        let (width, height, alpha, threshold) = match self {
+            WindowShape::Rectangles(rectangles) => return rectangles.clone(),
+            WindowShape::AlphaMask {
+                width,
+                height,
+                alpha,
+                threshold,
+            } => (*width, *height, alpha, *threshold),
+        };
+
+        // Turn every row into spans of opaque pixels, growing the span's rectangle from the row
+        // above when it covers the exact same columns.
+        let mut rectangles: Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)> = Vec::new();
+        let mut previous_row = Vec::new();
+        for y in 0..height {
+            let mut current_row = Vec::new();
+            let mut x = 0;
+            while x < width {
+                let is_opaque = |x: u32| {
+                    let index = y as usize * width as usize + x as usize;
+                    alpha.get(index).map_or(false, |&alpha| alpha > threshold)
+                };
+
+                if !is_opaque(x) {
+                    x += 1;
+                    continue;
+                }
+
+                let start = x;
+                while x < width && is_opaque(x) {
+                    x += 1;
+                }
+
+                let span = (start, x - start);
+                let index = match previous_row.iter().find(|&&(_, previous)| previous == span) {
+                    Some(&(index, _)) => {
+                        let (_, size): &mut (_, PhysicalSize<u32>) = &mut rectangles[index];
+                        size.height += 1;
+                        index
+                    }
+                    None => {
+                        rectangles.push((
+                            PhysicalPosition::new(start as i32, y as i32),
+                            PhysicalSize::new(x - start, 1),
+                        ));
+                        rectangles.len() - 1
+                    }
+                };
+                current_row.push((index, span));
+            }
+            previous_row = current_row;
+        }
+
+        rectangles
+    }
+}
+
+/// The thickness of the decorations on each side of a window, in physical pixels.
+///
+/// See [`Window::frame_insets`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Insets {
+    pub top: u32,
+    pub bottom: u32,
+    pub left: u32,
+    pub right: u32,
+}
+
+/// The pixels of a window captured with [`Window::capture`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImageBuffer {
+    size: PhysicalSize<u32>,
+    rgba: Vec<u8>,
+}
+
+impl ImageBuffer {
+    pub(crate) fn from_rgba(rgba: Vec<u8>, size: PhysicalSize<u32>) -> Self {
+        debug_assert_eq!(rgba.len(), size.width as usize * size.height as usize * 4);
+        Self { size, rgba }
+    }
+
+    /// Returns the size of the image, in pixels.
+    #[inline]
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    /// Returns the 32bpp RGBA pixels of the image, row by row from the top-left corner, with
+    /// straight alpha.
+    #[inline]
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// Returns the pixels, in the same format as [`ImageBuffer::rgba`].
+    #[inline]
+    pub fn into_rgba(self) -> Vec<u8> {
+        self.rgba
+    }
+}
+
+/// Which size of the window is kept when its decorations are turned on or off, see
+/// [`Window::set_decorations_preserving`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DecorationsResize {
+    /// Keep the inner size: the outer size shrinks by the frame when the decorations are
+    /// removed, and grows by it when they're added back, so the layout of the content is
+    /// unaffected.
+    #[default]
+    PreserveContent,
+    /// Keep the outer size: the content takes the space of the frame when the decorations are
+    /// removed, and gives it back when they're added.
+    PreserveOuter,
+}
+
+impl DecorationsResize {
+    /// Returns the inner size to use once the frame changed from `insets` to `new_insets`.
+    fn inner_size(
+        self,
+        inner_size: PhysicalSize<u32>,
+        insets: Insets,
+        new_insets: Insets,
+    ) -> PhysicalSize<u32> {
+        match self {
+            DecorationsResize::PreserveContent => inner_size,
+            DecorationsResize::PreserveOuter => PhysicalSize::new(
+                (inner_size.width + insets.left + insets.right)
+                    .saturating_sub(new_insets.left + new_insets.right),
+                (inner_size.height + insets.top + insets.bottom)
+                    .saturating_sub(new_insets.top + new_insets.bottom),
+            ),
+        }
+    }
+}
+
 /// Fullscreen modes.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Fullscreen {
     Exclusive(VideoMode),
 
-    /// Providing `None` to `Borderless` will fullscreen on the current monitor.
+    /// Providing `None` to `Borderless` will fullscreen on the current monitor, the one
+    /// returned by [`Window::current_monitor`].
     Borderless(Option<MonitorHandle>),
 }
 
@@ -1659,6 +2726,7 @@ pub enum Theme {
 /// [`Critical`]: Self::Critical
 /// [`Informational`]: Self::Informational
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UserAttentionType {
     /// ## Platform-specific
     ///
@@ -1674,6 +2742,35 @@ pub enum UserAttentionType {
     Informational,
 }
 
+/// The feedback shown to the user while files are dragged over a window.
+///
+/// Use this enum with [`Window::set_drop_feedback`] in response to
+/// [`WindowEvent::HoveredFile`] and [`WindowEvent::HoveredFileMoved`].
+///
+/// [`WindowEvent::HoveredFile`]: crate::event::WindowEvent::HoveredFile
+/// [`WindowEvent::HoveredFileMoved`]: crate::event::WindowEvent::HoveredFileMoved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DropEffect {
+    /// The drop will be rejected.
+    ///
+    /// No [`WindowEvent::DroppedFile`] will be emitted if the files are released while this
+    /// effect is active.
+    ///
+    /// [`WindowEvent::DroppedFile`]: crate::event::WindowEvent::DroppedFile
+    None,
+
+    /// The files will be copied (default).
+    #[default]
+    Copy,
+
+    /// The files will be moved.
+    Move,
+
+    /// A link to the files will be created.
+    Link,
+}
+
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct WindowButtons: u32 {
@@ -1692,6 +2789,7 @@ bitflags! {
 ///
 /// - **iOS / Android / Web / Wayland:** Unsupported.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowLevel {
     /// The window will always be below normal windows.
     ///
@@ -1715,6 +2813,7 @@ pub enum WindowLevel {
 ///
 /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum ImePurpose {
     /// No special hints for the IME (default).
@@ -1733,10 +2832,34 @@ impl Default for ImePurpose {
     }
 }
 
+bitflags! {
+    /// Hints on how the IME should assist the text input, for use in [`Window::set_ime_hints`].
+    ///
+    /// The hints combine with the [`ImePurpose`]: the purpose picks the kind of input, like a
+    /// password, and the hints refine it. They're mostly used by on-screen keyboards, and the
+    /// IMEs of desktop platforms typically ignore them.
+    ///
+    /// No hints are set by default, which leaves the behavior to the IME.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ImeHints: u32 {
+        /// Capitalize the first letter of sentences.
+        const AUTO_CAPITALIZE = 1 << 0;
+        /// Correct the spelling of the words as they are typed.
+        const AUTO_CORRECT = 1 << 1;
+        /// Don't suggest words to complete the input with, even if [`Self::AUTO_CORRECT`] is
+        /// set.
+        const NO_SUGGESTIONS = 1 << 2;
+        /// The input can span several lines, so that the IME doesn't turn the enter key into a
+        /// submit action.
+        const MULTILINE = 1 << 3;
+    }
+}
+
 /// An opaque token used to activate the [`Window`].
 ///
 /// [`Window`]: crate::window::Window
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ActivationToken {
     pub(crate) _token: String,
 }
@@ -1746,3 +2869,120 @@ impl ActivationToken {
         Self { _token }
     }
 }
+
+/// Moves the window at `position` flush to the edges of `area` it's within `distance` of.
+fn snap_to_edges(
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    area: PhysicalRect,
+    distance: u32,
+) -> PhysicalPosition<i32> {
+    let snap = |position: i32, size: u32, start: i32, area_size: u32| {
+        let end = start + area_size as i32;
+        let to_start = (position - start).unsigned_abs();
+        let to_end = (position + size as i32 - end).unsigned_abs();
+        if to_start <= distance && to_start <= to_end {
+            start
+        } else if to_end <= distance {
+            end - size as i32
+        } else {
+            position
+        }
+    };
+    PhysicalPosition::new(
+        snap(position.x, size.width, area.position.x, area.size.width),
+        snap(position.y, size.height, area.position.y, area.size.height),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_snap_to_the_nearest_edges() {
+        // A monitor with a 40 pixels tall taskbar at the bottom.
+        let work_area =
+            PhysicalRect::new(PhysicalPosition::new(0, 0), PhysicalSize::new(1920, 1040));
+        let size = PhysicalSize::new(400, 300);
+
+        assert_eq!(
+            snap_to_edges(PhysicalPosition::new(12, 735), size, work_area, 16),
+            PhysicalPosition::new(0, 740)
+        );
+        assert_eq!(
+            snap_to_edges(PhysicalPosition::new(1510, -10), size, work_area, 16),
+            PhysicalPosition::new(1520, 0)
+        );
+        // Positions further than the snap distance are kept.
+        assert_eq!(
+            snap_to_edges(PhysicalPosition::new(17, 500), size, work_area, 16),
+            PhysicalPosition::new(17, 500)
+        );
+    }
+
+    #[test]
+    fn decorations_resize_keeps_the_chosen_size() {
+        let inner_size = PhysicalSize::new(800, 600);
+        let frame = Insets {
+            top: 30,
+            bottom: 2,
+            left: 2,
+            right: 2,
+        };
+
+        // Removing the decorations.
+        let resize = DecorationsResize::PreserveContent;
+        assert_eq!(
+            resize.inner_size(inner_size, frame, Insets::default()),
+            inner_size
+        );
+        let resize = DecorationsResize::PreserveOuter;
+        assert_eq!(
+            resize.inner_size(inner_size, frame, Insets::default()),
+            PhysicalSize::new(804, 632)
+        );
+        // And adding them back.
+        assert_eq!(
+            resize.inner_size(PhysicalSize::new(804, 632), Insets::default(), frame),
+            inner_size
+        );
+    }
+
+    #[test]
+    fn alpha_mask_shape_into_rectangles() {
+        // A 4x3 mask shaped like a `T`, with a translucent pixel below the threshold.
+        #[rustfmt::skip]
+        let alpha = vec![
+            255, 255, 255, 255,
+            0,   255, 255, 0,
+            0,   255, 255, 10,
+        ];
+        let shape = WindowShape::AlphaMask {
+            width: 4,
+            height: 3,
+            alpha,
+            threshold: 127,
+        };
+
+        assert_eq!(
+            shape.rectangles(),
+            vec![
+                (PhysicalPosition::new(0, 0), PhysicalSize::new(4, 1)),
+                (PhysicalPosition::new(1, 1), PhysicalSize::new(2, 2)),
+            ]
+        );
+
+        // Missing bytes are transparent.
+        let shape = WindowShape::AlphaMask {
+            width: 2,
+            height: 2,
+            alpha: vec![255],
+            threshold: 0,
+        };
+        assert_eq!(
+            shape.rectangles(),
+            vec![(PhysicalPosition::new(0, 0), PhysicalSize::new(1, 1))]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+#![cfg(any(x11_platform, wayland_platform))]
+
+use winit::dpi::{LogicalSize, PhysicalSize};
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::{Backend, EventLoopBuilder};
+use winit::platform::headless::{
+    EventLoopBuilderExtHeadless, EventLoopWindowTargetExtHeadless, WindowExtHeadless,
+};
+use winit::window::WindowBuilder;
+
+#[test]
+fn headless_windows_are_driven_by_injected_events() {
+    // Works without a display server, and off of the main thread.
+    let event_loop = EventLoopBuilder::new()
+        .with_headless(true)
+        .with_headless_monitor(PhysicalSize::new(1280, 720), 2.0)
+        .build()
+        .unwrap();
+
+    let mut window = None;
+    let mut events = Vec::new();
+    event_loop
+        .run(|event, elwt| {
+            let event = match event {
+                Event::Resumed => {
+                    assert!(elwt.is_headless());
+                    assert_eq!(elwt.backend(), Backend::Headless);
+                    let monitor = elwt.primary_monitor().unwrap();
+                    assert_eq!(monitor.size(), PhysicalSize::new(1280, 720));
+                    assert_eq!(monitor.scale_factor(), 2.0);
+
+                    let new_window = WindowBuilder::new()
+                        .with_inner_size(LogicalSize::new(100, 50))
+                        .build(elwt)
+                        .unwrap();
+                    assert_eq!(new_window.inner_size(), PhysicalSize::new(200, 100));
+                    elwt.inject_window_event(new_window.id(), WindowEvent::Focused(true));
+                    window = Some(new_window);
+                    return;
+                }
+                Event::WindowEvent { event, .. } => event,
+                _ => return,
+            };
+
+            match &event {
+                WindowEvent::RedrawRequested { .. } => {
+                    let pixels = window.as_ref().unwrap().with_pixel_buffer(|pixels, size| {
+                        pixels.fill(0xff0000);
+                        (pixels.len(), size)
+                    });
+                    assert_eq!(pixels, Some((200 * 100, PhysicalSize::new(200, 100))));
+                }
+                WindowEvent::Focused(true) => {
+                    let window = window.as_ref().unwrap();
+                    assert!(window.has_focus());
+                    let size = window.request_inner_size(PhysicalSize::new(30, 20));
+                    assert_eq!(size, Some(PhysicalSize::new(30, 20)));
+                    elwt.inject_window_event(window.id(), WindowEvent::CloseRequested);
+                }
+                WindowEvent::Resized(size) => {
+                    let window = window.as_ref().unwrap();
+                    let pixels = window.with_pixel_buffer(|pixels, _| pixels.to_vec());
+                    assert_eq!(pixels, Some(vec![0; 30 * 20]));
+                    assert_eq!(*size, window.inner_size());
+                }
+                WindowEvent::CloseRequested => window = None,
+                WindowEvent::Destroyed => elwt.exit(),
+                _ => (),
+            }
+            events.push(event);
+        })
+        .unwrap();
+
+    assert_eq!(
+        events,
+        [
+            WindowEvent::RedrawRequested { dirty_rects: None },
+            WindowEvent::Focused(true),
+            WindowEvent::Resized(PhysicalSize::new(30, 20)),
+            WindowEvent::CloseRequested,
+            WindowEvent::Destroyed,
+        ]
+    );
+}
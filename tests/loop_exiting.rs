@@ -0,0 +1,42 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::{Event, StartCause};
+use winit::event_loop::EventLoopBuilder;
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::WindowAttributes;
+
+#[test]
+#[ignore = "needs a display server"]
+fn loop_exiting_is_delivered_once() {
+    let event_loop = EventLoopBuilder::new()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    let mut loop_exiting = 0;
+    event_loop
+        .run(|event, elwt| match event {
+            Event::NewEvents(StartCause::Init) => {
+                assert!(!elwt.exiting());
+                elwt.exit();
+                elwt.exit();
+                assert!(elwt.exiting());
+            }
+            Event::AboutToWait => elwt.exit(),
+            Event::LoopExiting => {
+                loop_exiting += 1;
+                assert!(elwt.exiting());
+                assert!(elwt.create_window(WindowAttributes::default()).is_err());
+                elwt.exit();
+            }
+            _ => (),
+        })
+        .unwrap();
+
+    assert_eq!(loop_exiting, 1);
+}
@@ -0,0 +1,15 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform, macos_platform))]
+
+use std::thread;
+
+use winit::error::EventLoopError;
+use winit::event_loop::EventLoop;
+
+#[test]
+fn building_off_the_main_thread_returns_an_error() {
+    let result = thread::spawn(|| EventLoop::new().map(drop))
+        .join()
+        .expect("building the event loop shouldn't panic");
+
+    assert!(matches!(result, Err(EventLoopError::NotMainThread)));
+}
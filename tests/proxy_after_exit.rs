@@ -0,0 +1,45 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::{Event, StartCause};
+use winit::event_loop::{EventLoopBuilder, EventLoopClosed};
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+
+#[test]
+#[ignore = "needs a display server"]
+fn send_event_after_exit_returns_the_event() {
+    let event_loop = EventLoopBuilder::with_user_event()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    let proxy = event_loop.create_proxy();
+    proxy.send_event(1).unwrap();
+
+    let mut received = Vec::new();
+    let mut target_proxy = None;
+    event_loop
+        .run(|event, elwt| match event {
+            Event::NewEvents(StartCause::Init) => {
+                let proxy = elwt.create_proxy();
+                proxy.send_event(2).unwrap();
+                target_proxy = Some(proxy);
+            }
+            Event::UserEvent(event) => {
+                received.push(event);
+                if received.len() == 2 {
+                    elwt.exit();
+                }
+            }
+            _ => (),
+        })
+        .unwrap();
+
+    assert_eq!(received, [1, 2]);
+    assert_eq!(proxy.send_event(3), Err(EventLoopClosed(3)));
+    assert_eq!(target_proxy.unwrap().send_event(4), Err(EventLoopClosed(4)));
+}
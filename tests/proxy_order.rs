@@ -0,0 +1,58 @@
+#![cfg(any(x11_platform, wayland_platform))]
+
+use std::thread;
+
+use winit::event::Event;
+use winit::event_loop::{EventLoopBuilder, Sequenced};
+use winit::platform::headless::EventLoopBuilderExtHeadless;
+
+const THREADS: usize = 4;
+const EVENTS: usize = 100;
+
+#[test]
+fn user_events_from_several_threads_keep_their_order() {
+    let event_loop = EventLoopBuilder::<Sequenced<(usize, usize)>>::with_user_event()
+        .with_headless(true)
+        .build()
+        .unwrap();
+
+    let senders: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            let proxy = event_loop.create_proxy();
+            thread::spawn(move || {
+                for index in 0..EVENTS {
+                    proxy.send_sequenced((thread, index)).unwrap();
+                }
+            })
+        })
+        .collect();
+
+    let mut received = Vec::new();
+    event_loop
+        .run(|event, elwt| {
+            if let Event::UserEvent(event) = event {
+                received.push(event);
+                if received.len() == THREADS * EVENTS {
+                    elwt.exit();
+                }
+            }
+        })
+        .unwrap();
+    for sender in senders {
+        sender.join().unwrap();
+    }
+
+    // Every thread's events arrive in the order it sent them.
+    for thread in 0..THREADS {
+        let indices: Vec<_> = received
+            .iter()
+            .filter(|sequenced| sequenced.event.0 == thread)
+            .map(|sequenced| sequenced.event.1)
+            .collect();
+        assert_eq!(indices, (0..EVENTS).collect::<Vec<_>>());
+    }
+    // And all of them in the order of their sequence numbers.
+    assert!(received
+        .windows(2)
+        .all(|pair| pair[0].sequence < pair[1].sequence && pair[0].sent_at <= pair[1].sent_at));
+}
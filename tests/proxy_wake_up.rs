@@ -0,0 +1,43 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use std::thread;
+
+use winit::event::Event;
+use winit::event_loop::{ControlFlow, EventLoopBuilder};
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+
+#[test]
+#[ignore = "needs a display server"]
+fn wake_up_iterates_without_user_event() {
+    let event_loop = EventLoopBuilder::new()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    let proxy = event_loop.create_proxy();
+    let mut about_to_wait = 0;
+    event_loop
+        .run(move |event, elwt| match event {
+            Event::NewEvents(_) => elwt.set_control_flow(ControlFlow::Wait),
+            Event::UserEvent(()) => panic!("`wake_up` must not emit a user event"),
+            Event::AboutToWait => {
+                about_to_wait += 1;
+                if about_to_wait == 1 {
+                    let proxy = proxy.clone();
+                    thread::spawn(move || {
+                        proxy.wake_up();
+                        proxy.wake_up();
+                    });
+                } else {
+                    elwt.exit();
+                }
+            }
+            _ => (),
+        })
+        .unwrap();
+}
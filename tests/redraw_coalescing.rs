@@ -0,0 +1,50 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoopBuilder;
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::WindowBuilder;
+
+#[test]
+#[ignore = "needs a display server"]
+fn two_redraw_requests_produce_one_event() {
+    let event_loop = EventLoopBuilder::new()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    let mut window = None;
+    // The number of redraws since the requests, or `None` until the window was first drawn.
+    let mut redraws = None;
+    event_loop
+        .run(|event, elwt| match event {
+            Event::Resumed => {
+                window = Some(WindowBuilder::new().build(elwt).unwrap());
+            }
+            Event::WindowEvent {
+                event: WindowEvent::RedrawRequested { .. },
+                ..
+            } => match redraws.as_mut() {
+                Some(redraws) => *redraws += 1,
+                None => {
+                    let window = window.as_ref().unwrap();
+                    window.request_redraw();
+                    window.request_redraw();
+                    redraws = Some(0);
+                }
+            },
+            Event::AboutToWait => {
+                if let Some(redraws) = redraws.filter(|&redraws| redraws > 0) {
+                    assert_eq!(redraws, 1);
+                    elwt.exit();
+                }
+            }
+            _ => (),
+        })
+        .unwrap();
+}
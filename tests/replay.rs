@@ -0,0 +1,117 @@
+#![cfg(any(x11_platform, wayland_platform))]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoopBuilder;
+use winit::platform::headless::{EventLoopBuilderExtHeadless, EventLoopWindowTargetExtHeadless};
+use winit::window::{WindowBuilder, WindowId};
+
+#[test]
+fn replayed_traces_are_remapped_to_the_windows() {
+    let recorded = Rc::new(RefCell::new(Vec::new()));
+    let recorder = recorded.clone();
+    let event_loop = EventLoopBuilder::with_user_event()
+        .with_headless(true)
+        .build()
+        .unwrap()
+        .with_recorder(move |event: &Event<u32>| match event {
+            Event::WindowEvent {
+                event: WindowEvent::RedrawRequested { .. },
+                ..
+            } => (),
+            Event::WindowEvent { .. } | Event::UserEvent(_) | Event::AboutToWait => {
+                recorder.borrow_mut().push(event.clone());
+            }
+            _ => (),
+        });
+
+    // A trace of another event loop, whose windows have unrelated ids.
+    let (first, second) = (WindowId::from(42), WindowId::from(7));
+    let trace = vec![
+        Event::WindowEvent {
+            window_id: first,
+            event: WindowEvent::Focused(true),
+        },
+        Event::AboutToWait,
+        Event::UserEvent(1),
+        Event::WindowEvent {
+            window_id: second,
+            event: WindowEvent::Occluded(true),
+        },
+        // Generated again by the event loop.
+        Event::WindowEvent {
+            window_id: second,
+            event: WindowEvent::RedrawRequested { dirty_rects: None },
+        },
+        Event::AboutToWait,
+        Event::WindowEvent {
+            window_id: first,
+            event: WindowEvent::CloseRequested,
+        },
+    ];
+
+    let mut windows = Vec::new();
+    event_loop
+        .run(|event, elwt| match event {
+            Event::Resumed => {
+                for _ in 0..2 {
+                    windows.push(WindowBuilder::new().build(elwt).unwrap());
+                }
+                elwt.replay_events(trace.clone());
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => elwt.exit(),
+            _ => (),
+        })
+        .unwrap();
+
+    let (first, second) = (windows[0].id(), windows[1].id());
+    let replayed: Vec<_> = recorded
+        .borrow()
+        .iter()
+        .filter(|event| !matches!(event, Event::AboutToWait))
+        .cloned()
+        .collect();
+    assert_eq!(
+        replayed,
+        [
+            Event::WindowEvent {
+                window_id: first,
+                event: WindowEvent::Focused(true),
+            },
+            Event::UserEvent(1),
+            Event::WindowEvent {
+                window_id: second,
+                event: WindowEvent::Occluded(true),
+            },
+            Event::WindowEvent {
+                window_id: first,
+                event: WindowEvent::CloseRequested,
+            },
+        ]
+    );
+    // Each recorded iteration is replayed by its own iteration.
+    let iterations = recorded
+        .borrow()
+        .iter()
+        .position(|event| {
+            matches!(
+                event,
+                Event::WindowEvent {
+                    event: WindowEvent::CloseRequested,
+                    ..
+                }
+            )
+        })
+        .map(|index| {
+            recorded.borrow()[..index]
+                .iter()
+                .filter(|event| matches!(event, Event::AboutToWait))
+                .count()
+        });
+    assert_eq!(iterations, Some(2));
+}
@@ -0,0 +1,72 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoopBuilder;
+use winit::platform::run_on_demand::EventLoopExtRunOnDemand;
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::{Window, WindowAttributes};
+
+#[test]
+#[ignore = "needs a display server"]
+fn run_on_demand_can_be_reentered() {
+    let mut event_loop = EventLoopBuilder::<u32>::with_user_event()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+    let proxy = event_loop.create_proxy();
+    // The window of the first run, kept alive into the second one.
+    let mut kept: Option<Window> = None;
+
+    for run in 0..2 {
+        let mut window: Option<Window> = None;
+        let mut user_events = Vec::new();
+        let mut kept_redraws = 0;
+        let mut loop_exiting = 0;
+
+        event_loop
+            .run_on_demand(|event, elwt| match event {
+                Event::Resumed => {
+                    assert!(!elwt.exiting());
+                    window = Some(elwt.create_window(WindowAttributes::default()).unwrap());
+                }
+                Event::UserEvent(event) => user_events.push(event),
+                Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::RedrawRequested { .. },
+                } if kept.as_ref().map(Window::id) == Some(window_id) => kept_redraws += 1,
+                Event::WindowEvent {
+                    event: WindowEvent::CloseRequested,
+                    ..
+                } => elwt.exit(),
+                Event::AboutToWait if window.is_some() => elwt.exit(),
+                Event::LoopExiting => {
+                    loop_exiting += 1;
+                    if run == 0 {
+                        kept = window.take();
+                    }
+                    window = None;
+                }
+                _ => (),
+            })
+            .unwrap();
+
+        assert_eq!(loop_exiting, 1);
+        assert!(window.is_none());
+        if run == 1 {
+            // Sent in between the two runs.
+            assert_eq!(user_events, [0]);
+            assert_eq!(kept_redraws, 1);
+        }
+
+        // The proxy and the kept window stay valid while the event loop isn't running.
+        proxy.send_event(run).unwrap();
+        if let Some(window) = &kept {
+            window.request_redraw();
+        }
+    }
+}
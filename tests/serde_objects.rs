@@ -2,10 +2,19 @@
 
 use serde::{Deserialize, Serialize};
 use winit::{
-    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
-    event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase},
+    dpi::{
+        LogicalPosition, LogicalSize, PhysicalPosition, PhysicalRect, PhysicalSize, Position, Size,
+    },
+    event::{
+        DeviceEvent, DeviceId, ElementState, Event, KeyEvent, Modifiers, MouseButton,
+        MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+    },
     keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey},
-    window::CursorIcon,
+    monitor::MonitorId,
+    window::{
+        Cursor, CursorIcon, DecorationsResize, ImePurpose, Insets, RedrawHint, ResizeDirection,
+        Theme, UserAttentionType, WindowId, WindowLevel,
+    },
 };
 
 #[allow(dead_code)]
@@ -14,6 +23,16 @@ fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<Cursor>();
+    needs_serde::<Theme>();
+    needs_serde::<WindowLevel>();
+    needs_serde::<UserAttentionType>();
+    needs_serde::<ResizeDirection>();
+    needs_serde::<ImePurpose>();
+    needs_serde::<Insets>();
+    needs_serde::<DecorationsResize>();
+    needs_serde::<RedrawHint>();
+    needs_serde::<WindowId>();
 }
 
 #[test]
@@ -28,6 +47,18 @@ fn events_serde() {
     needs_serde::<PhysicalKey>();
     needs_serde::<KeyLocation>();
     needs_serde::<ModifiersState>();
+    needs_serde::<Modifiers>();
+    needs_serde::<KeyEvent>();
+    needs_serde::<Touch>();
+    needs_serde::<DeviceId>();
+    needs_serde::<WindowEvent>();
+    needs_serde::<DeviceEvent>();
+    needs_serde::<Event<()>>();
+}
+
+#[test]
+fn monitor_serde() {
+    needs_serde::<MonitorId>();
 }
 
 #[test]
@@ -37,4 +68,7 @@ fn dpi_serde() {
     needs_serde::<PhysicalPosition<f64>>();
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
+    needs_serde::<Position>();
+    needs_serde::<Size>();
+    needs_serde::<PhysicalRect>();
 }
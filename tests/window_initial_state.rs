@@ -0,0 +1,42 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::Event;
+use winit::event_loop::EventLoopBuilder;
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::{Theme, WindowBuilder};
+
+#[test]
+#[ignore = "needs a display server"]
+fn builder_state_is_queryable_right_after_build() {
+    let event_loop = EventLoopBuilder::new()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    event_loop
+        .run(|event, elwt| {
+            if let Event::Resumed = event {
+                let window = WindowBuilder::new()
+                    .with_visible(false)
+                    .with_resizable(false)
+                    .with_decorations(false)
+                    .with_theme(Some(Theme::Light))
+                    .build(elwt)
+                    .unwrap();
+
+                assert!(!window.is_resizable());
+                assert!(!window.is_decorated());
+                assert!(!window.is_maximized());
+                assert_eq!(window.fullscreen(), None);
+                assert_eq!(window.theme(), Some(Theme::Light));
+
+                elwt.exit();
+            }
+        })
+        .unwrap();
+}
@@ -0,0 +1,48 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoopBuilder;
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::WindowBuilder;
+
+#[test]
+#[ignore = "needs a display server"]
+fn inner_position_is_outer_position_plus_frame_insets() {
+    let event_loop = EventLoopBuilder::new()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    let mut window = None;
+    event_loop
+        .run(|event, elwt| match event {
+            Event::Resumed => {
+                let created = WindowBuilder::new()
+                    .with_decorations(true)
+                    .build(elwt)
+                    .unwrap();
+                window = Some(created);
+            }
+            // The frame is only known once the window has been mapped.
+            Event::WindowEvent {
+                event: WindowEvent::RedrawRequested { .. },
+                ..
+            } => {
+                let window = window.as_ref().unwrap();
+                // Wayland can't report either position, so only check when there's an answer.
+                if let (Ok(inner), Ok(outer)) = (window.inner_position(), window.outer_position()) {
+                    let insets = window.frame_insets();
+                    assert_eq!(inner.x, outer.x + insets.left as i32);
+                    assert_eq!(inner.y, outer.y + insets.top as i32);
+                }
+                elwt.exit();
+            }
+            _ => (),
+        })
+        .unwrap();
+}
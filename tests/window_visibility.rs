@@ -0,0 +1,54 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoopBuilder;
+#[cfg(all(wayland_platform, not(x11_platform)))]
+use winit::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(windows_platform)]
+use winit::platform::windows::EventLoopBuilderExtWindows;
+#[cfg(x11_platform)]
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::WindowBuilder;
+
+#[test]
+#[ignore = "needs a display server"]
+fn is_visible_tracks_set_visible() {
+    let event_loop = EventLoopBuilder::new()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    // Wayland can't report the visibility, so only check when there's an answer.
+    let assert_visible = |visible: Option<bool>, expected: bool| {
+        if let Some(visible) = visible {
+            assert_eq!(visible, expected);
+        }
+    };
+
+    let mut window = None;
+    event_loop
+        .run(|event, elwt| match event {
+            Event::Resumed => {
+                let created = WindowBuilder::new()
+                    .with_visible(false)
+                    .build(elwt)
+                    .unwrap();
+                assert_visible(created.is_visible(), false);
+                created.set_visible(true);
+                window = Some(created);
+            }
+            // The window has to be on screen to get drawn.
+            Event::WindowEvent {
+                event: WindowEvent::RedrawRequested { .. },
+                ..
+            } => {
+                let window = window.as_ref().unwrap();
+                assert_visible(window.is_visible(), true);
+                window.set_visible(false);
+                assert_visible(window.is_visible(), false);
+                elwt.exit();
+            }
+            _ => (),
+        })
+        .unwrap();
+}
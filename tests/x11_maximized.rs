@@ -0,0 +1,31 @@
+#![cfg(x11_platform)]
+
+use winit::event::Event;
+use winit::event_loop::EventLoopBuilder;
+use winit::platform::x11::EventLoopBuilderExtX11;
+use winit::window::WindowBuilder;
+
+#[test]
+#[ignore = "needs a display server"]
+fn maximized_is_reported_before_the_window_manager_applies_it() {
+    let event_loop = EventLoopBuilder::new()
+        .with_x11()
+        .with_any_thread(true)
+        .build()
+        .unwrap();
+
+    event_loop
+        .run(|event, elwt| {
+            if let Event::Resumed = event {
+                let window = WindowBuilder::new()
+                    .with_maximized(true)
+                    .build(elwt)
+                    .unwrap();
+                assert!(window.is_maximized());
+                window.set_maximized(false);
+                assert!(!window.is_maximized());
+                elwt.exit();
+            }
+        })
+        .unwrap();
+}